@@ -0,0 +1,50 @@
+//! Times publication date extraction on a large article page. Run with
+//! `cargo bench --bench article_dates`.
+
+use std::time::{Duration, Instant};
+use _ferriscope_native::WebExtractor;
+
+const RUNS: usize = 25;
+
+/// A ~1 MB article page with date metas, JSON-LD, microdata and `<time>` elements
+fn large_article() -> String {
+    let mut body = String::new();
+    for i in 0..3_000 {
+        body.push_str(&format!(
+            r#"<p>Update {i}: the council met again, as it did on <time datetime="2023-{m:02}-{d:02}">{d} March</time>,
+            and published the minutes on 2023-{m:02}-{d:02} alongside a summary of comments.</p>"#,
+            m = i % 12 + 1,
+            d = i % 28 + 1,
+        ));
+    }
+    format!(
+        r#"<html><head><title>Council minutes</title>
+        <meta property="article:published_time" content="2024-03-01T08:00:00Z">
+        <meta property="article:modified_time" content="2024-03-02T09:30:00Z">
+        <script type="application/ld+json">{{"@context":"https://schema.org","@type":"NewsArticle",
+        "headline":"Council minutes","datePublished":"2024-03-01T08:00:00Z","dateModified":"2024-03-02T09:30:00Z"}}</script>
+        </head><body><article><h1>Council minutes</h1>
+        <p>Published <time itemprop="datePublished" datetime="2024-03-01T08:00:00Z">1 March 2024</time>,
+        updated <time itemprop="dateModified" datetime="2024-03-02T09:30:00Z">2 March 2024</time></p>
+        {body}</article></body></html>"#,
+    )
+}
+
+fn main() {
+    let html = large_article();
+    let mut extractor = WebExtractor::new_with_html("https://news.example.com/2024/03/01/minutes".to_string(), html.clone());
+    extractor.extract_article(vec!["dates".to_string()]);
+    // Warm up allocators and lazily compiled regexes
+    extractor.extract().expect("extraction succeeds");
+
+    let mut times: Vec<Duration> = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            extractor.extract().expect("extraction succeeds");
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    println!("page size: {} KiB", html.len() / 1024);
+    println!("dates:     {:?} (median of {})", times[RUNS / 2], RUNS);
+}
@@ -0,0 +1,76 @@
+//! Compares sequential and parallel extraction passes on a large page with every activity
+//! enabled. Run with `cargo bench --bench parallel_passes`.
+
+use std::time::{Duration, Instant};
+use _ferriscope_native::WebExtractor;
+
+const RUNS: usize = 15;
+
+/// A ~1.5 MB article page with product JSON-LD, microdata, lists, links and social metas
+fn large_page() -> String {
+    let mut body = String::new();
+    for i in 0..2_500 {
+        body.push_str(&format!(
+            r#"<p>Paragraph {i} of the long read explains <a href="/section/{i}">section {i}</a> and
+            links to <a href="https://partner{m}.example.org/item/{i}">a partner item</a> with
+            enough prose around it to make text extraction and link context do real work.</p>
+            <ul><li>Point {i}a</li><li>Point {i}b contact sales{i}@shop.example.com</li></ul>"#,
+            m = i % 40,
+        ));
+        if i % 100 == 0 {
+            body.push_str(&format!(
+                r#"<div itemscope itemtype="https://schema.org/Product"><span itemprop="name">Item {i}</span>
+                <span itemprop="price" content="{i}.99">{i},99 €</span></div>"#,
+            ));
+        }
+    }
+    format!(
+        r#"<html lang="en"><head><title>Long read</title>
+        <meta property="og:title" content="Long read"><meta property="og:image" content="/cover.jpg">
+        <meta name="twitter:card" content="summary_large_image"><meta name="author" content="Jane Doe">
+        <script type="application/ld+json">{{"@context":"https://schema.org","@type":"Product",
+        "name":"Trail Runner","offers":{{"@type":"Offer","price":"139.95","priceCurrency":"EUR"}}}}</script>
+        <script type="application/ld+json">{{"@context":"https://schema.org","@type":"NewsArticle",
+        "headline":"Long read","datePublished":"2024-03-01T08:00:00Z","author":{{"@type":"Person","name":"Jane Doe"}}}}</script>
+        </head><body><article><h1>Long read</h1>{body}</article></body></html>"#,
+    )
+}
+
+fn extractor(html: &str, parallel: bool) -> WebExtractor {
+    let mut extractor = WebExtractor::new_with_html("https://news.example.com/long-read".to_string(), html.to_string());
+    extractor.extract_all();
+    extractor.extract_lists();
+    extractor.extract_contacts();
+    extractor.extract_emails();
+    extractor.set_parallel_passes(parallel);
+    extractor
+}
+
+fn median(extractor: &WebExtractor) -> Duration {
+    let mut times: Vec<Duration> = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            extractor.extract().expect("extraction succeeds");
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    times[RUNS / 2]
+}
+
+fn main() {
+    let html = large_page();
+    let sequential = extractor(&html, false);
+    let parallel = extractor(&html, true);
+    // Warm up allocators and lazily compiled regexes
+    sequential.extract().expect("extraction succeeds");
+    parallel.extract().expect("extraction succeeds");
+
+    let sequential_time = median(&sequential);
+    let parallel_time = median(&parallel);
+    println!("page size:  {} KiB", html.len() / 1024);
+    println!("threads:    {}", std::thread::available_parallelism().map_or(1, |n| n.get()));
+    println!("sequential: {:?} (median of {})", sequential_time, RUNS);
+    println!("parallel:   {:?} (median of {})", parallel_time, RUNS);
+    println!("speedup:    {:.2}x", sequential_time.as_secs_f64() / parallel_time.as_secs_f64());
+}
@@ -0,0 +1,134 @@
+//! Times product extraction on a product page carrying a lot of JSON-LD. Run with
+//! `cargo bench --bench product_extraction`.
+
+use std::time::{Duration, Instant};
+use _ferriscope_native::WebExtractor;
+
+const RUNS: usize = 101;
+
+/// The fields a typical shop scraper asks for
+const CORE_FIELDS: &[&str] = &["title", "brand", "sku", "price", "currency", "availability", "rating", "review_count"];
+
+/// A product page with a large JSON-LD graph (offers, reviews, related products, breadcrumbs),
+/// og/product metas and a related-products grid
+fn json_ld_heavy_product() -> String {
+    let offers: Vec<String> = (0..40)
+        .map(|i| {
+            format!(
+                r#"{{"@type":"Offer","price":"{}.95","priceCurrency":"EUR","sku":"TR2-{i}",
+                "availability":"https://schema.org/{}","seller":{{"@type":"Organization","name":"Seller {i}"}},
+                "priceValidUntil":"2026-12-31"}}"#,
+                120 + i,
+                if i % 3 == 0 { "OutOfStock" } else { "InStock" },
+            )
+        })
+        .collect();
+    let reviews: Vec<String> = (0..60)
+        .map(|i| {
+            format!(
+                r#"{{"@type":"Review","author":{{"@type":"Person","name":"Runner {i}"}},"datePublished":"2024-0{}-1{}",
+                "reviewRating":{{"@type":"Rating","ratingValue":"{}"}},"name":"Review {i}",
+                "reviewBody":"Review {i} of the shoe covers grip, fit and how it held up over the first few hundred kilometres."}}"#,
+                i % 9 + 1,
+                i % 10,
+                i % 5 + 1,
+            )
+        })
+        .collect();
+    let related: Vec<String> = (0..30)
+        .map(|i| {
+            format!(
+                r#"{{"@type":"ListItem","position":{},"item":{{"@type":"Product","name":"Related {i}","sku":"REL-{i}",
+                "offers":{{"@type":"Offer","price":"{}.00","priceCurrency":"EUR"}}}}}}"#,
+                i + 1,
+                60 + i,
+            )
+        })
+        .collect();
+    let mut grid = String::new();
+    for i in 0..30 {
+        grid.push_str(&format!(
+            r#"<div class="product-card" itemscope itemtype="https://schema.org/Product">
+            <a href="/p/related-{i}"><img src="/img/related-{i}.jpg" alt="Related {i}"></a>
+            <span itemprop="name">Related {i}</span><span class="price">{p},00 €</span></div>"#,
+            p = 60 + i,
+        ));
+    }
+    format!(
+        r#"<html lang="de-DE"><head><title>Trail Runner 2 | Stride</title>
+        <meta property="og:type" content="product"><meta property="og:title" content="Trail Runner 2">
+        <meta property="og:image" content="/img/tr2.jpg"><meta property="product:brand" content="Stride">
+        <script type="application/ld+json">{{"@context":"https://schema.org","@graph":[
+        {{"@type":"Organization","name":"Stride","url":"https://stride.example.com"}},
+        {{"@type":"BreadcrumbList","itemListElement":[
+            {{"@type":"ListItem","position":1,"name":"Shoes","item":"https://stride.example.com/shoes"}},
+            {{"@type":"ListItem","position":2,"name":"Trail","item":"https://stride.example.com/shoes/trail"}}]}},
+        {{"@type":"Product","name":"Trail Runner 2","sku":"TR2","gtin13":"4006381333931",
+            "brand":{{"@type":"Brand","name":"Stride"}},"image":["/img/tr2.jpg","/img/tr2-side.jpg","/img/tr2-sole.jpg"],
+            "description":"A lightweight trail running shoe.",
+            "aggregateRating":{{"@type":"AggregateRating","ratingValue":"4.6","reviewCount":"212"}},
+            "weight":{{"@type":"QuantitativeValue","value":0.31,"unitCode":"KGM"}},
+            "offers":[{offers}],"review":[{reviews}]}},
+        {{"@type":"ItemList","name":"You may also like","itemListElement":[{related}]}}]}}</script>
+        </head><body><h1>Trail Runner 2</h1><div class="price">139,95 €</div>
+        <section class="related">{grid}</section></body></html>"#,
+        offers = offers.join(","),
+        reviews = reviews.join(","),
+        related = related.join(","),
+    )
+}
+
+/// A product page without structured data above a grid of 200 product cards, so every field
+/// falls through to the CSS selectors
+fn card_grid_product() -> String {
+    let mut grid = String::new();
+    for i in 0..200 {
+        grid.push_str(&format!(
+            r#"<div class="product-card"><a href="/p/item-{i}"><img src="/img/item-{i}.jpg" alt="Item {i}"></a>
+            <h3 class="product-name">Item {i}</h3><span class="price">{p},99 €</span>
+            <span class="old-price">{o},99 €</span><div class="rating">4.{r}</div></div>"#,
+            p = 20 + i,
+            o = 30 + i,
+            r = i % 10,
+        ));
+    }
+    format!(
+        r#"<html lang="de-DE"><head><title>Kettle | Shop</title></head><body>
+        <h1>Kettle</h1><span class="old-price">24,99 €</span><span class="price">19,99 €</span>
+        <div class="rating">4.5</div><span class="review-count">12 reviews</span>
+        <section class="related">{grid}</section></body></html>"#,
+    )
+}
+
+/// Median and fastest of `RUNS` extractions; the fastest is steadier on a busy machine
+fn timings(extractor: &WebExtractor) -> String {
+    // Warm up allocators and lazily compiled regexes
+    extractor.extract().expect("extraction succeeds");
+    let mut times: Vec<Duration> = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            extractor.extract().expect("extraction succeeds");
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    format!("median {:?}, best {:?} ({} runs)", times[RUNS / 2], times[0], RUNS)
+}
+
+fn main() {
+    let html = json_ld_heavy_product();
+    let mut extractor = WebExtractor::new_with_html("https://stride.example.com/p/trail-runner-2".to_string(), html.clone());
+    extractor.extract_product(CORE_FIELDS.iter().map(|f| f.to_string()).collect());
+
+    println!("page size:   {} KiB", html.len() / 1024);
+    println!("core fields: {}", timings(&extractor));
+    let mut all = WebExtractor::new_with_html("https://stride.example.com/p/trail-runner-2".to_string(), html.clone());
+    all.extract_product(vec!["all".to_string()]);
+    println!("all fields:  {}", timings(&all));
+
+    let html = card_grid_product();
+    let mut grid = WebExtractor::new_with_html("https://shop.example.de/kettle".to_string(), html.clone());
+    grid.extract_product(vec!["all".to_string()]);
+    println!("card grid:   {} KiB", html.len() / 1024);
+    println!("all fields:  {}", timings(&grid));
+}
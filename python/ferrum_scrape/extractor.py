@@ -71,6 +71,47 @@ class WebExtractor:
         self._extractor = _PyWebExtractor(url, html)
         self._activities_set = False
     
+    @classmethod
+    def from_html(cls, url: str, html: str) -> "WebExtractor":
+        """
+        Create an extractor for an HTML string. Pair with extract() to run
+        without any network access or async runtime.
+        
+        Args:
+            url: The URL used for link resolution and metadata (can be a placeholder)
+            html: The HTML content to extract from
+        
+        Example:
+            >>> extractor = WebExtractor.from_html("https://example.com", "<h1>Hello</h1>")
+            >>> extractor.extract_text()
+            >>> result = extractor.extract()
+        """
+        return cls(url, html=html)
+    
+    @classmethod
+    def from_gzip_html(cls, url: str, data: bytes) -> "WebExtractor":
+        """
+        Create an extractor for gzip-compressed HTML, such as stored page
+        snapshots. Behaves like from_html() once decompressed.
+        
+        Args:
+            url: The URL used for link resolution and metadata (can be a placeholder)
+            data: The gzip-compressed HTML
+        
+        Raises:
+            RuntimeError: If data is not valid gzip
+        
+        Example:
+            >>> with open("snapshot.html.gz", "rb") as f:
+            ...     extractor = WebExtractor.from_gzip_html("https://example.com", f.read())
+            >>> extractor.extract_text()
+            >>> result = extractor.extract()
+        """
+        extractor = cls.__new__(cls)
+        extractor._extractor = _PyWebExtractor.from_gzip_html(url, data)
+        extractor._activities_set = False
+        return extractor
+    
     def extract_text(self, language_detection: bool = False) -> None:
         """
         Enable text extraction from the page.
@@ -81,12 +122,103 @@ class WebExtractor:
         self._extractor.extract_text(language_detection)
         self._activities_set = True
     
+    def set_inline_link_mode(self, mode: Literal["drop", "text_only", "with_url"]) -> None:
+        """
+        Control how inline hyperlinks are rendered in extracted text.
+        
+        Args:
+            mode: "drop" removes anchors and their text, "text_only" keeps the anchor text (default),
+                "with_url" keeps the anchor text followed by its resolved URL, e.g. "foo (https://...)".
+                Links inside boilerplate (navigation, footers, etc.) are skipped in every mode.
+        """
+        self._extractor.set_inline_link_mode(mode)
+    
+    def set_min_content_length(self, chars: int) -> None:
+        """
+        Set the length (in characters, not bytes) a main-content container must
+        exceed before it is used instead of falling back to the whole body (default: 50).
+        
+        Args:
+            chars: Minimum number of characters
+        """
+        self._extractor.set_min_content_length(chars)
+    
+    def set_compute_simhash(self, enabled: bool) -> None:
+        """
+        Compute a 64-bit simhash of the extracted text (result.simhash) to
+        cluster near-identical pages by Hamming distance. Off by default since
+        it costs extra work; enabling it extracts the text even without
+        extract_text().
+        
+        Args:
+            enabled: Whether to compute the simhash
+        """
+        self._extractor.set_compute_simhash(enabled)
+    
+    def set_unicode_normalization(self, form: Literal["nfc", "nfkc", "none"]) -> None:
+        """
+        Set the Unicode normalization applied to extracted text after whitespace
+        is collapsed (default: "nfc").
+        
+        Args:
+            form: "nfc" composes decomposed characters (e.g. "e" + combining accent -> "é"),
+                "nfkc" additionally folds compatibility forms such as ligatures and
+                full-width characters, "none" leaves the text untouched
+        """
+        self._extractor.set_unicode_normalization(form)
+    
+    def set_text_sanitize(
+        self,
+        *,
+        strip_emoji: bool = False,
+        strip_zero_width: bool = False,
+        normalize_unicode: bool = False
+    ) -> None:
+        """
+        Configure post-processing of the extracted text. Sanitization runs
+        before language detection, so detection sees the cleaned text.
+        
+        Args:
+            strip_emoji: Remove emoji, flag sequences and whole ZWJ sequences
+                such as 👨‍👩‍👧; text symbols such as ✓ and ⌘ are kept (default: False)
+            strip_zero_width: Remove zero-width characters, bidi marks and control characters (default: False)
+            normalize_unicode: Normalize the text to Unicode NFC (default: False)
+        """
+        self._extractor.set_text_sanitize(strip_emoji, strip_zero_width, normalize_unicode)
+    
+    def set_allowed_languages(self, langs: List[str]) -> None:
+        """
+        Only accept pages whose detected language is in the allowlist.
+        Enables language detection; other pages get
+        result.language_allowed == False (see set_language_filter_mode).
+        
+        Args:
+            langs: ISO 639-3 codes as reported in result.language, e.g.
+                ["eng", "deu"]; an empty list accepts every page
+        """
+        self._extractor.set_allowed_languages(langs)
+    
+    def set_language_filter_mode(self, mode: Literal["flag", "skip", "reject"]) -> None:
+        """
+        Choose what happens to pages outside the language allowlist.
+        
+        Args:
+            mode: "flag" extracts as usual and only sets result.language_allowed
+                (default), "skip" also skips the extractors that would run after
+                language detection, "reject" makes run() raise RuntimeError
+        
+        Raises:
+            RuntimeError: If the mode is unknown
+        """
+        self._extractor.set_language_filter_mode(mode)
+    
     def extract_links(
         self,
         *,
         internal: bool = False,
         external: bool = False,
-        all: bool = False
+        all: bool = False,
+        with_context: bool = False
     ) -> None:
         """
         Enable link extraction from the page.
@@ -95,6 +227,9 @@ class WebExtractor:
             internal: Extract only internal links (same domain) (default: False)
             external: Extract only external links (different domain) (default: False)
             all: Extract all links (both internal and external) (default: False)
+            with_context: Add a "context" entry to each link with up to ~100
+                characters of text before and after it from its block element
+                (default: False, since it costs an extra walk per link)
             
         Note:
             If no options are specified (all False), defaults to extracting all links.
@@ -111,9 +246,42 @@ class WebExtractor:
         else:
             # If nothing specified, default to all
             fields = ["all"]
+        if with_context:
+            fields.append("with_context")
         self._extractor.extract_links(fields)
         self._activities_set = True
     
+    def set_allowed_schemes(self, schemes: List[str]) -> None:
+        """
+        Set the URL schemes kept during link extraction (default: ["http", "https"]).
+        Links with any other scheme, such as javascript: or data:, are dropped.
+        
+        Args:
+            schemes: Allowed schemes, e.g. ["http", "https", "ftp"]
+        """
+        self._extractor.set_allowed_schemes(schemes)
+    
+    def set_max_links(self, total: int) -> None:
+        """
+        Limit the total number of extracted links. The first links found are kept;
+        when links are dropped, links['summary']['truncated'] is True and
+        links['summary']['dropped'] holds the number of dropped links.
+        
+        Args:
+            total: Maximum number of links to keep
+        """
+        self._extractor.set_max_links(total)
+    
+    def set_max_links_per_domain(self, n: int) -> None:
+        """
+        Limit the number of extracted links per domain. The first links found for
+        each domain are kept; dropped links are counted in links['summary'].
+        
+        Args:
+            n: Maximum number of links to keep per domain
+        """
+        self._extractor.set_max_links_per_domain(n)
+    
     def extract_socials(
         self,
         fields: Optional[List[str]] = None
@@ -128,6 +296,7 @@ class WebExtractor:
             >>> extractor.extract_socials()  # Extract all fields
             >>> extractor.extract_socials(fields=["twitter:card", "og:title"])  # Extract only specific fields
             >>> extractor.extract_socials(fields=["all"])  # Explicitly extract all fields
+            >>> extractor.extract_socials(fields=["video"])  # The og:video cluster (og_video, og_video_type, ...)
         """
         if fields is None:
             warnings.warn(
@@ -189,6 +358,62 @@ class WebExtractor:
         self._extractor.extract_product(fields)
         self._activities_set = True
     
+    def set_max_product_reviews(self, n: int) -> None:
+        """
+        Limit the number of individual reviews extracted into the
+        'product_reviews' field and product_info['reviews']. The first
+        declared reviews are kept.
+        
+        Args:
+            n: Maximum number of reviews to keep
+        """
+        self._extractor.set_max_product_reviews(n)
+    
+    def set_sale_epsilon(self, epsilon: float) -> None:
+        """
+        Set how far the original price must exceed the current price before
+        'product_on_sale' is True (default 0.01).
+        
+        Args:
+            epsilon: Minimum price difference, in the product's currency
+        """
+        self._extractor.set_sale_epsilon(epsilon)
+    
+    def set_weight_unit(self, unit: Literal["g", "kg", "lb", "oz"]) -> None:
+        """
+        Set the unit 'product_weight' is converted to (default "g").
+        
+        Args:
+            unit: "g", "kg", "lb" or "oz"
+        """
+        self._extractor.set_weight_unit(unit)
+    
+    def set_dimension_unit(self, unit: Literal["mm", "cm", "m", "in"]) -> None:
+        """
+        Set the unit 'product_width', 'product_height' and 'product_depth'
+        are converted to (default "mm").
+        
+        Args:
+            unit: "mm", "cm", "m" or "in"
+        """
+        self._extractor.set_dimension_unit(unit)
+    
+    def extract_products_list(self, max: int = 100) -> None:
+        """
+        Enable extraction of every product on a listing page (category or
+        search results) into the 'products' result: ItemList entries and
+        standalone Product JSON-LD objects, or repeated Product microdata
+        cards when no JSON-LD products are declared. Each entry is a dict
+        with 'product_title', 'product_price', 'product_currency',
+        'product_image', 'product_url' (absolute) and 'product_rating' when
+        present, in declaration order.
+        
+        Args:
+            max: Maximum number of products to keep
+        """
+        self._extractor.extract_products_list(max)
+        self._activities_set = True
+    
     def extract_article(
         self,
         fields: Optional[List[str]] = None
@@ -215,6 +440,212 @@ class WebExtractor:
         self._extractor.extract_article(fields)
         self._activities_set = True
     
+    def extract_lists(self) -> None:
+        """
+        Enable extraction of <ul>/<ol> lists as nested data (e.g. recipe
+        ingredients or spec bullet points). Navigation and other boilerplate
+        lists are skipped.
+        """
+        self._extractor.extract_lists()
+        self._activities_set = True
+    
+    def extract_recipe(self) -> None:
+        """
+        Enable extraction of a schema.org Recipe (JSON-LD or microdata): name,
+        ingredients, instructions, prep/cook/total time in minutes, yield and calories.
+        """
+        self._extractor.extract_recipe()
+        self._activities_set = True
+    
+    def extract_event(self) -> None:
+        """
+        Enable extraction of a schema.org Event (or subtype such as MusicEvent)
+        from JSON-LD or microdata: name, ISO start/end dates, location, offers
+        and performers.
+        """
+        self._extractor.extract_event()
+        self._activities_set = True
+    
+    def extract_faqs(self) -> None:
+        """
+        Enable extraction of question/answer pairs from schema.org FAQPage
+        JSON-LD. Answer text is returned without HTML markup.
+        """
+        self._extractor.extract_faqs()
+        self._activities_set = True
+    
+    def extract_contacts(self) -> None:
+        """
+        Enable detection of phone numbers from tel: links and the visible page
+        text, validated and formatted as E.164. Email addresses are a separate
+        activity (see extract_emails).
+        """
+        self._extractor.extract_contacts()
+        self._activities_set = True
+    
+    def extract_emails(self) -> None:
+        """
+        Enable detection of email addresses in mailto: links and the visible
+        page text, deduplicated case-insensitively (first-seen casing is kept).
+        """
+        self._extractor.extract_emails()
+        self._activities_set = True
+    
+    def extract_preview_image(self) -> None:
+        """
+        Enable selection of the best image for link previews: og:image, then
+        twitter:image, the first JSON-LD image and the largest <img> by
+        declared width x height (read it from preview_image).
+        """
+        self._extractor.extract_preview_image()
+        self._activities_set = True
+    
+    def extract_alternate_links(self) -> None:
+        """
+        Enable grouping of every <link> element by rel value (read it from
+        alternate_links), with hrefs resolved absolute.
+        """
+        self._extractor.extract_alternate_links()
+        self._activities_set = True
+    
+    def set_phone_region(self, region: str) -> None:
+        """
+        Set the default region for phone numbers written without a "+" prefix.
+        Without it only international numbers are detected.
+        
+        Args:
+            region: ISO 3166 region code, e.g. "US" or "DE"
+        
+        Raises:
+            RuntimeError: If the region code is unknown
+        """
+        self._extractor.set_phone_region(region)
+    
+    def set_follow_meta_refresh(self, enabled: bool) -> None:
+        """
+        Follow <meta http-equiv="refresh"> redirects when running: when the
+        delay is at most 5 seconds the target page is fetched and extracted
+        instead, up to five hops and never back to a page already visited.
+        result.url is then the page the data came from; a hop that fails keeps
+        the last page's result. Disabled by default.
+        
+        Args:
+            enabled: Whether to follow meta refresh redirects
+        """
+        self._extractor.set_follow_meta_refresh(enabled)
+    
+    def set_fetch_oembed(self, enabled: bool) -> None:
+        """
+        Fetch the JSON oEmbed document the page links to when running, with
+        the configured client (its timeout applies; bodies over 256 KiB are
+        dropped), and attach its fields as result.oembed. A failed fetch
+        leaves just result.oembed_url. Disabled by default.
+        
+        Args:
+            enabled: Whether to fetch the oEmbed document
+        """
+        self._extractor.set_fetch_oembed(enabled)
+    
+    def set_parallel_passes(self, enabled: bool) -> None:
+        """
+        Spread the extraction passes (text, products, links, ...) of pages
+        over 256 KiB across worker threads, each parsing its own copy of the
+        document. Helps single large pages with several activities enabled.
+        batch_extract keeps one thread per page regardless. Disabled by
+        default.
+        
+        Args:
+            enabled: Whether to run the passes of large pages in parallel
+        """
+        self._extractor.set_parallel_passes(enabled)
+    
+    def set_strict_fields(self, strict: bool) -> None:
+        """
+        Enable strict field validation. When enabled, running the extractor
+        raises RuntimeError("... unknown field: <name>") if a requested field
+        (after alias normalization) doesn't exist, instead of silently skipping it.
+        Disabled by default.
+        
+        Args:
+            strict: Whether to reject unknown field names
+        """
+        self._extractor.set_strict_fields(strict)
+    
+    def set_collect_provenance(self, enabled: bool) -> None:
+        """
+        Record where each article and product value came from, returned
+        as result.article_sources / result.product_sources (field name ->
+        "meta", "jsonld", "microdata", "css", ...). Disabled by default.
+        
+        Args:
+            enabled: Whether to collect value sources
+        """
+        self._extractor.set_collect_provenance(enabled)
+    
+    def set_structured_only(self, enabled: bool) -> None:
+        """
+        Trust only structured data (JSON-LD, meta tags, microdata). Product
+        and article extraction skip their CSS-selector, <h1>/<title> and
+        text-scan fallbacks, so a page showing its price only in a .price
+        element gets no product_price. Disabled by default.
+        
+        Args:
+            enabled: Whether to skip the markup fallbacks
+        """
+        self._extractor.set_structured_only(enabled)
+    
+    def set_max_json_ld_bytes(self, bytes: int) -> None:
+        """
+        Limit the total size of JSON-LD parsed per page (default: 2 MiB).
+        Scripts that would exceed the limit are skipped, so pages embedding
+        megabytes of JSON-LD don't slow down extraction.
+        
+        Args:
+            bytes: Maximum total JSON-LD size in bytes
+        """
+        self._extractor.set_max_json_ld_bytes(bytes)
+    
+    def validate(self) -> List[str]:
+        """
+        Check the configuration without making any request: URL, custom
+        headers, Accept-Language, field names and the date confidence
+        threshold. Useful before starting a long crawl.
+        
+        Returns:
+            Every problem found as an error string; an empty list means the
+            configuration is valid
+        """
+        return self._extractor.validate()
+    
+    def extract_all(self) -> None:
+        """
+        Enable every extraction activity at once: text with language detection,
+        links, socials, videos, products and articles with all fields.
+        """
+        self._extractor.extract_all()
+        self._activities_set = True
+    
+    def set_date_confidence_threshold(self, threshold: float) -> None:
+        """
+        Set the minimum confidence a publication or modified date needs to be
+        reported as `best_publication_date` / `best_modified_date` (default: 0.2).
+        
+        Args:
+            threshold: Confidence threshold between 0.0 and 1.0
+        """
+        self._extractor.set_date_confidence_threshold(threshold)
+    
+    def add_date_cues(self, cues: List[str]) -> None:
+        """
+        Add words that mark a nearby date in the page body as the publication
+        date, on top of the built-in English/German/French/Spanish cues
+        ("published", "veröffentlicht", "publié", "publicado", ...).
+        
+        Args:
+            cues: Cue words or phrases, matched case-insensitively
+        """
+        self._extractor.add_date_cues(cues)
+    
     def set_timeout(self, timeout_secs: float) -> None:
         """
         Set the HTTP request timeout in seconds.
@@ -262,6 +693,63 @@ class WebExtractor:
         """
         self._extractor.set_headers(headers)
     
+    def set_accept_language(self, langs: str) -> None:
+        """
+        Set the Accept-Language header to request a specific locale.
+        
+        Args:
+            langs: Accept-Language value (e.g., "fr-FR,fr;q=0.9")
+        """
+        self._extractor.set_accept_language(langs)
+    
+    def set_danger_accept_invalid_certs(self, enabled: bool) -> None:
+        """
+        Accept any TLS certificate, including self-signed, expired and
+        wrong-host ones.
+        
+        Warning: this disables protection against man-in-the-middle attacks.
+        Only use it for internal or staging hosts you control, and prefer
+        add_root_certificate() where possible.
+        
+        Args:
+            enabled: Whether to skip certificate verification
+        """
+        self._extractor.set_danger_accept_invalid_certs(enabled)
+    
+    def add_root_certificate(self, pem: bytes) -> None:
+        """
+        Trust an additional root certificate (e.g. an internal CA) while
+        keeping certificate verification enabled.
+        
+        Args:
+            pem: PEM-encoded certificate
+        
+        Raises:
+            RuntimeError: If pem is not a valid PEM certificate
+        """
+        self._extractor.add_root_certificate(pem)
+    
+    def set_http2_prior_knowledge(self, enabled: bool) -> None:
+        """
+        Talk HTTP/2 from the first byte instead of negotiating it, so requests
+        to one host are multiplexed over a single connection.
+        
+        Warning: requests fail against servers that only speak HTTP/1.1.
+        
+        Args:
+            enabled: Whether to assume HTTP/2 support
+        """
+        self._extractor.set_http2_prior_knowledge(enabled)
+    
+    def set_pool_idle_timeout(self, idle_secs: int) -> None:
+        """
+        Keep idle connections open for reuse for this many seconds.
+        
+        Args:
+            idle_secs: Idle timeout in seconds
+        """
+        self._extractor.set_pool_idle_timeout(idle_secs)
+    
     def run(self):
         """
         Execute the extraction with the configured activities.
@@ -280,6 +768,27 @@ class WebExtractor:
         result = self._extractor.run()
         return ExtractionResult(result)
     
+    def extract(self):
+        """
+        Execute the extraction synchronously on the provided HTML, without
+        creating an async runtime or HTTP client. Faster than run() when
+        extracting from HTML strings.
+        
+        Returns:
+            ExtractionResult: An object containing the extracted data
+            
+        Raises:
+            RuntimeError: If activities are not set, no HTML was provided, or
+                robots.txt checking is enabled (use run() instead)
+        """
+        if not self._activities_set:
+            raise RuntimeError(
+                "No activities configured. Call extract_text(), extract_article(), extract_links(), etc. before extract()."
+            )
+        
+        result = self._extractor.extract()
+        return ExtractionResult(result)
+    
     def enable_robots_check(self) -> None:
         """
         Enable robots.txt checking with in-memory cache.
@@ -354,6 +863,42 @@ class WebExtractor:
         """
         self._extractor.clear_robots_cache()
     
+    @staticmethod
+    def parse_robots_directives(content: str) -> Dict[str, Any]:
+        """
+        Parse the non-rule directives of a robots.txt, without fetching or
+        caching anything.
+        
+        Args:
+            content: The robots.txt content as a string
+        
+        Returns:
+            Dict with 'sitemaps' (list of URLs), 'host' (str or None) and
+            'crawl_delays' (dict of lowercased user agent to delay in seconds)
+        
+        Example:
+            >>> WebExtractor.parse_robots_directives("User-agent: *\nCrawl-delay: 5")
+            {'sitemaps': [], 'host': None, 'crawl_delays': {'*': 5.0}}
+        """
+        if _PyWebExtractor is None:
+            raise ImportError(
+                "Rust extension not found. Please build the package first:\n"
+                "  maturin develop  # for development\n"
+                "  maturin build    # for distribution"
+            )
+        return _PyWebExtractor.parse_robots_directives(content)
+    
+    def robots_stats(self) -> Optional[Dict[str, int]]:
+        """
+        robots.txt cache statistics, useful for tuning the Redis TTL.
+        
+        Returns:
+            Dict with 'memory_hits', 'memory_misses', 'redis_hits',
+            'redis_misses' and 'fetches' counts, or None if robots.txt
+            checking is not enabled
+        """
+        return self._extractor.robots_stats()
+    
     def __enter__(self):
         """
         Context manager entry. Returns self for use with 'with' statement.
@@ -401,6 +946,30 @@ class ExtractionResult:
         """
         return self._result.links
     
+    @property
+    def title(self) -> Optional[str]:
+        """Document title from <title>, falling back to og:title, twitter:title and the first <h1>."""
+        return self._result.title
+    
+    @property
+    def description(self) -> Optional[str]:
+        """Document description from meta description, falling back to og:description and twitter:description."""
+        return self._result.description
+    
+    @property
+    def preview_image(self) -> Optional[str]:
+        """
+        Best single image for link previews: og:image, then twitter:image,
+        JSON-LD image and the largest <img> by declared width x height,
+        resolved absolute (if extract_preview_image was set).
+        """
+        return self._result.preview_image
+    
+    @property
+    def amp_url(self) -> Optional[str]:
+        """Absolute URL of the page's AMP variant (or the page itself when it is AMP), if any."""
+        return self._result.amp_url
+    
     @property
     def language(self) -> Optional[str]:
         """Detected language code (if extract_text has language_detection enabled)."""
@@ -411,6 +980,88 @@ class ExtractionResult:
         """Confidence score for language detection (0.0 to 1.0)."""
         return self._result.language_confidence
     
+    @property
+    def language_allowed(self) -> Optional[bool]:
+        """
+        Whether the detected language is in the allowlist set with
+        set_allowed_languages(); None without an allowlist or when no
+        language was detected.
+        """
+        return self._result.language_allowed
+    
+    @property
+    def alternate_links(self) -> Optional[Dict[str, List[Dict[str, str]]]]:
+        """
+        Every <link> element of the page keyed by rel value ("stylesheet",
+        "canonical", "alternate", "icon", "preload", ...), each a list of
+        {"url": <absolute href>, "text": <title attribute or "">} in
+        document order (if extract_alternate_links was set). None when the
+        page has no <link> elements.
+        """
+        return self._result.alternate_links
+    
+    @property
+    def meta_refresh(self) -> Optional[Dict[str, Any]]:
+        """
+        Client-side redirect declared by <meta http-equiv="refresh">, as
+        {"url": <absolute target>, "delay": <seconds>}; None when the page
+        has none.
+        """
+        return self._result.meta_refresh
+    
+    @property
+    def oembed_url(self) -> Optional[str]:
+        """
+        Absolute oEmbed endpoint from <link rel="alternate"
+        type="application/json+oembed"> (or the XML variant); None when the
+        page declares none.
+        """
+        return self._result.oembed_url
+    
+    @property
+    def oembed(self) -> Optional[Dict[str, Any]]:
+        """
+        Fetched oEmbed fields (title, author_name, author_url, provider_name,
+        thumbnail_url, html), each possibly None. Only set when
+        set_fetch_oembed(True) was used and the fetch succeeded.
+        """
+        return self._result.oembed
+    
+    @property
+    def robots_warning(self) -> Optional[str]:
+        """
+        Set when robots checking is enabled and the site's robots.txt could
+        not be parsed; extraction then proceeds as if everything is allowed.
+        """
+        return self._result.robots_warning
+    
+    @property
+    def warnings(self) -> List[str]:
+        """
+        Problems that didn't stop the run, such as requested field names no
+        extractor knows ("unknown socials field: titel"). Empty when there
+        were none; set_strict_fields(True) raises on unknown fields instead.
+        """
+        return self._result.warnings
+    
+    @property
+    def content_hash(self) -> Optional[str]:
+        """
+        Hex SHA-256 of the extracted text (if extract_text was set), computed
+        after lowercasing and collapsing whitespace. Equal hashes mean the
+        pages carry the same main text.
+        """
+        return self._result.content_hash
+    
+    @property
+    def simhash(self) -> Optional[int]:
+        """
+        64-bit simhash of the extracted text (if set_compute_simhash(True) was
+        called). Near-duplicate pages differ in only a few bits:
+        bin(a.simhash ^ b.simhash).count("1") is their Hamming distance.
+        """
+        return self._result.simhash
+    
     @property
     def grouped_links(self) -> Optional[Dict[str, Any]]:
         """
@@ -428,6 +1079,18 @@ class ExtractionResult:
         """
         return self._result.socials
     
+    @property
+    def socials_info(self) -> Optional[Dict[str, Any]]:
+        """
+        Typed social metadata (if extract_socials was set), as
+        {'open_graph': {...}, 'twitter': {...}}. open_graph has url, type,
+        title, description, images (list of dicts with 'url', 'width' and
+        'height' (ints) and 'alt'), site_name, locale and locale_alternates
+        (list); twitter has card, site, creator, title, description, image
+        and image_alt.
+        """
+        return self._result.socials_info
+    
     @property
     def videos(self) -> Optional[Dict[str, str]]:
         """
@@ -445,13 +1108,128 @@ class ExtractionResult:
         return self._result.product
     
     @property
-    def article(self) -> Optional[Dict[str, str]]:
+    def product_info(self) -> Optional[Dict[str, Any]]:
+        """
+        Typed product metadata (if extract_product was set).
+        Contains title, brand, sku, price and original_price (floats, with
+        thousands separators and decimal commas resolved; the low end when
+        price_is_range is True), currency, currency_inferred (True when the
+        currency was inferred from the price symbol rather than declared),
+        discount_percent (float, one decimal) and on_sale (bool; None unless
+        both prices parsed in the same currency),
+        availability ("InStock", "OutOfStock", "PreOrder", "BackOrder",
+        "Discontinued", "LimitedAvailability" or "Unknown"), rating (float),
+        review_count (int), images (list) and reviews (list of dicts with
+        'author', 'rating' (float), 'date', 'title' and 'body'). Only the
+        values behind the requested product fields are filled in (every one
+        for "all"); the currency comes with any price.
+        """
+        return self._result.product_info
+    
+    @property
+    def product_sources(self) -> Optional[Dict[str, str]]:
+        """
+        Where each product value came from (if set_collect_provenance(True)
+        was called), keyed like product: 'meta', 'jsonld', 'microdata',
+        'css', 'h1', 'breadcrumb', 'manufacturer' or 'jsonld_pricespec'.
+        Only fields found through a tiered lookup have a source.
+        """
+        return self._result.product_sources
+    
+    @property
+    def products(self) -> Optional[List[Dict[str, str]]]:
+        """
+        Products on a listing page (if extract_products_list was set), each
+        a dict keyed like the product fields ('product_title',
+        'product_price', 'product_currency', 'product_image', 'product_url',
+        'product_rating').
+        """
+        return self._result.products
+    
+    @property
+    def article(self) -> Optional[Dict[str, Any]]:
         """
         Extracted article metadata dictionary (if extract_article was set).
         Contains article title, author, description, publication_date, categories, etc.
+        List-valued fields (authors, author_profiles, publication_date,
+        modified_date_confidence, all_tags, sections) are lists; publication_date and
+        modified_date_confidence hold dicts with 'date' and 'confidence'.
         """
         return self._result.article
     
+    @property
+    def article_info(self) -> Optional[Dict[str, Any]]:
+        """
+        Typed article metadata (if extract_article was set).
+        Contains article_type, is_article (bool), title, description, authors (list),
+        published/modified (ISO 8601), section, tags (list), dates_with_confidence and
+        modified_dates_with_confidence (lists of dicts), and word_count /
+        reading_time (integers). Only the values behind the requested article
+        fields are filled in (every one for "all").
+        """
+        return self._result.article_info
+    
+    @property
+    def article_sources(self) -> Optional[Dict[str, str]]:
+        """
+        Where each article value came from (if set_collect_provenance(True)
+        was called), keyed like article: 'meta', 'jsonld', 'microdata',
+        'link', 'title_tag', 'h1', 'content', ... Only fields found through a
+        tiered lookup have a source.
+        """
+        return self._result.article_sources
+    
+    @property
+    def lists(self) -> Optional[List[Dict[str, Any]]]:
+        """
+        Extracted lists (if extract_lists was set). Each list is a dict with
+        'ordered' (bool) and 'items'; each item has 'text' and 'children'
+        (nested lists in the same shape).
+        """
+        return self._result.lists
+    
+    @property
+    def recipe(self) -> Optional[Dict[str, Any]]:
+        """
+        Extracted recipe (if extract_recipe was set and the page declares one).
+        A dict with 'name', 'ingredients' and 'instructions' (lists of strings),
+        'prep_time', 'cook_time' and 'total_time' (minutes), 'yield' and 'calories'.
+        """
+        return self._result.recipe
+    
+    @property
+    def event(self) -> Optional[Dict[str, Any]]:
+        """
+        Extracted event (if extract_event was set and the page declares one).
+        A dict with 'event_type', 'name', 'start_date', 'end_date' (ISO 8601),
+        'location' (dict with 'name', 'address', 'url'), 'offers' (list of dicts
+        with 'price', 'currency', 'url') and 'performers' (list of names).
+        """
+        return self._result.event
+    
+    @property
+    def faqs(self) -> Optional[List[Dict[str, str]]]:
+        """
+        Extracted FAQ entries (if extract_faqs was set), each a dict with
+        'question' and 'answer'.
+        """
+        return self._result.faqs
+    
+    @property
+    def phones(self) -> Optional[List[str]]:
+        """
+        Detected phone numbers (if extract_contacts was set), deduplicated and
+        formatted as E.164.
+        """
+        return self._result.phones
+    
+    @property
+    def emails(self) -> Optional[List[str]]:
+        """
+        Detected email addresses (if extract_emails was set).
+        """
+        return self._result.emails
+    
     @property
     def content(self) -> Optional[Dict[str, Any]]:
         """
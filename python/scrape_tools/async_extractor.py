@@ -3,7 +3,7 @@ Async WebExtractor - Async version for better performance with multiple URLs.
 """
 
 import warnings
-from typing import Optional, List, Dict, Any, Union, Literal
+from typing import Optional, List, Dict, Any, Union, Literal, Callable
 import asyncio
 
 from .constants import FIELDS_WARNING_MESSAGE
@@ -91,7 +91,8 @@ class AsyncWebExtractor:
         *,
         internal: bool = False,
         external: bool = False,
-        all: bool = False
+        all: bool = False,
+        with_context: bool = False
     ) -> None:
         """
         Enable link extraction from the page.
@@ -100,6 +101,9 @@ class AsyncWebExtractor:
             internal: Extract only internal links (same domain) (default: False)
             external: Extract only external links (different domain) (default: False)
             all: Extract all links (both internal and external) (default: False)
+            with_context: Add a "context" entry to each link with up to ~100
+                characters of text before and after it from its block element
+                (default: False, since it costs an extra walk per link)
             
         Note:
             If no options are specified (all False), defaults to extracting all links.
@@ -116,9 +120,42 @@ class AsyncWebExtractor:
         else:
             # If nothing specified, default to all
             fields = ["all"]
+        if with_context:
+            fields.append("with_context")
         self._extractor.extract_links(fields)
         self._activities_set = True
     
+    def set_allowed_schemes(self, schemes: List[str]) -> None:
+        """
+        Set the URL schemes kept during link extraction (default: ["http", "https"]).
+        Links with any other scheme, such as javascript: or data:, are dropped.
+        
+        Args:
+            schemes: Allowed schemes, e.g. ["http", "https", "ftp"]
+        """
+        self._extractor.set_allowed_schemes(schemes)
+    
+    def set_max_links(self, total: int) -> None:
+        """
+        Limit the total number of extracted links. The first links found are kept;
+        when links are dropped, links['summary']['truncated'] is True and
+        links['summary']['dropped'] holds the number of dropped links.
+        
+        Args:
+            total: Maximum number of links to keep
+        """
+        self._extractor.set_max_links(total)
+    
+    def set_max_links_per_domain(self, n: int) -> None:
+        """
+        Limit the number of extracted links per domain. The first links found for
+        each domain are kept; dropped links are counted in links['summary'].
+        
+        Args:
+            n: Maximum number of links to keep per domain
+        """
+        self._extractor.set_max_links_per_domain(n)
+    
     def extract_socials(
         self,
         fields: Optional[List[str]] = None
@@ -133,6 +170,7 @@ class AsyncWebExtractor:
             >>> extractor.extract_socials()  # Extract all fields
             >>> extractor.extract_socials(fields=["twitter:card", "og:title"])  # Extract only specific fields
             >>> extractor.extract_socials(fields=["all"])  # Explicitly extract all fields
+            >>> extractor.extract_socials(fields=["video"])  # The og:video cluster (og_video, og_video_type, ...)
         """
         if fields is None:
             warnings.warn(
@@ -194,6 +232,62 @@ class AsyncWebExtractor:
         self._extractor.extract_product(fields)
         self._activities_set = True
     
+    def set_max_product_reviews(self, n: int) -> None:
+        """
+        Limit the number of individual reviews extracted into the
+        'product_reviews' field and product_info['reviews']. The first
+        declared reviews are kept.
+        
+        Args:
+            n: Maximum number of reviews to keep
+        """
+        self._extractor.set_max_product_reviews(n)
+    
+    def set_sale_epsilon(self, epsilon: float) -> None:
+        """
+        Set how far the original price must exceed the current price before
+        'product_on_sale' is True (default 0.01).
+        
+        Args:
+            epsilon: Minimum price difference, in the product's currency
+        """
+        self._extractor.set_sale_epsilon(epsilon)
+    
+    def set_weight_unit(self, unit: Literal["g", "kg", "lb", "oz"]) -> None:
+        """
+        Set the unit 'product_weight' is converted to (default "g").
+        
+        Args:
+            unit: "g", "kg", "lb" or "oz"
+        """
+        self._extractor.set_weight_unit(unit)
+    
+    def set_dimension_unit(self, unit: Literal["mm", "cm", "m", "in"]) -> None:
+        """
+        Set the unit 'product_width', 'product_height' and 'product_depth'
+        are converted to (default "mm").
+        
+        Args:
+            unit: "mm", "cm", "m" or "in"
+        """
+        self._extractor.set_dimension_unit(unit)
+    
+    def extract_products_list(self, max: int = 100) -> None:
+        """
+        Enable extraction of every product on a listing page (category or
+        search results) into the 'products' result: ItemList entries and
+        standalone Product JSON-LD objects, or repeated Product microdata
+        cards when no JSON-LD products are declared. Each entry is a dict
+        with 'product_title', 'product_price', 'product_currency',
+        'product_image', 'product_url' (absolute) and 'product_rating' when
+        present, in declaration order.
+        
+        Args:
+            max: Maximum number of products to keep
+        """
+        self._extractor.extract_products_list(max)
+        self._activities_set = True
+    
     def extract_article(
         self,
         fields: Optional[List[str]] = None
@@ -220,6 +314,229 @@ class AsyncWebExtractor:
         self._extractor.extract_article(fields)
         self._activities_set = True
     
+    def extract_lists(self) -> None:
+        """
+        Enable extraction of <ul>/<ol> lists as nested data (e.g. recipe
+        ingredients or spec bullet points). Navigation and other boilerplate
+        lists are skipped.
+        """
+        self._extractor.extract_lists()
+        self._activities_set = True
+    
+    def extract_recipe(self) -> None:
+        """
+        Enable extraction of a schema.org Recipe (JSON-LD or microdata): name,
+        ingredients, instructions, prep/cook/total time in minutes, yield and calories.
+        """
+        self._extractor.extract_recipe()
+        self._activities_set = True
+    
+    def extract_event(self) -> None:
+        """
+        Enable extraction of a schema.org Event (or subtype such as MusicEvent)
+        from JSON-LD or microdata: name, ISO start/end dates, location, offers
+        and performers.
+        """
+        self._extractor.extract_event()
+        self._activities_set = True
+    
+    def extract_faqs(self) -> None:
+        """
+        Enable extraction of question/answer pairs from schema.org FAQPage
+        JSON-LD. Answer text is returned without HTML markup.
+        """
+        self._extractor.extract_faqs()
+        self._activities_set = True
+    
+    def extract_contacts(self) -> None:
+        """
+        Enable detection of phone numbers from tel: links and the visible page
+        text, validated and formatted as E.164. Email addresses are a separate
+        activity (see extract_emails).
+        """
+        self._extractor.extract_contacts()
+        self._activities_set = True
+    
+    def extract_emails(self) -> None:
+        """
+        Enable detection of email addresses in mailto: links and the visible
+        page text, deduplicated case-insensitively (first-seen casing is kept).
+        """
+        self._extractor.extract_emails()
+        self._activities_set = True
+    
+    def extract_preview_image(self) -> None:
+        """
+        Enable selection of the best image for link previews: og:image, then
+        twitter:image, the first JSON-LD image and the largest <img> by
+        declared width x height (read it from preview_image).
+        """
+        self._extractor.extract_preview_image()
+        self._activities_set = True
+    
+    def extract_alternate_links(self) -> None:
+        """
+        Enable grouping of every <link> element by rel value (read it from
+        alternate_links), with hrefs resolved absolute.
+        """
+        self._extractor.extract_alternate_links()
+        self._activities_set = True
+    
+    def set_phone_region(self, region: str) -> None:
+        """
+        Set the default region for phone numbers written without a "+" prefix.
+        Without it only international numbers are detected.
+        
+        Args:
+            region: ISO 3166 region code, e.g. "US" or "DE"
+        
+        Raises:
+            RuntimeError: If the region code is unknown
+        """
+        self._extractor.set_phone_region(region)
+    
+    def set_compute_simhash(self, enabled: bool) -> None:
+        """
+        Compute a 64-bit simhash of the extracted text (result.simhash) to
+        cluster near-identical pages by Hamming distance. Off by default since
+        it costs extra work; enabling it extracts the text even without
+        extract_text().
+        
+        Args:
+            enabled: Whether to compute the simhash
+        """
+        self._extractor.set_compute_simhash(enabled)
+    
+    def set_allowed_languages(self, langs: List[str]) -> None:
+        """
+        Only accept pages whose detected language is in the allowlist.
+        Enables language detection; other pages get
+        result.language_allowed == False (see set_language_filter_mode).
+        
+        Args:
+            langs: ISO 639-3 codes as reported in result.language, e.g.
+                ["eng", "deu"]; an empty list accepts every page
+        """
+        self._extractor.set_allowed_languages(langs)
+    
+    def set_language_filter_mode(self, mode: Literal["flag", "skip", "reject"]) -> None:
+        """
+        Choose what happens to pages outside the language allowlist.
+        
+        Args:
+            mode: "flag" extracts as usual and only sets result.language_allowed
+                (default), "skip" also skips the extractors that would run after
+                language detection, "reject" makes run() raise RuntimeError
+        
+        Raises:
+            RuntimeError: If the mode is unknown
+        """
+        self._extractor.set_language_filter_mode(mode)
+    
+    def set_follow_meta_refresh(self, enabled: bool) -> None:
+        """
+        Follow <meta http-equiv="refresh"> redirects when running: when the
+        delay is at most 5 seconds the target page is fetched and extracted
+        instead, up to five hops and never back to a page already visited.
+        result.url is then the page the data came from; a hop that fails keeps
+        the last page's result. Disabled by default.
+        
+        Args:
+            enabled: Whether to follow meta refresh redirects
+        """
+        self._extractor.set_follow_meta_refresh(enabled)
+    
+    def set_fetch_oembed(self, enabled: bool) -> None:
+        """
+        Fetch the JSON oEmbed document the page links to when running, with
+        the configured client (its timeout applies; bodies over 256 KiB are
+        dropped), and attach its fields as result.oembed. A failed fetch
+        leaves just result.oembed_url. Disabled by default.
+        
+        Args:
+            enabled: Whether to fetch the oEmbed document
+        """
+        self._extractor.set_fetch_oembed(enabled)
+    
+    def set_parallel_passes(self, enabled: bool) -> None:
+        """
+        Spread the extraction passes (text, products, links, ...) of pages
+        over 256 KiB across worker threads, each parsing its own copy of the
+        document. Helps single large pages with several activities enabled.
+        batch_extract keeps one thread per page regardless. Disabled by
+        default.
+        
+        Args:
+            enabled: Whether to run the passes of large pages in parallel
+        """
+        self._extractor.set_parallel_passes(enabled)
+    
+    def set_strict_fields(self, strict: bool) -> None:
+        """
+        Enable strict field validation. When enabled, running the extractor
+        raises RuntimeError("... unknown field: <name>") if a requested field
+        (after alias normalization) doesn't exist, instead of silently skipping it.
+        Disabled by default.
+        
+        Args:
+            strict: Whether to reject unknown field names
+        """
+        self._extractor.set_strict_fields(strict)
+    
+    def set_collect_provenance(self, enabled: bool) -> None:
+        """
+        Record where each article and product value came from, returned
+        as result.article_sources / result.product_sources (field name ->
+        "meta", "jsonld", "microdata", "css", ...). Disabled by default.
+        
+        Args:
+            enabled: Whether to collect value sources
+        """
+        self._extractor.set_collect_provenance(enabled)
+    
+    def set_structured_only(self, enabled: bool) -> None:
+        """
+        Trust only structured data (JSON-LD, meta tags, microdata). Product
+        and article extraction skip their CSS-selector, <h1>/<title> and
+        text-scan fallbacks, so a page showing its price only in a .price
+        element gets no product_price. Disabled by default.
+        
+        Args:
+            enabled: Whether to skip the markup fallbacks
+        """
+        self._extractor.set_structured_only(enabled)
+    
+    def set_max_json_ld_bytes(self, bytes: int) -> None:
+        """
+        Limit the total size of JSON-LD parsed per page (default: 2 MiB).
+        Scripts that would exceed the limit are skipped, so pages embedding
+        megabytes of JSON-LD don't slow down extraction.
+        
+        Args:
+            bytes: Maximum total JSON-LD size in bytes
+        """
+        self._extractor.set_max_json_ld_bytes(bytes)
+    
+    def validate(self) -> List[str]:
+        """
+        Check the configuration without making any request: URL, custom
+        headers, Accept-Language, field names and the date confidence
+        threshold. Useful before starting a long crawl.
+        
+        Returns:
+            Every problem found as an error string; an empty list means the
+            configuration is valid
+        """
+        return self._extractor.validate()
+    
+    def extract_all(self) -> None:
+        """
+        Enable every extraction activity at once: text with language detection,
+        links, socials, videos, products and articles with all fields.
+        """
+        self._extractor.extract_all()
+        self._activities_set = True
+    
     def set_timeout(self, timeout_secs: float) -> None:
         """
         Set the HTTP request timeout in seconds.
@@ -267,6 +584,63 @@ class AsyncWebExtractor:
         """
         self._extractor.set_headers(headers)
     
+    def set_accept_language(self, langs: str) -> None:
+        """
+        Set the Accept-Language header to request a specific locale.
+        
+        Args:
+            langs: Accept-Language value (e.g., "fr-FR,fr;q=0.9")
+        """
+        self._extractor.set_accept_language(langs)
+    
+    def set_danger_accept_invalid_certs(self, enabled: bool) -> None:
+        """
+        Accept any TLS certificate, including self-signed, expired and
+        wrong-host ones.
+        
+        Warning: this disables protection against man-in-the-middle attacks.
+        Only use it for internal or staging hosts you control, and prefer
+        add_root_certificate() where possible.
+        
+        Args:
+            enabled: Whether to skip certificate verification
+        """
+        self._extractor.set_danger_accept_invalid_certs(enabled)
+    
+    def add_root_certificate(self, pem: bytes) -> None:
+        """
+        Trust an additional root certificate (e.g. an internal CA) while
+        keeping certificate verification enabled.
+        
+        Args:
+            pem: PEM-encoded certificate
+        
+        Raises:
+            RuntimeError: If pem is not a valid PEM certificate
+        """
+        self._extractor.add_root_certificate(pem)
+    
+    def set_http2_prior_knowledge(self, enabled: bool) -> None:
+        """
+        Talk HTTP/2 from the first byte instead of negotiating it, so requests
+        to one host are multiplexed over a single connection.
+        
+        Warning: requests fail against servers that only speak HTTP/1.1.
+        
+        Args:
+            enabled: Whether to assume HTTP/2 support
+        """
+        self._extractor.set_http2_prior_knowledge(enabled)
+    
+    def set_pool_idle_timeout(self, idle_secs: int) -> None:
+        """
+        Keep idle connections open for reuse for this many seconds.
+        
+        Args:
+            idle_secs: Idle timeout in seconds
+        """
+        self._extractor.set_pool_idle_timeout(idle_secs)
+    
     async def run(self):
         """
         Execute the extraction with the configured activities (async).
@@ -303,7 +677,10 @@ async def batch_extract(
     extract_video_all: bool = True,
     extract_product_all: bool = True,
     extract_article_all: bool = True,
-    max_concurrent: int = 10
+    max_concurrent: int = 10,
+    http2_prior_knowledge: bool = False,
+    pool_idle_timeout: Optional[int] = None,
+    progress_callback: Optional[Callable[[int, int], None]] = None
 ) -> List[Any]:
     """
     Extract data from multiple URLs concurrently.
@@ -320,6 +697,15 @@ async def batch_extract(
         extract_product_all: Extract all product metadata fields (default: True)
         extract_article_all: Extract all article metadata fields (default: True)
         max_concurrent: Maximum number of concurrent requests
+        http2_prior_knowledge: Talk HTTP/2 without negotiating it (fails on
+            HTTP/1.1-only servers) (default: False)
+        pool_idle_timeout: Seconds idle connections are kept open for reuse
+            (default: reqwest's default)
+        progress_callback: Called as ``progress_callback(completed, total)`` each
+            time a URL finishes, successfully or not, e.g. to drive a progress
+            bar. It runs on the event loop's thread (never on the extraction
+            worker threads), so it may touch Python objects freely; it should
+            return quickly since it blocks the loop while running.
         
     Returns:
         List of ExtractionResult objects in the same order as input URLs
@@ -337,36 +723,41 @@ async def batch_extract(
         ...         print(result.article.get("title"))
         >>> asyncio.run(main())
     """
-    semaphore = asyncio.Semaphore(max_concurrent)
-    
-    async def extract_one(url: str):
-        async with semaphore:
-            extractor = AsyncWebExtractor(url)
-            if extract_text:
-                extractor.extract_text(language_detection=language_detection)
-            if extract_links_internal or extract_links_external or extract_links_all:
-                extractor.extract_links(
-                    internal=extract_links_internal,
-                    external=extract_links_external,
-                    all=extract_links_all
-                )
-            if extract_socials_all:
-                extractor.extract_socials()  # Extracts all by default
-            if extract_video_all:
-                extractor.extract_video()  # Extracts all by default
-            if extract_product_all:
-                extractor.extract_product()  # Extracts all by default
-            if extract_article_all:
-                extractor.extract_article()  # Extracts all by default
-            try:
-                return await extractor.run()
-            except Exception as e:
-                # Return None for errors - could be enhanced to return error results
-                return None
-    
-    tasks = [extract_one(url) for url in urls]
-    results = await asyncio.gather(*tasks, return_exceptions=True)
-    
-    # Filter out None results (errors)
-    return [r for r in results if r is not None and not isinstance(r, Exception)]
+    # One native extractor runs the whole batch, sharing its HTTP client across URLs
+    extractor = AsyncWebExtractor("")
+    if http2_prior_knowledge:
+        extractor.set_http2_prior_knowledge(True)
+    if pool_idle_timeout is not None:
+        extractor.set_pool_idle_timeout(pool_idle_timeout)
+    if extract_text:
+        extractor.extract_text(language_detection=language_detection)
+    if extract_links_internal or extract_links_external or extract_links_all:
+        extractor.extract_links(
+            internal=extract_links_internal,
+            external=extract_links_external,
+            all=extract_links_all
+        )
+    if extract_socials_all:
+        extractor.extract_socials()  # Extracts all by default
+    if extract_video_all:
+        extractor.extract_video()  # Extracts all by default
+    if extract_product_all:
+        extractor.extract_product()  # Extracts all by default
+    if extract_article_all:
+        extractor.extract_article()  # Extracts all by default
+    
+    loop = asyncio.get_running_loop()
+    if progress_callback is not None:
+        # The native hook fires on the batch's worker threads; hand each report to the
+        # event loop so the callback runs on its thread
+        def forward_progress(completed: int, total: int) -> None:
+            loop.call_soon_threadsafe(progress_callback, completed, total)
+        extractor._extractor.set_progress_callback(forward_progress)
+    
+    results = await loop.run_in_executor(
+        None, extractor._extractor.run_batch, list(urls), max(1, max_concurrent)
+    )
+    
+    # Failed URLs come back as None and are left out
+    return [ExtractionResult(r) for r in results if r is not None]
 
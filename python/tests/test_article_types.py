@@ -0,0 +1,59 @@
+"""
+Article values reach Python as real lists and dicts, not JSON strings.
+
+Run from python/ after `maturin develop`: python -m unittest discover -s tests
+"""
+
+import unittest
+
+from scrape_tools.extractor import WebExtractor
+
+HTML = """<html><head><title>Joint report</title>
+<meta property="article:published_time" content="2024-03-01T08:00:00Z">
+<meta property="article:tag" content="Energy">
+<meta property="article:section" content="Business">
+<meta name="author" content="Jane Doe">
+<script type="application/ld+json">{"@context":"https://schema.org","@type":"NewsArticle",
+ "headline":"Joint report","datePublished":"2024-03-01T08:00:00Z",
+ "keywords":"Energy, Grid","author":[{"@type":"Person","name":"John Smith"}]}</script>
+</head><body><article><h1>Joint report</h1><p>Published March 1, 2024.</p></article></body></html>"""
+
+LIST_FIELDS = ["authors", "publication_date", "all_tags", "sections"]
+
+
+def extract_article():
+    extractor = WebExtractor(url="https://news.example.com/2024/03/01/joint-report", html=HTML)
+    extractor.extract_article(fields=["title", "author"] + LIST_FIELDS)
+    return extractor.extract()
+
+
+class ArticleTypesTest(unittest.TestCase):
+    def assert_article_types(self, article):
+        self.assertIsInstance(article["title"], str)
+        self.assertIsInstance(article["author"], str)
+        for field in LIST_FIELDS:
+            self.assertIsInstance(article[field], list, field)
+
+        self.assertEqual(article["authors"], ["Jane Doe", "John Smith"])
+        self.assertEqual(article["all_tags"], ["Energy", "Grid"])
+        self.assertEqual(article["sections"], ["Business"])
+        for entry in article["publication_date"]:
+            self.assertEqual(set(entry), {"date", "confidence"})
+            self.assertIsInstance(entry["date"], str)
+            self.assertIsInstance(entry["confidence"], float)
+
+    def test_article_getter_decodes_list_fields(self):
+        self.assert_article_types(extract_article().article)
+
+    def test_to_dict_decodes_list_fields(self):
+        self.assert_article_types(extract_article().to_dict()["article"])
+
+    def test_article_info_dates_are_dicts(self):
+        info = extract_article().article_info
+        self.assertIsInstance(info["authors"], list)
+        self.assertIsInstance(info["dates_with_confidence"], list)
+        self.assertIsInstance(info["dates_with_confidence"][0], dict)
+
+
+if __name__ == "__main__":
+    unittest.main()
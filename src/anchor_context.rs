@@ -0,0 +1,170 @@
+use std::ops::Deref;
+use scraper::{ElementRef, Node};
+
+/// A node of the parsed tree (the tree crate isn't a direct dependency)
+type NodeRef<'a> = <ElementRef<'a> as Deref>::Target;
+
+/// Characters of surrounding text kept on each side of an anchor in `LinkInfo.context`
+const LINK_CONTEXT_CHARS: usize = 100;
+
+/// Characters of raw sibling text read on each side of an anchor; more than
+/// `LINK_CONTEXT_CHARS`, since whitespace is collapsed afterwards
+const RAW_CONTEXT_CHARS: usize = 2 * LINK_CONTEXT_CHARS;
+
+/// Elements whose text is an anchor's context: the nearest one around the link
+const CONTEXT_BLOCK_TAGS: &[&str] = &[
+    "p", "li", "td", "th", "dd", "dt", "blockquote", "figcaption", "caption",
+    "h1", "h2", "h3", "h4", "h5", "h6", "div", "section", "article", "body",
+];
+
+/// Text around an anchor, from its nearest block-level ancestor: up to `LINK_CONTEXT_CHARS`
+/// characters before it (cut at a word boundary), the anchor text, and as many after it.
+/// Only the anchor's neighbours are read, and only as much of their text as the context can
+/// use, so links next to large containers stay cheap.
+pub fn anchor_context(anchor: ElementRef) -> Option<String> {
+    let block = anchor.ancestors()
+        .filter_map(ElementRef::wrap)
+        .find(|ancestor| CONTEXT_BLOCK_TAGS.contains(&ancestor.value().name()))?;
+
+    let mut before = Vec::new();
+    let mut after = Vec::new();
+    let (mut before_len, mut after_len) = (0, 0);
+    let mut node = *anchor;
+    while node.id() != block.id() {
+        for sibling in node.prev_siblings() {
+            if before_len >= RAW_CONTEXT_CHARS {
+                break;
+            }
+            collect_text(sibling, true, &mut before, &mut before_len);
+        }
+        for sibling in node.next_siblings() {
+            if after_len >= RAW_CONTEXT_CHARS {
+                break;
+            }
+            collect_text(sibling, false, &mut after, &mut after_len);
+        }
+        node = node.parent()?;
+    }
+    before.reverse();
+
+    let before = collapse_whitespace(&before.concat());
+    let after = collapse_whitespace(&after.concat());
+    let anchor_text = collapse_whitespace(&anchor.text().collect::<String>());
+    let mut context = tail_words(&before, LINK_CONTEXT_CHARS);
+    for part in [anchor_text, head_words(&after, LINK_CONTEXT_CHARS)] {
+        // Punctuation right after the anchor ("see <a>this</a>.") stays attached
+        let attached = part.starts_with(['.', ',', ';', ':', '!', '?', ')']);
+        if !context.is_empty() && !part.is_empty() && !attached {
+            context.push(' ');
+        }
+        context.push_str(&part);
+    }
+    if context.is_empty() { None } else { Some(context) }
+}
+
+/// Append a sibling's text to `parts` until `len` reaches `RAW_CONTEXT_CHARS`, walking it
+/// backwards (`reverse`, for text before the anchor, collected nearest first) or forwards.
+/// `<br>` counts as a space and block elements are padded with spaces.
+fn collect_text<'a>(node: NodeRef<'a>, reverse: bool, parts: &mut Vec<&'a str>, len: &mut usize) {
+    if *len >= RAW_CONTEXT_CHARS {
+        return;
+    }
+    match node.value() {
+        Node::Text(text) => {
+            let text: &str = text;
+            let budget = RAW_CONTEXT_CHARS - *len;
+            let count = text.chars().count();
+            let kept = match (count > budget, reverse) {
+                (false, _) => text,
+                (true, true) => &text[text.char_indices().nth(count - budget).map_or(0, |(i, _)| i)..],
+                (true, false) => &text[..text.char_indices().nth(budget).map_or(text.len(), |(i, _)| i)],
+            };
+            *len += count.min(budget);
+            parts.push(kept);
+        }
+        Node::Element(element) if element.name() == "br" => {
+            *len += 1;
+            parts.push(" ");
+        }
+        Node::Element(element) => {
+            let block = CONTEXT_BLOCK_TAGS.contains(&element.name());
+            if block {
+                parts.push(" ");
+            }
+            if reverse {
+                for child in node.children().rev() {
+                    collect_text(child, reverse, parts, len);
+                }
+            } else {
+                for child in node.children() {
+                    collect_text(child, reverse, parts, len);
+                }
+            }
+            if block {
+                parts.push(" ");
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The last `max` characters of `text`, without a leading partial word
+fn tail_words(text: &str, max: usize) -> String {
+    let count = text.chars().count();
+    if count <= max {
+        return text.to_string();
+    }
+    let tail: String = text.chars().skip(count - max).collect();
+    match tail.split_once(' ') {
+        Some((_, rest)) => rest.to_string(),
+        None => tail,
+    }
+}
+
+/// The first `max` characters of `text`, without a trailing partial word
+fn head_words(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let head: String = text.chars().take(max).collect();
+    match head.rsplit_once(' ') {
+        Some((rest, _)) => rest.to_string(),
+        None => head,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scraper::{Html, Selector};
+
+    fn context_of_first_link(html: &str) -> Option<String> {
+        let document = Html::parse_document(html);
+        let selector = Selector::parse("a[href]").unwrap();
+        anchor_context(document.select(&selector).next().unwrap())
+    }
+
+    #[test]
+    fn anchor_context_reads_the_surrounding_sentence() {
+        let html = r#"<p>Read the <b>full</b> <a href="/report">annual report</a>, published today.<br>Next line</p>"#;
+        assert_eq!(
+            context_of_first_link(html).as_deref(),
+            Some("Read the full annual report, published today. Next line"),
+        );
+    }
+
+    #[test]
+    fn anchor_context_is_bounded_next_to_large_siblings() {
+        let filler = "lorem ipsum ".repeat(100_000);
+        let html = format!(
+            r#"<div><span>{filler}</span>{filler}<a href="/x">link</a><span>{filler}</span><div>{filler}</div></div>"#,
+        );
+        let context = context_of_first_link(&html).unwrap();
+        assert!(context.contains("link"));
+        assert!(context.chars().count() <= 2 * LINK_CONTEXT_CHARS + "link".len() + 2, "{} chars", context.chars().count());
+    }
+}
@@ -0,0 +1,398 @@
+use scraper::Selector;
+use crate::dom_index::{json_ld_image_url, DomIndex};
+use std::collections::HashSet;
+use unicode_segmentation::UnicodeSegmentation;
+use crate::link_extractor::resolve_url;
+use crate::text_extractor::find_main_content_element;
+use super::helpers::{extract_json_ld_property_from_index, extract_json_ld_property_typed, extract_schema_property_from_index, extract_schema_property_with_source, collect_json_ld_names, extract_json_value_from_object, json_ld_strings};
+use crate::json_ld::json_ld_types;
+use crate::microdata::{find_main_item, item_property_values};
+
+/// Average adult silent reading speed used for reading time estimates
+const WORDS_PER_MINUTE: u32 = 225;
+
+/// Images with a declared width or height below this are treated as icons/tracking pixels
+const MIN_SIGNIFICANT_IMAGE_SIZE: u32 = 50;
+
+/// Dublin Core / Highwire Press (Google Scholar) title metas
+const DC_TITLE_NAMES: &[&str] = &["DC.title", "dc.title", "dcterms.title", "citation_title"];
+
+/// Dublin Core / Highwire Press author metas; pages usually repeat these once per author
+const DC_AUTHOR_NAMES: &[&str] = &["DC.creator", "dc.creator", "dcterms.creator", "citation_author"];
+
+const DC_DESCRIPTION_NAMES: &[&str] = &["DC.description", "dc.description", "dcterms.description", "dcterms.abstract"];
+
+/// Look up JSON-LD properties on Article-typed objects first, then on any object
+pub fn article_json_ld_property(dom_index: &DomIndex, properties: &[&str]) -> Option<String> {
+    extract_json_ld_property_typed(dom_index, ARTICLE_TYPES, properties)
+        .or_else(|| extract_json_ld_property_from_index(dom_index, properties))
+}
+
+/// First value of the first `<meta name>` in `names` that is present
+fn first_meta_by_names(dom_index: &DomIndex, names: &[&str]) -> Option<String> {
+    names.iter().find_map(|name| dom_index.get_meta_by_name(name).cloned())
+}
+
+/// Pair a lookup's value with the source it came from
+fn tagged(value: Option<String>, source: &'static str) -> Option<(String, &'static str)> {
+    value.map(|value| (value, source))
+}
+
+pub fn extract_article_title(dom_index: &DomIndex) -> Option<String> {
+    extract_article_title_with_source(dom_index).map(|(title, _)| title)
+}
+
+/// The title and where it came from: `"meta"` (Open Graph, Twitter Card, Dublin Core),
+/// `"jsonld"`, `"title_tag"` or `"h1"`
+pub fn extract_article_title_with_source(dom_index: &DomIndex) -> Option<(String, &'static str)> {
+    // Try Open Graph title first (from index)
+    tagged(dom_index.get_meta_by_property("og:title").cloned(), "meta")
+        // Try Twitter Card title
+        .or_else(|| tagged(dom_index.get_meta_by_name("twitter:title").cloned(), "meta"))
+        // Try JSON-LD (headline, name)
+        .or_else(|| tagged(article_json_ld_property(dom_index, &["headline", "name"]), "jsonld"))
+        // Try Dublin Core / citation metas (academic and institutional sites)
+        .or_else(|| tagged(first_meta_by_names(dom_index, DC_TITLE_NAMES), "meta"))
+        // Try title tag, then h1 as fallback (not in structured-only mode)
+        .or_else(|| tagged(dom_index.get_first_element_by_tag("title").cloned(), "title_tag").filter(|_| !dom_index.structured_only))
+        .or_else(|| tagged(dom_index.get_first_element_by_tag("h1").cloned(), "h1").filter(|_| !dom_index.structured_only))
+}
+
+/// Collect every declared author, deduplicated case-insensitively (first spelling wins).
+/// Sources are tried in the order the single `author` field has always used, so the first
+/// entry matches it.
+pub fn extract_article_authors(dom_index: &DomIndex) -> Vec<String> {
+    let mut candidates: Vec<String> = Vec::new();
+
+    if let Some(values) = dom_index.meta_by_property.get("article:author") {
+        candidates.extend(values.iter().cloned());
+    }
+    if let Some(values) = dom_index.meta_by_name.get("author") {
+        candidates.extend(values.iter().cloned());
+    }
+    if let Some(values) = dom_index.meta_by_property.get("og:article:author") {
+        candidates.extend(values.iter().cloned());
+    }
+
+    // Every rel="author" link
+    if let Ok(selector) = Selector::parse("a[rel~='author']") {
+        for link in dom_index.document().select(&selector) {
+            candidates.push(link.text().collect::<String>());
+        }
+    }
+
+    // JSON-LD author values (strings, Person objects, arrays and @list forms)
+    for obj in dom_index.get_json_ld_entities() {
+        if let Some(author) = obj.get("author") {
+            collect_json_ld_names(author, &mut candidates);
+        }
+    }
+
+    // Microdata authors, of the main article item when there is one
+    if let Some(article) = find_main_item(dom_index.document(), ARTICLE_TYPES) {
+        candidates.extend(item_property_values(article, "author"));
+    } else if let Some(values) = dom_index.schema_by_itemprop.get("author") {
+        candidates.extend(values.iter().cloned());
+    }
+
+    // Every Dublin Core / citation author
+    for name in DC_AUTHOR_NAMES {
+        if let Some(values) = dom_index.meta_by_name.get(*name) {
+            candidates.extend(values.iter().cloned());
+        }
+    }
+
+    // Regex fallback for malformed JSON-LD
+    if candidates.is_empty() {
+        if let Some(author) = extract_schema_property_from_index(dom_index, "author") {
+            candidates.push(author);
+        }
+    }
+
+    let mut seen = HashSet::new();
+    candidates
+        .into_iter()
+        .map(|a| a.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|a| !a.is_empty() && seen.insert(a.to_lowercase()))
+        .collect()
+}
+
+/// Links identifying the (first) article author
+#[derive(Debug, Default)]
+pub struct AuthorProfile {
+    /// The author's page: the `rel=author` href or JSON-LD `author.url`
+    pub url: Option<String>,
+    /// JSON-LD `author.sameAs` URLs (social and other profiles)
+    pub same_as: Vec<String>,
+}
+
+/// First JSON-LD author given as an object (a Person/Organization, possibly inside an array)
+fn first_json_ld_author_object(dom_index: &DomIndex) -> Option<serde_json::Map<String, serde_json::Value>> {
+    for obj in dom_index.get_json_ld_entities() {
+        let author = match extract_json_value_from_object(obj, "author") {
+            Some(serde_json::Value::Array(arr)) => arr.first(),
+            other => other,
+        };
+        if let Some(serde_json::Value::Object(author)) = author {
+            return Some(author.clone());
+        }
+    }
+    None
+}
+
+/// Extract the author's profile URL and `sameAs` profiles, resolved against `base_url`
+pub fn extract_article_author_profile(dom_index: &DomIndex, base_url: &str) -> AuthorProfile {
+    // rel="author" links carry the byline's href alongside the name
+    let rel_author_url = Selector::parse("a[rel~='author']")
+        .ok()
+        .and_then(|selector| {
+            dom_index.document().select(&selector)
+                .find_map(|link| link.value().attr("href"))
+                .map(|href| href.trim().to_string())
+                .filter(|href| !href.is_empty())
+        });
+
+    let json_ld_author = first_json_ld_author_object(dom_index);
+    let json_ld_url = json_ld_author.as_ref()
+        .and_then(|author| extract_json_value_from_object(author, "url"))
+        .and_then(|url| json_ld_strings(url).into_iter().next());
+
+    let mut same_as: Vec<String> = Vec::new();
+    if let Some(values) = json_ld_author.as_ref().and_then(|author| extract_json_value_from_object(author, "sameAs")) {
+        for url in json_ld_strings(values) {
+            let url = resolve_url(base_url, url.trim());
+            if !same_as.contains(&url) {
+                same_as.push(url);
+            }
+        }
+    }
+
+    AuthorProfile {
+        url: rel_author_url.or(json_ld_url).map(|url| resolve_url(base_url, &url)),
+        same_as,
+    }
+}
+
+pub fn extract_article_description(dom_index: &DomIndex) -> Option<String> {
+    extract_article_description_with_source(dom_index).map(|(description, _)| description)
+}
+
+/// The description and where it came from: `"meta"` (Open Graph, Twitter Card, the standard
+/// meta description, Dublin Core), `"jsonld"` or `"microdata"`
+pub fn extract_article_description_with_source(dom_index: &DomIndex) -> Option<(String, &'static str)> {
+    tagged(dom_index.get_meta_by_property("og:description").cloned(), "meta")
+        // Try Twitter Card description
+        .or_else(|| tagged(dom_index.get_meta_by_name("twitter:description").cloned(), "meta"))
+        // Try standard meta description
+        .or_else(|| tagged(dom_index.get_meta_by_name("description").cloned(), "meta"))
+        // Try schema.org description (Article-typed JSON-LD first)
+        .or_else(|| tagged(extract_json_ld_property_typed(dom_index, ARTICLE_TYPES, &["description"]), "jsonld"))
+        .or_else(|| extract_schema_property_with_source(dom_index, "description"))
+        // Try Dublin Core description
+        .or_else(|| tagged(first_meta_by_names(dom_index, DC_DESCRIPTION_NAMES), "meta"))
+}
+
+pub fn extract_article_modified_date(dom_index: &DomIndex) -> Option<String> {
+    dom_index.get_meta_by_property("article:modified_time")
+        .cloned()
+        .or_else(|| dom_index.get_meta_by_property("og:updated_time").cloned())
+}
+
+/// The categories and where they came from: `"meta"` (`article:tag`, `article:section`, then
+/// the keywords meta) or `"jsonld"`
+pub fn extract_article_categories_with_source(dom_index: &DomIndex) -> Option<(String, &'static str)> {
+    tagged(dom_index.get_meta_by_property("article:tag").cloned(), "meta")
+        .or_else(|| tagged(dom_index.get_meta_by_property("article:section").cloned(), "meta"))
+        // Try JSON-LD (articleSection, keywords)
+        .or_else(|| tagged(article_json_ld_property(dom_index, &["articleSection", "keywords"]), "jsonld"))
+        // Try keywords meta tag
+        .or_else(|| tagged(dom_index.get_meta_by_name("keywords").cloned(), "meta"))
+}
+
+/// Push comma-separated keywords (or a JSON-LD array of them) onto `tags`
+fn push_keywords(value: &serde_json::Value, tags: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(s) => tags.extend(s.split(',').map(|k| k.to_string())),
+        serde_json::Value::Array(arr) => {
+            for item in arr {
+                push_keywords(item, tags);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Aggregate tags from every `article:tag`, the comma-separated `keywords` meta and JSON-LD
+/// `keywords`/`articleSection`, deduplicated case-insensitively (first spelling wins)
+pub fn extract_article_tags(dom_index: &DomIndex) -> Vec<String> {
+    let mut candidates: Vec<String> = Vec::new();
+
+    // The index keeps every article:tag value, not just the first
+    if let Some(values) = dom_index.meta_by_property.get("article:tag") {
+        candidates.extend(values.iter().cloned());
+    }
+    if let Some(values) = dom_index.meta_by_name.get("keywords") {
+        for keywords in values {
+            candidates.extend(keywords.split(',').map(|k| k.to_string()));
+        }
+    }
+
+    for obj in dom_index.get_json_ld_entities() {
+        if let Some(keywords) = obj.get("keywords") {
+            push_keywords(keywords, &mut candidates);
+        }
+        if let Some(section) = obj.get("articleSection") {
+            push_keywords(section, &mut candidates);
+        }
+    }
+
+    let mut seen = HashSet::new();
+    candidates
+        .into_iter()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty() && seen.insert(t.to_lowercase()))
+        .collect()
+}
+
+/// schema.org `Article` and its subtypes
+pub const ARTICLE_TYPES: &[&str] = &[
+    "Article",
+    "NewsArticle",
+    "AnalysisNewsArticle",
+    "AskPublicNewsArticle",
+    "BackgroundNewsArticle",
+    "OpinionNewsArticle",
+    "ReportageNewsArticle",
+    "ReviewNewsArticle",
+    "BlogPosting",
+    "LiveBlogPosting",
+    "SocialMediaPosting",
+    "DiscussionForumPosting",
+    "ScholarlyArticle",
+    "MedicalScholarlyArticle",
+    "TechArticle",
+    "APIReference",
+    "Report",
+    "SatiricalArticle",
+    "AdvertiserContentArticle",
+];
+
+/// Site furniture that commonly sits next to the main entity and never describes the page itself
+const SUPPORTING_TYPES: &[&str] = &[
+    "WebSite",
+    "WebPage",
+    "BreadcrumbList",
+    "ListItem",
+    "Organization",
+    "Person",
+    "ImageObject",
+    "SiteNavigationElement",
+    "SearchAction",
+];
+
+pub fn is_article_type(schema_type: &str) -> bool {
+    ARTICLE_TYPES.iter().any(|t| t.eq_ignore_ascii_case(schema_type))
+}
+
+/// Report the JSON-LD `@type` of the page's main entity, preferring Article subtypes, then the
+/// first non-supporting type; falls back to `og:type`
+pub fn extract_article_type(dom_index: &DomIndex) -> Option<String> {
+    let mut first_other: Option<String> = None;
+
+    for obj in dom_index.get_json_ld_entities() {
+        let types = json_ld_types(obj);
+        // With `["Article", "NewsArticle"]` report the more specific subtype
+        let article_types: Vec<&str> = types.iter().copied().filter(|t| is_article_type(t)).collect();
+        if let Some(t) = article_types.iter().find(|t| **t != "Article").or(article_types.first()) {
+            return Some(t.to_string());
+        }
+        if first_other.is_none() {
+            first_other = types.iter()
+                .find(|t| !SUPPORTING_TYPES.contains(t))
+                .map(|t| t.to_string());
+        }
+    }
+
+    first_other.or_else(|| dom_index.get_meta_by_property("og:type").cloned())
+}
+
+/// Whether the page is an article: an Article-subtype `@type`, or `og:type` "article"
+pub fn is_article(dom_index: &DomIndex, article_type: Option<&str>) -> bool {
+    article_type.is_some_and(is_article_type)
+        || dom_index.get_meta_by_property("og:type")
+            .is_some_and(|t| t.trim().eq_ignore_ascii_case("article"))
+}
+
+/// Canonical URL from `<link rel="canonical">` (source `"link"`), falling back to og:url
+/// (`"meta"`), resolved against `base_url`. Cross-domain canonicals are returned as declared.
+pub fn extract_article_canonical_url_with_source(dom_index: &DomIndex, base_url: &str) -> Option<(String, &'static str)> {
+    dom_index.get_first_link_href("canonical").map(|href| (href, "link"))
+        .or_else(|| dom_index.get_meta_by_property("og:url").map(|href| (href, "meta")))
+        .filter(|(href, _)| !href.trim().is_empty())
+        .map(|(href, source)| (resolve_url(base_url, href.trim()), source))
+}
+
+/// Find the article's lead image, its alt text and where it came from.
+///
+/// Tries og:image, JSON-LD `image`, twitter:image (sources `"meta"`, `"jsonld"`, `"meta"`),
+/// then the first significant `<img>` in the main content container (`"content"`, skipped in
+/// structured-only mode). The URL is resolved against `base_url`.
+pub fn extract_article_image(dom_index: &DomIndex, base_url: &str) -> Option<(String, Option<String>, &'static str)> {
+    let og_alt = dom_index.get_meta_by_property("og:image:alt").cloned();
+
+    if let Some(image) = dom_index.get_meta_by_property("og:image") {
+        return Some((resolve_url(base_url, image), og_alt, "meta"));
+    }
+
+    for obj in dom_index.get_json_ld_entities() {
+        if let Some(image) = obj.get("image").and_then(json_ld_image_url) {
+            return Some((resolve_url(base_url, &image), og_alt, "jsonld"));
+        }
+    }
+
+    if let Some(image) = dom_index.get_meta_by_name("twitter:image")
+        .or_else(|| dom_index.get_meta_by_property("twitter:image"))
+    {
+        return Some((resolve_url(base_url, image), og_alt, "meta"));
+    }
+
+    if dom_index.structured_only {
+        return None;
+    }
+    let container = find_main_content_element(dom_index.document())?;
+    let img_selector = Selector::parse("img").ok()?;
+    for img in container.select(&img_selector) {
+        let src = img.value().attr("src")
+            .or_else(|| img.value().attr("data-src"))
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty() && !s.starts_with("data:"));
+        let Some(src) = src else { continue };
+
+        // Skip tiny images and tracking pixels when dimensions are declared
+        let too_small = ["width", "height"].iter().any(|attr| {
+            img.value().attr(attr)
+                .and_then(|v| v.trim().trim_end_matches("px").parse::<u32>().ok())
+                .is_some_and(|size| size < MIN_SIGNIFICANT_IMAGE_SIZE)
+        });
+        if too_small {
+            continue;
+        }
+
+        let alt = img.value().attr("alt")
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty());
+        return Some((resolve_url(base_url, src), alt, "content"));
+    }
+
+    None
+}
+
+/// Count words using Unicode word boundaries (handles punctuation and non-Latin scripts)
+pub fn count_words(text: &str) -> u32 {
+    text.unicode_words().count() as u32
+}
+
+/// Estimate reading time in whole minutes, rounding up (at least one minute for non-empty text)
+pub fn reading_time_minutes(word_count: u32) -> u32 {
+    word_count.div_ceil(WORDS_PER_MINUTE)
+}
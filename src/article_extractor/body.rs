@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use scraper::{ElementRef, Selector};
+use crate::dom_index::DomIndex;
+use crate::text_extractor::{extract_element_text, find_main_content_element, is_boilerplate_element};
+use super::basic::article_json_ld_property;
+
+/// Extract the article body text only, without the bios, teasers and newsletter prompts that
+/// often sit inside `<article>` next to it
+///
+/// Sources in order: JSON-LD `articleBody` (the publisher's own body text), the
+/// `[itemprop=articleBody]` container, then the densest paragraph container inside the
+/// article element.
+pub fn extract_article_body(dom_index: &DomIndex) -> Option<String> {
+    if let Some(body) = article_json_ld_property(dom_index, &["articleBody"]) {
+        let body = body.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !body.is_empty() {
+            return Some(body);
+        }
+    }
+
+    let document = dom_index.document();
+    if let Ok(selector) = Selector::parse("[itemprop~='articleBody']") {
+        if let Some(element) = document.select(&selector).next() {
+            let text = extract_element_text(element);
+            if !text.is_empty() {
+                return Some(text);
+            }
+        }
+    }
+
+    let scope = Selector::parse("article")
+        .ok()
+        .and_then(|selector| document.select(&selector).next())
+        .or_else(|| find_main_content_element(document))?;
+    let container = densest_paragraph_container(scope).unwrap_or(scope);
+    let text = extract_element_text(container);
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+/// Find the element inside `scope` whose direct `<p>` children carry the most non-link text
+///
+/// Paragraphs under boilerplate elements (author bios, related links, newsletter boxes) are
+/// not counted, so the container holding the story itself wins.
+fn densest_paragraph_container(scope: ElementRef) -> Option<ElementRef> {
+    let paragraph_selector = Selector::parse("p").ok()?;
+    let link_selector = Selector::parse("a").ok()?;
+    let mut scores: HashMap<_, (ElementRef, usize)> = HashMap::new();
+
+    for paragraph in scope.select(&paragraph_selector) {
+        let in_boilerplate = paragraph
+            .ancestors()
+            .take_while(|node| node.id() != scope.id())
+            .filter_map(ElementRef::wrap)
+            .any(|element| is_boilerplate_element(&element));
+        if in_boilerplate {
+            continue;
+        }
+
+        let text_len = paragraph.text().map(|t| t.trim().chars().count()).sum::<usize>();
+        let link_len = paragraph
+            .select(&link_selector)
+            .flat_map(|a| a.text())
+            .map(|t| t.trim().chars().count())
+            .sum::<usize>();
+        let score = text_len.saturating_sub(link_len);
+        if score == 0 {
+            continue;
+        }
+
+        if let Some(parent) = paragraph.parent().and_then(ElementRef::wrap) {
+            scores.entry(parent.id()).or_insert((parent, 0)).1 += score;
+        }
+    }
+
+    scores.into_values().max_by_key(|(_, score)| *score).map(|(element, _)| element)
+}
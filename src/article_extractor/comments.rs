@@ -0,0 +1,112 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::Selector;
+use crate::dom_index::DomIndex;
+use crate::json_ld::json_ld_types;
+
+/// Elements that usually hold a comment count
+const COMMENT_COUNT_SELECTORS: &[&str] = &[".comments-count", ".comment-count", ".comments_count"];
+
+/// Elements whose short text may read "42 Comments"
+const COMMENT_TEXT_SELECTORS: &str = "a, span, button, h2, h3, h4";
+
+/// Containers that mark a comment section
+const COMMENT_SECTION_SELECTORS: &[&str] = &["#comments", ".comment-list", "section[aria-label*='comment' i]"];
+
+/// Texts longer than this are prose, not a comment counter
+const MAX_COMMENT_TEXT_LEN: usize = 40;
+
+/// "42 Comments", "1,204 comments", "1 Comment"
+static COMMENT_TEXT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(\d[\d,.]*)\s+comments?\b").unwrap()
+});
+
+/// Parse an integer that may be embedded in text or use thousands separators ("1,204")
+fn parse_count(text: &str) -> Option<u64> {
+    let digits: String = text.trim()
+        .chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(|c| c.is_ascii_digit() || *c == ',' || *c == '.')
+        .filter(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse().ok()
+}
+
+/// A count given as a JSON integer or a string ("42")
+fn json_count(value: &serde_json::Value) -> Option<u64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_u64(),
+        serde_json::Value::String(s) => parse_count(s),
+        _ => None,
+    }
+}
+
+/// Whether an `interactionType` (a URL/name string or a typed object) is a CommentAction
+fn is_comment_action(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::String(s) => s.rsplit('/').next() == Some("CommentAction"),
+        serde_json::Value::Object(obj) => json_ld_types(obj).contains(&"CommentAction"),
+        _ => false,
+    }
+}
+
+/// `userInteractionCount` of the CommentAction entry in an `interactionStatistic` value
+fn interaction_comment_count(value: &serde_json::Value) -> Option<u64> {
+    match value {
+        serde_json::Value::Array(arr) => arr.iter().find_map(interaction_comment_count),
+        serde_json::Value::Object(obj) => {
+            if obj.get("interactionType").is_some_and(is_comment_action) {
+                obj.get("userInteractionCount").and_then(json_count)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Find the number of comments: JSON-LD `commentCount` or a CommentAction
+/// `interactionStatistic`, microdata `commentCount`, then common counter elements
+pub fn extract_comment_count(dom_index: &DomIndex) -> Option<u64> {
+    for obj in dom_index.get_json_ld_entities() {
+        let count = obj.get("commentCount")
+            .and_then(json_count)
+            .or_else(|| obj.get("interactionStatistic").and_then(interaction_comment_count));
+        if count.is_some() {
+            return count;
+        }
+    }
+
+    if let Some(count) = dom_index.get_first_schema_by_itemprop("commentCount").and_then(|c| parse_count(c)) {
+        return Some(count);
+    }
+
+    if dom_index.structured_only {
+        return None;
+    }
+    let document = dom_index.document();
+    for selector in COMMENT_COUNT_SELECTORS.iter().filter_map(|s| Selector::parse(s).ok()) {
+        if let Some(count) = document.select(&selector).find_map(|e| parse_count(&e.text().collect::<String>())) {
+            return Some(count);
+        }
+    }
+
+    let selector = Selector::parse(COMMENT_TEXT_SELECTORS).ok()?;
+    document.select(&selector).find_map(|element| {
+        let text = element.text().collect::<Vec<_>>().join(" ");
+        let text = text.trim();
+        if text.len() > MAX_COMMENT_TEXT_LEN {
+            return None;
+        }
+        COMMENT_TEXT_RE.captures(text).and_then(|captures| parse_count(&captures[1]))
+    })
+}
+
+/// Whether the page has a comment section container
+pub fn has_comments_section(dom_index: &DomIndex) -> bool {
+    let document = dom_index.document();
+    COMMENT_SECTION_SELECTORS
+        .iter()
+        .filter_map(|s| Selector::parse(s).ok())
+        .any(|selector| document.select(&selector).next().is_some())
+}
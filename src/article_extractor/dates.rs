@@ -1,14 +1,76 @@
-use scraper::{Html, Selector};
-use std::collections::HashSet;
+use scraper::Selector;
+use crate::dom_index::DomIndex;
+use std::collections::{HashMap, HashSet};
 use regex::Regex;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, SecondsFormat};
 use crate::types::DateWithConfidence;
+use crate::json_ld::has_json_ld_type;
+use super::basic::ARTICLE_TYPES;
+use super::helpers::json_ld_strings;
+use once_cell::sync::Lazy;
+use url::Url;
+
+/// Parse a date string in any of the formats found in meta tags, JSON-LD or page bodies.
+/// Values without timezone information are interpreted as UTC.
+pub fn parse_date(value: &str) -> Option<DateTime<FixedOffset>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    // Full datetimes with offset
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt);
+    }
+    for format in ["%Y-%m-%dT%H:%M:%S%z", "%Y-%m-%dT%H:%M:%S%.f%z", "%Y-%m-%dT%H:%M%z"] {
+        if let Ok(dt) = DateTime::parse_from_str(value, format) {
+            return Some(dt);
+        }
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc2822(value) {
+        return Some(dt);
+    }
+
+    let utc = FixedOffset::east_opt(0)?;
+
+    // Datetimes without offset
+    for format in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M"] {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(value, format) {
+            return naive.and_local_timezone(utc).single();
+        }
+    }
+
+    // Date-only values
+    let normalized = value.replace(',', "");
+    for format in ["%Y-%m-%d", "%Y/%m/%d", "%m/%d/%Y", "%B %d %Y", "%b %d %Y", "%d %B %Y", "%d %b %Y"] {
+        if let Ok(date) = NaiveDate::parse_from_str(&normalized, format) {
+            return date.and_hms_opt(0, 0, 0)?.and_local_timezone(utc).single();
+        }
+    }
+
+    None
+}
+
+/// Pick the highest-confidence date at or above `threshold` that can be parsed into a timestamp
+pub fn best_parsed_date(dates: &[DateWithConfidence], threshold: f64) -> Option<DateTime<FixedOffset>> {
+    // Dates are sorted by confidence (highest first)
+    dates.iter()
+        .take_while(|d| d.confidence >= threshold)
+        .find_map(|d| parse_date(&d.date))
+}
+
+/// Format a parsed date as a normalized ISO 8601 string
+pub fn to_iso_string(date: &DateTime<FixedOffset>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
 
 /// Extract publication dates with confidence scores
-pub fn extract_publication_dates_with_confidence(document: &Html) -> Vec<DateWithConfidence> {
-    use std::collections::HashMap as Map;
-    
-    // Track where each date appears: meta, json_ld, body
-    let mut date_sources: Map<String, (bool, bool, bool)> = Map::new();
+///
+/// `body_dates` is the result of `extract_dates_from_body`, shared with the modified-date scan.
+/// `page_url` is checked for dates encoded in the path (`/2023/11/05/slug`).
+pub fn extract_publication_dates_with_confidence(dom_index: &DomIndex, page_url: &str, body_dates: &[BodyDate]) -> Vec<DateWithConfidence> {
+    // Track where each date appears: meta, json_ld, body, url
+    let mut date_sources: DateSources = HashMap::new();
     
     // Extract dates from meta tags
     let meta_date_fields = vec![
@@ -17,91 +79,246 @@ pub fn extract_publication_dates_with_confidence(document: &Html) -> Vec<DateWit
         "pubdate",
         "date",
         "publication_date",
+        // Dublin Core and Highwire Press (Google Scholar) citation metas
+        "DC.date",
+        "dc.date",
+        "DC.date.issued",
+        "dcterms.date",
+        "dcterms.issued",
+        "citation_publication_date",
+        "citation_date",
+        "citation_online_date",
     ];
     
     for field in &meta_date_fields {
-        if field.starts_with("article:") || field.starts_with("og:") {
-            if let Ok(selector) = Selector::parse(&format!("meta[property='{}']", field)) {
-                if let Some(meta) = document.select(&selector).next() {
-                    if let Some(date) = meta.value().attr("content") {
-                        let entry = date_sources.entry(date.to_string()).or_insert((false, false, false));
-                        entry.0 = true; // meta tag
-                    }
-                }
-            }
+        let date = if field.starts_with("article:") || field.starts_with("og:") {
+            dom_index.get_meta_by_property(field)
         } else {
-            if let Ok(selector) = Selector::parse(&format!("meta[name='{}']", field)) {
-                if let Some(meta) = document.select(&selector).next() {
-                    if let Some(date) = meta.value().attr("content") {
-                        let entry = date_sources.entry(date.to_string()).or_insert((false, false, false));
-                        entry.0 = true; // meta tag
-                    }
-                }
-            }
+            dom_index.get_meta_by_name(field)
+        };
+        if let Some(date) = date {
+            let entry = date_sources.entry(date.to_string()).or_default();
+            entry.meta = true; // meta tag
         }
     }
     
-    // Extract dates from time elements
-    if let Ok(selector) = Selector::parse("time[datetime]") {
-        for time in document.select(&selector) {
-            if let Some(datetime) = time.value().attr("datetime") {
-                let entry = date_sources.entry(datetime.to_string()).or_insert((false, false, false));
-                entry.0 = true; // meta tag (time element is structured metadata)
-            }
-        }
+    // Dates from time elements (indexed during DomIndex::build_for)
+    for datetime in &dom_index.time_datetimes {
+        let entry = date_sources.entry(datetime.to_string()).or_default();
+        entry.meta = true; // meta tag (time element is structured metadata)
     }
     
-    // Extract dates from JSON-LD
-    let json_ld_dates = extract_all_json_ld_dates(document);
-    for date in json_ld_dates {
-        let entry = date_sources.entry(date).or_insert((false, false, false));
-        entry.1 = true; // json-ld
+    // Extract dates from Article-typed JSON-LD entities
+    for date in extract_json_ld_property_dates(dom_index, JsonLdDate::Published) {
+        let entry = date_sources.entry(date).or_default();
+        entry.json_ld = true; // json-ld
     }
     
     // Extract dates from page body
-    let body_dates = extract_dates_from_body(document);
-    for date in body_dates {
-        let entry = date_sources.entry(date).or_insert((false, false, false));
-        entry.2 = true; // body
+    for body_date in body_dates {
+        let entry = date_sources.entry(body_date.date.clone()).or_default();
+        entry.body = true; // body
+        entry.body_cue |= body_date.has_cue;
+    }
+    
+    // Extract dates encoded in the URL path (a pattern match, skipped in structured-only mode)
+    let url_dates = if dom_index.structured_only { Vec::new() } else { extract_dates_from_url(page_url) };
+    for date in url_dates {
+        let entry = date_sources.entry(date).or_default();
+        entry.url = true;
     }
     
+    score_date_sources(merge_equivalent_dates(date_sources), &HashSet::new())
+}
+
+/// Extract last-modified dates with confidence scores, triangulated the same way as
+/// publication dates across meta tags, JSON-LD and the page body.
+///
+/// Body dates only corroborate dates already found in structured sources. Equivalent values
+/// are merged as for publication dates, and dates equivalent to one of `publication_dates`
+/// are exempt from the ambiguity penalty, since unedited articles usually carry the same date
+/// for both.
+pub fn extract_modified_dates_with_confidence(
+    dom_index: &DomIndex,
+    body_dates: &[BodyDate],
+    publication_dates: &[DateWithConfidence],
+) -> Vec<DateWithConfidence> {
+    let mut date_sources: DateSources = HashMap::new();
+
+    for field in ["article:modified_time", "og:updated_time"] {
+        if let Some(date) = dom_index.get_meta_by_property(field) {
+            date_sources.entry(date.to_string()).or_default().meta = true;
+        }
+    }
+
+    // Microdata: <time itemprop="dateModified" datetime="..."> or <meta itemprop="dateModified" content="...">
+    for date in &dom_index.modified_itemprop_dates {
+        date_sources.entry(date.to_string()).or_default().meta = true;
+    }
+
+    for date in extract_json_ld_property_dates(dom_index, JsonLdDate::Modified) {
+        date_sources.entry(date).or_default().json_ld = true;
+    }
+
+    for body_date in body_dates {
+        if let Some(entry) = date_sources.get_mut(&body_date.date) {
+            entry.body = true;
+            entry.body_cue |= body_date.has_cue;
+        }
+    }
+
+    let date_sources = merge_equivalent_dates(date_sources);
+    let published: Vec<(&str, Option<DateTime<FixedOffset>>)> = publication_dates.iter()
+        .map(|d| (d.date.as_str(), parse_date(&d.date)))
+        .collect();
+    let shared: HashSet<String> = date_sources.keys()
+        .filter(|date| {
+            let parsed = parse_date(date);
+            published.iter().any(|(published, published_parsed)| match (&parsed, published_parsed) {
+                (Some(parsed), Some(published_parsed)) => same_date((date, parsed), (published, published_parsed)),
+                _ => date.as_str() == *published,
+            })
+        })
+        .cloned()
+        .collect();
+    score_date_sources(date_sources, &shared)
+}
+
+/// Where a date was seen
+#[derive(Debug, Clone, Copy, Default)]
+struct DateSourceFlags {
+    /// Meta tags and other structured markup (`<time datetime>`, microdata)
+    meta: bool,
+    json_ld: bool,
+    body: bool,
+    /// A body occurrence sits next to a publication cue ("Published:", "by <author>", ...)
+    body_cue: bool,
+    /// Encoded in the page URL path
+    url: bool,
+}
+
+impl DateSourceFlags {
+    fn union(&mut self, other: DateSourceFlags) {
+        self.meta |= other.meta;
+        self.json_ld |= other.json_ld;
+        self.body |= other.body;
+        self.body_cue |= other.body_cue;
+        self.url |= other.url;
+    }
+}
+
+type DateSources = HashMap<String, DateSourceFlags>;
+
+/// Trailing `Z`, `+02:00`, `-0500` or `GMT` on a datetime string
+static EXPLICIT_OFFSET_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(?:z|[+-]\d{2}:?\d{2}|gmt)$").unwrap()
+});
+
+fn has_time(value: &str) -> bool {
+    value.contains(':')
+}
+
+/// How precisely a date string pins down its instant: time of day first, then an explicit
+/// offset, then length (fractional seconds)
+fn date_precision(value: &str) -> (bool, bool, usize) {
+    let value = value.trim();
+    let has_time = has_time(value);
+    (has_time, has_time && EXPLICIT_OFFSET_RE.is_match(value), value.len())
+}
+
+/// Merge entries that denote the same date so their sources reinforce each other instead of
+/// competing: datetimes are compared by instant (`2024-05-03T10:00:00Z` equals
+/// `2024-05-03T12:00:00+02:00`), date-only values by calendar date, which also matches a
+/// datetime falling on that day in its own offset. Each merged entry carries the union of
+/// the sources and is reported under its most precise representation.
+fn merge_equivalent_dates(date_sources: DateSources) -> DateSources {
+    let mut entries: Vec<(String, DateSourceFlags)> = date_sources.into_iter().collect();
+    // Sorted for deterministic output; datetimes first so date-only values can join them
+    entries.sort_by(|a, b| has_time(&b.0).cmp(&has_time(&a.0)).then_with(|| a.0.cmp(&b.0)));
+
+    let mut merged: Vec<(String, DateSourceFlags, Option<DateTime<FixedOffset>>)> = Vec::new();
+    for (date, flags) in entries {
+        let parsed = parse_date(&date);
+        let target = parsed.and_then(|parsed| {
+            merged.iter().position(|(existing, _, existing_parsed)| {
+                existing_parsed.is_some_and(|existing_parsed| same_date((&date, &parsed), (existing, &existing_parsed)))
+            })
+        });
+
+        match target {
+            Some(index) => {
+                let (existing, existing_flags, _) = &mut merged[index];
+                existing_flags.union(flags);
+                if date_precision(&date) > date_precision(existing) {
+                    *existing = date;
+                }
+            }
+            None => merged.push((date, flags, parsed)),
+        }
+    }
+
+    merged.into_iter().map(|(date, flags, _)| (date, flags)).collect()
+}
+
+/// Whether two parsed date strings denote the same date: two datetimes by instant, otherwise
+/// by calendar date
+fn same_date(a: (&str, &DateTime<FixedOffset>), b: (&str, &DateTime<FixedOffset>)) -> bool {
+    if has_time(a.0) && has_time(b.0) {
+        a.1 == b.1
+    } else {
+        a.1.date_naive() == b.1.date_naive()
+    }
+}
+
+/// Score each date by how many sources agree on it, reducing confidence when the page
+/// carries many competing dates. Dates in `exempt` skip that reduction.
+fn score_date_sources(date_sources: DateSources, exempt: &HashSet<String>) -> Vec<DateWithConfidence> {
     // Calculate confidence scores
     let total_dates = date_sources.len();
     
     // Count how many dates come from body only (for more aggressive penalty)
     let mut body_only_count = 0;
     
-    for (_, (in_meta, in_json_ld, in_body)) in &date_sources {
-        if *in_body && !*in_meta && !*in_json_ld {
+    for flags in date_sources.values() {
+        if flags.body && !flags.meta && !flags.json_ld && !flags.url {
             body_only_count += 1;
         }
     }
     
     let mut dates_with_confidence = Vec::new();
     
-    for (date, (in_meta, in_json_ld, in_body)) in date_sources {
+    for (date, flags) in date_sources {
+        let DateSourceFlags { meta: in_meta, json_ld: in_json_ld, body: in_body, body_cue, url: in_url } = flags;
         let mut confidence = 0.0;
         
         // If date appears in all three sources, confidence = 1.0
-        if in_meta && in_json_ld && in_body {
+        if in_meta && in_json_ld && (in_body || in_url) {
             confidence = 1.0;
         } else if in_meta && in_json_ld {
             // Meta + JSON-LD = high confidence
             confidence = 0.8;
         } else if in_meta || in_json_ld {
-            // Only meta or JSON-LD = medium confidence
-            confidence = 0.5;
+            // Only meta or JSON-LD = medium confidence, a bit more when the URL agrees
+            confidence = if in_url { 0.65 } else { 0.5 };
+        } else if in_url {
+            // URL path dates are deliberate but unstructured: between meta and body-only
+            confidence = URL_DATE_CONFIDENCE;
         } else if in_body {
             // Only in body = low confidence
             confidence = 0.1;
         }
         
+        // A publication cue next to the body occurrence makes the date more trustworthy
+        if in_body && body_cue {
+            confidence = f64::min(confidence + BODY_CUE_BOOST, 1.0);
+        }
+        
         // If there are many dates, reduce confidence for all
         // More dates = lower confidence (since it's ambiguous)
-        if total_dates > 1 {
+        if total_dates > 1 && !exempt.contains(&date) {
             // More aggressive reduction when there are many dates
             // If there are many body-only dates, they should have even lower confidence
-            let reduction_factor = if in_body && !in_meta && !in_json_ld && body_only_count > 1 {
+            let reduction_factor = if in_body && !in_meta && !in_json_ld && !in_url && body_only_count > 1 {
                 // Body-only dates with many similar dates get heavily penalized
                 1.0 / (1.0 + (body_only_count as f64 - 1.0) * 0.3)
             } else {
@@ -125,91 +342,400 @@ pub fn extract_publication_dates_with_confidence(document: &Html) -> Vec<DateWit
     dates_with_confidence
 }
 
-/// Extract all dates from JSON-LD scripts
-fn extract_all_json_ld_dates(document: &Html) -> Vec<String> {
+/// Confidence added when a body date sits next to a publication cue
+const BODY_CUE_BOOST: f64 = 0.15;
+
+/// Base confidence for a date found only in the URL path
+const URL_DATE_CONFIDENCE: f64 = 0.3;
+
+/// `/2023/11/05/` style path segments (trailing slash optional at the end of the path)
+static URL_SLASH_DATE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"/((?:19|20)\d{2})/(0?\d|1[0-2])/([0-2]?\d|3[01])(?:/|$)").unwrap()
+});
+
+/// `2023-11-05` inside a path segment, e.g. `/posts/2023-11-05-title`
+static URL_DASH_DATE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:^|[^\d])((?:19|20)\d{2})-(\d{2})-(\d{2})(?:[^\d]|$)").unwrap()
+});
+
+/// Extract dates encoded in the page URL path, normalized to `YYYY-MM-DD`
+fn extract_dates_from_url(page_url: &str) -> Vec<String> {
+    let path = match Url::parse(page_url) {
+        Ok(url) => url.path().to_string(),
+        Err(_) => return Vec::new(),
+    };
+
     let mut dates = Vec::new();
-    
-    if let Ok(selector) = Selector::parse("script[type='application/ld+json']") {
-        for script in document.select(&selector) {
-            if let Some(text) = script.text().next() {
-                // Try to extract datePublished
-                let escaped_property = regex::escape("datePublished");
-                let pattern = format!(r#""{}"\s*:\s*"([^"]+)""#, escaped_property);
-                if let Ok(re) = Regex::new(&pattern) {
-                    for captures in re.captures_iter(text) {
-                        if let Some(value) = captures.get(1) {
-                            dates.push(value.as_str().to_string());
-                        }
-                    }
-                }
-                
-                // Also try to find any ISO 8601 dates in the JSON
-                // This is a simple regex for ISO 8601 dates
-                let iso_date_pattern = r#"\d{4}-\d{2}-\d{2}(T\d{2}:\d{2}:\d{2}(\.\d+)?(Z|[+-]\d{2}:\d{2})?)?"#;
-                if let Ok(re) = Regex::new(iso_date_pattern) {
-                    for captures in re.captures_iter(text) {
-                        if let Some(date_match) = captures.get(0) {
-                            dates.push(date_match.as_str().to_string());
-                        }
+    for re in [&*URL_SLASH_DATE_RE, &*URL_DASH_DATE_RE] {
+        for captures in re.captures_iter(&path) {
+            let parts = (
+                captures[1].parse::<i32>(),
+                captures[2].parse::<u32>(),
+                captures[3].parse::<u32>(),
+            );
+            if let (Ok(year), Ok(month), Ok(day)) = parts {
+                // Reject impossible dates such as month 0 or Feb 30
+                if let Some(date) = NaiveDate::from_ymd_opt(year, month, day) {
+                    let date = date.format("%Y-%m-%d").to_string();
+                    if !dates.contains(&date) {
+                        dates.push(date);
                     }
                 }
             }
         }
     }
-    
     dates
 }
 
-/// Extract dates from the page body using regex patterns
-fn extract_dates_from_body(document: &Html) -> Vec<String> {
-    let mut dates = Vec::new();
-    
-    // Get all text content from the document body
-    let body_selector = Selector::parse("body").unwrap_or_else(|_| {
-        Selector::parse("html").unwrap()
-    });
-    
-    let text = if let Some(body) = document.select(&body_selector).next() {
-        body.text().collect::<Vec<_>>().join(" ")
-    } else {
-        document.root_element().text().collect::<Vec<_>>().join(" ")
-    };
-    
-    // Common date patterns
-    // ISO 8601: YYYY-MM-DD or YYYY-MM-DDTHH:MM:SS
-    let iso_pattern = r#"\b\d{4}-\d{2}-\d{2}(T\d{2}:\d{2}:\d{2}(\.\d+)?(Z|[+-]\d{2}:\d{2})?)?\b"#;
-    if let Ok(re) = Regex::new(iso_pattern) {
-        for captures in re.captures_iter(&text) {
-            if let Some(date_match) = captures.get(0) {
-                dates.push(date_match.as_str().to_string());
-            }
+/// The JSON-LD date properties read for publication and modified dates
+#[derive(Debug, Clone, Copy)]
+enum JsonLdDate {
+    Published,
+    Modified,
+}
+
+impl JsonLdDate {
+    fn property(self) -> &'static str {
+        match self {
+            JsonLdDate::Published => "datePublished",
+            JsonLdDate::Modified => "dateModified",
         }
     }
-    
-    // Common formats: MM/DD/YYYY, DD/MM/YYYY, YYYY/MM/DD
-    let slash_pattern = r#"\b\d{1,2}/\d{1,2}/\d{4}\b"#;
-    if let Ok(re) = Regex::new(slash_pattern) {
-        for captures in re.captures_iter(&text) {
-            if let Some(date_match) = captures.get(0) {
-                dates.push(date_match.as_str().to_string());
-            }
+
+    /// Pattern for the property's string values in a block that isn't valid JSON
+    fn pattern(self) -> &'static Regex {
+        match self {
+            JsonLdDate::Published => &JSON_LD_DATE_PUBLISHED_RE,
+            JsonLdDate::Modified => &JSON_LD_DATE_MODIFIED_RE,
         }
     }
-    
-    // Month name formats: "January 1, 2024", "Jan 1, 2024", "1 January 2024"
-    let month_pattern = r#"\b(January|February|March|April|May|June|July|August|September|October|November|December|Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec)\s+\d{1,2},?\s+\d{4}\b"#;
-    if let Ok(re) = Regex::new(month_pattern) {
-        for captures in re.captures_iter(&text) {
-            if let Some(date_match) = captures.get(0) {
-                dates.push(date_match.as_str().to_string());
+}
+
+static JSON_LD_DATE_PUBLISHED_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#""datePublished"\s*:\s*"([^"]+)""#).unwrap()
+});
+
+static JSON_LD_DATE_MODIFIED_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#""dateModified"\s*:\s*"([^"]+)""#).unwrap()
+});
+
+/// `"@type": "NewsArticle"` (or an array starting with an Article type) in raw JSON-LD text
+static JSON_LD_ARTICLE_TYPE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(r#""@type"\s*:\s*\[?\s*"(?:{})""#, ARTICLE_TYPES.join("|"))).unwrap()
+});
+
+/// Every value of a JSON-LD date property on Article-typed entities, read from the entities
+/// parsed during indexing and pattern-matched out of blocks that aren't valid JSON but declare
+/// an Article type. Dates on other entities (a product review's `datePublished`, an
+/// organization's `foundingDate`) are not the article's.
+fn extract_json_ld_property_dates(dom_index: &DomIndex, property: JsonLdDate) -> Vec<String> {
+    let mut dates: Vec<String> = dom_index.get_json_ld_entities()
+        .filter(|obj| has_json_ld_type(obj, ARTICLE_TYPES))
+        .filter_map(|obj| obj.get(property.property()))
+        .flat_map(json_ld_strings)
+        .collect();
+
+    let malformed = dom_index.get_json_ld_blocks()
+        .filter(|(text, value)| value.is_none() && JSON_LD_ARTICLE_TYPE_RE.is_match(text));
+    for (text, _) in malformed {
+        dates.extend(
+            property.pattern()
+                .captures_iter(text)
+                .filter_map(|captures| captures.get(1))
+                .map(|value| value.as_str().to_string())
+        );
+    }
+
+    dates
+}
+
+/// Only this many characters of body text are scanned for dates; publication dates sit near
+/// the top of the page and long pages otherwise dominate the article activity's cost
+const MAX_BODY_DATE_SCAN_CHARS: usize = 100_000;
+
+/// Body text date patterns: ISO 8601, slash dates (MM/DD/YYYY, DD/MM/YYYY) and month names
+/// ("January 1, 2024", "Jan 1, 2024")
+static BODY_DATE_RES: Lazy<Vec<Regex>> = Lazy::new(|| {
+    [
+        r#"\b\d{4}-\d{2}-\d{2}(T\d{2}:\d{2}:\d{2}(\.\d+)?(Z|[+-]\d{2}:\d{2})?)?\b"#,
+        r#"\b\d{1,2}/\d{1,2}/\d{4}\b"#,
+        r#"\b(January|February|March|April|May|June|July|August|September|October|November|December|Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec)\s+\d{1,2},?\s+\d{4}\b"#,
+    ]
+    .iter()
+    .map(|pattern| Regex::new(pattern).unwrap())
+    .collect()
+});
+
+/// Built-in publication cues (en/de/fr/es) looked for near body dates; extend them with
+/// `ArticleOptions::date_cues`
+const DATE_CUES: &[&str] = &[
+    "published", "posted", "updated", "last modified",
+    "veröffentlicht", "aktualisiert", "erschienen", "stand:",
+    "publié", "mis à jour", "posté",
+    "publicado", "actualizado",
+];
+
+/// Characters of context examined before / after a body date
+const CUE_WINDOW_BEFORE: usize = 40;
+const CUE_WINDOW_AFTER: usize = 20;
+
+/// A date found in the body text, and whether a publication cue sits next to it
+#[derive(Debug, Clone)]
+pub struct BodyDate {
+    pub date: String,
+    pub has_cue: bool,
+}
+
+/// Lowercased text around `start..end`, bounded by the cue window sizes and by sentence ends so
+/// a cue belonging to a neighbouring sentence isn't attributed to this date
+fn context_window(text: &str, start: usize, end: usize) -> String {
+    let is_sentence_end = |c: char| matches!(c, '.' | '!' | '?' | '|');
+    let before: Vec<char> = text[..start].chars().rev()
+        .take(CUE_WINDOW_BEFORE)
+        .take_while(|c| !is_sentence_end(*c))
+        .collect();
+    let before: String = before.into_iter().rev().collect();
+    let after: String = text[end..].chars()
+        .take(CUE_WINDOW_AFTER)
+        .take_while(|c| !is_sentence_end(*c))
+        .collect();
+    format!("{} {}", before, after).to_lowercase()
+}
+
+/// Extract dates from the first `MAX_BODY_DATE_SCAN_CHARS` characters of body text.
+///
+/// A date counts as cued when its surrounding text contains a publication cue (built-in or
+/// from `extra_cues`) or "by " followed by one of `authors`. Nothing is scanned in
+/// structured-only mode.
+pub fn extract_dates_from_body(dom_index: &DomIndex, authors: &[String], extra_cues: &[String]) -> Vec<BodyDate> {
+    if dom_index.structured_only {
+        return Vec::new();
+    }
+    let document = dom_index.document();
+
+    // Get text content from the document body, stopping once the scan budget is reached
+    let root = Selector::parse("body")
+        .ok()
+        .and_then(|selector| document.select(&selector).next())
+        .unwrap_or_else(|| document.root_element());
+
+    let mut text = String::new();
+    let mut scanned_chars = 0;
+    for node_text in root.text() {
+        let remaining = MAX_BODY_DATE_SCAN_CHARS - scanned_chars;
+        let node_chars = node_text.chars().count();
+        if node_chars >= remaining {
+            text.extend(node_text.chars().take(remaining));
+            break;
+        }
+        text.push_str(node_text);
+        text.push(' ');
+        scanned_chars += node_chars;
+    }
+
+    let author_cues: Vec<String> = authors.iter()
+        .map(|author| format!("by {}", author.to_lowercase()))
+        .collect();
+    let extra_cues: Vec<String> = extra_cues.iter().map(|cue| cue.to_lowercase()).collect();
+
+    let mut dates: Vec<BodyDate> = Vec::new();
+    for re in BODY_DATE_RES.iter() {
+        for date_match in re.find_iter(&text) {
+            let window = context_window(&text, date_match.start(), date_match.end());
+            let has_cue = DATE_CUES.iter().any(|cue| window.contains(cue))
+                || extra_cues.iter().any(|cue| window.contains(cue.as_str()))
+                || author_cues.iter().any(|cue| window.contains(cue.as_str()));
+
+            // Remove duplicates, keeping the cue if any occurrence had one
+            match dates.iter_mut().find(|d| d.date == date_match.as_str()) {
+                Some(existing) => existing.has_cue |= has_cue,
+                None => dates.push(BodyDate {
+                    date: date_match.as_str().to_string(),
+                    has_cue,
+                }),
             }
         }
     }
     
-    // Remove duplicates
-    let mut unique_dates: HashSet<String> = HashSet::new();
-    dates.retain(|d| unique_dates.insert(d.clone()));
-    
     dates
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Activities;
+    use scraper::Html;
+
+    fn publication_dates(html: &str, page_url: &str) -> Vec<DateWithConfidence> {
+        let document = Html::parse_document(html);
+        let activities = Activities { extract_article: vec!["all".to_string()], ..Default::default() };
+        let index = DomIndex::build_for(&document, &activities);
+        let body_dates = extract_dates_from_body(&index, &[], &[]);
+        extract_publication_dates_with_confidence(&index, page_url, &body_dates)
+    }
+
+    #[test]
+    fn url_dates_table() {
+        let cases: &[(&str, &[&str])] = &[
+            ("https://blog.example.com/2023/11/05/slug", &["2023-11-05"]),
+            ("https://blog.example.com/2023/1/5/", &["2023-01-05"]),
+            ("https://blog.example.com/news/1999/12/31", &["1999-12-31"]),
+            ("https://blog.example.com/posts/2023-11-05-title", &["2023-11-05"]),
+            ("https://blog.example.com/archive/2023-11-05.html", &["2023-11-05"]),
+            // Not dates
+            ("https://blog.example.com/12345/678/90/", &[]),
+            ("https://blog.example.com/2023/13/05/slug", &[]),
+            ("https://blog.example.com/2023/02/30/slug", &[]),
+            ("https://blog.example.com/item/120231105-sale", &[]),
+            ("https://blog.example.com/2023/11/slug", &[]),
+            ("https://blog.example.com/slug?date=2023-11-05", &[]),
+            ("not a url", &[]),
+        ];
+        for (url, expected) in cases {
+            assert_eq!(extract_dates_from_url(url), *expected, "url {}", url);
+        }
+    }
+
+    #[test]
+    fn url_date_ranks_between_structured_and_body_dates() {
+        let dates = publication_dates("<body><p>Nothing dated here.</p></body>", "https://blog.example.com/2023/11/05/slug");
+        assert_eq!(dates.len(), 1);
+        assert_eq!(dates[0].date, "2023-11-05");
+        assert_eq!(dates[0].confidence, URL_DATE_CONFIDENCE);
+
+        // A meta date the URL agrees with scores above a meta date alone
+        let html = r#"<head><meta property="article:published_time" content="2023-11-05"></head>"#;
+        let with_url = publication_dates(html, "https://blog.example.com/2023/11/05/slug");
+        let without_url = publication_dates(html, "https://blog.example.com/slug");
+        assert!(with_url[0].confidence > without_url[0].confidence);
+    }
+
+    fn confidence_of(dates: &[DateWithConfidence], date: &str) -> f64 {
+        dates.iter().find(|d| d.date == date).map(|d| d.confidence).unwrap()
+    }
+
+    #[test]
+    fn publication_cue_raises_the_adjacent_body_date() {
+        let html = "<body><p>Published: March 3, 2024</p>\
+                    <p>Reader comment. The epoch began January 1, 1970 for most systems.</p></body>";
+        let dates = publication_dates(html, "https://blog.example.com/post");
+        assert_eq!(dates[0].date, "March 3, 2024");
+        assert!(confidence_of(&dates, "March 3, 2024") > confidence_of(&dates, "January 1, 1970"));
+
+        // Without the cue both body-only dates score the same
+        let html = "<body><p>March 3, 2024</p><p>Reader comment. January 1, 1970 for most systems.</p></body>";
+        let dates = publication_dates(html, "https://blog.example.com/post");
+        assert_eq!(confidence_of(&dates, "March 3, 2024"), confidence_of(&dates, "January 1, 1970"));
+    }
+
+    #[test]
+    fn cues_cover_other_languages_authors_and_extra_cues() {
+        let cued = |html: &str, authors: &[String], extra: &[String]| {
+            let document = Html::parse_document(html);
+            let activities = Activities { extract_article: vec!["all".to_string()], ..Default::default() };
+            let index = DomIndex::build_for(&document, &activities);
+            extract_dates_from_body(&index, authors, extra).iter().map(|d| d.has_cue).collect::<Vec<_>>()
+        };
+
+        assert_eq!(cued("<p>Veröffentlicht am 2024-03-03</p>", &[], &[]), vec![true]);
+        assert_eq!(cued("<p>Publié le 2024-03-03</p>", &[], &[]), vec![true]);
+        assert_eq!(cued("<p>Publicado 2024-03-03</p>", &[], &[]), vec![true]);
+        assert_eq!(cued("<p>By Jane Doe, 2024-03-03</p>", &["Jane Doe".to_string()], &[]), vec![true]);
+        assert_eq!(cued("<p>By Jane Doe, 2024-03-03</p>", &[], &[]), vec![false]);
+        assert_eq!(cued("<p>Gepubliceerd 2024-03-03</p>", &[], &["gepubliceerd".to_string()]), vec![true]);
+        // A cue in the previous sentence doesn't count
+        assert_eq!(cued("<p>Updated yesterday. The law dates from 2024-03-03</p>", &[], &[]), vec![false]);
+    }
+
+    fn json_ld_and_meta(json_ld_date: &str, meta_date: &str) -> String {
+        format!(
+            r#"<head><meta property="article:published_time" content="{}">
+               <script type="application/ld+json">{{"@type":"NewsArticle","datePublished":"{}"}}</script></head>"#,
+            meta_date, json_ld_date,
+        )
+    }
+
+    #[test]
+    fn same_instant_in_different_offsets_is_one_date() {
+        let html = json_ld_and_meta("2024-05-03T10:00:00Z", "2024-05-03T12:00:00+02:00");
+        let dates = publication_dates(&html, "https://news.example.com/story");
+        assert_eq!(dates.len(), 1);
+        assert_eq!(dates[0].date, "2024-05-03T12:00:00+02:00");
+        assert_eq!(dates[0].confidence, 0.8);
+
+        // Different instants on the same day stay apart
+        let html = json_ld_and_meta("2024-05-03T10:00:00Z", "2024-05-03T10:00:00+02:00");
+        assert_eq!(publication_dates(&html, "https://news.example.com/story").len(), 2);
+    }
+
+    #[test]
+    fn date_only_value_joins_the_datetime_on_that_day() {
+        let html = json_ld_and_meta("2024-05-03T23:30:00-04:00", "2024-05-03");
+        let dates = publication_dates(&html, "https://news.example.com/story");
+        assert_eq!(dates.len(), 1);
+        assert_eq!(dates[0].date, "2024-05-03T23:30:00-04:00");
+        assert_eq!(dates[0].confidence, 0.8);
+
+        let html = json_ld_and_meta("2024-05-03T10:00:00Z", "2024-05-04");
+        assert_eq!(publication_dates(&html, "https://news.example.com/story").len(), 2);
+    }
+
+    fn modified_dates(html: &str) -> Vec<DateWithConfidence> {
+        let document = Html::parse_document(html);
+        let activities = Activities { extract_article: vec!["all".to_string()], ..Default::default() };
+        let index = DomIndex::build_for(&document, &activities);
+        let body_dates = extract_dates_from_body(&index, &[], &[]);
+        let published = extract_publication_dates_with_confidence(&index, "https://news.example.com/story", &body_dates);
+        extract_modified_dates_with_confidence(&index, &body_dates, &published)
+    }
+
+    #[test]
+    fn modified_dates_merge_and_match_publication_dates_by_instant() {
+        // The meta and JSON-LD modified dates are one instant
+        let html = r#"<head><meta property="article:modified_time" content="2024-06-01T09:00:00Z">
+            <script type="application/ld+json">{"@type":"NewsArticle","dateModified":"2024-06-01T11:00:00+02:00"}</script></head>"#;
+        let dates = modified_dates(html);
+        assert_eq!(dates.len(), 1);
+        assert_eq!(dates[0].confidence, 0.8);
+
+        // The modified date equal to the publication instant keeps its confidence, the other
+        // competing one is penalized
+        let html = r#"<head><meta property="article:published_time" content="2024-05-03T10:00:00Z">
+            <meta property="article:modified_time" content="2024-05-03T12:00:00+02:00">
+            <meta property="og:updated_time" content="2024-06-01T09:00:00Z"></head>"#;
+        let dates = modified_dates(html);
+        assert_eq!(confidence_of(&dates, "2024-05-03T12:00:00+02:00"), 0.5);
+        assert!(confidence_of(&dates, "2024-06-01T09:00:00Z") < 0.5);
+    }
+
+    fn json_ld_dates(html: &str, property: JsonLdDate) -> Vec<String> {
+        let document = Html::parse_document(html);
+        let activities = Activities { extract_article: vec!["all".to_string()], ..Default::default() };
+        extract_json_ld_property_dates(&DomIndex::build_for(&document, &activities), property)
+    }
+
+    #[test]
+    fn json_ld_date_properties_from_parsed_and_malformed_blocks() {
+        let html = r#"<script type="application/ld+json">{"@graph": [
+                {"@type": "NewsArticle", "datePublished": ["2024-05-03", "2024-05-04"], "dateModified": "2024-06-01"}
+            ]}</script>
+            <script type="application/ld+json">{"@type": "BlogPosting", "datePublished": "2023-01-02",}</script>"#;
+        assert_eq!(json_ld_dates(html, JsonLdDate::Published), vec!["2024-05-03", "2024-05-04", "2023-01-02"]);
+        assert_eq!(json_ld_dates(html, JsonLdDate::Modified), vec!["2024-06-01"]);
+    }
+
+    #[test]
+    fn only_article_entities_supply_json_ld_dates() {
+        let html = r#"<script type="application/ld+json">[
+                {"@type": "Organization", "name": "Ledger", "foundingDate": "1999-04-01", "datePublished": "2001-01-01"},
+                {"@type": "Product", "name": "Kettle",
+                 "offers": {"@type": "Offer", "price": "49.99", "priceValidUntil": "2026-12-31"},
+                 "review": {"@type": "Review", "datePublished": "2022-08-09"}},
+                {"@type": "NewsArticle", "headline": "Kettles", "datePublished": "2024-05-03"}
+            ]</script>
+            <script type="application/ld+json">{"@type": "Product", "datePublished": "2020-02-02",}</script>"#;
+        let dates = publication_dates(html, "https://news.example.com/story");
+        let found: Vec<&str> = dates.iter().map(|d| d.date.as_str()).collect();
+        assert_eq!(found, vec!["2024-05-03"]);
+    }
+}
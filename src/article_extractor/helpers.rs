@@ -1,25 +1,27 @@
 use scraper::Selector;
-use serde_json;
 use regex::Regex;
 use crate::dom_index::DomIndex;
+use crate::json_ld::has_json_ld_type;
+use crate::microdata::{find_main_item, item_property};
+use super::basic::ARTICLE_TYPES;
 
-/// Recursively extract a value from a JSON object, handling nested paths like "publisher.name"
-pub fn extract_value_from_object(obj: &serde_json::Map<String, serde_json::Value>, path: &str) -> Option<String> {
-    let parts: Vec<&str> = path.split('.').collect();
-    let mut current: &serde_json::Value = &serde_json::Value::Object(obj.clone());
+/// Look up a nested path like "author.sameAs" in a JSON object, returning the raw value
+/// (string, array or object) for callers that need more than the first string
+pub fn extract_json_value_from_object<'a>(obj: &'a serde_json::Map<String, serde_json::Value>, path: &str) -> Option<&'a serde_json::Value> {
+    let mut parts = path.split('.');
+    let mut current = obj.get(parts.next()?)?;
     
     for part in parts {
-        if let Some(map) = current.as_object() {
-            if let Some(value) = map.get(part) {
-                current = value;
-            } else {
-                return None;
-            }
-        } else {
-            return None;
-        }
+        current = current.as_object()?.get(part)?;
     }
     
+    Some(current)
+}
+
+/// Recursively extract a value from a JSON object, handling nested paths like "publisher.name"
+pub fn extract_value_from_object(obj: &serde_json::Map<String, serde_json::Value>, path: &str) -> Option<String> {
+    let current = extract_json_value_from_object(obj, path)?;
+    
     // Extract string value, handling arrays
     match current {
         serde_json::Value::String(s) => Some(s.clone()),
@@ -49,32 +51,53 @@ pub fn extract_value_from_object(obj: &serde_json::Map<String, serde_json::Value
     }
 }
 
-/// Extract JSON-LD property from indexed JSON-LD content
+/// Collect person/organization names from a JSON-LD value: plain strings, objects with `name`,
+/// arrays of either, and `@list` containers
+pub fn collect_json_ld_names(value: &serde_json::Value, names: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(s) => names.push(s.clone()),
+        serde_json::Value::Array(arr) => {
+            for item in arr {
+                collect_json_ld_names(item, names);
+            }
+        }
+        serde_json::Value::Object(obj) => {
+            if let Some(list) = obj.get("@list") {
+                collect_json_ld_names(list, names);
+            } else if let Some(name) = obj.get("name") {
+                collect_json_ld_names(name, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collect the string values of a JSON-LD value that is a single string or an array of strings
+pub fn json_ld_strings(value: &serde_json::Value) -> Vec<String> {
+    match value {
+        serde_json::Value::String(s) => vec![s.clone()],
+        serde_json::Value::Array(arr) => arr.iter().filter_map(|v| v.as_str()).map(|s| s.to_string()).collect(),
+        _ => vec![],
+    }
+}
+
+/// Extract JSON-LD property from the entities parsed once during indexing, falling back to
+/// pattern matching on blocks that aren't valid JSON
 pub fn extract_json_ld_property_from_index(dom_index: &DomIndex, properties: &[&str]) -> Option<String> {
-    for json_content in dom_index.get_json_ld_content() {
-        // Try to parse as JSON
-        if let Ok(json_value) = serde_json::from_str::<serde_json::Value>(json_content) {
-            // Handle both single objects and arrays of objects
-            let objects = match json_value {
-                serde_json::Value::Object(obj) => vec![obj],
-                serde_json::Value::Array(arr) => {
-                    arr.into_iter()
-                        .filter_map(|v| v.as_object().cloned())
-                        .collect()
-                }
-                _ => vec![],
-            };
-            
-            for obj in objects {
-                for property in properties {
-                    if let Some(value) = extract_value_from_object(&obj, property) {
-                        return Some(value);
-                    }
-                }
+    for obj in dom_index.get_json_ld_entities() {
+        for property in properties {
+            if let Some(value) = extract_value_from_object(obj, property) {
+                return Some(value);
             }
         }
-        
-        // Fallback to regex for malformed JSON
+    }
+
+    // Fallback to regex for malformed JSON
+    if dom_index.structured_only {
+        return None;
+    }
+    let malformed = dom_index.get_json_ld_blocks().filter(|(_, value)| value.is_none());
+    for (json_content, _) in malformed {
         for property in properties {
             let escaped_property = regex::escape(property);
             let pattern = format!(r#""{}"\s*:\s*"([^"]+)""#, escaped_property);
@@ -90,13 +113,44 @@ pub fn extract_json_ld_property_from_index(dom_index: &DomIndex, properties: &[&
     None
 }
 
+/// Extract a JSON-LD property only from objects whose `@type` is one of `types`, so a page that
+/// also embeds an Organization or Product doesn't leak that entity's `name`/`description`
+pub fn extract_json_ld_property_typed(dom_index: &DomIndex, types: &[&str], properties: &[&str]) -> Option<String> {
+    for obj in dom_index.get_json_ld_entities().filter(|obj| has_json_ld_type(obj, types)) {
+        for property in properties {
+            if let Some(value) = extract_value_from_object(obj, property) {
+                return Some(value);
+            }
+        }
+    }
+    None
+}
+
 /// Extract schema.org property using index and fallback to document
 pub fn extract_schema_property_from_index(dom_index: &DomIndex, property: &str) -> Option<String> {
-    // Try JSON-LD first
-    if let Some(value) = extract_json_ld_property_from_index(dom_index, &[property]) {
-        return Some(value);
+    extract_schema_property_with_source(dom_index, property).map(|(value, _)| value)
+}
+
+/// A schema.org property and where it came from: `"jsonld"` (any JSON-LD entity, or a pattern
+/// match over malformed blocks), else `"microdata"`
+pub fn extract_schema_property_with_source(dom_index: &DomIndex, property: &str) -> Option<(String, &'static str)> {
+    extract_json_ld_property_from_index(dom_index, &[property]).map(|value| (value, "jsonld"))
+        .or_else(|| extract_microdata_property_from_index(dom_index, property).map(|value| (value, "microdata")))
+}
+
+/// Microdata value of a schema.org property: the main article item's, else the first
+/// `itemprop` on the page
+fn extract_microdata_property_from_index(dom_index: &DomIndex, property: &str) -> Option<String> {
+    // Try microdata of the main article item, ignoring teasers of other articles
+    if let Some(article) = find_main_item(dom_index.document(), ARTICLE_TYPES) {
+        return item_property(article, property, ARTICLE_TYPES);
     }
-    
+
+    // Any itemprop on the page may belong to an unrelated item
+    if dom_index.structured_only {
+        return None;
+    }
+
     // Try microdata from index
     if let Some(first) = dom_index.get_first_schema_by_itemprop(property) {
         return Some(first.clone());
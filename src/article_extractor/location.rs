@@ -0,0 +1,104 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::Selector;
+use crate::dom_index::DomIndex;
+use crate::text_extractor::find_main_content_element;
+use super::helpers::collect_json_ld_names;
+
+/// Only the start of the first paragraph is checked for a dateline
+const DATELINE_SCAN_CHARS: usize = 100;
+
+/// An ALL-CAPS place ("LONDON", "NEW YORK", "WASHINGTON, D.C.") optionally followed by a date
+/// and an agency credit, then a dash: "LONDON —", "NEW YORK, Nov 5 (Reuters) -"
+static DATELINE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^([A-Z][A-Z'.\-]+(?:(?:\s+|,\s*)[A-Z][A-Z'.\-]*)*)(?:,\s*[A-Za-z]{3,9}\.?\s+\d{1,2})?\s*(?:\([^)]{1,40}\)\s*)?[—–-]{1,2}\s").unwrap()
+});
+
+/// Dateline / content location of an article and where it was found
+#[derive(Debug, Default)]
+pub struct LocationInfo {
+    pub location: Option<String>,
+    /// `content_location` (JSON-LD `contentLocation`), `dateline` (JSON-LD `dateline`),
+    /// `meta` (`og:locality` / `geo.placename`) or `heuristic` (leading "LONDON —" in the text)
+    pub source: Option<&'static str>,
+}
+
+impl LocationInfo {
+    fn found(location: String, source: &'static str) -> Self {
+        Self { location: Some(location), source: Some(source) }
+    }
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// First JSON-LD `property` value, taking `name` from Place objects
+fn json_ld_place(dom_index: &DomIndex, property: &str) -> Option<String> {
+    for obj in dom_index.get_json_ld_entities() {
+        if let Some(value) = obj.get(property) {
+            let mut names = Vec::new();
+            collect_json_ld_names(value, &mut names);
+            if let Some(name) = names.iter().find_map(|n| non_empty(n)) {
+                return Some(name);
+            }
+        }
+    }
+    None
+}
+
+/// Match a leading ALL-CAPS dateline in the first paragraph of the main content
+fn dateline_from_text(dom_index: &DomIndex) -> Option<String> {
+    let document = dom_index.document();
+    let paragraph_selector = Selector::parse("p").ok()?;
+    let paragraph_text = |root: scraper::ElementRef| {
+        root.select(&paragraph_selector)
+            .map(|p| p.text().collect::<Vec<_>>().join(" "))
+            .find_map(|text| non_empty(&text))
+    };
+
+    let text = find_main_content_element(document)
+        .and_then(paragraph_text)
+        .or_else(|| paragraph_text(document.root_element()))?;
+    let start: String = text.chars().take(DATELINE_SCAN_CHARS).collect();
+
+    let captures = DATELINE_RE.captures(&start)?;
+    let place = captures[1].trim_end_matches([',', ' ']).to_string();
+    // A dash after a lone capitalized word ("A — ...") isn't a place
+    if place.chars().filter(|c| c.is_alphabetic()).count() < 2 {
+        return None;
+    }
+    Some(place)
+}
+
+/// Find the article's dateline location: JSON-LD `contentLocation`, then JSON-LD `dateline`,
+/// then `og:locality` / `geo.placename` metas, then a leading "LONDON —" / "LONDON (Reuters) -"
+/// style dateline in the first paragraph (lowest confidence)
+pub fn extract_location(dom_index: &DomIndex) -> LocationInfo {
+    if let Some(location) = json_ld_place(dom_index, "contentLocation") {
+        return LocationInfo::found(location, "content_location");
+    }
+    if let Some(location) = json_ld_place(dom_index, "dateline") {
+        return LocationInfo::found(location, "dateline");
+    }
+
+    let meta_location = dom_index.get_meta_by_property("og:locality")
+        .or_else(|| dom_index.get_meta_by_name("geo.placename"))
+        .and_then(|value| non_empty(value));
+    if let Some(location) = meta_location {
+        return LocationInfo::found(location, "meta");
+    }
+
+    if dom_index.structured_only {
+        return LocationInfo::default();
+    }
+    match dateline_from_text(dom_index) {
+        Some(location) => LocationInfo::found(location, "heuristic"),
+        None => LocationInfo::default(),
+    }
+}
@@ -1,35 +1,101 @@
 mod helpers;
 mod dates;
+mod basic;
+mod paywall;
+mod location;
+mod comments;
+mod sections;
+mod body;
+
+pub use dates::{parse_date, to_iso_string};
+pub use sections::breadcrumb_sections;
 
 use std::collections::HashMap;
 use crate::dom_index::DomIndex;
+use crate::link_extractor::extract_amp_url;
+use crate::types::{ArticleInfo, ArticleOptions};
 
 /// Returns a list of all available article metadata field names
 pub fn get_all_article_fields() -> Vec<String> {
     vec![
+        "article_type".to_string(),
+        "is_article".to_string(),
         "title".to_string(),
         "author".to_string(),
+        "authors".to_string(),
+        "author_url".to_string(),
+        "author_profiles".to_string(),
         "description".to_string(),
+        "location".to_string(),
+        "location_source".to_string(),
+        "image".to_string(),
+        "image_alt".to_string(),
         "publication_date".to_string(),
+        "best_publication_date".to_string(),
         "modified_date".to_string(),
+        "modified_date_confidence".to_string(),
+        "best_modified_date".to_string(),
         "article_section".to_string(),
+        "sections".to_string(),
+        "primary_section".to_string(),
         "article_tag".to_string(),
+        "all_tags".to_string(),
         "article_author".to_string(),
         "article_published_time".to_string(),
         "article_modified_time".to_string(),
         "article_expiration_time".to_string(),
         "categories".to_string(),
+        "comment_count".to_string(),
+        "has_comments_section".to_string(),
+        "paywall".to_string(),
+        "paywall_selector".to_string(),
+        "canonical_url".to_string(),
+        "amp_url".to_string(),
+        "word_count".to_string(),
+        "reading_time".to_string(),
     ]
 }
 
+/// Fields that are only extracted when requested by name, never as part of "all"
+/// (`article_body` can be as long as the page itself)
+pub const OPT_IN_ARTICLE_FIELDS: &[&str] = &["article_body"];
+
+/// Fields whose values are JSON-encoded lists in the `HashMap<String, String>` output.
+/// The Python bindings decode these into real lists.
+#[cfg(feature = "python")]
+pub const JSON_ARTICLE_FIELDS: &[&str] = &[
+    "authors",
+    "author_profiles",
+    "publication_date",
+    "modified_date_confidence",
+    "all_tags",
+    "sections",
+];
+
+/// Whether `field` is an article field name or alias (or "all")
+pub fn is_known_article_field(field: &str) -> bool {
+    let field = normalize_field_name(field);
+    field == "all" || OPT_IN_ARTICLE_FIELDS.contains(&field.as_str()) || get_all_article_fields().contains(&field)
+}
+
+/// Whether the requested article fields need the main-content text to be extracted
+pub fn article_fields_need_text(article_fields: &[String]) -> bool {
+    article_fields.iter().any(|f| {
+        matches!(normalize_field_name(f).as_str(), "all" | "word_count" | "reading_time")
+    })
+}
+
 /// Normalize field name - converts aliases to full field names
 fn normalize_field_name(field: &str) -> String {
     match field {
         // Short aliases
         "title" => "title".to_string(),
+        "type" => "article_type".to_string(),
         "author" => "author".to_string(),
+        "profiles" => "author_profiles".to_string(),
         "description" => "description".to_string(),
         "pub_date" => "publication_date".to_string(),
+        "best_pub_date" => "best_publication_date".to_string(),
         "pub_date_time" => "article_published_time".to_string(),
         "modified_time" => "article_modified_time".to_string(),
         "expiration_time" => "article_expiration_time".to_string(),
@@ -37,114 +103,256 @@ fn normalize_field_name(field: &str) -> String {
         "tag" => "article_tag".to_string(),
         "tags" => "article_tag".to_string(),
         "category" => "categories".to_string(),
+        "canonical" => "canonical_url".to_string(),
+        "amp" => "amp_url".to_string(),
+        "comments" => "comment_count".to_string(),
+        "words" => "word_count".to_string(),
+        "read_time" => "reading_time".to_string(),
+        "body" => "article_body".to_string(),
         // Full names pass through
         _ => field.to_string(),
     }
 }
 
-/// Extract article metadata from HTML document using DOM index
-pub fn extract_article_with_index(dom_index: &DomIndex, article_fields: &[String]) -> HashMap<String, String> {
-    use helpers::{extract_json_ld_property_from_index, extract_schema_property_from_index};
-    use dates::extract_publication_dates_with_confidence;
-    use scraper::Selector;
+/// Requested article fields with aliases resolved and "all" expanded; opt-in fields still
+/// have to be named explicitly
+fn fields_to_extract(article_fields: &[String]) -> Vec<String> {
+    if article_fields.iter().any(|f| f == "all") {
+        let mut fields = get_all_article_fields();
+        fields.extend(
+            article_fields
+                .iter()
+                .map(|f| normalize_field_name(f))
+                .filter(|f| OPT_IN_ARTICLE_FIELDS.contains(&f.as_str())),
+        );
+        fields
+    } else {
+        article_fields.iter().map(|f| normalize_field_name(f)).collect()
+    }
+}
+
+/// Build typed article metadata from OG/meta/JSON-LD/microdata using the same fallback order as the HashMap output
+///
+/// Only the parts the requested `article_fields` read are looked up (all of them for "all");
+/// the others keep their defaults. The body date scan, the slowest of them, only runs for
+/// publication and modified date fields.
+///
+/// `base_url` is the page URL (its path can carry the publication date).
+/// `text` is the page text as `extract_text` returns it (the main content, or the whole body
+/// when no container is long enough, after sanitizing), when it was extracted; word count and
+/// reading time are derived from it.
+pub fn extract_article_info(
+    dom_index: &DomIndex,
+    base_url: &str,
+    article_fields: &[String],
+    options: &ArticleOptions,
+    text: Option<&str>,
+) -> ArticleInfo {
+    let fields = fields_to_extract(article_fields);
+    let wants = |names: &[&str]| fields.iter().any(|f| names.contains(&f.as_str()));
+    let wants_modified = wants(&["modified_date_confidence", "best_modified_date"]);
+    let wants_dates = wants_modified || wants(&["publication_date", "best_publication_date"]);
+
+    // The body date scan skips bylines, so dates need the authors as well
+    let authors = if wants_dates || wants(&["author", "authors"]) {
+        basic::extract_article_authors(dom_index)
+    } else {
+        Vec::new()
+    };
+    let (dates_with_confidence, modified_dates_with_confidence) = if wants_dates {
+        // Scan the body for dates once; both confidence scorers reuse it
+        let body_dates = dates::extract_dates_from_body(dom_index, &authors, &options.date_cues);
+        let published = dates::extract_publication_dates_with_confidence(dom_index, base_url, &body_dates);
+        let modified = if wants_modified {
+            dates::extract_modified_dates_with_confidence(dom_index, &body_dates, &published)
+        } else {
+            Vec::new()
+        };
+        (published, modified)
+    } else {
+        (Vec::new(), Vec::new())
+    };
+    let word_count = text.map(basic::count_words);
+    let article_type = if wants(&["article_type", "is_article"]) {
+        basic::extract_article_type(dom_index)
+    } else {
+        None
+    };
+
+    ArticleInfo {
+        is_article: wants(&["is_article"]) && basic::is_article(dom_index, article_type.as_deref()),
+        article_type,
+        // Sections drop a final breadcrumb naming the article itself
+        title: if wants(&["title", "sections", "primary_section"]) { basic::extract_article_title(dom_index) } else { None },
+        description: if wants(&["description"]) { basic::extract_article_description(dom_index) } else { None },
+        authors,
+        published: dates::best_parsed_date(&dates_with_confidence, options.date_confidence_threshold),
+        modified: dates::best_parsed_date(&modified_dates_with_confidence, options.date_confidence_threshold),
+        section: dom_index.get_meta_by_property("article:section").cloned(),
+        tags: if wants(&["all_tags"]) { basic::extract_article_tags(dom_index) } else { Vec::new() },
+        dates_with_confidence,
+        modified_dates_with_confidence,
+        word_count,
+        reading_time: word_count.map(basic::reading_time_minutes),
+    }
+}
+
+/// Keep the value of a lookup that reports its source, noting the source in `source`
+fn tracked(found: Option<(String, &'static str)>, source: &mut Option<&'static str>) -> Option<String> {
+    found.map(|(value, from)| {
+        *source = Some(from);
+        value
+    })
+}
+
+/// Extract article metadata from HTML document using DOM index, along with where each value
+/// came from (`"meta"`, `"jsonld"`, `"microdata"`, `"title_tag"`, ...) keyed by the same field
+/// names. Only fields found through a tiered lookup (title, description, image, location,
+/// categories, canonical URL) get a source.
+///
+/// Fields shared with the typed `ArticleInfo` are read from it so the
+/// underlying sources are only scanned once; with `collect_sources`, title and description
+/// are looked up again for their source.
+pub fn extract_article_with_sources(
+    dom_index: &DomIndex,
+    base_url: &str,
+    article_fields: &[String],
+    info: &ArticleInfo,
+    collect_sources: bool,
+) -> (HashMap<String, String>, HashMap<String, String>) {
     use serde_json;
     
     let mut articles = HashMap::new();
+    let mut sources = HashMap::new();
 
-    // Check if "all" is in the list
-    let fields_to_extract = if article_fields.iter().any(|f| f == "all") {
-        get_all_article_fields()
-    } else {
-        article_fields.iter().map(|f| normalize_field_name(f)).collect()
-    };
+    let fields_to_extract = fields_to_extract(article_fields);
+
+    // Image and alt come from the same lookup, so resolve it at most once
+    let mut image: Option<Option<(String, Option<String>, &'static str)>> = None;
+    let mut paywall: Option<paywall::PaywallInfo> = None;
+    let mut author_profile: Option<basic::AuthorProfile> = None;
+    let mut location: Option<location::LocationInfo> = None;
+    let mut sections: Option<sections::SectionInfo> = None;
 
     for field in &fields_to_extract {
+        let mut source = None;
         let value = match field.as_str() {
-            "title" => {
-                // Try Open Graph title first (from index)
-                dom_index.get_meta_by_property("og:title")
-                    .cloned()
-                    // Try Twitter Card title
-                    .or_else(|| dom_index.get_meta_by_name("twitter:title").cloned())
-                    // Try JSON-LD (headline, name)
-                    .or_else(|| extract_json_ld_property_from_index(dom_index, &["headline", "name"]))
-                    // Try title tag
-                    .or_else(|| dom_index.get_first_element_by_tag("title").cloned())
-                    // Try h1 as fallback
-                    .or_else(|| dom_index.get_first_element_by_tag("h1").cloned())
+            "article_type" => info.article_type.clone(),
+            "is_article" => Some(info.is_article.to_string()),
+            "title" if collect_sources => tracked(basic::extract_article_title_with_source(dom_index), &mut source),
+            "title" => info.title.clone(),
+            "author" => info.authors.first().cloned(),
+            "authors" => {
+                if info.authors.is_empty() {
+                    None
+                } else {
+                    serde_json::to_string(&info.authors).ok()
+                }
             },
-            "author" => {
-                dom_index.get_meta_by_property("article:author")
-                    .cloned()
-                    .or_else(|| dom_index.get_meta_by_name("author").cloned())
-                    .or_else(|| dom_index.get_meta_by_property("og:article:author").cloned())
-                    // Try rel="author" link
-                    .or_else(|| {
-                        if let Ok(selector) = Selector::parse("a[rel='author']") {
-                            if let Some(link) = dom_index.document().select(&selector).next() {
-                                let text = link.text().collect::<String>().trim().to_string();
-                                if !text.is_empty() {
-                                    Some(text)
-                                } else {
-                                    None
-                                }
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
-                        }
-                    })
-                    // Try schema.org author
-                    .or_else(|| extract_schema_property_from_index(dom_index, "author"))
+            "author_url" => author_profile
+                .get_or_insert_with(|| basic::extract_article_author_profile(dom_index, base_url))
+                .url
+                .clone(),
+            "author_profiles" => {
+                let profile = author_profile
+                    .get_or_insert_with(|| basic::extract_article_author_profile(dom_index, base_url));
+                if profile.same_as.is_empty() {
+                    None
+                } else {
+                    serde_json::to_string(&profile.same_as).ok()
+                }
             },
-            "description" => {
-                dom_index.get_meta_by_property("og:description")
-                    .cloned()
-                    // Try Twitter Card description
-                    .or_else(|| dom_index.get_meta_by_name("twitter:description").cloned())
-                    // Try standard meta description
-                    .or_else(|| dom_index.get_meta_by_name("description").cloned())
-                    // Try schema.org description
-                    .or_else(|| extract_schema_property_from_index(dom_index, "description"))
+            "description" if collect_sources => tracked(basic::extract_article_description_with_source(dom_index), &mut source),
+            "description" => info.description.clone(),
+            "location" => {
+                let found = location.get_or_insert_with(|| location::extract_location(dom_index));
+                source = found.source;
+                found.location.clone()
             },
+            "location_source" => location
+                .get_or_insert_with(|| location::extract_location(dom_index))
+                .source
+                .map(|s| s.to_string()),
+            "image" => tracked(
+                image
+                    .get_or_insert_with(|| basic::extract_article_image(dom_index, base_url))
+                    .as_ref()
+                    .map(|(url, _, from)| (url.clone(), *from)),
+                &mut source,
+            ),
+            "image_alt" => image
+                .get_or_insert_with(|| basic::extract_article_image(dom_index, base_url))
+                .as_ref()
+                .and_then(|(_, alt, _)| alt.clone()),
             "publication_date" => {
-                // For dates with confidence, we still need the full document
-                let dates = extract_publication_dates_with_confidence(dom_index.document());
-                if dates.is_empty() {
+                if info.dates_with_confidence.is_empty() {
                     None
                 } else {
-                    serde_json::to_string(&dates).ok()
+                    serde_json::to_string(&info.dates_with_confidence).ok()
                 }
             },
-            "modified_date" => {
-                dom_index.get_meta_by_property("article:modified_time")
-                    .cloned()
-                    .or_else(|| dom_index.get_meta_by_property("og:updated_time").cloned())
+            "best_publication_date" => info.published.as_ref().map(dates::to_iso_string),
+            "modified_date" => basic::extract_article_modified_date(dom_index),
+            "modified_date_confidence" => {
+                if info.modified_dates_with_confidence.is_empty() {
+                    None
+                } else {
+                    serde_json::to_string(&info.modified_dates_with_confidence).ok()
+                }
             },
+            "best_modified_date" => info.modified.as_ref().map(dates::to_iso_string),
             "article_section" => dom_index.get_meta_by_property("article:section").cloned(),
+            "sections" => {
+                let section_info = sections
+                    .get_or_insert_with(|| sections::extract_sections(dom_index, base_url, info.title.as_deref()));
+                if section_info.sections.is_empty() {
+                    None
+                } else {
+                    serde_json::to_string(&section_info.sections).ok()
+                }
+            },
+            "primary_section" => sections
+                .get_or_insert_with(|| sections::extract_sections(dom_index, base_url, info.title.as_deref()))
+                .primary
+                .clone(),
             "article_tag" => dom_index.get_meta_by_property("article:tag").cloned(),
+            "all_tags" => {
+                if info.tags.is_empty() {
+                    None
+                } else {
+                    serde_json::to_string(&info.tags).ok()
+                }
+            },
             "article_author" => dom_index.get_meta_by_property("article:author").cloned(),
             "article_published_time" => dom_index.get_meta_by_property("article:published_time").cloned(),
             "article_modified_time" => dom_index.get_meta_by_property("article:modified_time").cloned(),
             "article_expiration_time" => dom_index.get_meta_by_property("article:expiration_time").cloned(),
-            "categories" => {
-                dom_index.get_meta_by_property("article:tag")
-                    .cloned()
-                    .or_else(|| dom_index.get_meta_by_property("article:section").cloned())
-                    // Try JSON-LD (articleSection, keywords)
-                    .or_else(|| extract_json_ld_property_from_index(dom_index, &["articleSection", "keywords"]))
-                    // Try keywords meta tag
-                    .or_else(|| dom_index.get_meta_by_name("keywords").cloned())
-            },
+            "categories" => tracked(basic::extract_article_categories_with_source(dom_index), &mut source),
+            "comment_count" => comments::extract_comment_count(dom_index).map(|c| c.to_string()),
+            "has_comments_section" => Some(comments::has_comments_section(dom_index).to_string()),
+            "paywall" => paywall
+                .get_or_insert_with(|| paywall::extract_paywall(dom_index))
+                .paywalled
+                .map(|p| p.to_string()),
+            "paywall_selector" => paywall
+                .get_or_insert_with(|| paywall::extract_paywall(dom_index))
+                .selector
+                .clone(),
+            "canonical_url" => tracked(basic::extract_article_canonical_url_with_source(dom_index, base_url), &mut source),
+            "amp_url" => extract_amp_url(dom_index, base_url),
+            "word_count" => info.word_count.map(|c| c.to_string()),
+            "reading_time" => info.reading_time.map(|m| m.to_string()),
+            "article_body" => body::extract_article_body(dom_index),
             _ => None,
         };
 
         if let Some(v) = value {
+            if let Some(source) = source {
+                sources.insert(field.clone(), source.to_string());
+            }
             articles.insert(field.clone(), v);
         }
     }
 
-    articles
+    (articles, sources)
 }
-
@@ -0,0 +1,82 @@
+use crate::dom_index::DomIndex;
+
+/// Paywall signals found on the page
+pub struct PaywallInfo {
+    /// `Some(true)` when paywalled, `Some(false)` when declared free, `None` when unknown
+    pub paywalled: Option<bool>,
+    /// CSS selector(s) of the paywalled region declared via `hasPart`
+    pub selector: Option<String>,
+}
+
+/// Interpret an `isAccessibleForFree` value (boolean or "True"/"False" string)
+fn parse_accessible_for_free(value: &serde_json::Value) -> Option<bool> {
+    match value {
+        serde_json::Value::Bool(b) => Some(*b),
+        serde_json::Value::String(s) => match s.trim().to_lowercase().as_str() {
+            "true" | "yes" => Some(true),
+            "false" | "no" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Collect `cssSelector` values from `hasPart` entries that are not accessible for free
+fn collect_paywalled_selectors(has_part: &serde_json::Value, selectors: &mut Vec<String>) {
+    match has_part {
+        serde_json::Value::Array(arr) => {
+            for part in arr {
+                collect_paywalled_selectors(part, selectors);
+            }
+        }
+        serde_json::Value::Object(obj) => {
+            let is_free = obj.get("isAccessibleForFree").and_then(parse_accessible_for_free);
+            if is_free == Some(false) {
+                if let Some(selector) = obj.get("cssSelector").and_then(|v| v.as_str()) {
+                    let selector = selector.trim();
+                    if !selector.is_empty() && !selectors.iter().any(|s| s == selector) {
+                        selectors.push(selector.to_string());
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Detect paywalls from JSON-LD `isAccessibleForFree` / `hasPart` markup and the
+/// `article:content_tier` meta tag
+pub fn extract_paywall(dom_index: &DomIndex) -> PaywallInfo {
+    let mut paywalled = None;
+    let mut selectors = Vec::new();
+
+    for obj in dom_index.get_json_ld_entities() {
+        if let Some(is_free) = obj.get("isAccessibleForFree").and_then(parse_accessible_for_free) {
+            // A paywalled declaration wins over a free one elsewhere on the page
+            if paywalled != Some(true) {
+                paywalled = Some(!is_free);
+            }
+        }
+        if let Some(has_part) = obj.get("hasPart") {
+            collect_paywalled_selectors(has_part, &mut selectors);
+        }
+    }
+
+    if !selectors.is_empty() {
+        paywalled = Some(true);
+    }
+
+    if paywalled.is_none() {
+        paywalled = dom_index.get_meta_by_property("article:content_tier")
+            .and_then(|tier| match tier.trim().to_lowercase().as_str() {
+                "locked" | "metered" => Some(true),
+                "free" => Some(false),
+                _ => None,
+            });
+    }
+
+    PaywallInfo {
+        paywalled,
+        selector: if selectors.is_empty() { None } else { Some(selectors.join(", ")) },
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::HashSet;
+use crate::dom_index::DomIndex;
+use crate::link_extractor::resolve_url;
+use super::helpers::json_ld_strings;
+use crate::json_ld::json_ld_types;
+
+/// Breadcrumb entries that point at the site root rather than a section
+const HOME_CRUMBS: &[&str] = &["home", "homepage", "start", "startseite", "accueil", "inicio"];
+
+/// Declared article sections, from broadest to most specific where a breadcrumb gives order
+#[derive(Debug, Default)]
+pub struct SectionInfo {
+    pub sections: Vec<String>,
+    /// The most specific breadcrumb section, else the first `article:section`
+    pub primary: Option<String>,
+}
+
+/// A breadcrumb entry's name: `name`, or `item.name` when the item is an object
+fn crumb_name(obj: &serde_json::Map<String, serde_json::Value>) -> Option<String> {
+    obj.get("name")
+        .and_then(|n| n.as_str())
+        .or_else(|| obj.get("item").and_then(|i| i.get("name")).and_then(|n| n.as_str()))
+        .map(|n| n.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|n| !n.is_empty())
+}
+
+/// A breadcrumb entry's URL: `item` as a string, or `item.@id` / `item.url`
+fn crumb_url(obj: &serde_json::Map<String, serde_json::Value>) -> Option<String> {
+    match obj.get("item")? {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Object(item) => item.get("@id")
+            .or_else(|| item.get("url"))
+            .and_then(|u| u.as_str())
+            .map(|u| u.to_string()),
+        _ => None,
+    }
+}
+
+/// Section names from the first JSON-LD `BreadcrumbList`, ordered by `position`. The leading
+/// home crumb and a trailing crumb for the page itself (same URL or same title) are dropped.
+pub fn breadcrumb_sections(dom_index: &DomIndex, base_url: &str, title: Option<&str>) -> Vec<String> {
+    let list = dom_index.get_json_ld_entities()
+        .find(|obj| json_ld_types(obj).contains(&"BreadcrumbList"));
+    let Some(elements) = list.and_then(|l| l.get("itemListElement")).and_then(|e| e.as_array()) else {
+        return Vec::new();
+    };
+
+    let mut crumbs: Vec<(i64, String, Option<String>)> = elements.iter()
+        .enumerate()
+        .filter_map(|(i, element)| {
+            let obj = element.as_object()?;
+            let position = obj.get("position")
+                .and_then(|p| p.as_i64().or_else(|| p.as_str().and_then(|s| s.trim().parse().ok())))
+                .unwrap_or(i as i64);
+            Some((position, crumb_name(obj)?, crumb_url(obj)))
+        })
+        .collect();
+    crumbs.sort_by_key(|(position, _, _)| *position);
+
+    if crumbs.first().is_some_and(|(_, name, _)| HOME_CRUMBS.contains(&name.to_lowercase().as_str())) {
+        crumbs.remove(0);
+    }
+
+    let page_url = resolve_url(base_url, "");
+    let is_page_crumb = |(_, name, url): &(i64, String, Option<String>)| {
+        url.as_deref().is_some_and(|u| resolve_url(base_url, u) == page_url)
+            || title.is_some_and(|t| t.trim().eq_ignore_ascii_case(name))
+    };
+    if crumbs.last().is_some_and(is_page_crumb) {
+        crumbs.pop();
+    }
+
+    crumbs.into_iter().map(|(_, name, _)| name).collect()
+}
+
+/// Aggregate the breadcrumb trail, every `article:section` meta and JSON-LD `articleSection`
+/// values (string or array), in that order, deduplicated case-insensitively
+pub fn extract_sections(dom_index: &DomIndex, base_url: &str, title: Option<&str>) -> SectionInfo {
+    let breadcrumbs = breadcrumb_sections(dom_index, base_url, title);
+    let meta_sections: Vec<String> = dom_index.meta_by_property
+        .get("article:section")
+        .cloned()
+        .unwrap_or_default();
+
+    let mut json_ld_sections = Vec::new();
+    for obj in dom_index.get_json_ld_entities() {
+        if let Some(section) = obj.get("articleSection") {
+            json_ld_sections.extend(json_ld_strings(section));
+        }
+    }
+
+    let primary = breadcrumbs.last()
+        .or_else(|| meta_sections.first())
+        .or_else(|| json_ld_sections.first())
+        .map(|s| s.trim().to_string());
+
+    let mut seen = HashSet::new();
+    let sections = breadcrumbs.into_iter()
+        .chain(meta_sections)
+        .chain(json_ld_sections)
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty() && seen.insert(s.to_lowercase()))
+        .collect();
+
+    SectionInfo { sections, primary }
+}
@@ -0,0 +1,562 @@
+use crate::error::ExtractionError;
+use crate::extractor::WebExtractor;
+use crate::types::{DimensionUnit, InlineLinkMode, LanguageFilterMode, TextSanitize, UnicodeNormalizationForm, WeightUnit};
+use reqwest::Client;
+use std::collections::HashMap;
+
+/// Fluent builder for `WebExtractor`
+///
+/// ```no_run
+/// use _ferriscope_native::WebExtractorBuilder;
+///
+/// # fn main() -> Result<(), _ferriscope_native::ExtractionError> {
+/// let mut extractor = WebExtractorBuilder::new()
+///     .url("https://example.com/article")
+///     .timeout(10)
+///     .user_agent("MyBot/1.0")
+///     .extract_text(true)
+///     .extract_links(["internal"])
+///     .build()?;
+/// let result = extractor.run()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Every method maps onto an existing `WebExtractor` setter; `build` applies them in order.
+#[derive(Default)]
+pub struct WebExtractorBuilder {
+    url: Option<String>,
+    html: Option<String>,
+    client: Option<Client>,
+    timeout_secs: Option<u64>,
+    user_agent: Option<String>,
+    random_user_agent: Option<bool>,
+    headers: HashMap<String, String>,
+    accept_language: Option<String>,
+    danger_accept_invalid_certs: bool,
+    root_certificates: Vec<Vec<u8>>,
+    http2_prior_knowledge: bool,
+    pool_idle_timeout_secs: Option<u64>,
+    text: Option<bool>,
+    inline_link_mode: Option<InlineLinkMode>,
+    min_content_length: Option<usize>,
+    compute_simhash: bool,
+    unicode_normalization: Option<UnicodeNormalizationForm>,
+    text_sanitize: Option<TextSanitize>,
+    allowed_languages: Option<Vec<String>>,
+    language_filter_mode: Option<LanguageFilterMode>,
+    links: Option<Vec<String>>,
+    allowed_schemes: Option<Vec<String>>,
+    max_links: Option<usize>,
+    max_links_per_domain: Option<usize>,
+    socials: Option<Vec<String>>,
+    video: Option<Vec<String>>,
+    product: Option<Vec<String>>,
+    max_product_reviews: Option<usize>,
+    sale_epsilon: Option<f64>,
+    weight_unit: Option<WeightUnit>,
+    dimension_unit: Option<DimensionUnit>,
+    products_list: Option<usize>,
+    article: Option<Vec<String>>,
+    lists: bool,
+    strict_fields: bool,
+    collect_provenance: bool,
+    structured_only: bool,
+    follow_meta_refresh: bool,
+    fetch_oembed: bool,
+    parallel_passes: bool,
+    max_json_ld_bytes: Option<usize>,
+    recipe: bool,
+    event: bool,
+    faqs: bool,
+    contacts: bool,
+    emails: bool,
+    preview_image: bool,
+    alternate_links: bool,
+    phone_region: Option<String>,
+    date_confidence_threshold: Option<f64>,
+    date_cues: Vec<String>,
+}
+
+fn to_fields<I, S>(fields: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    fields.into_iter().map(Into::into).collect()
+}
+
+impl WebExtractorBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// URL to fetch (or to resolve links against when `html` is provided)
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Use this HTML instead of downloading the URL
+    pub fn html(mut self, html: impl Into<String>) -> Self {
+        self.html = Some(html.into());
+        self
+    }
+
+    /// Use this HTTP client instead of building one; see `WebExtractor::with_client`
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    pub fn timeout(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = Some(timeout_secs);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    pub fn random_user_agent(mut self, enabled: bool) -> Self {
+        self.random_user_agent = Some(enabled);
+        self
+    }
+
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    pub fn accept_language(mut self, langs: impl Into<String>) -> Self {
+        self.accept_language = Some(langs.into());
+        self
+    }
+
+    /// Accept any TLS certificate; see `WebExtractor::set_danger_accept_invalid_certs`
+    pub fn danger_accept_invalid_certs(mut self, enabled: bool) -> Self {
+        self.danger_accept_invalid_certs = enabled;
+        self
+    }
+
+    /// Trust an additional PEM-encoded root certificate; invalid PEM fails `build`
+    pub fn add_root_certificate(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.root_certificates.push(pem.into());
+        self
+    }
+
+    pub fn http2_prior_knowledge(mut self, enabled: bool) -> Self {
+        self.http2_prior_knowledge = enabled;
+        self
+    }
+
+    pub fn pool_idle_timeout(mut self, idle_secs: u64) -> Self {
+        self.pool_idle_timeout_secs = Some(idle_secs);
+        self
+    }
+
+    pub fn extract_text(mut self, language_detection: bool) -> Self {
+        self.text = Some(language_detection);
+        self
+    }
+
+    pub fn inline_link_mode(mut self, mode: InlineLinkMode) -> Self {
+        self.inline_link_mode = Some(mode);
+        self
+    }
+
+    pub fn min_content_length(mut self, chars: usize) -> Self {
+        self.min_content_length = Some(chars);
+        self
+    }
+
+    pub fn compute_simhash(mut self, enabled: bool) -> Self {
+        self.compute_simhash = enabled;
+        self
+    }
+
+    pub fn unicode_normalization(mut self, form: UnicodeNormalizationForm) -> Self {
+        self.unicode_normalization = Some(form);
+        self
+    }
+
+    pub fn text_sanitize(mut self, options: TextSanitize) -> Self {
+        self.text_sanitize = Some(options);
+        self
+    }
+
+    pub fn allowed_languages<I, S>(mut self, langs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_languages = Some(to_fields(langs));
+        self
+    }
+
+    pub fn language_filter_mode(mut self, mode: LanguageFilterMode) -> Self {
+        self.language_filter_mode = Some(mode);
+        self
+    }
+
+    pub fn extract_links<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.links = Some(to_fields(fields));
+        self
+    }
+
+    pub fn allowed_schemes<I, S>(mut self, schemes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_schemes = Some(to_fields(schemes));
+        self
+    }
+
+    pub fn max_links(mut self, total: usize) -> Self {
+        self.max_links = Some(total);
+        self
+    }
+
+    pub fn max_links_per_domain(mut self, n: usize) -> Self {
+        self.max_links_per_domain = Some(n);
+        self
+    }
+
+    pub fn extract_socials<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.socials = Some(to_fields(fields));
+        self
+    }
+
+    pub fn extract_video<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.video = Some(to_fields(fields));
+        self
+    }
+
+    pub fn extract_product<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.product = Some(to_fields(fields));
+        self
+    }
+
+    pub fn max_product_reviews(mut self, n: usize) -> Self {
+        self.max_product_reviews = Some(n);
+        self
+    }
+
+    pub fn sale_epsilon(mut self, epsilon: f64) -> Self {
+        self.sale_epsilon = Some(epsilon);
+        self
+    }
+
+    pub fn weight_unit(mut self, unit: WeightUnit) -> Self {
+        self.weight_unit = Some(unit);
+        self
+    }
+
+    pub fn dimension_unit(mut self, unit: DimensionUnit) -> Self {
+        self.dimension_unit = Some(unit);
+        self
+    }
+
+    pub fn extract_products_list(mut self, max: usize) -> Self {
+        self.products_list = Some(max);
+        self
+    }
+
+    pub fn extract_article<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.article = Some(to_fields(fields));
+        self
+    }
+
+    pub fn extract_lists(mut self) -> Self {
+        self.lists = true;
+        self
+    }
+
+    pub fn strict_fields(mut self, strict: bool) -> Self {
+        self.strict_fields = strict;
+        self
+    }
+
+    pub fn collect_provenance(mut self, enabled: bool) -> Self {
+        self.collect_provenance = enabled;
+        self
+    }
+
+    pub fn structured_only(mut self, enabled: bool) -> Self {
+        self.structured_only = enabled;
+        self
+    }
+
+    pub fn follow_meta_refresh(mut self, enabled: bool) -> Self {
+        self.follow_meta_refresh = enabled;
+        self
+    }
+
+    pub fn fetch_oembed(mut self, enabled: bool) -> Self {
+        self.fetch_oembed = enabled;
+        self
+    }
+
+    pub fn parallel_passes(mut self, enabled: bool) -> Self {
+        self.parallel_passes = enabled;
+        self
+    }
+
+    pub fn max_json_ld_bytes(mut self, bytes: usize) -> Self {
+        self.max_json_ld_bytes = Some(bytes);
+        self
+    }
+
+    pub fn extract_recipe(mut self) -> Self {
+        self.recipe = true;
+        self
+    }
+
+    pub fn extract_event(mut self) -> Self {
+        self.event = true;
+        self
+    }
+
+    pub fn extract_faqs(mut self) -> Self {
+        self.faqs = true;
+        self
+    }
+
+    pub fn extract_contacts(mut self) -> Self {
+        self.contacts = true;
+        self
+    }
+
+    pub fn extract_emails(mut self) -> Self {
+        self.emails = true;
+        self
+    }
+
+    pub fn extract_preview_image(mut self) -> Self {
+        self.preview_image = true;
+        self
+    }
+
+    pub fn extract_alternate_links(mut self) -> Self {
+        self.alternate_links = true;
+        self
+    }
+
+    /// Default region for phone numbers without a `+` prefix; validated by `build()`
+    pub fn phone_region(mut self, region: impl Into<String>) -> Self {
+        self.phone_region = Some(region.into());
+        self
+    }
+
+    /// Enable every activity with all fields (see `WebExtractor::extract_all`)
+    pub fn extract_all(self) -> Self {
+        self.extract_text(true)
+            .extract_links(["all"])
+            .extract_socials(["all"])
+            .extract_video(["all"])
+            .extract_product(["all"])
+            .extract_article(["all"])
+    }
+
+    pub fn date_confidence_threshold(mut self, threshold: f64) -> Self {
+        self.date_confidence_threshold = Some(threshold);
+        self
+    }
+
+    pub fn date_cues<I, S>(mut self, cues: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.date_cues.extend(to_fields(cues));
+        self
+    }
+
+    /// Build the configured extractor. Fails if no URL was set.
+    pub fn build(self) -> Result<WebExtractor, ExtractionError> {
+        let url = self.url
+            .ok_or_else(|| ExtractionError::InvalidUrl("No URL set on WebExtractorBuilder".to_string()))?;
+
+        let mut extractor = match self.html {
+            Some(html) => WebExtractor::new_with_html(url, html),
+            None => WebExtractor::new(url),
+        };
+        if let Some(client) = self.client {
+            extractor = extractor.with_client(client);
+        }
+
+        if let Some(timeout_secs) = self.timeout_secs {
+            extractor.set_timeout(timeout_secs);
+        }
+        if let Some(user_agent) = self.user_agent {
+            extractor.set_user_agent(user_agent);
+        }
+        if let Some(enabled) = self.random_user_agent {
+            extractor.set_random_user_agent(enabled);
+        }
+        if !self.headers.is_empty() {
+            extractor.set_headers(self.headers);
+        }
+        if let Some(langs) = self.accept_language {
+            extractor.set_accept_language(langs);
+        }
+        if self.danger_accept_invalid_certs {
+            extractor.set_danger_accept_invalid_certs(true);
+        }
+        for pem in self.root_certificates {
+            extractor.add_root_certificate(pem)?;
+        }
+        if self.http2_prior_knowledge {
+            extractor.set_http2_prior_knowledge(true);
+        }
+        if let Some(idle_secs) = self.pool_idle_timeout_secs {
+            extractor.set_pool_idle_timeout(idle_secs);
+        }
+        if let Some(language_detection) = self.text {
+            extractor.extract_text(language_detection);
+        }
+        if let Some(mode) = self.inline_link_mode {
+            extractor.set_inline_link_mode(mode);
+        }
+        if let Some(chars) = self.min_content_length {
+            extractor.set_min_content_length(chars);
+        }
+        if self.compute_simhash {
+            extractor.set_compute_simhash(true);
+        }
+        if let Some(form) = self.unicode_normalization {
+            extractor.set_unicode_normalization(form);
+        }
+        if let Some(options) = self.text_sanitize {
+            extractor.set_text_sanitize(options);
+        }
+        if let Some(langs) = self.allowed_languages {
+            extractor.set_allowed_languages(langs);
+        }
+        if let Some(mode) = self.language_filter_mode {
+            extractor.set_language_filter_mode(mode);
+        }
+        if let Some(fields) = self.links {
+            extractor.extract_links(fields);
+        }
+        if let Some(schemes) = self.allowed_schemes {
+            extractor.set_allowed_schemes(schemes);
+        }
+        if let Some(total) = self.max_links {
+            extractor.set_max_links(total);
+        }
+        if let Some(n) = self.max_links_per_domain {
+            extractor.set_max_links_per_domain(n);
+        }
+        if let Some(fields) = self.socials {
+            extractor.extract_socials(fields);
+        }
+        if let Some(fields) = self.video {
+            extractor.extract_video(fields);
+        }
+        if let Some(fields) = self.product {
+            extractor.extract_product(fields);
+        }
+        if let Some(n) = self.max_product_reviews {
+            extractor.set_max_product_reviews(n);
+        }
+        if let Some(epsilon) = self.sale_epsilon {
+            extractor.set_sale_epsilon(epsilon);
+        }
+        if let Some(unit) = self.weight_unit {
+            extractor.set_weight_unit(unit);
+        }
+        if let Some(unit) = self.dimension_unit {
+            extractor.set_dimension_unit(unit);
+        }
+        if let Some(max) = self.products_list {
+            extractor.extract_products_list(max);
+        }
+        if let Some(fields) = self.article {
+            extractor.extract_article(fields);
+        }
+        if self.lists {
+            extractor.extract_lists();
+        }
+        if self.strict_fields {
+            extractor.set_strict_fields(true);
+        }
+        if self.collect_provenance {
+            extractor.set_collect_provenance(true);
+        }
+        if self.structured_only {
+            extractor.set_structured_only(true);
+        }
+        if self.follow_meta_refresh {
+            extractor.set_follow_meta_refresh(true);
+        }
+        if self.fetch_oembed {
+            extractor.set_fetch_oembed(true);
+        }
+        if self.parallel_passes {
+            extractor.set_parallel_passes(true);
+        }
+        if let Some(bytes) = self.max_json_ld_bytes {
+            extractor.set_max_json_ld_bytes(bytes);
+        }
+        if self.recipe {
+            extractor.extract_recipe();
+        }
+        if self.event {
+            extractor.extract_event();
+        }
+        if self.faqs {
+            extractor.extract_faqs();
+        }
+        if self.contacts {
+            extractor.extract_contacts();
+        }
+        if self.emails {
+            extractor.extract_emails();
+        }
+        if self.preview_image {
+            extractor.extract_preview_image();
+        }
+        if self.alternate_links {
+            extractor.extract_alternate_links();
+        }
+        if let Some(region) = self.phone_region {
+            extractor.set_phone_region(&region)?;
+        }
+        if let Some(threshold) = self.date_confidence_threshold {
+            extractor.set_date_confidence_threshold(threshold);
+        }
+        if !self.date_cues.is_empty() {
+            extractor.add_date_cues(self.date_cues);
+        }
+
+        Ok(extractor)
+    }
+}
@@ -0,0 +1,62 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use crate::dom_index::DomIndex;
+
+static EMAIL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[A-Za-z0-9._%+\-]+@[A-Za-z0-9\-]+(?:\.[A-Za-z0-9\-]+)*\.[A-Za-z]{2,24}").unwrap()
+});
+
+/// Asset file extensions that look like TLDs in retina image names (`logo@2x.png`)
+const FILE_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "svg", "webp", "avif", "ico", "bmp", "css", "js", "mp4", "webm",
+];
+
+/// Whether a matched address is a real email rather than an asset name such as `image@2x.png`
+fn is_plausible_email(email: &str) -> bool {
+    let tld = email.rsplit('.').next().unwrap_or_default().to_lowercase();
+    !FILE_EXTENSIONS.contains(&tld.as_str())
+}
+
+/// Addresses in a `mailto:` href: several recipients may be comma separated and the query
+/// string (`?subject=...`) is dropped
+fn mailto_addresses(href: &str) -> Vec<String> {
+    let Some(recipients) = href.get(..7)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("mailto:"))
+        .map(|_| &href[7..])
+    else {
+        return Vec::new();
+    };
+    let recipients = recipients.split('?').next().unwrap_or_default();
+    recipients
+        .split(',')
+        .map(|r| r.trim().replace("%40", "@"))
+        .filter(|r| EMAIL_RE.find(r).is_some_and(|m| m.as_str().len() == r.len()))
+        .collect()
+}
+
+/// Collect email addresses from `mailto:` links and the visible body text, deduplicated
+/// case-insensitively while keeping the first-seen spelling, in document order
+pub fn extract_emails(dom_index: &DomIndex, body_text: &str) -> Vec<String> {
+    let mut emails: Vec<String> = Vec::new();
+    let mut seen: Vec<String> = Vec::new();
+    let mut push = |email: String| {
+        let email = email.trim_end_matches('.').to_string();
+        let key = email.to_lowercase();
+        if is_plausible_email(&email) && !seen.contains(&key) {
+            seen.push(key);
+            emails.push(email);
+        }
+    };
+
+    for (href, _) in dom_index.get_link_data() {
+        for email in mailto_addresses(href) {
+            push(email);
+        }
+    }
+
+    for email in EMAIL_RE.find_iter(body_text) {
+        push(email.as_str().to_string());
+    }
+
+    emails
+}
@@ -0,0 +1,35 @@
+mod phones;
+mod emails;
+
+use scraper::{Node, Selector};
+use crate::dom_index::DomIndex;
+
+pub use phones::extract_phones;
+pub use emails::extract_emails;
+
+/// Elements whose text is never visible page content
+const NON_CONTENT_TAGS: &[&str] = &["script", "style", "noscript", "template"];
+
+/// Visible text of the page body (script/style content skipped), with text nodes separated
+/// by newlines so values from neighbouring elements don't run together
+pub fn body_text(dom_index: &DomIndex) -> String {
+    let document = dom_index.document();
+    let root = Selector::parse("body")
+        .ok()
+        .and_then(|selector| document.select(&selector).next())
+        .unwrap_or_else(|| document.root_element());
+
+    let mut text = String::new();
+    for node in root.descendants() {
+        if let Node::Text(t) = node.value() {
+            let in_non_content = node.parent()
+                .and_then(|parent| parent.value().as_element().map(|e| NON_CONTENT_TAGS.contains(&e.name())))
+                .unwrap_or(false);
+            if !in_non_content {
+                text.push_str(t);
+                text.push('\n');
+            }
+        }
+    }
+    text
+}
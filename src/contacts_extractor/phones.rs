@@ -0,0 +1,58 @@
+use once_cell::sync::Lazy;
+use phonenumber::{country, Mode};
+use regex::Regex;
+use crate::dom_index::DomIndex;
+
+/// Phone-number-shaped runs: an optional `+`, then digits with the usual separators
+static PHONE_CANDIDATE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\+?\(?\d[\d \t().\-/]{5,18}\d").unwrap()
+});
+
+/// Dates share the shape of phone numbers ("2024-01-15", "15.01.2024") and must be skipped
+static DATE_LIKE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:\d{4}[-/.]\d{1,2}[-/.]\d{1,2}|\d{1,2}[-/.]\d{1,2}[-/.]\d{2,4})$").unwrap()
+});
+
+/// Parse and validate a candidate, formatting it as E.164
+fn normalize_phone(candidate: &str, region: Option<country::Id>) -> Option<String> {
+    if DATE_LIKE_RE.is_match(candidate.trim()) {
+        return None;
+    }
+    let number = phonenumber::parse(region, candidate.trim()).ok()?;
+    if !phonenumber::is_valid(&number) {
+        return None;
+    }
+    Some(number.format().mode(Mode::E164).to_string())
+}
+
+/// Detect phone numbers in `tel:` links and the visible body text, validated and formatted as
+/// E.164 and deduplicated in document order.
+///
+/// `region` (an ISO 3166 code such as `"US"` or `"DE"`) is used for numbers written without an
+/// international prefix; without it only numbers with a `+` prefix are recognized.
+pub fn extract_phones(dom_index: &DomIndex, body_text: &str, region: Option<&str>) -> Vec<String> {
+    let region = region.and_then(|r| r.to_uppercase().parse::<country::Id>().ok());
+    let mut phones: Vec<String> = Vec::new();
+    let mut push = |phone: String| {
+        if !phones.contains(&phone) {
+            phones.push(phone);
+        }
+    };
+
+    for (href, _) in dom_index.get_link_data() {
+        let tel = href.get(..4)
+            .filter(|scheme| scheme.eq_ignore_ascii_case("tel:"))
+            .map(|_| &href[4..]);
+        if let Some(phone) = tel.and_then(|number| normalize_phone(number, region)) {
+            push(phone);
+        }
+    }
+
+    for candidate in PHONE_CANDIDATE_RE.find_iter(body_text) {
+        if let Some(phone) = normalize_phone(candidate.as_str(), region) {
+            push(phone);
+        }
+    }
+
+    phones
+}
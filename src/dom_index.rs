@@ -1,5 +1,63 @@
 use scraper::{Html, Selector};
 use std::collections::HashMap;
+use crate::types::Activities;
+use crate::anchor_context::anchor_context;
+
+/// Default cap on the total size of the JSON-LD scripts indexed from one page
+pub const DEFAULT_MAX_JSON_LD_BYTES: usize = 2 * 1024 * 1024;
+
+/// Which parts of the page a `DomIndex` collects. `<meta>` and `<link>` tags, the AMP flag,
+/// the title and the meta refresh are always indexed, since the title, description, AMP and
+/// redirect discovery run on every parse.
+#[derive(Debug, Clone, Copy)]
+pub struct IndexSections {
+    /// `<a href>` hrefs and texts
+    pub links: bool,
+    /// The text around each `<a href>` (`extract_links(["with_context"])`)
+    pub link_contexts: bool,
+    /// JSON-LD scripts, parsed and flattened into entities
+    pub json_ld: bool,
+    /// Texts of the common tags (title, headings, article, main)
+    pub tags: bool,
+    /// Schema.org `itemprop` values
+    pub itemprops: bool,
+    /// `<time datetime>` values
+    pub times: bool,
+}
+
+/// Whether the requested social fields include `social_profiles`, which reads page links and
+/// JSON-LD besides the meta tags
+fn wants_social_profiles(social_fields: &[String]) -> bool {
+    social_fields.iter().any(|f| matches!(f.as_str(), "all" | "social_profiles" | "profiles"))
+}
+
+impl IndexSections {
+    /// Only the sections the enabled activities read
+    pub fn for_activities(activities: &Activities) -> Self {
+        let article = !activities.extract_article.is_empty();
+        let product = !activities.extract_product.is_empty() || activities.extract_products_list.is_some();
+        let profiles = wants_social_profiles(&activities.extract_socials);
+        Self {
+            links: !activities.extract_links.is_empty() || activities.extract_contacts || activities.extract_emails || profiles,
+            link_contexts: activities.extract_links.iter().any(|option| option == "with_context"),
+            json_ld: article || product || profiles || activities.extract_recipe || activities.extract_event || activities.extract_faqs
+                || activities.extract_preview_image,
+            tags: article,
+            itemprops: article,
+            times: article,
+        }
+    }
+}
+
+/// A `<link>` element as declared
+#[derive(Debug, Clone)]
+pub struct LinkTag {
+    pub href: String,
+    /// `title` attribute (empty when absent)
+    pub title: String,
+    /// `type` attribute, lowercased (empty when absent)
+    pub link_type: String,
+}
 
 /// Index of DOM elements built from a single traversal
 /// This allows reusing selected elements across multiple extractors
@@ -9,33 +67,103 @@ pub struct DomIndex<'a> {
     pub meta_by_property: HashMap<String, Vec<String>>,
     /// All meta tags indexed by name attribute - stores content values
     pub meta_by_name: HashMap<String, Vec<String>>,
-    /// Link data (href and text) extracted during traversal
+    /// (property, content) of every `<meta property>` tag in document order, for structured
+    /// properties that pair with the tag before them (`og:image` then `og:image:width`)
+    pub meta_properties: Vec<(String, String)>,
+    /// Href and trimmed text of every `<a href>`, including icon-only links with empty text
     pub link_data: Vec<(String, String)>, // (href, text)
+    /// Text around each link, aligned with `link_data` (empty unless link contexts are indexed)
+    pub link_contexts: Vec<Option<String>>,
+    /// `<link>` elements indexed by each (lowercased) rel token, in document order; canonical,
+    /// AMP, oEmbed and `rel="me"` discovery and `alternate_links` all read it
+    pub link_tags_by_rel: HashMap<String, Vec<LinkTag>>,
+    /// Whether the document declares itself as AMP (`<html amp>` or `<html ⚡>`)
+    pub is_amp: bool,
+    /// `content` of the first `<meta http-equiv="refresh">`
+    pub meta_refresh: Option<String>,
+    /// Document title: `<title>`, then `og:title`, `twitter:title` and the first `<h1>`,
+    /// whitespace-collapsed
+    pub title: Option<String>,
+    /// Document description: `<meta name="description">` (the name in any case), then
+    /// `og:description` and `twitter:description`, whitespace-collapsed
+    pub description: Option<String>,
     /// JSON-LD script content
     pub json_ld_content: Vec<String>,
+    /// JSON-LD script content parsed once, aligned with `json_ld_content` (None for malformed blocks)
+    pub json_ld_values: Vec<Option<serde_json::Value>>,
+    /// Every JSON-LD entity (objects at the top level of a block, in arrays or in `@graph`
+    /// containers) as (block index, JSON pointer into that block's value), in document order
+    pub json_ld_entities: Vec<(usize, String)>,
     /// Common elements by tag name - stores text content
     pub elements_by_tag: HashMap<String, Vec<String>>,
     /// Schema.org elements by itemprop - stores content or text
     pub schema_by_itemprop: HashMap<String, Vec<String>>,
+    /// `itemprop="dateModified"` values, preferring `datetime` over `content` over text
+    pub modified_itemprop_dates: Vec<String>,
+    /// `datetime` attribute of every `<time datetime>` element, in document order
+    pub time_datetimes: Vec<String>,
+    /// Extractors trust only structured data (JSON-LD, meta tags, microdata) and skip their
+    /// CSS-selector and text-scan fallbacks (`set_structured_only`)
+    pub structured_only: bool,
     /// The original document (for cases where we need to traverse again)
     pub document: &'a Html,
 }
 
 impl<'a> DomIndex<'a> {
-    /// Build an index by traversing the DOM once
-    pub fn build(document: &'a Html) -> Self {
+    /// Build an index by traversing the DOM once, holding only the sections the enabled
+    /// activities read (a text-only run skips the link, JSON-LD and itemprop traversals), with
+    /// the activities' JSON-LD cap and structured-only setting
+    pub fn build_for(document: &'a Html, activities: &Activities) -> Self {
+        let max_json_ld_bytes = activities.max_json_ld_bytes.unwrap_or(DEFAULT_MAX_JSON_LD_BYTES);
+        let mut index = Self::build_sections(document, IndexSections::for_activities(activities), max_json_ld_bytes);
+        index.structured_only = activities.structured_only;
+        index
+    }
+
+    /// Build an index of `sections`, indexing JSON-LD scripts only while their total size stays
+    /// within `max_json_ld_bytes`; scripts that would exceed it are skipped unparsed
+    fn build_sections(document: &'a Html, sections: IndexSections, max_json_ld_bytes: usize) -> Self {
         let mut meta_by_property = HashMap::new();
         let mut meta_by_name = HashMap::new();
+        let mut meta_properties = Vec::new();
         let mut link_data = Vec::new();
+        let mut link_contexts = Vec::new();
+        let mut link_tags_by_rel: HashMap<String, Vec<LinkTag>> = HashMap::new();
         let mut json_ld_content = Vec::new();
         let mut elements_by_tag: HashMap<String, Vec<String>> = HashMap::new();
         let mut schema_by_itemprop = HashMap::new();
+        let mut time_datetimes = Vec::new();
+        let mut modified_itemprop_dates = Vec::new();
 
-        // Single traversal: collect all meta tags
-        if let Ok(meta_selector) = Selector::parse("meta") {
-            for element in document.select(&meta_selector) {
+        // Single traversal: collect all meta tags, the <title> and the first non-blank <h1>
+        let mut meta_refresh = None;
+        let mut title_tag = None;
+        let mut first_h1 = None;
+        let mut description_meta = None;
+        if let Ok(head_selector) = Selector::parse("meta, title, h1") {
+            for element in document.select(&head_selector) {
+                match element.value().name() {
+                    "title" => {
+                        // Only the document's own title, not an SVG <title> in the body
+                        let in_head = element.ancestors().any(|node| node.value().as_element().is_some_and(|e| e.name() == "head"));
+                        if title_tag.is_none() && in_head {
+                            title_tag = collapsed(&element.text().collect::<String>());
+                        }
+                        continue;
+                    }
+                    "h1" => {
+                        if first_h1.is_none() {
+                            first_h1 = collapsed(&element.text().collect::<String>());
+                        }
+                        continue;
+                    }
+                    _ => {}
+                }
                 let content_opt = element.value().attr("content");
-                
+                if meta_refresh.is_none() && element.value().attr("http-equiv").is_some_and(|v| v.trim().eq_ignore_ascii_case("refresh")) {
+                    meta_refresh = content_opt.map(str::to_string);
+                }
+
                 // Index by property
                 if let Some(property) = element.value().attr("property") {
                     if let Some(content) = content_opt {
@@ -43,11 +171,15 @@ impl<'a> DomIndex<'a> {
                             .entry(property.to_string())
                             .or_insert_with(Vec::new)
                             .push(content.to_string());
+                        meta_properties.push((property.to_string(), content.to_string()));
                     }
                 }
                 // Index by name
                 if let Some(name) = element.value().attr("name") {
                     if let Some(content) = content_opt {
+                        if description_meta.is_none() && name.eq_ignore_ascii_case("description") {
+                            description_meta = collapsed(content);
+                        }
                         meta_by_name
                             .entry(name.to_string())
                             .or_insert_with(Vec::new)
@@ -58,30 +190,83 @@ impl<'a> DomIndex<'a> {
         }
 
         // Single traversal: collect all links
-        if let Ok(link_selector) = Selector::parse("a[href]") {
+        if let (true, Ok(link_selector)) = (sections.links, Selector::parse("a[href]")) {
             for element in document.select(&link_selector) {
                 if let Some(href) = element.value().attr("href") {
+                    // Icon-only links (social footers) are kept with empty text
                     let text: String = element.text().collect();
                     let trimmed = text.trim();
-                    if !trimmed.is_empty() {
-                        link_data.push((href.to_string(), trimmed.to_string()));
+                    if sections.link_contexts {
+                        link_contexts.push((!trimmed.is_empty()).then(|| anchor_context(element)).flatten());
+                    }
+                    link_data.push((href.to_string(), trimmed.to_string()));
+                }
+            }
+        }
+
+        // Single traversal: collect <link> elements (canonical, amphtml, alternate, icon, ...)
+        if let Ok(link_tag_selector) = Selector::parse("link[rel][href]") {
+            for element in document.select(&link_tag_selector) {
+                if let (Some(rel), Some(href)) = (element.value().attr("rel"), element.value().attr("href")) {
+                    let tag = LinkTag {
+                        href: href.trim().to_string(),
+                        title: element.value().attr("title").unwrap_or_default().trim().to_string(),
+                        link_type: element.value().attr("type").unwrap_or_default().trim().to_lowercase(),
+                    };
+                    for rel_token in rel.split_whitespace() {
+                        link_tags_by_rel
+                            .entry(rel_token.to_lowercase())
+                            .or_default()
+                            .push(tag.clone());
                     }
                 }
             }
         }
 
-        // Single traversal: collect JSON-LD scripts
-        if let Ok(script_selector) = Selector::parse("script[type='application/ld+json']") {
+        let root = document.root_element().value();
+        let is_amp = root.attr("amp").is_some() || root.attr("⚡").is_some();
+
+        let first_meta = |values: Option<&Vec<String>>| values.into_iter().flatten().find_map(|value| collapsed(value));
+        let title = title_tag
+            .or_else(|| first_meta(meta_by_property.get("og:title")))
+            .or_else(|| first_meta(meta_by_name.get("twitter:title")))
+            .or(first_h1);
+        let description = description_meta
+            .or_else(|| first_meta(meta_by_property.get("og:description")))
+            .or_else(|| first_meta(meta_by_name.get("twitter:description")));
+
+        // Single traversal: collect JSON-LD scripts, up to the byte cap
+        if let (true, Ok(script_selector)) = (sections.json_ld, Selector::parse("script[type='application/ld+json']")) {
+            let mut json_ld_bytes = 0;
             for element in document.select(&script_selector) {
                 if let Some(text) = element.text().next() {
+                    if json_ld_bytes + text.len() > max_json_ld_bytes {
+                        continue;
+                    }
+                    json_ld_bytes += text.len();
                     json_ld_content.push(text.to_string());
                 }
             }
         }
 
+        // Parse each JSON-LD block exactly once so extractors don't re-parse per property
+        let json_ld_values: Vec<Option<serde_json::Value>> = json_ld_content
+            .iter()
+            .map(|content| serde_json::from_str::<serde_json::Value>(content).ok())
+            .collect();
+
+        // Flatten arrays and @graph containers once; entities are stored as pointers into the
+        // parsed values so they aren't copied
+        let mut json_ld_entities = Vec::new();
+        for (block, value) in json_ld_values.iter().enumerate() {
+            if let Some(value) = value {
+                collect_json_ld_entities(value, block, String::new(), &mut json_ld_entities);
+            }
+        }
+
         // Single traversal: collect common elements by tag name
-        let common_tags = ["title", "h1", "h2", "h3", "article", "main"];
-        for tag in &common_tags {
+        let common_tags: &[&str] = if sections.tags { &["title", "h1", "h2", "h3", "article", "main"] } else { &[] };
+        for tag in common_tags {
             if let Ok(selector) = Selector::parse(tag) {
                 let mut texts = Vec::new();
                 for element in document.select(&selector) {
@@ -97,9 +282,19 @@ impl<'a> DomIndex<'a> {
         }
 
         // Single traversal: collect schema.org elements by itemprop
-        if let Ok(schema_selector) = Selector::parse("[itemprop]") {
+        if let (true, Ok(schema_selector)) = (sections.itemprops, Selector::parse("[itemprop]")) {
             for element in document.select(&schema_selector) {
                 if let Some(itemprop) = element.value().attr("itemprop") {
+                    if itemprop == "dateModified" {
+                        let date = element.value().attr("datetime")
+                            .or_else(|| element.value().attr("content"))
+                            .map(|d| d.to_string())
+                            .unwrap_or_else(|| element.text().collect::<String>().trim().to_string());
+                        if !date.is_empty() {
+                            modified_itemprop_dates.push(date);
+                        }
+                    }
+
                     // Try content attribute first, then text
                     let value = element.value().attr("content")
                         .map(|s| s.to_string())
@@ -122,22 +317,61 @@ impl<'a> DomIndex<'a> {
             }
         }
 
+        // Single traversal: collect <time datetime> values for date extraction
+        if let (true, Ok(time_selector)) = (sections.times, Selector::parse("time[datetime]")) {
+            for element in document.select(&time_selector) {
+                if let Some(datetime) = element.value().attr("datetime") {
+                    time_datetimes.push(datetime.to_string());
+                }
+            }
+        }
+
         Self {
             meta_by_property,
             meta_by_name,
+            meta_properties,
             link_data,
+            link_contexts,
+            link_tags_by_rel,
+            is_amp,
+            meta_refresh,
+            title,
+            description,
             json_ld_content,
+            json_ld_values,
+            json_ld_entities,
             elements_by_tag,
             schema_by_itemprop,
+            time_datetimes,
+            modified_itemprop_dates,
+            structured_only: false,
             document,
         }
     }
 
+    /// Representative image for link previews, as declared: `og:image`, then `twitter:image`,
+    /// the first JSON-LD `image` and the largest `<img>` by declared dimensions (not in
+    /// structured-only mode). Reads the meta and JSON-LD sections; only the `<img>` fallback
+    /// walks the document.
+    pub fn preview_image(&self) -> Option<String> {
+        let first_meta = |values: &[String]| values.iter().find_map(|value| collapsed(value));
+        first_meta(self.get_all_meta_by_property("og:image"))
+            .or_else(|| first_meta(self.meta_by_name.get("twitter:image").map_or(&[], |values| values.as_slice())))
+            .or_else(|| first_meta(self.get_all_meta_by_property("twitter:image")))
+            .or_else(|| self.json_ld_values.iter().flatten().find_map(json_ld_image))
+            .or_else(|| (!self.structured_only).then(|| largest_image(self.document)).flatten())
+    }
+
     /// Get first meta tag content by property
     pub fn get_meta_by_property(&self, property: &str) -> Option<&String> {
         self.meta_by_property.get(property)?.first()
     }
 
+    /// Every meta tag content for a property, in document order
+    pub fn get_all_meta_by_property(&self, property: &str) -> &[String] {
+        self.meta_by_property.get(property).map_or(&[], |values| values.as_slice())
+    }
+
     /// Get first meta tag content by name
     pub fn get_meta_by_name(&self, name: &str) -> Option<&String> {
         self.meta_by_name.get(name)?.first()
@@ -148,9 +382,34 @@ impl<'a> DomIndex<'a> {
         &self.link_data
     }
 
-    /// Get all JSON-LD script contents
-    pub fn get_json_ld_content(&self) -> &[String] {
-        &self.json_ld_content
+    /// Get the href of the first `<link>` element with the given rel token
+    pub fn get_first_link_href(&self, rel: &str) -> Option<&String> {
+        self.get_link_tags(rel).first().map(|link| &link.href)
+    }
+
+    /// Every `<link>` element with the given rel token, in document order
+    pub fn get_link_tags(&self, rel: &str) -> &[LinkTag] {
+        self.link_tags_by_rel.get(rel).map_or(&[], |links| links.as_slice())
+    }
+
+    /// Get all JSON-LD blocks as (raw content, parsed value) pairs
+    pub fn get_json_ld_blocks(&self) -> impl Iterator<Item = (&String, Option<&serde_json::Value>)> {
+        self.json_ld_content
+            .iter()
+            .zip(self.json_ld_values.iter().map(|v| v.as_ref()))
+    }
+
+    /// Get every successfully parsed JSON-LD block, in document order
+    pub fn get_parsed_json_ld(&self) -> impl Iterator<Item = &serde_json::Value> {
+        self.json_ld_values.iter().flatten()
+    }
+
+    /// Get every JSON-LD entity (top-level objects, array members and `@graph` members),
+    /// in document order
+    pub fn get_json_ld_entities(&self) -> impl Iterator<Item = &serde_json::Map<String, serde_json::Value>> {
+        self.json_ld_entities.iter().filter_map(|(block, pointer)| {
+            self.json_ld_values.get(*block)?.as_ref()?.pointer(pointer)?.as_object()
+        })
     }
 
     /// Get first element text by tag name
@@ -169,3 +428,133 @@ impl<'a> DomIndex<'a> {
     }
 }
 
+/// Collapse runs of whitespace to single spaces and trim; `None` when nothing is left
+fn collapsed(text: &str) -> Option<String> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() { None } else { Some(text) }
+}
+
+/// Declared size of an `<img>` (`width="300"`, `width="300px"`)
+fn declared_size(value: Option<&str>) -> Option<u32> {
+    value.and_then(|v| v.trim().trim_end_matches("px").parse().ok())
+}
+
+/// The `src` (or `data-src`) of the `<img>` with the largest declared area (the first on
+/// ties); images missing either dimension aren't ranked
+fn largest_image(document: &Html) -> Option<String> {
+    let selector = Selector::parse("img").ok()?;
+    let mut best: Option<(&str, u64)> = None;
+    for img in document.select(&selector) {
+        let value = img.value();
+        let Some(src) = value.attr("src")
+            .or_else(|| value.attr("data-src"))
+            .map(str::trim)
+            .filter(|src| !src.is_empty() && !src.starts_with("data:")) else { continue };
+        let (Some(width), Some(height)) = (declared_size(value.attr("width")), declared_size(value.attr("height"))) else { continue };
+        let area = u64::from(width) * u64::from(height);
+        if area > 0 && best.is_none_or(|(_, best_area)| area > best_area) {
+            best = Some((src, area));
+        }
+    }
+    best.map(|(src, _)| src.to_string())
+}
+
+/// Get the URL of a JSON-LD image value: a plain string, an ImageObject with `url`/`contentUrl`,
+/// or an array of either (first entry wins)
+pub fn json_ld_image_url(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Array(arr) => arr.iter().find_map(json_ld_image_url),
+        serde_json::Value::Object(obj) => obj.get("url")
+            .or_else(|| obj.get("contentUrl"))
+            .and_then(json_ld_image_url),
+        _ => None,
+    }
+}
+
+/// The first `image` of a JSON-LD entity, descending into arrays and `@graph` containers
+fn json_ld_image(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Object(obj) => obj.get("image").and_then(json_ld_image_url)
+            .or_else(|| obj.get("@graph").and_then(json_ld_image)),
+        serde_json::Value::Array(arr) => arr.iter().find_map(json_ld_image),
+        _ => None,
+    }
+}
+
+/// Record the JSON pointer of every object in a JSON-LD value, descending into arrays and
+/// `@graph` containers
+fn collect_json_ld_entities(value: &serde_json::Value, block: usize, pointer: String, entities: &mut Vec<(usize, String)>) {
+    match value {
+        serde_json::Value::Object(obj) => {
+            if let Some(graph) = obj.get("@graph") {
+                entities.push((block, pointer.clone()));
+                collect_json_ld_entities(graph, block, format!("{}/@graph", pointer), entities);
+            } else {
+                entities.push((block, pointer));
+            }
+        }
+        serde_json::Value::Array(arr) => {
+            for (i, item) in arr.iter().enumerate() {
+                collect_json_ld_entities(item, block, format!("{}/{}", pointer, i), entities);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn title_and_description(html: &str) -> (Option<String>, Option<String>) {
+        let document = Html::parse_document(html);
+        // No activities: the title and description are indexed on every parse
+        let index = DomIndex::build_for(&document, &Activities::default());
+        (index.title, index.description)
+    }
+
+    #[test]
+    fn title_and_description_table() {
+        let cases: &[(&str, Option<&str>, Option<&str>)] = &[
+            (
+                r#"<head><title> Page
+                   title </title><meta name="Description" content="Mixed case name"></head>"#,
+                Some("Page title"), Some("Mixed case name"),
+            ),
+            (
+                r#"<head><meta property="og:title" content="OG title"><meta name="description" content=" ">
+                   <meta property="og:description" content="OG description"></head>"#,
+                Some("OG title"), Some("OG description"),
+            ),
+            (
+                r#"<head><meta name="twitter:title" content="Card title">
+                   <meta name="twitter:description" content="Card description"></head>"#,
+                Some("Card title"), Some("Card description"),
+            ),
+            (
+                r#"<body><svg><title>Icon</title></svg><h1> </h1><h1>First heading</h1></body>"#,
+                Some("First heading"), None,
+            ),
+        ];
+        for (html, title, description) in cases {
+            let expected = (title.map(str::to_string), description.map(str::to_string));
+            assert_eq!(title_and_description(html), expected, "html {:?}", html);
+        }
+    }
+
+    #[test]
+    fn itemprop_values_prefer_content_then_text() {
+        let document = Html::parse_document(
+            r#"<time itemprop="datePublished" datetime="2024-03-01">1 March</time>
+               <meta itemprop="dateModified" content="2024-03-02">
+               <time itemprop="dateModified" datetime="2024-03-03" content="2024-03-04">3 March</time>"#,
+        );
+        let activities = Activities { extract_article: vec!["dates".to_string()], ..Default::default() };
+        let index = DomIndex::build_for(&document, &activities);
+
+        assert_eq!(index.schema_by_itemprop["datePublished"], vec!["1 March"]);
+        assert_eq!(index.schema_by_itemprop["dateModified"], vec!["2024-03-02", "2024-03-04"]);
+        assert_eq!(index.modified_itemprop_dates, vec!["2024-03-02", "2024-03-03"]);
+    }
+}
@@ -1,5 +1,3 @@
-use pyo3::exceptions::PyRuntimeError;
-use pyo3::PyErr;
 use std::fmt;
 
 #[derive(Debug, Clone)]
@@ -8,6 +6,8 @@ pub enum ExtractionError {
     ParseError(String),
     InvalidUrl(String),
     Timeout(String),
+    /// The page's detected language isn't in the allowlist
+    LanguageNotAllowed(String),
     Other(String),
 }
 
@@ -18,6 +18,7 @@ impl fmt::Display for ExtractionError {
             ExtractionError::ParseError(msg) => write!(f, "Parse error: {}", msg),
             ExtractionError::InvalidUrl(msg) => write!(f, "Invalid URL: {}", msg),
             ExtractionError::Timeout(msg) => write!(f, "Timeout: {}", msg),
+            ExtractionError::LanguageNotAllowed(lang) => write!(f, "Language not allowed: {}", lang),
             ExtractionError::Other(msg) => write!(f, "Error: {}", msg),
         }
     }
@@ -43,9 +44,10 @@ impl From<url::ParseError> for ExtractionError {
     }
 }
 
-impl From<ExtractionError> for PyErr {
+#[cfg(feature = "python")]
+impl From<ExtractionError> for pyo3::PyErr {
     fn from(err: ExtractionError) -> Self {
-        PyRuntimeError::new_err(err.to_string())
+        pyo3::exceptions::PyRuntimeError::new_err(err.to_string())
     }
 }
 
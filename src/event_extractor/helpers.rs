@@ -0,0 +1,46 @@
+use crate::article_extractor::{parse_date, to_iso_string};
+
+/// Collapse whitespace runs and trim, returning None for empty values
+pub fn clean_text(text: &str) -> Option<String> {
+    let cleaned = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if cleaned.is_empty() {
+        None
+    } else {
+        Some(cleaned)
+    }
+}
+
+/// Normalize an event date to ISO 8601. Date-only values stay dates (an all-day event has no
+/// meaningful time); values that can't be parsed are kept as written.
+pub fn normalize_date(value: &str) -> Option<String> {
+    let value = clean_text(value)?;
+    if chrono::NaiveDate::parse_from_str(&value, "%Y-%m-%d").is_ok() {
+        return Some(value);
+    }
+    Some(parse_date(&value).map(|date| to_iso_string(&date)).unwrap_or(value))
+}
+
+/// Whether a schema.org type is an Event or one of its subtypes (MusicEvent, SportsEvent, ...)
+pub fn is_event_type(schema_type: &str) -> bool {
+    let schema_type = schema_type.rsplit('/').next().unwrap_or(schema_type);
+    schema_type.ends_with("Event") || matches!(schema_type, "Festival" | "Hackathon")
+}
+
+/// Join the non-empty parts of an address with ", "
+pub fn join_address(parts: &[Option<String>]) -> Option<String> {
+    let parts: Vec<&str> = parts.iter().flatten().map(|s| s.as_str()).collect();
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(", "))
+    }
+}
+
+/// Address properties of a `PostalAddress`, in display order
+pub const ADDRESS_PARTS: &[&str] = &[
+    "streetAddress",
+    "addressLocality",
+    "addressRegion",
+    "postalCode",
+    "addressCountry",
+];
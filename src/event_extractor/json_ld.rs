@@ -0,0 +1,138 @@
+use crate::dom_index::DomIndex;
+use crate::json_ld::json_ld_types;
+use crate::link_extractor::resolve_url;
+use crate::types::{Event, EventLocation, EventOffer};
+use super::helpers::{clean_text, is_event_type, join_address, normalize_date, ADDRESS_PARTS};
+
+/// Find the first JSON-LD object typed as an Event (or subtype), looking through arrays and
+/// `@graph` containers
+fn find_event_object(value: &serde_json::Value) -> Option<&serde_json::Map<String, serde_json::Value>> {
+    match value {
+        serde_json::Value::Object(obj) => {
+            if json_ld_types(obj).into_iter().any(is_event_type) {
+                return Some(obj);
+            }
+            obj.get("@graph").and_then(find_event_object)
+        }
+        serde_json::Value::Array(arr) => arr.iter().find_map(find_event_object),
+        _ => None,
+    }
+}
+
+/// Text of a string or number value; for objects the `name`; for arrays the first entry
+fn text_value(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => clean_text(s),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::Array(arr) => arr.iter().find_map(text_value),
+        serde_json::Value::Object(obj) => obj.get("name").and_then(text_value),
+        _ => None,
+    }
+}
+
+fn property(obj: &serde_json::Map<String, serde_json::Value>, name: &str) -> Option<String> {
+    obj.get(name).and_then(text_value)
+}
+
+/// A plain string address or a `PostalAddress` object
+fn address_value(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Object(obj) => {
+            let parts: Vec<Option<String>> = ADDRESS_PARTS.iter().map(|p| property(obj, p)).collect();
+            join_address(&parts)
+        }
+        other => text_value(other),
+    }
+}
+
+/// `location`: a Place (name + address), a VirtualLocation (url), a plain string, or an array
+/// of those (first entry wins)
+fn location_value(value: &serde_json::Value, base_url: &str) -> Option<EventLocation> {
+    match value {
+        serde_json::Value::Array(arr) => arr.iter().find_map(|v| location_value(v, base_url)),
+        serde_json::Value::Object(obj) => {
+            let location = EventLocation {
+                name: property(obj, "name"),
+                address: obj.get("address").and_then(address_value),
+                url: property(obj, "url").map(|url| resolve_url(base_url, &url)),
+            };
+            if location.name.is_none() && location.address.is_none() && location.url.is_none() {
+                None
+            } else {
+                Some(location)
+            }
+        }
+        other => text_value(other).map(|name| EventLocation { name: Some(name), ..Default::default() }),
+    }
+}
+
+/// Collect every offer: single objects, arrays, and `AggregateOffer`s (their nested `offers`,
+/// or the aggregate's `lowPrice` when none are listed)
+fn collect_offers(value: &serde_json::Value, base_url: &str, offers: &mut Vec<EventOffer>) {
+    match value {
+        serde_json::Value::Array(arr) => {
+            for item in arr {
+                collect_offers(item, base_url, offers);
+            }
+        }
+        serde_json::Value::Object(obj) => {
+            if let Some(nested) = obj.get("offers") {
+                collect_offers(nested, base_url, offers);
+                return;
+            }
+            let offer = EventOffer {
+                price: property(obj, "price").or_else(|| property(obj, "lowPrice")),
+                currency: property(obj, "priceCurrency"),
+                url: property(obj, "url").map(|url| resolve_url(base_url, &url)),
+            };
+            if offer.price.is_some() || offer.url.is_some() {
+                offers.push(offer);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Performer names from a Person/Organization object, a string, or an array of either
+fn collect_performers(value: &serde_json::Value, performers: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Array(arr) => {
+            for item in arr {
+                collect_performers(item, performers);
+            }
+        }
+        other => {
+            if let Some(name) = text_value(other) {
+                if !performers.contains(&name) {
+                    performers.push(name);
+                }
+            }
+        }
+    }
+}
+
+/// Build an event from the first JSON-LD Event object on the page
+pub fn extract_json_ld_event(dom_index: &DomIndex, base_url: &str) -> Option<Event> {
+    let obj = dom_index.get_parsed_json_ld()
+        .find_map(find_event_object)?;
+
+    let mut offers = Vec::new();
+    if let Some(value) = obj.get("offers") {
+        collect_offers(value, base_url, &mut offers);
+    }
+
+    let mut performers = Vec::new();
+    if let Some(value) = obj.get("performer") {
+        collect_performers(value, &mut performers);
+    }
+
+    Some(Event {
+        event_type: obj.get("@type").and_then(text_value),
+        name: property(obj, "name"),
+        start_date: property(obj, "startDate").and_then(|d| normalize_date(&d)),
+        end_date: property(obj, "endDate").and_then(|d| normalize_date(&d)),
+        location: obj.get("location").and_then(|v| location_value(v, base_url)),
+        offers,
+        performers,
+    })
+}
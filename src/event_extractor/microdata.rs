@@ -0,0 +1,69 @@
+use scraper::{ElementRef, Selector};
+use crate::dom_index::DomIndex;
+use crate::microdata::{item_property_values, own_properties, property_value};
+use crate::link_extractor::resolve_url;
+use crate::types::{Event, EventLocation, EventOffer};
+use super::helpers::{is_event_type, join_address, normalize_date, ADDRESS_PARTS};
+
+fn own_property(scope: ElementRef, itemprop: &str) -> Option<String> {
+    own_properties(scope, itemprop).into_iter().find_map(property_value)
+}
+
+fn location_value(element: ElementRef, base_url: &str) -> Option<EventLocation> {
+    if element.value().attr("itemscope").is_none() {
+        return property_value(element).map(|name| EventLocation { name: Some(name), ..Default::default() });
+    }
+
+    let address = own_properties(element, "address").into_iter().next().and_then(|address| {
+        if address.value().attr("itemscope").is_some() {
+            let parts: Vec<Option<String>> = ADDRESS_PARTS.iter().map(|p| own_property(address, p)).collect();
+            join_address(&parts)
+        } else {
+            property_value(address)
+        }
+    });
+
+    Some(EventLocation {
+        name: own_property(element, "name"),
+        address,
+        url: own_property(element, "url").map(|url| resolve_url(base_url, &url)),
+    })
+}
+
+/// Build an event from the first microdata Event scope (or subtype) on the page
+pub fn extract_microdata_event(dom_index: &DomIndex, base_url: &str) -> Option<Event> {
+    let selector = Selector::parse("[itemscope][itemtype]").ok()?;
+    let (event, event_type) = dom_index.document().select(&selector).find_map(|element| {
+        let item_type = element.value().attr("itemtype")?.split_whitespace().find(|t| is_event_type(t))?;
+        Some((element, item_type.rsplit('/').next().unwrap_or(item_type).to_string()))
+    })?;
+
+    let offers = own_properties(event, "offers")
+        .into_iter()
+        .filter_map(|offer| {
+            let offer = EventOffer {
+                price: own_property(offer, "price").or_else(|| own_property(offer, "lowPrice")),
+                currency: own_property(offer, "priceCurrency"),
+                url: own_property(offer, "url").map(|url| resolve_url(base_url, &url)),
+            };
+            (offer.price.is_some() || offer.url.is_some()).then_some(offer)
+        })
+        .collect();
+
+    let mut performers: Vec<String> = Vec::new();
+    for name in item_property_values(event, "performer") {
+        if !performers.contains(&name) {
+            performers.push(name);
+        }
+    }
+
+    Some(Event {
+        event_type: Some(event_type),
+        name: own_property(event, "name"),
+        start_date: own_property(event, "startDate").and_then(|d| normalize_date(&d)),
+        end_date: own_property(event, "endDate").and_then(|d| normalize_date(&d)),
+        location: own_properties(event, "location").into_iter().next().and_then(|l| location_value(l, base_url)),
+        offers,
+        performers,
+    })
+}
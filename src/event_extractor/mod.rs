@@ -0,0 +1,15 @@
+mod helpers;
+mod json_ld;
+mod microdata;
+
+use crate::dom_index::DomIndex;
+use crate::types::Event;
+
+/// Extract a schema.org `Event` (or subtype such as `MusicEvent`) from JSON-LD, falling back
+/// to microdata. Offer and location URLs are resolved against `base_url`.
+///
+/// Returns None when the page declares no event.
+pub fn extract_event(dom_index: &DomIndex, base_url: &str) -> Option<Event> {
+    json_ld::extract_json_ld_event(dom_index, base_url)
+        .or_else(|| microdata::extract_microdata_event(dom_index, base_url))
+}
@@ -1,18 +1,27 @@
 use crate::error::ExtractionError;
-use crate::types::{Activities, ExtractionResult, ContentInfo};
-use crate::text_extractor::extract_text_content;
-use crate::link_extractor::extract_links_with_index;
-use crate::socials_extractor::extract_socials_with_index;
-use crate::videos_extractor::extract_video;
-use crate::products_extractor::extract_products;
-use crate::article_extractor::extract_article_with_index;
+use crate::types::{Activities, ExtractionResult, ContentInfo, InlineLinkMode, LanguageFilterMode, TextSanitize, UnicodeNormalizationForm, WeightUnit, DimensionUnit, MetaRefresh, OEmbed};
+use crate::text_extractor::{extract_text_content, extract_lists, sanitize_text, content_hash, simhash};
+use crate::link_extractor::{extract_links_with_index, extract_alternate_links, extract_amp_url, extract_meta_refresh, extract_oembed_url, is_known_link_filter, parse_oembed, resolve_url};
+use crate::socials_extractor::{extract_socials_with_index, extract_socials_typed, is_known_social_field};
+use crate::videos_extractor::{extract_video, is_known_video_field};
+use crate::products_extractor::{extract_products_with_sources, extract_products_list, is_known_product_field};
+use crate::article_extractor::{extract_article_with_sources, extract_article_info, article_fields_need_text, is_known_article_field};
+use crate::recipe_extractor::extract_recipe;
+use crate::event_extractor::extract_event;
+use crate::faq_extractor::extract_faqs;
+use crate::contacts_extractor::{body_text, extract_emails, extract_phones};
 use crate::dom_index::DomIndex;
-use crate::robots::RobotsChecker;
-use reqwest::{Client, ClientBuilder, header::HeaderMap, header::HeaderValue};
+use crate::robots::{RobotsChecker, RobotsStats};
+use crate::builder::WebExtractorBuilder;
+use reqwest::{Certificate, Client, ClientBuilder, header::HeaderMap, header::HeaderName, header::HeaderValue, header::ACCEPT_LANGUAGE};
 use scraper::Html;
 use whatlang::detect;
 use std::collections::HashMap;
+use std::io::Read;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
+use flate2::read::GzDecoder;
 use rand::Rng;
 
 #[derive(Clone, Debug)]
@@ -21,6 +30,15 @@ pub struct ClientConfig {
     pub user_agent: Option<String>,
     pub random_user_agent: bool,
     pub headers: HashMap<String, String>,
+    pub accept_language: Option<String>,
+    /// Skip certificate and hostname verification (self-signed staging sites)
+    pub danger_accept_invalid_certs: bool,
+    /// Extra trusted root certificates, on top of the built-in roots
+    pub root_certificates: Vec<Certificate>,
+    /// Speak HTTP/2 without negotiating it first (fails on HTTP/1.1-only servers)
+    pub http2_prior_knowledge: bool,
+    /// How long idle pooled connections are kept open for reuse (reqwest's default when unset)
+    pub pool_idle_timeout: Option<Duration>,
 }
 
 impl Default for ClientConfig {
@@ -30,6 +48,11 @@ impl Default for ClientConfig {
             user_agent: Some("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36".to_string()),
             random_user_agent: false,
             headers: HashMap::new(),
+            accept_language: None,
+            danger_accept_invalid_certs: false,
+            root_certificates: Vec::new(),
+            http2_prior_knowledge: false,
+            pool_idle_timeout: None,
         }
     }
 }
@@ -53,14 +76,38 @@ fn generate_random_user_agent() -> &'static str {
     USER_AGENTS[index]
 }
 
+fn parse_header(key: &str, value: &str) -> Result<(HeaderName, HeaderValue), ExtractionError> {
+    let header_name = key.parse::<HeaderName>()
+        .map_err(|e| ExtractionError::HttpError(format!("Invalid header name '{}': {}", key, e)))?;
+    let header_value = HeaderValue::from_str(value)
+        .map_err(|e| ExtractionError::HttpError(format!("Invalid header value for '{}': {}", key, e)))?;
+    Ok((header_name, header_value))
+}
+
+fn parse_accept_language(langs: &str) -> Result<HeaderValue, ExtractionError> {
+    HeaderValue::from_str(langs)
+        .map_err(|e| ExtractionError::HttpError(format!("Invalid Accept-Language value '{}': {}", langs, e)))
+}
+
 pub struct WebExtractor {
     url: String,
     html: Option<String>,
     activities: Activities,
     client: Option<Client>,
+    /// Whether `client` was supplied by the caller (`with_client`) and must not be rebuilt
+    custom_client: bool,
     client_config: ClientConfig,
-    robots_checker: Option<RobotsChecker>,
+    /// Shared with the workers of `run_batch`
+    robots_checker: Option<Arc<RobotsChecker>>,
     robots_enabled: bool,
+    /// Re-fetch the target of a `<meta http-equiv="refresh">` redirect in `run_async`
+    follow_meta_refresh: bool,
+    /// Fetch the page's JSON oEmbed document in `run_async`
+    fetch_oembed: bool,
+    /// Spread the extraction passes of large pages over worker threads
+    parallel_passes: bool,
+    /// Called as (completed, total) after each URL of `run_batch` finishes
+    progress_callback: Option<Arc<dyn Fn(usize, usize) + Send + Sync>>,
 }
 
 impl WebExtractor {
@@ -70,9 +117,14 @@ impl WebExtractor {
             html: None,
             activities: Activities::default(),
             client: None,
+            custom_client: false,
             client_config: ClientConfig::default(),
             robots_checker: None,
             robots_enabled: false,
+            follow_meta_refresh: false,
+            fetch_oembed: false,
+            parallel_passes: false,
+            progress_callback: None,
         }
     }
     
@@ -82,16 +134,56 @@ impl WebExtractor {
             html: Some(html),
             activities: Activities::default(),
             client: None,
+            custom_client: false,
             client_config: ClientConfig::default(),
             robots_checker: None,
             robots_enabled: false,
+            follow_meta_refresh: false,
+            fetch_oembed: false,
+            parallel_passes: false,
+            progress_callback: None,
         }
     }
     
+    /// Like `new_with_html`, for gzip-compressed HTML (e.g. stored snapshots).
+    /// Invalid UTF-8 in the decompressed page is replaced rather than rejected.
+    pub fn new_with_gzip_html(url: String, bytes: Vec<u8>) -> Result<Self, ExtractionError> {
+        let mut decompressed = Vec::new();
+        GzDecoder::new(bytes.as_slice())
+            .read_to_end(&mut decompressed)
+            .map_err(|e| ExtractionError::ParseError(format!("Invalid gzip data: {}", e)))?;
+        let html = String::from_utf8_lossy(&decompressed).into_owned();
+        Ok(Self::new_with_html(url, html))
+    }
+    
+    /// Start a fluent `WebExtractorBuilder`
+    pub fn builder() -> WebExtractorBuilder {
+        WebExtractorBuilder::new()
+    }
+
+    /// Use a caller-supplied HTTP client (shared connection pool, instrumentation, custom TLS)
+    /// for page and robots.txt fetches instead of building one.
+    ///
+    /// The client is used as-is: `set_timeout`, `set_user_agent`, `set_random_user_agent`,
+    /// `add_header`, `set_headers`, `set_accept_language`, the TLS settings and the
+    /// connection settings (`set_http2_prior_knowledge`, `set_pool_idle_timeout`) no longer
+    /// affect requests, and `configure_client` returns an error.
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self.custom_client = true;
+        self
+    }
+
     pub fn configure_client<F>(&mut self, f: F) -> Result<(), ExtractionError>
     where
         F: FnOnce(&mut reqwest::ClientBuilder) -> Result<(), ExtractionError>,
     {
+        if self.custom_client {
+            return Err(ExtractionError::Other(
+                "configure_client can't be used with a client supplied through with_client".to_string()
+            ));
+        }
+
         // Invalidate existing client so it will be rebuilt with new config
         self.client = None;
         
@@ -131,47 +223,123 @@ impl WebExtractor {
         builder = builder.user_agent(user_agent);
         
         // Add custom headers
-        if !self.client_config.headers.is_empty() {
-            let mut header_map = HeaderMap::new();
-            for (key, value) in &self.client_config.headers {
-                let header_name = key.parse::<reqwest::header::HeaderName>()
-                    .map_err(|e| ExtractionError::HttpError(format!("Invalid header name '{}': {}", key, e)))?;
-                let header_value = HeaderValue::from_str(value)
-                    .map_err(|e| ExtractionError::HttpError(format!("Invalid header value for '{}': {}", key, e)))?;
-                header_map.insert(header_name, header_value);
-            }
+        let mut header_map = HeaderMap::new();
+        for (key, value) in &self.client_config.headers {
+            let (header_name, header_value) = parse_header(key, value)?;
+            header_map.insert(header_name, header_value);
+        }
+        
+        // The dedicated Accept-Language setting wins over a custom header of the same name
+        if let Some(ref langs) = self.client_config.accept_language {
+            header_map.insert(ACCEPT_LANGUAGE, parse_accept_language(langs)?);
+        }
+        
+        if !header_map.is_empty() {
             builder = builder.default_headers(header_map);
         }
+
+        for certificate in &self.client_config.root_certificates {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+        if self.client_config.danger_accept_invalid_certs {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        if self.client_config.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        if let Some(idle) = self.client_config.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(idle);
+        }
         
         Ok(builder)
     }
     
     pub fn set_timeout(&mut self, timeout_secs: u64) {
         self.client_config.timeout = Some(Duration::from_secs(timeout_secs));
-        self.client = None; // Invalidate existing client
+        self.invalidate_client();
     }
     
     pub fn set_user_agent(&mut self, user_agent: String) {
         self.client_config.user_agent = Some(user_agent);
         self.client_config.random_user_agent = false;
-        self.client = None; // Invalidate existing client
+        self.invalidate_client();
     }
     
     pub fn set_random_user_agent(&mut self, enabled: bool) {
         self.client_config.random_user_agent = enabled;
-        self.client = None; // Invalidate existing client
+        self.invalidate_client();
     }
     
     pub fn add_header(&mut self, name: String, value: String) {
         self.client_config.headers.insert(name, value);
-        self.client = None; // Invalidate existing client
+        self.invalidate_client();
     }
     
     pub fn set_headers(&mut self, headers: HashMap<String, String>) {
         self.client_config.headers = headers;
-        self.client = None; // Invalidate existing client
+        self.invalidate_client();
+    }
+    
+    /// Set the `Accept-Language` header sent with requests (e.g. `"fr-FR,fr;q=0.9"`)
+    /// to request a specific locale
+    pub fn set_accept_language(&mut self, langs: String) {
+        self.client_config.accept_language = Some(langs);
+        self.invalidate_client();
     }
     
+    /// Accept any TLS certificate, including self-signed, expired and wrong-host ones.
+    ///
+    /// This disables protection against man-in-the-middle attacks: anyone on the network
+    /// path can impersonate the site and read or alter the traffic. Only use it for internal
+    /// or staging hosts you control, and prefer `add_root_certificate` where possible.
+    pub fn set_danger_accept_invalid_certs(&mut self, enabled: bool) {
+        self.client_config.danger_accept_invalid_certs = enabled;
+        self.invalidate_client();
+    }
+
+    /// Trust an additional PEM-encoded root certificate (e.g. an internal CA), keeping
+    /// certificate verification enabled
+    pub fn add_root_certificate(&mut self, pem: Vec<u8>) -> Result<(), ExtractionError> {
+        let invalid = |reason: String| ExtractionError::HttpError(format!("Invalid root certificate: {}", reason));
+        if !String::from_utf8_lossy(&pem).contains("-----BEGIN CERTIFICATE-----") {
+            return Err(invalid("no PEM certificate block found".to_string()));
+        }
+        let certificate = Certificate::from_pem(&pem).map_err(|e| invalid(e.to_string()))?;
+        // The certificate itself is only decoded when a client is built; do it now so bad
+        // input fails here rather than on the first request
+        Client::builder()
+            .add_root_certificate(certificate.clone())
+            .build()
+            .map_err(|e| invalid(e.to_string()))?;
+        self.client_config.root_certificates.push(certificate);
+        self.invalidate_client();
+        Ok(())
+    }
+
+    /// Talk HTTP/2 from the first byte instead of negotiating it, so requests to one host are
+    /// multiplexed over a single connection. Requests fail against servers that only speak
+    /// HTTP/1.1, so only enable it for hosts known to support HTTP/2.
+    pub fn set_http2_prior_knowledge(&mut self, enabled: bool) {
+        self.client_config.http2_prior_knowledge = enabled;
+        self.invalidate_client();
+    }
+
+    /// Keep idle connections in the client's pool for `idle_secs` seconds so later requests
+    /// to the same host skip the TCP/TLS handshake
+    pub fn set_pool_idle_timeout(&mut self, idle_secs: u64) {
+        self.client_config.pool_idle_timeout = Some(Duration::from_secs(idle_secs));
+        self.invalidate_client();
+    }
+
+    /// Drop the built client so the next request rebuilds it with the new config.
+    /// A client supplied through `with_client` is kept.
+    fn invalidate_client(&mut self) {
+        if !self.custom_client {
+            self.client = None;
+        }
+    }
+
     fn get_client(&mut self) -> Result<&Client, ExtractionError> {
         if self.client.is_none() {
             let builder = self.build_client_builder()?;
@@ -186,13 +354,77 @@ impl WebExtractor {
 
     pub fn extract_text(&mut self, language_detection: bool) {
         self.activities.extract_text.enabled = true;
-        self.activities.extract_text.language_detection = language_detection;
+        // A language allowlist keeps detection on
+        self.activities.extract_text.language_detection = language_detection
+            || !self.activities.extract_text.allowed_languages.is_empty();
+    }
+
+    /// Control how inline hyperlinks appear in extracted text (drop, text only, or text with URL)
+    pub fn set_inline_link_mode(&mut self, mode: InlineLinkMode) {
+        self.activities.extract_text.inline_link_mode = mode;
+    }
+
+    /// Only accept pages whose detected language (an ISO 639-3 code such as `"eng"`, as
+    /// reported in `ExtractionResult.language`) is one of `langs`; enables language detection.
+    /// Other pages get `language_allowed: Some(false)`, and are skipped or rejected depending
+    /// on `set_language_filter_mode`. An empty list accepts every page.
+    pub fn set_allowed_languages(&mut self, langs: Vec<String>) {
+        if !langs.is_empty() {
+            self.activities.extract_text.language_detection = true;
+        }
+        self.activities.extract_text.allowed_languages = langs;
+    }
+
+    /// Choose what happens to pages outside the language allowlist: flag them (default), skip
+    /// the extractors after language detection, or fail with `LanguageNotAllowed`
+    pub fn set_language_filter_mode(&mut self, mode: LanguageFilterMode) {
+        self.activities.extract_text.language_filter = mode;
+    }
+
+    /// Set the length (in characters) a main-content container must exceed to be used instead
+    /// of falling back to the whole body
+    pub fn set_min_content_length(&mut self, chars: usize) {
+        self.activities.extract_text.min_content_length = chars;
+    }
+
+    /// Compute a 64-bit simhash of the extracted text (`ExtractionResult.simhash`) so
+    /// near-identical pages can be clustered by Hamming distance. Off by default since it
+    /// costs an extra pass over the text; enabling it extracts the text even when
+    /// `extract_text` isn't set.
+    pub fn set_compute_simhash(&mut self, enabled: bool) {
+        self.activities.extract_text.compute_simhash = enabled;
+    }
+
+    /// Set the Unicode normalization form applied to extracted text (NFC by default)
+    pub fn set_unicode_normalization(&mut self, form: UnicodeNormalizationForm) {
+        self.activities.extract_text.unicode_normalization = form;
+    }
+
+    /// Strip emoji / zero-width and control characters and normalize to NFC in extracted text.
+    /// Applied before language detection, so detection sees the sanitized text.
+    pub fn set_text_sanitize(&mut self, options: TextSanitize) {
+        self.activities.extract_text.sanitize = options;
     }
 
     pub fn extract_links(&mut self, fields: Vec<String>) {
         self.activities.extract_links = fields;
     }
 
+    /// Set the URL schemes kept during link extraction (default `["http", "https"]`)
+    pub fn set_allowed_schemes(&mut self, schemes: Vec<String>) {
+        self.activities.link_options.allowed_schemes = schemes;
+    }
+
+    /// Keep at most `total` links; later links are dropped and counted in `LinkSummary`
+    pub fn set_max_links(&mut self, total: usize) {
+        self.activities.link_options.max_links = Some(total);
+    }
+
+    /// Keep at most `n` links per domain; later links are dropped and counted in `LinkSummary`
+    pub fn set_max_links_per_domain(&mut self, n: usize) {
+        self.activities.link_options.max_links_per_domain = Some(n);
+    }
+
     pub fn extract_socials(&mut self, fields: Vec<String>) {
         self.activities.extract_socials = fields;
     }
@@ -205,31 +437,284 @@ impl WebExtractor {
         self.activities.extract_product = fields;
     }
 
+    /// Keep at most `n` individual product reviews (`product_reviews` and `ProductInfo::reviews`)
+    pub fn set_max_product_reviews(&mut self, n: usize) {
+        self.activities.product_options.max_reviews = Some(n);
+    }
+
+    /// Margin by which the original price must exceed the current one for `product_on_sale`
+    pub fn set_sale_epsilon(&mut self, epsilon: f64) {
+        self.activities.product_options.sale_epsilon = Some(epsilon);
+    }
+
+    /// Unit `product_weight` is converted to (grams by default)
+    pub fn set_weight_unit(&mut self, unit: WeightUnit) {
+        self.activities.product_options.weight_unit = unit;
+    }
+
+    /// Unit `product_width`, `product_height` and `product_depth` are converted to
+    /// (millimetres by default)
+    pub fn set_dimension_unit(&mut self, unit: DimensionUnit) {
+        self.activities.product_options.dimension_unit = unit;
+    }
+
     pub fn extract_article(&mut self, fields: Vec<String>) {
         self.activities.extract_article = fields;
     }
 
+    /// Enable extraction of `<ul>`/`<ol>` lists as nested data
+    pub fn extract_lists(&mut self) {
+        self.activities.extract_lists = true;
+    }
+
+    /// Extract a schema.org `Recipe` (JSON-LD, then microdata) into `ExtractionResult::recipe`
+    pub fn extract_recipe(&mut self) {
+        self.activities.extract_recipe = true;
+    }
+
+    /// Enumerate up to `max` products on a listing page (ItemList or repeated Product JSON-LD,
+    /// else repeated microdata cards) into `ExtractionResult::products`
+    pub fn extract_products_list(&mut self, max: usize) {
+        self.activities.extract_products_list = Some(max);
+    }
+
+    /// Extract a schema.org `Event` (JSON-LD, then microdata) into `ExtractionResult::event`
+    pub fn extract_event(&mut self) {
+        self.activities.extract_event = true;
+    }
+
+    /// Extract JSON-LD `FAQPage` question/answer pairs into `ExtractionResult::faqs`
+    pub fn extract_faqs(&mut self) {
+        self.activities.extract_faqs = true;
+    }
+
+    /// Detect phone numbers in `tel:` links and the visible body text. Email addresses are a
+    /// separate activity (`extract_emails`).
+    pub fn extract_contacts(&mut self) {
+        self.activities.extract_contacts = true;
+    }
+
+    /// Detect email addresses in `mailto:` links and the visible body text
+    pub fn extract_emails(&mut self) {
+        self.activities.extract_emails = true;
+    }
+
+    /// Pick the best image for link previews (`preview_image`): `og:image`, then
+    /// `twitter:image`, the first JSON-LD `image` and the largest `<img>` by declared size
+    pub fn extract_preview_image(&mut self) {
+        self.activities.extract_preview_image = true;
+    }
+
+    /// Group every `<link>` element by rel token (`alternate_links`), hrefs resolved absolute
+    pub fn extract_alternate_links(&mut self) {
+        self.activities.extract_alternate_links = true;
+    }
+
+    /// Set the default region (ISO 3166 code such as `"US"`) for phone numbers written
+    /// without a `+` prefix. Without it only international numbers are detected.
+    pub fn set_phone_region(&mut self, region: &str) -> Result<(), ExtractionError> {
+        let region = region.trim().to_uppercase();
+        region.parse::<phonenumber::country::Id>()
+            .map_err(|_| ExtractionError::Other(format!("unknown phone region: {}", region)))?;
+        self.activities.contact_options.phone_region = Some(region);
+        Ok(())
+    }
+
+    /// Enable every activity at once: text with language detection, plus links, socials,
+    /// videos, products and articles with all fields
+    pub fn extract_all(&mut self) {
+        let all = vec!["all".to_string()];
+        self.extract_text(true);
+        self.extract_links(all.clone());
+        self.extract_socials(all.clone());
+        self.extract_video(all.clone());
+        self.extract_product(all.clone());
+        self.extract_article(all);
+    }
+
+    /// Follow `<meta http-equiv="refresh">` redirects in `run`/`run_async`: when the delay is
+    /// at most five seconds the target is fetched and extracted instead (up to five hops, never
+    /// looping back to a page already visited), and `ExtractionResult.url` is the page the data
+    /// came from. A hop that fails keeps the last page's result. `extract` never follows, since
+    /// it doesn't fetch.
+    pub fn set_follow_meta_refresh(&mut self, enabled: bool) {
+        self.follow_meta_refresh = enabled;
+    }
+
+    /// Fetch the JSON oEmbed document discovered on the page in `run`/`run_async`, with the
+    /// configured client (its timeout applies and bodies over 256 KiB are dropped), and attach
+    /// its fields as `ExtractionResult.oembed`. A failed fetch leaves just `oembed_url`.
+    /// `extract` never fetches.
+    pub fn set_fetch_oembed(&mut self, enabled: bool) {
+        self.fetch_oembed = enabled;
+    }
+
+    /// Spread the extraction passes (text, products, links, ...) of pages over 256 KiB across
+    /// worker threads, each parsing its own copy of the document (disabled by default). Helps
+    /// single large pages with several activities; `run_batch` always keeps one thread per
+    /// page, since its URLs already occupy the cores.
+    pub fn set_parallel_passes(&mut self, enabled: bool) {
+        self.parallel_passes = enabled;
+    }
+
+    /// Call `f(completed, total)` after each URL of `run_batch` finishes, successfully or not,
+    /// e.g. to drive a progress bar. It runs on the batch's worker threads.
+    pub fn set_progress_callback(&mut self, f: impl Fn(usize, usize) + Send + Sync + 'static) {
+        self.progress_callback = Some(Arc::new(f));
+    }
+
+    /// In strict mode, `run`/`extract` fail with `unknown field: <name>` when a requested field
+    /// (after alias normalization) doesn't exist, instead of silently skipping it
+    pub fn set_strict_fields(&mut self, strict: bool) {
+        self.activities.strict_fields = strict;
+    }
+
+    /// Record where each article and product value came from (meta tags, JSON-LD, microdata,
+    /// CSS selector fallbacks, ...) in `article_sources`/`product_sources`
+    pub fn set_collect_provenance(&mut self, enabled: bool) {
+        self.activities.collect_provenance = enabled;
+    }
+
+    /// Trust only structured data: product and article extractors (titles, prices, ratings,
+    /// images, dates, ...) skip their CSS-selector, `<h1>`/`<title>` and text-scan fallbacks, the
+    /// currency guess, pattern matches over malformed JSON-LD and `itemprop`s outside the main
+    /// item, leaving a field empty unless JSON-LD, a meta tag or microdata declares it
+    pub fn set_structured_only(&mut self, enabled: bool) {
+        self.activities.structured_only = enabled;
+    }
+
+    /// Parse at most `bytes` of JSON-LD per page (2 MiB by default); scripts that would exceed
+    /// the cap are skipped, so pages embedding megabytes of JSON-LD stay cheap to index
+    pub fn set_max_json_ld_bytes(&mut self, bytes: usize) {
+        self.activities.max_json_ld_bytes = Some(bytes);
+    }
+
+    /// Check every requested field name against its extractor's field list (strict mode only)
+    fn validate_fields(&self) -> Result<(), ExtractionError> {
+        if !self.activities.strict_fields {
+            return Ok(());
+        }
+        match self.unknown_field_errors().into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Every requested field that its extractor doesn't know, with the extractor's name
+    fn unknown_fields(&self) -> Vec<(&'static str, &String)> {
+        fn unknown<'a>(kind: &'static str, fields: &'a [String], is_known: fn(&str) -> bool) -> impl Iterator<Item = (&'static str, &'a String)> {
+            fields.iter().filter(move |f| !is_known(f)).map(move |f| (kind, f))
+        }
+
+        unknown("links", &self.activities.extract_links, is_known_link_filter)
+            .chain(unknown("socials", &self.activities.extract_socials, is_known_social_field))
+            .chain(unknown("video", &self.activities.extract_video, is_known_video_field))
+            .chain(unknown("product", &self.activities.extract_product, is_known_product_field))
+            .chain(unknown("article", &self.activities.extract_article, is_known_article_field))
+            .collect()
+    }
+
+    /// One `unknown field: <name>` error per requested field that no extractor knows
+    fn unknown_field_errors(&self) -> Vec<ExtractionError> {
+        self.unknown_fields().into_iter()
+            .map(|(_, field)| ExtractionError::Other(format!("unknown field: {}", field)))
+            .collect()
+    }
+
+    /// Dry-run check of the whole configuration without making any request: the URL parses as
+    /// http(s), custom headers and Accept-Language are valid header values, every requested
+    /// field name is known and the date confidence threshold is within 0.0-1.0.
+    /// Returns every problem found, not just the first one.
+    pub fn validate(&self) -> Result<(), Vec<ExtractionError>> {
+        let mut errors = Vec::new();
+
+        match url::Url::parse(&self.url) {
+            Ok(parsed) if !matches!(parsed.scheme(), "http" | "https") => errors.push(
+                ExtractionError::InvalidUrl(format!("unsupported scheme '{}' in {}", parsed.scheme(), self.url)),
+            ),
+            Ok(_) => {}
+            Err(e) => errors.push(ExtractionError::InvalidUrl(format!("{}: {}", self.url, e))),
+        }
+
+        let mut headers: Vec<_> = self.client_config.headers.iter().collect();
+        headers.sort();
+        for (key, value) in headers {
+            if let Err(e) = parse_header(key, value) {
+                errors.push(e);
+            }
+        }
+        if let Some(ref langs) = self.client_config.accept_language {
+            if let Err(e) = parse_accept_language(langs) {
+                errors.push(e);
+            }
+        }
+
+        errors.extend(self.unknown_field_errors());
+
+        for lang in &self.activities.extract_text.allowed_languages {
+            if whatlang::Lang::from_code(lang.to_lowercase()).is_none() {
+                errors.push(ExtractionError::Other(format!(
+                    "unknown language code: {} (expected ISO 639-3, e.g. \"eng\")", lang
+                )));
+            }
+        }
+
+        let threshold = self.activities.article_options.date_confidence_threshold;
+        if !(0.0..=1.0).contains(&threshold) {
+            errors.push(ExtractionError::Other(format!(
+                "date confidence threshold must be between 0.0 and 1.0, got {}", threshold
+            )));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Set the minimum confidence (0.0-1.0) for `best_publication_date` / `best_modified_date`
+    /// (`ArticleInfo.published` / `ArticleInfo.modified`)
+    pub fn set_date_confidence_threshold(&mut self, threshold: f64) {
+        self.activities.article_options.date_confidence_threshold = threshold;
+    }
+
+    /// Add words (matched case-insensitively) that boost a body date's confidence when they
+    /// appear next to it, e.g. a site-specific "Dateline:" label
+    pub fn add_date_cues(&mut self, cues: Vec<String>) {
+        self.activities.article_options.date_cues.extend(cues);
+    }
+
+    /// Robots checker that fetches robots.txt with the configured client (timeout, user agent,
+    /// headers); falls back to a bare client if the configuration can't build one
+    fn new_robots_checker(&mut self) -> RobotsChecker {
+        match self.get_client() {
+            Ok(client) => RobotsChecker::with_client(client.clone()),
+            Err(_) => RobotsChecker::new(),
+        }
+    }
+
     /// Enable robots.txt checking with in-memory cache
     pub fn enable_robots_check(&mut self) {
-        let mut checker = RobotsChecker::new();
+        let mut checker = self.new_robots_checker();
         checker.enable_memory_cache();
-        self.robots_checker = Some(checker);
+        self.robots_checker = Some(Arc::new(checker));
         self.robots_enabled = true;
     }
 
     /// Enable robots.txt checking with Redis cache
     pub fn enable_robots_check_with_redis(&mut self, redis_url: &str) -> Result<(), ExtractionError> {
-        let mut checker = RobotsChecker::new();
+        let mut checker = self.new_robots_checker();
         checker.enable_memory_cache();
         checker.enable_redis_cache(redis_url)?;
-        self.robots_checker = Some(checker);
+        self.robots_checker = Some(Arc::new(checker));
         self.robots_enabled = true;
         Ok(())
     }
 
     /// Set Redis TTL for robots.txt cache
     pub fn set_robots_redis_ttl(&mut self, ttl_secs: u64) -> Result<(), ExtractionError> {
-        if let Some(ref mut checker) = self.robots_checker {
+        if let Some(checker) = self.robots_checker.as_mut().and_then(Arc::get_mut) {
             checker.set_redis_ttl(ttl_secs);
             Ok(())
         } else {
@@ -248,6 +733,10 @@ impl WebExtractor {
 
     /// Check if current URL is allowed by robots.txt
     pub async fn check_robots_allowed(&self) -> Result<bool, ExtractionError> {
+        self.robots_allow(&self.url).await
+    }
+
+    async fn robots_allow(&self, url: &str) -> Result<bool, ExtractionError> {
         if let Some(ref checker) = self.robots_checker {
             let user_agent = if self.client_config.random_user_agent {
                 generate_random_user_agent()
@@ -256,7 +745,7 @@ impl WebExtractor {
             } else {
                 "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36"
             };
-            checker.is_allowed(&self.url, user_agent).await
+            checker.is_allowed(url, user_agent).await
         } else {
             Ok(true) // If robots checking is not enabled, allow by default
         }
@@ -269,7 +758,7 @@ impl WebExtractor {
             let domain = Url::parse(&self.url)
                 .ok()
                 .and_then(|u| u.host_str().map(|s| s.to_string()))
-                .unwrap_or_else(|| String::new());
+                .unwrap_or_default();
             checker.remove_from_redis(&domain).await
         } else {
             Err(ExtractionError::Other("Robots checker not enabled".to_string()))
@@ -283,9 +772,32 @@ impl WebExtractor {
         }
     }
 
+    /// robots.txt cache hit/miss counts, or `None` when robots checking is not enabled
+    pub fn robots_stats(&self) -> Option<RobotsStats> {
+        self.robots_checker.as_ref().map(|checker| checker.stats())
+    }
+
+    /// The enabled activities and their options
+    pub fn activities(&self) -> &Activities {
+        &self.activities
+    }
+
+    /// The settings the HTTP client is built from (ignored when a client was supplied with
+    /// `with_client`)
+    pub fn client_config(&self) -> &ClientConfig {
+        &self.client_config
+    }
+
     pub async fn run_async(&mut self) -> Result<ExtractionResult, ExtractionError> {
-        // Check robots.txt if enabled
+        self.validate_fields()?;
+
+        // Check robots.txt if enabled, fetching it with the same client settings as the page
         if self.robots_enabled {
+            // A checker shared by `run_batch` workers already has the batch's client
+            let client = self.get_client()?.clone();
+            if let Some(checker) = self.robots_checker.as_mut().and_then(Arc::get_mut) {
+                checker.set_client(client);
+            }
             let allowed = self.check_robots_allowed().await?;
             if !allowed {
                 return Err(ExtractionError::Other(
@@ -293,132 +805,634 @@ impl WebExtractor {
                 ));
             }
         }
-
-        let mut result = ExtractionResult {
-            url: self.url.clone(),
-            text: None,
-            language: None,
-            language_confidence: None,
-            links: None,
-            socials: None,
-            videos: None,
-            product: None,
-            article: None,
-            content: None,
+        let robots_malformed = match self.robots_checker {
+            Some(ref checker) if self.robots_enabled => checker.is_malformed(&self.url).await,
+            _ => false,
         };
 
         // Use provided HTML or download if needed
-        let html_content = if self.activities.extract_text.enabled
-            || !self.activities.extract_links.is_empty()
-            || !self.activities.extract_socials.is_empty()
-            || !self.activities.extract_video.is_empty()
-            || !self.activities.extract_product.is_empty()
-            || !self.activities.extract_article.is_empty()
-            || self.activities.extract_text.language_detection
-        {
+        let html_content = if self.needs_html() {
             // Use provided HTML if available, otherwise download
             if let Some(ref provided_html) = self.html {
                 Some(provided_html.clone())
             } else {
                 let url = self.url.clone();
-                let client = self.get_client()?;
-                let response = client
-                    .get(&url)
-                    .send()
-                    .await
-                    .map_err(|e| ExtractionError::from(e))?;
-
-                let html = response
-                    .text()
-                    .await
-                    .map_err(|e| ExtractionError::HttpError(format!("Failed to read response: {}", e)))?;
-
-                Some(html)
+                Some(self.fetch_html(&url).await?)
             }
         } else {
             None
         };
 
+        let mut result = self.extract_document(&self.url, html_content.as_deref())?;
+
+        // Follow meta refresh redirects (see `next_meta_refresh_hop`). A target disallowed by
+        // robots.txt or failing to fetch or extract isn't followed; the result of the last page
+        // reached then keeps the redirect in `meta_refresh`.
+        if self.follow_meta_refresh && html_content.is_some() {
+            let mut visited = vec![self.url.clone()];
+            while let Some(target) = next_meta_refresh_hop(result.meta_refresh.as_ref(), &visited) {
+                if self.robots_enabled && !matches!(self.robots_allow(&target).await, Ok(true)) {
+                    break;
+                }
+                let Ok(html) = self.fetch_html(&target).await else { break };
+                let Ok(next) = self.extract_document(&target, Some(&html)) else { break };
+                result = next;
+                visited.push(target);
+            }
+        }
+        if self.fetch_oembed {
+            if let Some(oembed_url) = result.oembed_url.clone() {
+                result.oembed = self.fetch_oembed_document(&oembed_url).await;
+            }
+        }
+        if robots_malformed {
+            result.robots_warning = Some("robots.txt could not be parsed; treated as allow-all".to_string());
+        }
+        Ok(result)
+    }
+
+    /// Fetch and extract every URL in `urls` with this extractor's activities, HTTP client and
+    /// robots.txt checker, at most `max_concurrent` at a time, calling the progress callback
+    /// after each one. Results are in input order; the extractor's own URL and HTML are unused.
+    pub async fn run_batch(&mut self, urls: Vec<String>, max_concurrent: usize) -> Result<Vec<Result<ExtractionResult, ExtractionError>>, ExtractionError> {
+        self.validate_fields()?;
+        let client = self.get_client()?.clone();
+        if let Some(checker) = self.robots_checker.as_mut().and_then(Arc::get_mut) {
+            checker.set_client(client.clone());
+        }
+
+        let total = urls.len();
+        let urls = Arc::new(urls);
+        let next = Arc::new(AtomicUsize::new(0));
+        let completed = Arc::new(AtomicUsize::new(0));
+        let mut workers = tokio::task::JoinSet::new();
+        for _ in 0..max_concurrent.clamp(1, total.max(1)) {
+            let mut worker = self.batch_worker(client.clone());
+            let progress = self.progress_callback.clone();
+            let (urls, next, completed) = (urls.clone(), next.clone(), completed.clone());
+            workers.spawn(async move {
+                let mut results = Vec::new();
+                // Take the next URL until none are left
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(url) = urls.get(index) else { break };
+                    worker.url = url.clone();
+                    results.push((index, worker.run_async().await));
+                    let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
+                    if let Some(ref progress) = progress {
+                        progress(done, total);
+                    }
+                }
+                results
+            });
+        }
+
+        let mut results: Vec<Option<Result<ExtractionResult, ExtractionError>>> = (0..total).map(|_| None).collect();
+        while let Some(joined) = workers.join_next().await {
+            let worker_results = joined.unwrap_or_else(|error| std::panic::resume_unwind(error.into_panic()));
+            for (index, result) in worker_results {
+                results[index] = Some(result);
+            }
+        }
+        Ok(results.into_iter().map(|result| result.expect("every URL is taken by a worker")).collect())
+    }
+
+    /// An extractor for one `run_batch` worker: the same activities and settings, sharing the
+    /// batch's client and robots.txt checker
+    fn batch_worker(&self, client: Client) -> Self {
+        Self {
+            url: String::new(),
+            html: None,
+            activities: self.activities.clone(),
+            client: Some(client),
+            custom_client: true,
+            client_config: self.client_config.clone(),
+            robots_checker: self.robots_checker.clone(),
+            robots_enabled: self.robots_enabled,
+            follow_meta_refresh: self.follow_meta_refresh,
+            fetch_oembed: self.fetch_oembed,
+            // The batch already keeps one worker per URL busy
+            parallel_passes: false,
+            progress_callback: None,
+        }
+    }
+
+    /// Download a page's HTML with the configured client
+    async fn fetch_html(&mut self, url: &str) -> Result<String, ExtractionError> {
+        let client = self.get_client()?;
+        let response = client
+            .get(url)
+            .send()
+            .await
+            .map_err(ExtractionError::from)?;
+
+        response
+            .text()
+            .await
+            .map_err(|e| ExtractionError::HttpError(format!("Failed to read response: {}", e)))
+    }
+
+    /// Fetch and parse a JSON oEmbed document with the configured client. Any failure (robots
+    /// disallow, HTTP error, a body over `MAX_OEMBED_BYTES`, XML or malformed JSON) yields
+    /// `None`, leaving just the discovered URL on the result.
+    async fn fetch_oembed_document(&mut self, oembed_url: &str) -> Option<OEmbed> {
+        if !url::Url::parse(oembed_url).is_ok_and(|u| matches!(u.scheme(), "http" | "https")) {
+            return None;
+        }
+        if self.robots_enabled && !self.robots_allow(oembed_url).await.unwrap_or(false) {
+            return None;
+        }
+        let client = self.get_client().ok()?;
+        let mut response = client.get(oembed_url).send().await.ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.ok()? {
+            body.extend_from_slice(&chunk);
+            if body.len() > MAX_OEMBED_BYTES {
+                return None;
+            }
+        }
+        parse_oembed(&String::from_utf8_lossy(&body))
+    }
+
+    /// Whether any configured activity (or the oEmbed fetch, which discovers its endpoint in
+    /// the page) needs the page HTML
+    fn needs_html(&self) -> bool {
+        self.fetch_oembed
+            || self.activities.extract_text.enabled
+            || !self.activities.extract_links.is_empty()
+            || !self.activities.extract_socials.is_empty()
+            || !self.activities.extract_video.is_empty()
+            || !self.activities.extract_product.is_empty()
+            || self.activities.extract_products_list.is_some()
+            || !self.activities.extract_article.is_empty()
+            || self.activities.extract_lists
+            || self.activities.extract_recipe
+            || self.activities.extract_event
+            || self.activities.extract_faqs
+            || self.activities.extract_contacts
+            || self.activities.extract_emails
+            || self.activities.extract_preview_image
+            || self.activities.extract_alternate_links
+            || self.activities.extract_text.language_detection
+            || self.activities.extract_text.compute_simhash
+    }
+
+    /// Run the configured extractors over already-fetched HTML. With `set_parallel_passes`, on
+    /// large pages with several passes enabled, the passes are spread over worker threads;
+    /// `Html` isn't `Sync`, so each worker parses and indexes its own copy of the document. A
+    /// page rejected by the language allowlist fails with `LanguageNotAllowed`.
+    fn extract_document(&self, url: &str, html_content: Option<&str>) -> Result<ExtractionResult, ExtractionError> {
+        let mut result = empty_result(url);
+        // Outside strict mode unknown field names are skipped, but reported
+        result.warnings = self.unknown_fields().into_iter()
+            .map(|(kind, field)| format!("unknown {} field: {}", kind, field))
+            .collect();
+
         // Parse HTML if we have content
         if let Some(html_content) = html_content {
-            let document = Html::parse_document(&html_content);
+            let activities = &self.activities;
+            let passes = enabled_passes(activities);
+            let workers = std::thread::available_parallelism()
+                .map_or(1, |n| n.get())
+                .min(passes.len());
+
+            // Skipping or rejecting by language needs the detected language before the other
+            // passes start, so those pages stay on one thread
+            let text_options = &activities.extract_text;
+            let language_gate = !text_options.allowed_languages.is_empty()
+                && text_options.language_filter != LanguageFilterMode::Flag;
+
+            if self.parallel_passes && workers > 1 && !language_gate && html_content.len() >= PARALLEL_MIN_HTML_BYTES {
+                // Deal passes round-robin (heaviest first) so the costly ones land on different workers
+                let mut chunks: Vec<Vec<Pass>> = vec![Vec::new(); workers];
+                for (i, pass) in passes.into_iter().enumerate() {
+                    chunks[i % workers].push(pass);
+                }
+                let own_passes = chunks.remove(0);
+
+                let partials: Vec<ExtractionResult> = std::thread::scope(|scope| {
+                    let handles: Vec<_> = chunks.into_iter()
+                        .map(|chunk| scope.spawn(move || {
+                            let mut partial = empty_result(url);
+                            parse_and_run(activities, url, html_content, &chunk, &mut partial);
+                            partial
+                        }))
+                        .collect();
+                    parse_and_run(activities, url, html_content, &own_passes, &mut result);
+                    handles.into_iter()
+                        .map(|handle| handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+                        .collect()
+                });
+                for partial in partials {
+                    merge_pass_result(&mut result, partial);
+                }
+            } else {
+                parse_and_run(activities, url, html_content, &passes, &mut result);
+            }
+        }
+
+        // Create content info (even if no HTML, when text exists)
+        let text_length = result.text.as_ref().map_or(0, |t| t.len());
+        result.content = Some(ContentInfo {
+            text: result.text.clone(),
+            text_length,
+        });
+
+        if result.language_allowed == Some(false) && self.activities.extract_text.language_filter == LanguageFilterMode::Reject {
+            return Err(ExtractionError::LanguageNotAllowed(result.language.clone().unwrap_or_default()));
+        }
+        Ok(result)
+    }
+
+    /// Extract synchronously from the HTML passed to `new_with_html`, without creating a
+    /// runtime or HTTP client. Fails if no HTML was provided or robots.txt checking is enabled,
+    /// since both need the network; use `run` for those.
+    pub fn extract(&self) -> Result<ExtractionResult, ExtractionError> {
+        self.validate_fields()?;
+        if self.robots_enabled {
+            return Err(ExtractionError::Other(
+                "extract() cannot check robots.txt; use run() when robots checking is enabled".to_string()
+            ));
+        }
+        let html = self.html.as_deref().ok_or_else(|| ExtractionError::Other(
+            "extract() requires HTML; create the extractor with new_with_html() or use run()".to_string()
+        ))?;
+
+        self.extract_document(&self.url, self.needs_html().then_some(html))
+    }
+
+    // Synchronous wrapper for backward compatibility
+    pub fn run(&mut self) -> Result<ExtractionResult, ExtractionError> {
+        // Create a runtime for blocking calls
+        let rt = tokio::runtime::Runtime::new()
+            .map_err(|e| ExtractionError::Other(format!("Failed to create runtime: {}", e)))?;
+        rt.block_on(self.run_async())
+    }
+}
 
-            // Build DOM index once - traverse the tree once and reuse the index
-            let dom_index = DomIndex::build(&document);
+/// Largest oEmbed document read; a bigger response is dropped rather than truncated
+const MAX_OEMBED_BYTES: usize = 256 * 1024;
 
-            // Extract text if requested or if language detection is needed
-            let text_needed = self.activities.extract_text.enabled || self.activities.extract_text.language_detection;
-            if text_needed {
-                let extracted_text = extract_text_content(&document);
-                
+/// Most meta refresh redirects followed by one `run_async`
+const MAX_META_REFRESH_HOPS: usize = 5;
+
+/// Longest meta refresh delay, in seconds, followed as a redirect; a slower refresh is a page
+/// reloading itself or a timed interstitial (a news page refreshing every 300 seconds)
+const MAX_META_REFRESH_DELAY: u32 = 5;
+
+/// The meta refresh target to fetch next, after the pages already `visited` (the start page
+/// first): only redirect-style delays to http(s) URLs, never back to a visited page nor more
+/// than `MAX_META_REFRESH_HOPS` deep
+fn next_meta_refresh_hop(refresh: Option<&MetaRefresh>, visited: &[String]) -> Option<String> {
+    let refresh = refresh.filter(|refresh| refresh.delay <= MAX_META_REFRESH_DELAY)?;
+    let fetchable = url::Url::parse(&refresh.url).is_ok_and(|u| matches!(u.scheme(), "http" | "https"));
+    (fetchable && visited.len() <= MAX_META_REFRESH_HOPS && !visited.contains(&refresh.url)).then(|| refresh.url.clone())
+}
+
+/// Pages smaller than this are extracted on one thread: parsing the document again for each
+/// worker costs more than the passes it would take off the calling thread
+const PARALLEL_MIN_HTML_BYTES: usize = 256 * 1024;
+
+/// An independent extraction pass over one parsed page, writing its own result fields
+#[derive(Clone, Copy, Debug)]
+enum Pass {
+    /// Text, hash, simhash and language, plus the article, which reads the extracted text
+    Text,
+    /// The single product and the listing's products
+    Products,
+    Links,
+    Socials,
+    Videos,
+    Lists,
+    Recipe,
+    Event,
+    Faqs,
+    /// Phones and emails, which share the body text
+    Contacts,
+    PreviewImage,
+    AlternateLinks,
+}
+
+/// Passes the activities need, roughly heaviest first
+fn enabled_passes(activities: &Activities) -> Vec<Pass> {
+    let text_needed = activities.extract_text.enabled
+        || activities.extract_text.language_detection
+        || activities.extract_text.compute_simhash
+        || !activities.extract_article.is_empty();
+    [
+        (Pass::Text, text_needed),
+        (Pass::Products, !activities.extract_product.is_empty() || activities.extract_products_list.is_some()),
+        (Pass::Links, !activities.extract_links.is_empty()),
+        (Pass::Socials, !activities.extract_socials.is_empty()),
+        (Pass::Videos, !activities.extract_video.is_empty()),
+        (Pass::Lists, activities.extract_lists),
+        (Pass::Recipe, activities.extract_recipe),
+        (Pass::Event, activities.extract_event),
+        (Pass::Faqs, activities.extract_faqs),
+        (Pass::Contacts, activities.extract_contacts || activities.extract_emails),
+        (Pass::PreviewImage, activities.extract_preview_image),
+        (Pass::AlternateLinks, activities.extract_alternate_links),
+    ]
+    .into_iter()
+    .filter_map(|(pass, enabled)| enabled.then_some(pass))
+    .collect()
+}
+
+/// A result with nothing extracted yet
+fn empty_result(url: &str) -> ExtractionResult {
+    ExtractionResult {
+        url: url.to_string(),
+        text: None,
+        language: None,
+        language_confidence: None,
+        language_allowed: None,
+        content_hash: None,
+        simhash: None,
+        title: None,
+        description: None,
+        preview_image: None,
+        links: None,
+        amp_url: None,
+        alternate_links: None,
+        meta_refresh: None,
+        oembed_url: None,
+        oembed: None,
+        socials: None,
+        socials_info: None,
+        videos: None,
+        product: None,
+        product_info: None,
+        product_sources: None,
+        products: None,
+        article: None,
+        article_info: None,
+        article_sources: None,
+        lists: None,
+        recipe: None,
+        event: None,
+        faqs: None,
+        phones: None,
+        emails: None,
+        content: None,
+        robots_warning: None,
+        warnings: Vec::new(),
+    }
+}
+
+/// Parse and index the HTML, then run `passes` over it into `result`
+fn parse_and_run(activities: &Activities, url: &str, html_content: &str, passes: &[Pass], result: &mut ExtractionResult) {
+    let document = Html::parse_document(html_content);
+
+    // Build DOM index once - traverse the tree once, collecting only the sections the
+    // activities read, and reuse the index
+    let dom_index = DomIndex::build_for(&document, activities);
+
+    // The title, description, AMP, meta refresh and oEmbed discovery are cheap lookups,
+    // mostly in the head, so they run on every parse
+    result.title = dom_index.title.clone();
+    result.description = dom_index.description.clone();
+    result.amp_url = extract_amp_url(&dom_index, url);
+    result.meta_refresh = extract_meta_refresh(&dom_index, url);
+    result.oembed_url = extract_oembed_url(&dom_index, url);
+
+    for pass in passes {
+        run_pass(activities, url, *pass, &document, &dom_index, result);
+        // A page outside the language allowlist skips the remaining passes
+        if result.language_allowed == Some(false) && activities.extract_text.language_filter != LanguageFilterMode::Flag {
+            break;
+        }
+    }
+}
+
+/// Run one extraction pass over a parsed page
+fn run_pass(activities: &Activities, url: &str, pass: Pass, document: &Html, dom_index: &DomIndex, result: &mut ExtractionResult) {
+    match pass {
+        Pass::Text => {
+            // Extract text once if requested, if language detection is needed, or if article
+            // fields (word count / reading time) depend on it
+            let text_needed = activities.extract_text.enabled
+                || activities.extract_text.language_detection
+                || activities.extract_text.compute_simhash
+                || article_fields_need_text(&activities.extract_article);
+            let extracted_text = if text_needed {
+                let text = extract_text_content(document, url, &activities.extract_text);
+                // Sanitize before language detection so it runs on the cleaned text
+                Some(sanitize_text(&text, &activities.extract_text.sanitize))
+            } else {
+                None
+            };
+
+            if let Some(ref extracted_text) = extracted_text {
                 // Store text if enabled
-                if self.activities.extract_text.enabled {
+                if activities.extract_text.enabled {
                     result.text = Some(extracted_text.clone());
+                    result.content_hash = Some(content_hash(extracted_text));
                 }
-                
+
+                if activities.extract_text.compute_simhash {
+                    result.simhash = Some(simhash(extracted_text));
+                }
+
                 // Language detection if needed
-                if self.activities.extract_text.language_detection {
-                    if let Some(info) = detect(&extracted_text) {
+                if activities.extract_text.language_detection {
+                    if let Some(info) = detect(extracted_text) {
                         result.language = Some(info.lang().code().to_string());
                         result.language_confidence = Some(info.confidence());
                     }
+                    let allowed = &activities.extract_text.allowed_languages;
+                    if !allowed.is_empty() {
+                        result.language_allowed = result.language.as_ref()
+                            .map(|code| allowed.iter().any(|lang| lang.eq_ignore_ascii_case(code)));
+                    }
                 }
             }
 
-            // Extract links if requested (already grouped) - uses index
-            if !self.activities.extract_links.is_empty() {
-                let links = extract_links_with_index(&dom_index, &self.url, &self.activities.extract_links);
-                result.links = Some(links);
-            }
-
-            // Extract socials if requested - uses index
-            if !self.activities.extract_socials.is_empty() {
-                let socials = extract_socials_with_index(&dom_index, &self.activities.extract_socials);
-                result.socials = Some(socials);
+            // Only flagging lets a page outside the allowlist go on to article extraction
+            if result.language_allowed == Some(false) && activities.extract_text.language_filter != LanguageFilterMode::Flag {
+                return;
             }
 
-            // Extract videos if requested
-            if !self.activities.extract_video.is_empty() {
-                let videos = extract_video(&document, &self.activities.extract_video);
-                result.videos = Some(videos);
+            // Extract article if requested - uses index
+            if !activities.extract_article.is_empty() {
+                let article_info = extract_article_info(
+                    dom_index,
+                    url,
+                    &activities.extract_article,
+                    &activities.article_options,
+                    extracted_text.as_deref(),
+                );
+                let (article, sources) = extract_article_with_sources(
+                    dom_index,
+                    url,
+                    &activities.extract_article,
+                    &article_info,
+                    activities.collect_provenance,
+                );
+                result.article = Some(article);
+                result.article_info = Some(article_info);
+                if activities.collect_provenance {
+                    result.article_sources = Some(sources);
+                }
             }
-
+        }
+        Pass::Products => {
             // Extract product if requested
-            if !self.activities.extract_product.is_empty() {
-                let product = extract_products(&document, &self.activities.extract_product);
+            if !activities.extract_product.is_empty() {
+                let (product, sources, product_info) =
+                    extract_products_with_sources(dom_index, url, &activities.extract_product, &activities.product_options);
                 result.product = Some(product);
+                result.product_info = Some(product_info);
+                if activities.collect_provenance {
+                    result.product_sources = Some(sources);
+                }
             }
 
-            // Extract article if requested - uses index
-            if !self.activities.extract_article.is_empty() {
-                let article = extract_article_with_index(&dom_index, &self.activities.extract_article);
-                result.article = Some(article);
+            // Extract listing products if requested - uses index
+            if let Some(max) = activities.extract_products_list {
+                result.products = Some(extract_products_list(dom_index, url, max));
             }
-
-            // Create content info
-            let text_length = result.text.as_ref().map_or(0, |t| t.len());
-            result.content = Some(ContentInfo {
-                text: result.text.clone(),
-                text_length,
-            });
-        } else {
-            // Even if no HTML, create content info if text exists
-            let text_length = result.text.as_ref().map_or(0, |t| t.len());
-            result.content = Some(ContentInfo {
-                text: result.text.clone(),
-                text_length,
-            });
         }
+        // Extract links (already grouped) - uses index
+        Pass::Links => {
+            result.links = Some(extract_links_with_index(dom_index, url, &activities.extract_links, &activities.link_options));
+        }
+        // Extract socials - uses index
+        Pass::Socials => {
+            result.socials = Some(extract_socials_with_index(dom_index, url, &activities.extract_socials));
+            result.socials_info = Some(extract_socials_typed(dom_index));
+        }
+        Pass::Videos => {
+            result.videos = Some(extract_video(document, &activities.extract_video));
+        }
+        Pass::Lists => {
+            result.lists = Some(extract_lists(document));
+        }
+        Pass::Recipe => {
+            result.recipe = extract_recipe(dom_index);
+        }
+        Pass::Event => {
+            result.event = extract_event(dom_index, url);
+        }
+        Pass::Faqs => {
+            result.faqs = Some(extract_faqs(dom_index));
+        }
+        // Extract contact details (the body text is shared by both passes)
+        Pass::Contacts => {
+            let text = body_text(dom_index);
+            if activities.extract_contacts {
+                let region = activities.contact_options.phone_region.as_deref();
+                result.phones = Some(extract_phones(dom_index, &text, region));
+            }
+            if activities.extract_emails {
+                result.emails = Some(extract_emails(dom_index, &text));
+            }
+        }
+        Pass::PreviewImage => {
+            result.preview_image = dom_index.preview_image().map(|image| resolve_url(url, &image));
+        }
+        Pass::AlternateLinks => {
+            let alternate_links = extract_alternate_links(dom_index, url);
+            result.alternate_links = (!alternate_links.is_empty()).then_some(alternate_links);
+        }
+    }
+}
 
-        Ok(result)
+/// Move the fields a worker's passes filled into the combined result
+fn merge_pass_result(result: &mut ExtractionResult, partial: ExtractionResult) {
+    result.text = partial.text.or(result.text.take());
+    result.language = partial.language.or(result.language.take());
+    result.language_confidence = partial.language_confidence.or(result.language_confidence.take());
+    result.language_allowed = partial.language_allowed.or(result.language_allowed.take());
+    result.content_hash = partial.content_hash.or(result.content_hash.take());
+    result.simhash = partial.simhash.or(result.simhash.take());
+    result.links = partial.links.or(result.links.take());
+    result.socials = partial.socials.or(result.socials.take());
+    result.socials_info = partial.socials_info.or(result.socials_info.take());
+    result.videos = partial.videos.or(result.videos.take());
+    result.product = partial.product.or(result.product.take());
+    result.product_info = partial.product_info.or(result.product_info.take());
+    result.product_sources = partial.product_sources.or(result.product_sources.take());
+    result.products = partial.products.or(result.products.take());
+    result.article = partial.article.or(result.article.take());
+    result.article_info = partial.article_info.or(result.article_info.take());
+    result.article_sources = partial.article_sources.or(result.article_sources.take());
+    result.lists = partial.lists.or(result.lists.take());
+    result.recipe = partial.recipe.or(result.recipe.take());
+    result.event = partial.event.or(result.event.take());
+    result.faqs = partial.faqs.or(result.faqs.take());
+    result.phones = partial.phones.or(result.phones.take());
+    result.emails = partial.emails.or(result.emails.take());
+    result.preview_image = partial.preview_image.or(result.preview_image.take());
+    result.alternate_links = partial.alternate_links.or(result.alternate_links.take());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skipped_language_gets_no_article() {
+        let html = "<html><head><title>Wetterbericht</title></head><body><article><h1>Wetterbericht</h1>\
+            <p>Morgen wird es in ganz Deutschland sonnig und warm, nur im Norden ziehen am Nachmittag \
+            einzelne Wolken auf. Die Temperaturen steigen auf bis zu dreißig Grad.</p></article></body></html>";
+        let extract = |mode: LanguageFilterMode| {
+            let mut extractor = WebExtractor::new_with_html("https://wetter.example.de/morgen".to_string(), html.to_string());
+            extractor.set_allowed_languages(vec!["eng".to_string()]);
+            extractor.set_language_filter_mode(mode);
+            extractor.extract_article(vec!["title".to_string()]);
+            extractor.extract().unwrap()
+        };
+
+        let skipped = extract(LanguageFilterMode::Skip);
+        assert_eq!(skipped.language.as_deref(), Some("deu"));
+        assert_eq!(skipped.language_allowed, Some(false));
+        assert!(skipped.article.is_none() && skipped.article_info.is_none());
+
+        let flagged = extract(LanguageFilterMode::Flag);
+        assert_eq!(flagged.language_allowed, Some(false));
+        assert!(flagged.article.is_some_and(|article| article.contains_key("title")));
     }
 
-    // Synchronous wrapper for backward compatibility
-    pub fn run(&mut self) -> Result<ExtractionResult, ExtractionError> {
-        // Create a runtime for blocking calls
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| ExtractionError::Other(format!("Failed to create runtime: {}", e)))?;
-        rt.block_on(self.run_async())
+    fn refresh(url: &str, delay: u32) -> MetaRefresh {
+        MetaRefresh { url: url.to_string(), delay }
     }
-}
 
+    #[test]
+    fn next_meta_refresh_hop_follows_redirect_delays_only() {
+        let visited = vec!["https://example.com/".to_string()];
+        let next = |refresh: MetaRefresh| next_meta_refresh_hop(Some(&refresh), &visited);
+
+        assert_eq!(next(refresh("https://example.com/a", 0)).as_deref(), Some("https://example.com/a"));
+        assert_eq!(next(refresh("https://example.com/a", MAX_META_REFRESH_DELAY)).as_deref(), Some("https://example.com/a"));
+        assert_eq!(next(refresh("https://example.com/a", MAX_META_REFRESH_DELAY + 1)), None);
+        assert_eq!(next(refresh("https://example.com/a", 300)), None);
+        assert_eq!(next(refresh("https://example.com/", 0)), None);
+        assert_eq!(next(refresh("ftp://example.com/file", 0)), None);
+        assert_eq!(next_meta_refresh_hop(None, &visited), None);
+    }
+
+    #[test]
+    fn meta_refresh_chain_stops_after_max_hops() {
+        // Each page redirects to the next one, so only the hop limit ends the chain
+        let mut visited = vec!["https://example.com/0".to_string()];
+        while let Some(target) = next_meta_refresh_hop(Some(&refresh(&format!("https://example.com/{}", visited.len()), 0)), &visited) {
+            visited.push(target);
+        }
+        assert_eq!(visited.len(), MAX_META_REFRESH_HOPS + 1);
+    }
+
+    #[test]
+    fn unknown_fields_become_warnings() {
+        let html = r#"<head><meta property="og:title" content="OG title"></head>"#;
+        let mut extractor = WebExtractor::new_with_html("https://acme.example/".to_string(), html.to_string());
+        extractor.extract_socials(vec!["title".to_string(), "titel".to_string()]);
+        extractor.extract_article(vec!["title".to_string(), "autor".to_string()]);
+
+        let result = extractor.extract().unwrap();
+        assert_eq!(result.socials.unwrap().get("og_title").map(String::as_str), Some("OG title"));
+        assert_eq!(result.warnings, vec!["unknown socials field: titel", "unknown article field: autor"]);
+    }
+}
@@ -0,0 +1,96 @@
+use scraper::{Html, Node};
+use crate::dom_index::DomIndex;
+use crate::json_ld::has_json_ld_type;
+use crate::types::Faq;
+
+/// Collect every JSON-LD `FAQPage` object, looking through arrays and `@graph` containers
+fn collect_faq_pages<'a>(value: &'a serde_json::Value, pages: &mut Vec<&'a serde_json::Map<String, serde_json::Value>>) {
+    match value {
+        serde_json::Value::Object(obj) => {
+            if has_json_ld_type(obj, &["FAQPage"]) {
+                pages.push(obj);
+            } else if let Some(graph) = obj.get("@graph") {
+                collect_faq_pages(graph, pages);
+            }
+        }
+        serde_json::Value::Array(arr) => {
+            for item in arr {
+                collect_faq_pages(item, pages);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Elements that start a new line of text, so their content isn't glued to the previous block
+const BLOCK_TAGS: &[&str] = &["p", "br", "div", "li", "ul", "ol", "h1", "h2", "h3", "h4", "h5", "h6", "tr", "td"];
+
+/// Plain text of a value that may contain HTML markup (answers often embed `<p>`/`<a>` tags)
+fn strip_html(value: &str) -> Option<String> {
+    let fragment = Html::parse_fragment(value);
+    let mut text = String::new();
+    for node in fragment.root_element().descendants() {
+        match node.value() {
+            Node::Text(t) => text.push_str(t),
+            Node::Element(e) if BLOCK_TAGS.contains(&e.name()) => text.push(' '),
+            _ => {}
+        }
+    }
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+/// `text` of an Answer object (or the first of an array), or a plain string answer
+fn answer_text(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => strip_html(s),
+        serde_json::Value::Array(arr) => arr.iter().find_map(answer_text),
+        serde_json::Value::Object(obj) => obj.get("text").and_then(|t| t.as_str()).and_then(strip_html),
+        _ => None,
+    }
+}
+
+/// Collect question/answer pairs from a `mainEntity` value (a single Question or an array).
+/// `acceptedAnswer` is preferred over `suggestedAnswer`.
+fn collect_questions(value: &serde_json::Value, faqs: &mut Vec<Faq>) {
+    match value {
+        serde_json::Value::Array(arr) => {
+            for item in arr {
+                collect_questions(item, faqs);
+            }
+        }
+        serde_json::Value::Object(obj) => {
+            let question = obj.get("name")
+                .or_else(|| obj.get("text"))
+                .and_then(|q| q.as_str())
+                .and_then(strip_html);
+            let answer = obj.get("acceptedAnswer")
+                .and_then(answer_text)
+                .or_else(|| obj.get("suggestedAnswer").and_then(answer_text));
+            if let (Some(question), Some(answer)) = (question, answer) {
+                faqs.push(Faq { question, answer });
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Extract question/answer pairs from JSON-LD `FAQPage` markup, in document order
+pub fn extract_faqs(dom_index: &DomIndex) -> Vec<Faq> {
+    let mut pages = Vec::new();
+    for json_value in dom_index.get_parsed_json_ld() {
+        collect_faq_pages(json_value, &mut pages);
+    }
+
+    let mut faqs = Vec::new();
+    for page in pages {
+        if let Some(main_entity) = page.get("mainEntity") {
+            collect_questions(main_entity, &mut faqs);
+        }
+    }
+    faqs
+}
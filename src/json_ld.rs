@@ -0,0 +1,13 @@
+/// Get the `@type` values of a JSON-LD object (a single string or an array of strings)
+pub fn json_ld_types(obj: &serde_json::Map<String, serde_json::Value>) -> Vec<&str> {
+    match obj.get("@type") {
+        Some(serde_json::Value::String(t)) => vec![t.as_str()],
+        Some(serde_json::Value::Array(arr)) => arr.iter().filter_map(|t| t.as_str()).collect(),
+        _ => vec![],
+    }
+}
+
+/// Whether a JSON-LD object's `@type` (string or array) is one of `types`
+pub fn has_json_ld_type(obj: &serde_json::Map<String, serde_json::Value>, types: &[&str]) -> bool {
+    json_ld_types(obj).iter().any(|t| types.contains(t))
+}
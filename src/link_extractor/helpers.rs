@@ -13,12 +13,23 @@ pub fn extract_base_domain(base_url: &str) -> String {
     Url::parse(base_url)
         .ok()
         .and_then(|u| u.host_str().map(|s| s.to_string()))
-        .unwrap_or_else(|| String::new())
+        .unwrap_or_default()
+}
+
+/// Whether a resolved link uses one of the allowed schemes (compared case-insensitively).
+/// Links that still aren't absolute URLs carry no scheme and are kept.
+pub fn has_allowed_scheme(url: &str, allowed_schemes: &[String]) -> bool {
+    match Url::parse(url) {
+        Ok(parsed) => allowed_schemes.iter().any(|s| s.eq_ignore_ascii_case(parsed.scheme())),
+        Err(_) => true,
+    }
 }
 
 /// Parse filter options into a configuration struct
 pub fn parse_filter_options(filter_options: &[String]) -> FilterConfig {
-    let wants_all = filter_options.is_empty() || filter_options.iter().any(|opt| opt == "all");
+    // "with_context" changes what each link carries, not which links are kept
+    let scope_given = filter_options.iter().any(|opt| opt != "with_context");
+    let wants_all = !scope_given || filter_options.iter().any(|opt| opt == "all");
     let wants_internal = wants_all || filter_options.iter().any(|opt| opt == "internal");
     let wants_external = wants_all || filter_options.iter().any(|opt| opt == "external");
 
@@ -29,6 +40,14 @@ pub fn parse_filter_options(filter_options: &[String]) -> FilterConfig {
     }
 }
 
+/// Whether a resolved link is on `base_domain`; links without a host count as external, as
+/// in `categorize_link`
+pub fn is_internal_link(url: &str, base_domain: &str) -> bool {
+    Url::parse(url).ok()
+        .and_then(|parsed| parsed.host_str().map(|host| host == base_domain || host.is_empty()))
+        .unwrap_or(false)
+}
+
 /// Categorize a link as internal or external and add to appropriate collections
 pub fn categorize_link(
     link: &LinkInfo,
@@ -45,7 +64,7 @@ pub fn categorize_link(
             
             // Group by domain
             by_domain.entry(domain_str.clone())
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(link_clone.clone());
 
             // Categorize as internal/external
@@ -1,22 +1,40 @@
 mod helpers;
 
 use url::Url;
-use crate::types::{LinkInfo, GroupedLinks, LinkSummary};
+use crate::types::{LinkInfo, GroupedLinks, LinkSummary, LinkOptions, MetaRefresh, OEmbed};
 use crate::dom_index::DomIndex;
 use std::collections::HashMap;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// `<meta http-equiv="refresh">` content: a delay in seconds, then optionally `;` or `,` and
+/// the target, with or without `url=` and quotes ("0; url='/new'", "5,https://example.com")
+static META_REFRESH_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?is)^\s*(\d+)(?:\.\d*)?\s*(?:[;,]\s*(?:url\s*=\s*)?(.*))?$").unwrap()
+});
+
+/// Link `type`s of oEmbed endpoints, JSON first
+const OEMBED_TYPES: &[&str] = &["application/json+oembed", "text/xml+oembed", "application/xml+oembed"];
 
 /// Extract links using pre-built DOM index (avoids re-traversing DOM)
 /// 
 /// # Arguments
 /// * `dom_index` - Pre-built DOM index containing link data
 /// * `base_url` - Base URL for resolving relative links and determining internal/external
-/// * `filter_options` - Vec of filter options: "internal", "external", or "all" (empty vec means "all")
-pub fn extract_links_with_index(dom_index: &DomIndex, base_url: &str, filter_options: &[String]) -> GroupedLinks {
+/// * `filter_options` - Vec of filter options: "internal", "external", or "all" (empty vec means "all"),
+///   plus "with_context" to fill `LinkInfo.context`
+/// * `options` - Link settings (allowed URL schemes, link count limits)
+pub fn extract_links_with_index(dom_index: &DomIndex, base_url: &str, filter_options: &[String], options: &LinkOptions) -> GroupedLinks {
     let base = Url::parse(base_url).ok();
     let mut all_links = Vec::new();
+    let mut per_domain_counts: HashMap<String, usize> = HashMap::new();
+    let mut dropped = 0;
+    let base_domain = helpers::extract_base_domain(base_url);
+    // Determine which links to include based on filter options
+    let filter_config = helpers::parse_filter_options(filter_options);
 
     // Use pre-indexed link data instead of traversing DOM again
-    for (href, text) in dom_index.get_link_data() {
+    for (i, (href, text)) in dom_index.get_link_data().iter().enumerate() {
         // Only process links with non-empty text
         if text.trim().is_empty() {
             continue;
@@ -28,17 +46,46 @@ pub fn extract_links_with_index(dom_index: &DomIndex, base_url: &str, filter_opt
             href.clone()
         };
 
+        // Drop non-navigational schemes (javascript:, data:, ...) unless explicitly allowed
+        if !helpers::has_allowed_scheme(&absolute_url, &options.allowed_schemes) {
+            continue;
+        }
+
+        // Links the filter drops don't count toward the limits
+        let wanted = if helpers::is_internal_link(&absolute_url, &base_domain) {
+            filter_config.wants_internal
+        } else {
+            filter_config.wants_external
+        };
+        if !wanted {
+            continue;
+        }
+
+        // Enforce link count limits, keeping first-seen links
+        if options.max_links.is_some_and(|max| all_links.len() >= max) {
+            dropped += 1;
+            continue;
+        }
+        if let Some(max_per_domain) = options.max_links_per_domain {
+            let domain = helpers::extract_base_domain(&absolute_url);
+            let count = per_domain_counts.entry(domain).or_insert(0);
+            if *count >= max_per_domain {
+                dropped += 1;
+                continue;
+            }
+            *count += 1;
+        }
+
         all_links.push(LinkInfo {
             url: absolute_url,
             text: text.clone(),
+            context: dom_index.link_contexts.get(i).cloned().flatten(),
         });
     }
 
     // All links in all_links are already valid (non-empty text)
     let valid_links = all_links;
 
-    let base_domain = helpers::extract_base_domain(base_url);
-
     let mut internal = Vec::new();
     let mut external = Vec::new();
     let mut by_domain: HashMap<String, Vec<LinkInfo>> = HashMap::new();
@@ -47,9 +94,6 @@ pub fn extract_links_with_index(dom_index: &DomIndex, base_url: &str, filter_opt
         helpers::categorize_link(link, &base_domain, &mut internal, &mut external, &mut by_domain);
     }
 
-    // Determine which links to include based on filter options
-    let filter_config = helpers::parse_filter_options(filter_options);
-
     // Filter internal and external based on options
     let filtered_internal: Vec<LinkInfo> = if filter_config.wants_internal {
         internal
@@ -72,6 +116,8 @@ pub fn extract_links_with_index(dom_index: &DomIndex, base_url: &str, filter_opt
         internal_count: filtered_internal.len(),
         external_count: filtered_external.len(),
         unique_domains: filtered_by_domain.len(),
+        truncated: dropped > 0,
+        dropped,
     };
 
     GroupedLinks {
@@ -81,3 +127,130 @@ pub fn extract_links_with_index(dom_index: &DomIndex, base_url: &str, filter_opt
         summary,
     }
 }
+
+/// Whether `option` is a link filter option: "internal", "external", "all" or "with_context"
+pub fn is_known_link_filter(option: &str) -> bool {
+    matches!(option, "internal" | "external" | "all" | "with_context")
+}
+
+/// Resolve a possibly relative href against the base URL, returning the href unchanged if it can't be resolved
+pub fn resolve_url(base_url: &str, href: &str) -> String {
+    Url::parse(base_url)
+        .and_then(|base| base.join(href))
+        .map(|u| u.to_string())
+        .unwrap_or_else(|_| href.to_string())
+}
+
+/// Discover the AMP variant of the page, resolved against `base_url`.
+///
+/// Regular pages link their AMP version via `<link rel="amphtml">`. When the page is
+/// itself AMP (`<html amp>` or `<html ⚡>`), the page URL is the AMP URL.
+pub fn extract_amp_url(dom_index: &DomIndex, base_url: &str) -> Option<String> {
+    if let Some(href) = dom_index.get_first_link_href("amphtml") {
+        return Some(resolve_url(base_url, href));
+    }
+
+    if dom_index.is_amp {
+        return Some(base_url.to_string());
+    }
+
+    None
+}
+
+/// Every `<link>` element grouped by rel token (`stylesheet`, `canonical`, `alternate`,
+/// `icon`, ...), hrefs resolved against `base_url`, in document order. The text of each
+/// entry is the element's `title`.
+pub fn extract_alternate_links(dom_index: &DomIndex, base_url: &str) -> HashMap<String, Vec<LinkInfo>> {
+    dom_index.link_tags_by_rel.iter()
+        .map(|(rel, links)| {
+            let resolved = links.iter()
+                .filter(|link| !link.href.is_empty())
+                .map(|link| LinkInfo { url: resolve_url(base_url, &link.href), text: link.title.clone(), context: None })
+                .collect::<Vec<_>>();
+            (rel.clone(), resolved)
+        })
+        .filter(|(_, links)| !links.is_empty())
+        .collect()
+}
+
+/// The page's `<meta http-equiv="refresh">` redirect, with its target resolved against
+/// `base_url`. A refresh without a target only reloads the page and isn't reported.
+pub fn extract_meta_refresh(dom_index: &DomIndex, base_url: &str) -> Option<MetaRefresh> {
+    let captures = META_REFRESH_RE.captures(dom_index.meta_refresh.as_deref()?)?;
+    let delay = captures[1].parse().unwrap_or(u32::MAX);
+    let target = captures.get(2)?.as_str().trim();
+    let target = target
+        .strip_prefix(['\'', '"'])
+        .map(|unquoted| unquoted.trim_end_matches(['\'', '"']))
+        .unwrap_or(target)
+        .trim();
+    if target.is_empty() {
+        return None;
+    }
+    Some(MetaRefresh { url: resolve_url(base_url, target), delay })
+}
+
+/// The page's oEmbed endpoint, resolved against `base_url`: the JSON variant when the page
+/// declares one, else the XML variant
+pub fn extract_oembed_url(dom_index: &DomIndex, base_url: &str) -> Option<String> {
+    let links = dom_index.get_link_tags("alternate");
+    OEMBED_TYPES.iter()
+        .find_map(|oembed_type| links.iter().find(|link| link.link_type == *oembed_type && !link.href.is_empty()))
+        .map(|link| resolve_url(base_url, &link.href))
+}
+
+/// Parse a JSON oEmbed document; `None` when it isn't a JSON object (an XML endpoint, an
+/// error page)
+pub fn parse_oembed(body: &str) -> Option<OEmbed> {
+    let value: serde_json::Value = serde_json::from_str(body).ok()?;
+    let obj = value.as_object()?;
+    let field = |name: &str| {
+        obj.get(name)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    Some(OEmbed {
+        title: field("title"),
+        author_name: field("author_name"),
+        author_url: field("author_url"),
+        provider_name: field("provider_name"),
+        thumbnail_url: field("thumbnail_url"),
+        html: field("html"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Activities;
+    use scraper::Html;
+
+    fn meta_refresh(content: &str) -> Option<(String, u32)> {
+        let html = format!(r#"<html><head><meta http-equiv="Refresh" content="{}"></head></html>"#, content);
+        let document = Html::parse_document(&html);
+        let dom_index = DomIndex::build_for(&document, &Activities::default());
+        extract_meta_refresh(&dom_index, "https://example.com/dir/page").map(|refresh| (refresh.url, refresh.delay))
+    }
+
+    #[test]
+    fn extract_meta_refresh_table() {
+        let cases: &[(&str, Option<(&str, u32)>)] = &[
+            ("0; url=https://example.com/new", Some(("https://example.com/new", 0))),
+            ("0;URL=/new", Some(("https://example.com/new", 0))),
+            ("3; url='next'", Some(("https://example.com/dir/next", 3))),
+            ("0; url=&quot;/quoted&quot;", Some(("https://example.com/quoted", 0))),
+            ("5,https://other.example.org/", Some(("https://other.example.org/", 5))),
+            ("1.5; url=/fraction", Some(("https://example.com/fraction", 1))),
+            ("  0 ;  url = /spaced ", Some(("https://example.com/spaced", 0))),
+            ("300", None),
+            ("0; url=", None),
+            ("soon; url=/new", None),
+        ];
+        for (content, expected) in cases {
+            let expected = expected.map(|(url, delay)| (url.to_string(), delay));
+            assert_eq!(meta_refresh(content), expected, "content {:?}", content);
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use once_cell::sync::Lazy;
+use scraper::{ElementRef, Html, Selector};
+
+static ITEMPROP_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("[itemprop]").unwrap());
+static ITEMSCOPE_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("[itemscope]").unwrap());
+static TYPED_ITEM_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("[itemscope][itemtype]").unwrap());
+
+/// Class/id fragments of containers holding secondary items (related products, carousels,
+/// recommendation widgets); an item inside one is never the page's main entity
+const SECONDARY_CONTAINER_HINTS: &[&str] = &[
+    "related", "similar", "recommend", "carousel", "slider", "upsell", "cross-sell",
+    "crosssell", "also-bought", "also-viewed", "alsobought", "alsoviewed", "recently-viewed",
+    "product-list", "product-grid", "products-list", "products-grid",
+];
+
+/// Value of a microdata property element: `content`, `datetime`, `href`/`src`, then its
+/// whitespace-collapsed text
+pub fn property_value(element: ElementRef) -> Option<String> {
+    let value = element.value();
+    let attr = value.attr("content")
+        .or_else(|| value.attr("datetime"))
+        .or_else(|| value.attr("href"))
+        .or_else(|| value.attr("src"))
+        .map(|v| v.trim().to_string());
+    let value = attr.unwrap_or_else(|| element.text().collect::<Vec<_>>().join(" ").split_whitespace().collect::<Vec<_>>().join(" "));
+    if value.is_empty() { None } else { Some(value) }
+}
+
+/// The nearest `itemscope` ancestor of an element
+fn owning_scope(element: ElementRef) -> Option<ElementRef> {
+    element.ancestors()
+        .filter_map(ElementRef::wrap)
+        .find(|ancestor| ancestor.value().attr("itemscope").is_some())
+}
+
+/// Elements carrying `itemprop` that belong to `scope` itself, not to an item nested in it
+pub fn own_properties<'a>(scope: ElementRef<'a>, itemprop: &str) -> Vec<ElementRef<'a>> {
+    scope.select(&ITEMPROP_SELECTOR)
+        .filter(|element| element.value().attr("itemprop").is_some_and(|props| props.split_whitespace().any(|p| p == itemprop)))
+        .filter(|element| owning_scope(*element).is_some_and(|owner| owner.id() == scope.id()))
+        .collect()
+}
+
+/// Whether an `itemscope` element's `itemtype` (one or more URLs) names one of `types`
+pub fn has_item_type(element: ElementRef, types: &[&str]) -> bool {
+    element.value().attr("itemtype").is_some_and(|itemtype| {
+        itemtype.split_whitespace().any(|url| {
+            let name = url.trim_end_matches('/').rsplit('/').next().unwrap_or(url);
+            types.iter().any(|t| t.eq_ignore_ascii_case(name))
+        })
+    })
+}
+
+/// Whether an element sits in a related-items list, carousel or recommendation widget
+fn in_secondary_container(element: ElementRef) -> bool {
+    element.ancestors().filter_map(ElementRef::wrap).any(|ancestor| {
+        let value = ancestor.value();
+        let hints = format!("{} {}", value.attr("class").unwrap_or_default(), value.attr("id").unwrap_or_default())
+            .to_lowercase();
+        matches!(value.name(), "aside")
+            || SECONDARY_CONTAINER_HINTS.iter().any(|hint| hints.contains(hint))
+    })
+}
+
+/// The page's main item of one of `types`: the first top-level item (not a property of
+/// another item) outside related-items lists and carousels, else the first top-level
+/// item, else the first item of those types
+pub fn find_main_item<'a>(document: &'a Html, types: &[&str]) -> Option<ElementRef<'a>> {
+    let items: Vec<ElementRef> = document.select(&TYPED_ITEM_SELECTOR).filter(|item| has_item_type(*item, types)).collect();
+    let top_level = |item: &&ElementRef| item.value().attr("itemprop").is_none();
+
+    items.iter().filter(top_level).find(|item| !in_secondary_container(**item))
+        .or_else(|| items.iter().find(top_level))
+        .or_else(|| items.first())
+        .copied()
+}
+
+/// Value of `itemprop` for `item`: its own property first, then the property of a nested
+/// item such as `offers` or `aggregateRating`. Nested items of `types` themselves (related
+/// products, similar articles) are skipped. A property that is itself an item (a `brand`
+/// Organization, an `author` Person) yields that item's `name`.
+pub fn item_property(item: ElementRef, itemprop: &str, types: &[&str]) -> Option<String> {
+    if let Some(value) = own_properties(item, itemprop).into_iter().find_map(item_or_property_value) {
+        return Some(value);
+    }
+
+    item.select(&ITEMSCOPE_SELECTOR)
+        .filter(|nested| owning_scope(*nested).is_some_and(|owner| owner.id() == item.id()))
+        .filter(|nested| !has_item_type(*nested, types))
+        .find_map(|nested| item_property(nested, itemprop, types))
+}
+
+/// Every own value of `itemprop` for `item`, in document order
+pub fn item_property_values(item: ElementRef, itemprop: &str) -> Vec<String> {
+    own_properties(item, itemprop).into_iter().filter_map(item_or_property_value).collect()
+}
+
+/// Name of a nested item, or the element's value when it isn't an item
+fn item_or_property_value(element: ElementRef) -> Option<String> {
+    if element.value().attr("itemscope").is_some() && element.value().attr("content").is_none() {
+        own_properties(element, "name").into_iter().find_map(property_value)
+    } else {
+        property_value(element)
+    }
+}
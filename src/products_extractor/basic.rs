@@ -1,163 +1,259 @@
-use scraper::{Html, Selector};
-use super::helpers::{extract_meta_property, extract_meta_name, extract_json_ld_property, extract_schema_property};
+use scraper::Selector;
+use once_cell::sync::Lazy;
+use crate::article_extractor::breadcrumb_sections;
+use crate::dom_index::DomIndex;
+use crate::link_extractor::resolve_url;
+use super::helpers::{entity_name, extract_meta_property, extract_meta_name, extract_json_ld_property, extract_json_ld_property_typed, extract_json_ld_value, extract_microdata_property, extract_schema_property, PRODUCT_ITEM_TYPES};
 
-pub fn extract_product_title(document: &Html) -> Option<String> {
+static H1_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("h1").unwrap());
+
+pub fn extract_product_title(dom_index: &DomIndex) -> Option<String> {
+    extract_product_title_with_source(dom_index).map(|(title, _)| title)
+}
+
+/// The title and where it came from: `"meta"` (`product:title`, `og:title`), `"jsonld"`,
+/// `"microdata"` or `"h1"`
+pub fn extract_product_title_with_source(dom_index: &DomIndex) -> Option<(String, &'static str)> {
     // Try product:title meta property
-    if let Some(title) = extract_meta_property(document, "product:title") {
-        return Some(title);
+    if let Some(title) = extract_meta_property(dom_index, "product:title") {
+        return Some((title, "meta"));
     }
 
     // Try og:title (often used for products)
-    if let Some(title) = extract_meta_property(document, "og:title") {
-        return Some(title);
+    if let Some(title) = extract_meta_property(dom_index, "og:title") {
+        return Some((title, "meta"));
     }
 
     // Try JSON-LD Product schema
-    if let Some(title) = extract_json_ld_property(document, &["name", "title"]) {
-        return Some(title);
+    if let Some(title) = extract_json_ld_property(dom_index, &["name", "title"]) {
+        return Some((title, "jsonld"));
     }
 
     // Try schema.org Product
-    if let Some(title) = extract_schema_property(document, "name") {
-        return Some(title);
+    if let Some(title) = extract_schema_property(dom_index, "name") {
+        return Some((title, "microdata"));
     }
 
     // Try h1 as fallback
-    if let Ok(selector) = Selector::parse("h1") {
-        if let Some(h1) = document.select(&selector).next() {
-            let text = h1.text().collect::<String>().trim().to_string();
-            if !text.is_empty() {
-                return Some(text);
-            }
+    if dom_index.structured_only {
+        return None;
+    }
+    if let Some(h1) = dom_index.document().select(&H1_SELECTOR).next() {
+        let text = h1.text().collect::<String>().trim().to_string();
+        if !text.is_empty() {
+            return Some((text, "h1"));
         }
     }
 
     None
 }
 
-pub fn extract_product_description(document: &Html) -> Option<String> {
+/// The description and where it came from: `"meta"` (`product:description`, `og:description`,
+/// then the standard meta description), `"jsonld"` or `"microdata"`
+pub fn extract_product_description_with_source(dom_index: &DomIndex) -> Option<(String, &'static str)> {
     // Try product:description meta property
-    if let Some(desc) = extract_meta_property(document, "product:description") {
-        return Some(desc);
+    if let Some(desc) = extract_meta_property(dom_index, "product:description") {
+        return Some((desc, "meta"));
     }
 
     // Try og:description
-    if let Some(desc) = extract_meta_property(document, "og:description") {
-        return Some(desc);
+    if let Some(desc) = extract_meta_property(dom_index, "og:description") {
+        return Some((desc, "meta"));
     }
 
     // Try JSON-LD Product schema
-    if let Some(desc) = extract_json_ld_property(document, &["description"]) {
-        return Some(desc);
+    if let Some(desc) = extract_json_ld_property(dom_index, &["description"]) {
+        return Some((desc, "jsonld"));
     }
 
     // Try schema.org Product
-    if let Some(desc) = extract_schema_property(document, "description") {
-        return Some(desc);
+    if let Some(desc) = extract_schema_property(dom_index, "description") {
+        return Some((desc, "microdata"));
     }
 
     // Try standard meta description
-    if let Some(desc) = extract_meta_name(document, "description") {
-        return Some(desc);
+    if let Some(desc) = extract_meta_name(dom_index, "description") {
+        return Some((desc, "meta"));
     }
 
     None
 }
 
-pub fn extract_product_brand(document: &Html) -> Option<String> {
-    // Try product:brand meta property
-    if let Some(brand) = extract_meta_property(document, "product:brand") {
-        return Some(brand);
+/// Placeholder values broken CMS templates emit instead of a brand
+const JUNK_BRANDS: &[&str] = &["[object object]", "undefined", "null", "none", "n/a", "brand"];
+
+/// Trim and collapse whitespace, rejecting empty and placeholder values
+fn clean_brand(value: &str) -> Option<String> {
+    let brand = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    if brand.is_empty() || JUNK_BRANDS.contains(&brand.to_lowercase().as_str()) {
+        None
+    } else {
+        Some(brand)
+    }
+}
+
+/// The brand and where it came from: `"meta"` (`product:brand` or `og:brand`), `"jsonld"`
+/// (`brand` as a string, a `Brand`/`Organization` object or an array of them), `"microdata"`, or
+/// `"manufacturer"` (JSON-LD or microdata `manufacturer` when no brand is declared)
+pub fn extract_product_brand_with_source(dom_index: &DomIndex) -> Option<(String, &'static str)> {
+    // Try product:brand meta property, then its og:-prefixed variant
+    let meta_brand = extract_meta_property(dom_index, "product:brand")
+        .or_else(|| extract_meta_property(dom_index, "og:brand"));
+    if let Some(brand) = meta_brand.as_deref().and_then(clean_brand) {
+        return Some((brand, "meta"));
     }
 
-    // Try JSON-LD Product schema
-    if let Some(brand) = extract_json_ld_property(document, &["brand", "brand.name", "manufacturer.name"]) {
-        return Some(brand);
+    // Try JSON-LD Product schema: the Product's own brand, then any object's
+    let json_ld_brand = extract_json_ld_value(dom_index, "brand")
+        .as_ref()
+        .and_then(entity_name)
+        .or_else(|| extract_json_ld_property(dom_index, &["brand"]));
+    if let Some(brand) = json_ld_brand.as_deref().and_then(clean_brand) {
+        return Some((brand, "jsonld"));
     }
 
-    // Try schema.org Product
-    if let Some(brand) = extract_schema_property(document, "brand") {
-        return Some(brand);
+    // Try schema.org Product microdata (a nested Brand item yields its name)
+    if let Some(brand) = extract_microdata_property(dom_index, "brand").as_deref().and_then(clean_brand) {
+        return Some((brand, "microdata"));
     }
 
-    None
+    // Fall back to the manufacturer
+    extract_json_ld_value(dom_index, "manufacturer")
+        .as_ref()
+        .and_then(entity_name)
+        .or_else(|| extract_microdata_property(dom_index, "manufacturer"))
+        .as_deref()
+        .and_then(clean_brand)
+        .map(|brand| (brand, "manufacturer"))
 }
 
-pub fn extract_product_category(document: &Html) -> Option<String> {
+/// The category and where it came from: `"meta"` (`product:category`), `"jsonld"`
+/// (`category`/`productCategory` as a string, a named object or an array of either),
+/// `"microdata"`, or `"breadcrumb"` when it's inferred from the JSON-LD `BreadcrumbList`
+/// trail, joined with " > " and without the home crumb or a final crumb for the product itself
+pub fn extract_product_category_with_source(dom_index: &DomIndex, base_url: &str) -> Option<(String, &'static str)> {
+    let clean = |value: String| {
+        let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+        if value.is_empty() { None } else { Some(value) }
+    };
+
     // Try product:category meta property
-    if let Some(category) = extract_meta_property(document, "product:category") {
-        return Some(category);
+    if let Some(category) = extract_meta_property(dom_index, "product:category").and_then(clean) {
+        return Some((category, "meta"));
     }
 
-    // Try JSON-LD Product schema
-    if let Some(category) = extract_json_ld_property(document, &["category", "productCategory"]) {
-        return Some(category);
+    // Try JSON-LD Product schema, then any object declaring a category
+    let json_ld_category = ["category", "productCategory"].iter()
+        .find_map(|property| extract_json_ld_value(dom_index, property).as_ref().and_then(entity_name))
+        .or_else(|| extract_json_ld_property(dom_index, &["category", "productCategory"]));
+    if let Some(category) = json_ld_category.and_then(clean) {
+        return Some((category, "jsonld"));
     }
 
-    // Try schema.org Product
-    if let Some(category) = extract_schema_property(document, "category") {
-        return Some(category);
+    // Try schema.org Product microdata
+    if let Some(category) = extract_microdata_property(dom_index, "category").and_then(clean) {
+        return Some((category, "microdata"));
     }
 
-    None
+    // Infer from the breadcrumb trail
+    let title = extract_product_title(dom_index);
+    let trail = breadcrumb_sections(dom_index, base_url, title.as_deref());
+    if trail.is_empty() {
+        None
+    } else {
+        Some((trail.join(" > "), "breadcrumb"))
+    }
 }
 
-pub fn extract_product_sku(document: &Html) -> Option<String> {
+/// The SKU and where it came from: `"meta"` (`product:sku`), `"jsonld"` or `"microdata"`
+pub fn extract_product_sku_with_source(dom_index: &DomIndex) -> Option<(String, &'static str)> {
     // Try product:sku meta property
-    if let Some(sku) = extract_meta_property(document, "product:sku") {
-        return Some(sku);
+    if let Some(sku) = extract_meta_property(dom_index, "product:sku") {
+        return Some((sku, "meta"));
     }
 
     // Try JSON-LD Product schema
-    if let Some(sku) = extract_json_ld_property(document, &["sku", "productID"]) {
-        return Some(sku);
+    if let Some(sku) = extract_json_ld_property(dom_index, &["sku", "productID"]) {
+        return Some((sku, "jsonld"));
     }
 
     // Try schema.org Product
-    if let Some(sku) = extract_schema_property(document, "sku") {
-        return Some(sku);
+    if let Some(sku) = extract_schema_property(dom_index, "sku") {
+        return Some((sku, "microdata"));
     }
 
     None
 }
 
-pub fn extract_product_mpn(document: &Html) -> Option<String> {
+/// The product's own URL, resolved against the page URL: JSON-LD `url` on the Product
+/// (not an Offer's), then `og:url`, then `<link rel="canonical">`
+pub fn extract_product_url(dom_index: &DomIndex, base_url: &str) -> Option<String> {
+    extract_json_ld_property_typed(dom_index, PRODUCT_ITEM_TYPES, &["url"])
+        .or_else(|| extract_meta_property(dom_index, "og:url"))
+        .or_else(|| dom_index.get_first_link_href("canonical").cloned())
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+        .map(|url| resolve_url(base_url, &url))
+}
+
+/// The product's declared identifier: JSON-LD `productID`, then the Product's `@id` (resolved
+/// against the page URL, so `#product` becomes `https://.../page#product`), then microdata
+/// `productID`
+pub fn extract_product_id(dom_index: &DomIndex, base_url: &str) -> Option<String> {
+    let non_empty = |id: String| {
+        let id = id.trim().to_string();
+        if id.is_empty() { None } else { Some(id) }
+    };
+
+    if let Some(id) = extract_json_ld_property_typed(dom_index, PRODUCT_ITEM_TYPES, &["productID"]).and_then(non_empty) {
+        return Some(id);
+    }
+
+    if let Some(id) = extract_json_ld_property_typed(dom_index, PRODUCT_ITEM_TYPES, &["@id"]).and_then(non_empty) {
+        return Some(resolve_url(base_url, &id));
+    }
+
+    extract_microdata_property(dom_index, "productID").and_then(non_empty)
+}
+
+/// The MPN and where it came from: `"meta"` (`product:mpn`), `"jsonld"` or `"microdata"`
+pub fn extract_product_mpn_with_source(dom_index: &DomIndex) -> Option<(String, &'static str)> {
     // Try product:mpn meta property
-    if let Some(mpn) = extract_meta_property(document, "product:mpn") {
-        return Some(mpn);
+    if let Some(mpn) = extract_meta_property(dom_index, "product:mpn") {
+        return Some((mpn, "meta"));
     }
 
     // Try JSON-LD Product schema
-    if let Some(mpn) = extract_json_ld_property(document, &["mpn"]) {
-        return Some(mpn);
+    if let Some(mpn) = extract_json_ld_property(dom_index, &["mpn"]) {
+        return Some((mpn, "jsonld"));
     }
 
     // Try schema.org Product
-    if let Some(mpn) = extract_schema_property(document, "mpn") {
-        return Some(mpn);
+    if let Some(mpn) = extract_schema_property(dom_index, "mpn") {
+        return Some((mpn, "microdata"));
     }
 
     None
 }
 
-pub fn extract_product_image(document: &Html) -> Option<String> {
+pub fn extract_product_image(dom_index: &DomIndex) -> Option<String> {
     // Try product:image meta property
-    if let Some(image) = extract_meta_property(document, "product:image") {
+    if let Some(image) = extract_meta_property(dom_index, "product:image") {
         return Some(image);
     }
 
     // Try og:image
-    if let Some(image) = extract_meta_property(document, "og:image") {
+    if let Some(image) = extract_meta_property(dom_index, "og:image") {
         return Some(image);
     }
 
     // Try JSON-LD Product schema
-    if let Some(image) = extract_json_ld_property(document, &["image", "image.url"]) {
+    if let Some(image) = extract_json_ld_property(dom_index, &["image", "image.url"]) {
         return Some(image);
     }
 
     // Try schema.org Product
-    if let Some(image) = extract_schema_property(document, "image") {
+    if let Some(image) = extract_schema_property(dom_index, "image") {
         return Some(image);
     }
 
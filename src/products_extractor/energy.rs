@@ -0,0 +1,96 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+use crate::dom_index::DomIndex;
+use super::helpers::{extract_json_ld_value, json_scalar_string};
+use super::shipping::offer_objects;
+
+/// A free-text energy class, optionally prefixed by a label: "A++", "Class B",
+/// "Energieeffizienzklasse: C"
+static ENERGY_CLASS_TEXT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(?:^|[\s:])([A-G])(\+{0,3})\s*$").unwrap()
+});
+
+/// The EU energy efficiency class from schema.org `hasEnergyConsumptionDetails`, declared on
+/// the product or on one of its offers. With several entries, the first one naming a class
+/// is used.
+pub fn extract_energy_class(dom_index: &DomIndex) -> Option<String> {
+    let from_offers = || {
+        let offers = extract_json_ld_value(dom_index, "offers")?;
+        offer_objects(&offers).into_iter().find_map(|offer| offer.get("hasEnergyConsumptionDetails").and_then(energy_details_class))
+    };
+    extract_json_ld_value(dom_index, "hasEnergyConsumptionDetails")
+        .and_then(|details| energy_details_class(&details))
+        .or_else(from_offers)
+}
+
+/// Class of an `EnergyConsumptionDetails` object (or array of them): its
+/// `hasEnergyEfficiencyCategory` (`energyEfficiencyClass` on some shops)
+fn energy_details_class(value: &Value) -> Option<String> {
+    match value {
+        Value::Array(arr) => arr.iter().find_map(energy_details_class),
+        Value::Object(obj) => ["hasEnergyEfficiencyCategory", "energyEfficiencyClass"]
+            .iter()
+            .filter_map(|property| obj.get(*property))
+            .find_map(category_class),
+        _ => None,
+    }
+}
+
+/// A category value: an enumeration URL or name, an object carrying one in `@id` or `name`,
+/// or a free-text class
+fn category_class(value: &Value) -> Option<String> {
+    match value {
+        Value::Array(arr) => arr.iter().find_map(category_class),
+        Value::Object(obj) => ["@id", "name"]
+            .iter()
+            .filter_map(|key| obj.get(*key).and_then(json_scalar_string))
+            .find_map(|text| normalize_energy_class(&text)),
+        _ => json_scalar_string(value).and_then(|text| normalize_energy_class(&text)),
+    }
+}
+
+/// Normalize an energy class to its label ("A"–"G", "A+" to "A+++" on the pre-2021
+/// scale): `EUEnergyEfficiencyEnumeration` members with or without the
+/// `https://schema.org/` prefix (`EUEnergyEfficiencyCategoryB`,
+/// `schema:EUEnergyEfficiencyCategoryA2Plus`) and free text ("A++", "Class C")
+pub fn normalize_energy_class(value: &str) -> Option<String> {
+    let name = value.trim().rsplit(['/', ':']).next().unwrap_or_default();
+    if let Some(category) = name.strip_prefix("EUEnergyEfficiencyCategory") {
+        return match category {
+            "A1Plus" => Some("A+".to_string()),
+            "A2Plus" => Some("A++".to_string()),
+            "A3Plus" => Some("A+++".to_string()),
+            "A" | "B" | "C" | "D" | "E" | "F" | "G" => Some(category.to_string()),
+            _ => None,
+        };
+    }
+
+    let captures = ENERGY_CLASS_TEXT_RE.captures(value.trim())?;
+    Some(format!("{}{}", captures[1].to_uppercase(), &captures[2]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_energy_class_table() {
+        let cases: &[(&str, Option<&str>)] = &[
+            ("https://schema.org/EUEnergyEfficiencyCategoryA", Some("A")),
+            ("http://schema.org/EUEnergyEfficiencyCategoryG", Some("G")),
+            ("schema:EUEnergyEfficiencyCategoryA1Plus", Some("A+")),
+            ("EUEnergyEfficiencyCategoryA2Plus", Some("A++")),
+            ("EUEnergyEfficiencyCategoryA3Plus", Some("A+++")),
+            ("EUEnergyEfficiencyCategoryH", None),
+            ("A++", Some("A++")),
+            ("Class b", Some("B")),
+            ("Energieeffizienzklasse: C", Some("C")),
+            ("Grade", None),
+            ("H", None),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(normalize_energy_class(value).as_deref(), *expected, "value {:?}", value);
+        }
+    }
+}
@@ -1,27 +1,43 @@
 use scraper::{Html, Selector};
+use once_cell::sync::Lazy;
 use regex::Regex;
-use serde_json;
+use crate::dom_index::DomIndex;
+use crate::json_ld::has_json_ld_type;
+use crate::microdata::{find_main_item, item_property, item_property_values, property_value};
+
+// Selectors used on every field lookup are compiled once; attribute values are compared in
+// code instead of being formatted into a selector per call. Meta tags and JSON-LD come from
+// the DomIndex, which parses each JSON-LD block once per page.
+static HTML_LANG_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("html[lang]").unwrap());
+static ITEMPROP_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("[itemprop]").unwrap());
+
+/// Compile a list of selectors (for `Lazy` statics); every entry must be valid
+pub fn parse_selectors(selectors: &[&str]) -> Vec<Selector> {
+    selectors.iter().map(|s| Selector::parse(s).unwrap()).collect()
+}
 
 /// Extract a property value from a meta tag with property attribute
-pub fn extract_meta_property(document: &Html, property: &str) -> Option<String> {
-    let selector = format!("meta[property='{}']", property);
-    if let Ok(sel) = Selector::parse(&selector) {
-        if let Some(meta) = document.select(&sel).next() {
-            return meta.value().attr("content").map(|s| s.to_string());
-        }
-    }
-    None
+pub fn extract_meta_property(dom_index: &DomIndex, property: &str) -> Option<String> {
+    dom_index.get_meta_by_property(property).cloned()
 }
 
 /// Extract a property value from a meta tag with name attribute
-pub fn extract_meta_name(document: &Html, name: &str) -> Option<String> {
-    let selector = format!("meta[name='{}']", name);
-    if let Ok(sel) = Selector::parse(&selector) {
-        if let Some(meta) = document.select(&sel).next() {
-            return meta.value().attr("content").map(|s| s.to_string());
-        }
+pub fn extract_meta_name(dom_index: &DomIndex, name: &str) -> Option<String> {
+    dom_index.get_meta_by_name(name).cloned()
+}
+
+/// Locale of the page: `og:locale` ("en_GB"), else the `<html lang>` attribute ("en-GB")
+pub fn extract_page_locale(dom_index: &DomIndex) -> Option<String> {
+    if let Some(locale) = extract_meta_property(dom_index, "og:locale").filter(|l| !l.trim().is_empty()) {
+        return Some(locale.trim().to_string());
     }
-    None
+    dom_index
+        .document()
+        .select(&HTML_LANG_SELECTOR)
+        .next()
+        .and_then(|html| html.value().attr("lang"))
+        .map(|lang| lang.trim().to_string())
+        .filter(|lang| !lang.is_empty())
 }
 
 /// Recursively extract a value from a JSON object, handling nested paths like "publisher.name"
@@ -41,72 +57,161 @@ pub fn extract_value_from_object(obj: &serde_json::Map<String, serde_json::Value
         }
     }
     
-    // Extract string value, handling arrays
-    match current {
+    json_value_string(current)
+}
+
+/// String form of a JSON-LD value: strings as-is, the first usable member of an array, and
+/// for objects their `name` (string or array), then `@id`, then `url`
+fn json_value_string(value: &serde_json::Value) -> Option<String> {
+    match value {
         serde_json::Value::String(s) => Some(s.clone()),
         serde_json::Value::Array(arr) => {
-            // Return first string value from array
-            for item in arr {
-                if let Some(s) = item.as_str() {
-                    return Some(s.to_string());
-                }
-            }
-            None
+            // Prefer a plain string member, then the first object that names something
+            arr.iter()
+                .find_map(|item| item.as_str().map(|s| s.to_string()))
+                .or_else(|| arr.iter().filter(|item| item.is_object()).find_map(json_value_string))
         }
         serde_json::Value::Object(nested_obj) => {
-            // For objects, try to get "name" or "@id" or "url"
-            if let Some(name) = nested_obj.get("name").and_then(|v| v.as_str()) {
-                return Some(name.to_string());
-            }
-            if let Some(id) = nested_obj.get("@id").and_then(|v| v.as_str()) {
-                return Some(id.to_string());
-            }
-            if let Some(url) = nested_obj.get("url").and_then(|v| v.as_str()) {
-                return Some(url.to_string());
+            // A name beats the entity's @id even when the @id comes first
+            ["name", "@id", "url"].iter().find_map(|key| {
+                match nested_obj.get(*key)? {
+                    serde_json::Value::String(s) => Some(s.clone()),
+                    serde_json::Value::Array(arr) => arr.iter().find_map(|v| v.as_str().map(|s| s.to_string())),
+                    _ => None,
+                }
+            })
+        }
+        _ => None,
+    }
+}
+
+/// JSON-LD types that describe the product itself; their properties win over other entities
+/// on the page (Organization, WebSite, BreadcrumbList, ...)
+const PRODUCT_TYPES: &[&str] = &[
+    "Product",
+    "ProductGroup",
+    "ProductModel",
+    "IndividualProduct",
+    "SomeProducts",
+    "Offer",
+    "AggregateOffer",
+];
+
+/// Extract a JSON-LD property only from objects whose `@type` is one of `types`
+pub fn extract_json_ld_property_typed(dom_index: &DomIndex, types: &[&str], properties: &[&str]) -> Option<String> {
+    for obj in dom_index.get_json_ld_entities().filter(|obj| has_json_ld_type(obj, types)) {
+        for property in properties {
+            if let Some(value) = extract_value_from_object(obj, property) {
+                return Some(value);
             }
-            None
         }
+    }
+    None
+}
+
+/// Raw value (object, array or scalar) of `property` on the first Product/Offer-typed JSON-LD
+/// object that declares it, for callers that need the full structure rather than a first string
+pub fn extract_json_ld_value(dom_index: &DomIndex, property: &str) -> Option<serde_json::Value> {
+    dom_index
+        .get_json_ld_entities()
+        .filter(|obj| has_json_ld_type(obj, PRODUCT_TYPES))
+        .find_map(|obj| obj.get(property))
+        .cloned()
+}
+
+/// String form of a JSON scalar: strings as-is (trimmed), numbers formatted
+pub fn json_scalar_string(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Numeric value of a JSON number or numeric string
+pub fn json_number(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Day multiplier for a UN/CEFACT or plain unit code ("DAY"/"d", "WEE"/"wk", "HUR"/"h");
+/// days when the code is missing
+fn unit_days(unit: Option<&str>) -> Option<f64> {
+    match unit.map(|u| u.trim().to_lowercase()).as_deref() {
+        None | Some("") | Some("day") | Some("d") | Some("days") => Some(1.0),
+        Some("wee") | Some("wk") | Some("week") | Some("weeks") => Some(7.0),
+        Some("hur") | Some("h") | Some("hour") | Some("hours") => Some(1.0 / 24.0),
         _ => None,
     }
 }
 
-/// Extract a property value from JSON-LD, handling nested objects and arrays
-pub fn extract_json_ld_property(document: &Html, properties: &[&str]) -> Option<String> {
-    if let Ok(selector) = Selector::parse("script[type='application/ld+json']") {
-        for script in document.select(&selector) {
-            if let Some(text) = script.text().next() {
-                // Try to parse as JSON
-                if let Ok(json_value) = serde_json::from_str::<serde_json::Value>(text) {
-                    // Handle both single objects and arrays of objects
-                    let objects = match json_value {
-                        serde_json::Value::Object(obj) => vec![obj],
-                        serde_json::Value::Array(arr) => {
-                            arr.into_iter()
-                                .filter_map(|v| v.as_object().cloned())
-                                .collect()
-                        }
-                        _ => vec![],
-                    };
-                    
-                    for obj in objects {
-                        for property in properties {
-                            if let Some(value) = extract_value_from_object(&obj, property) {
-                                return Some(value);
-                            }
-                        }
+/// (min, max) of a schema.org `QuantitativeValue` in days: `minValue`/`maxValue` (one side
+/// standing in for the other when missing) or a single `value`, converted by `unitCode`
+pub fn quantitative_days(value: &serde_json::Value) -> Option<(f64, f64)> {
+    let obj = value.as_object()?;
+    let days = unit_days(obj.get("unitCode").and_then(|u| u.as_str()))?;
+    let exact = obj.get("value").and_then(json_number);
+    let min = obj.get("minValue").and_then(json_number).or(exact);
+    let max = obj.get("maxValue").and_then(json_number).or(exact);
+    let (min, max) = match (min, max) {
+        (Some(min), Some(max)) => (min, max),
+        (Some(v), None) | (None, Some(v)) => (v, v),
+        (None, None) => return None,
+    };
+    Some((min * days, max * days))
+}
+
+/// Name of an Organization/Person value, or the value itself when it's a plain string
+pub fn entity_name(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Object(obj) => obj.get("name").and_then(json_scalar_string),
+        serde_json::Value::Array(arr) => arr.iter().find_map(entity_name),
+        _ => json_scalar_string(value),
+    }
+}
+
+/// Whether a JSON-LD object's `@type` (string or array) is `schema_type`
+pub fn is_json_ld_type(obj: &serde_json::Map<String, serde_json::Value>, schema_type: &str) -> bool {
+    has_json_ld_type(obj, &[schema_type])
+}
+
+/// Extract a property value from JSON-LD, handling nested objects and arrays.
+/// Product/Offer-typed objects are searched first, then every object.
+pub fn extract_json_ld_property(dom_index: &DomIndex, properties: &[&str]) -> Option<String> {
+    if let Some(value) = extract_json_ld_property_typed(dom_index, PRODUCT_TYPES, properties) {
+        return Some(value);
+    }
+
+    for (text, parsed) in dom_index.get_json_ld_blocks() {
+        if let Some(json_value) = parsed {
+            // Handle both single objects and arrays of objects
+            let objects: Vec<&serde_json::Map<String, serde_json::Value>> = match json_value {
+                serde_json::Value::Object(obj) => vec![obj],
+                serde_json::Value::Array(arr) => arr.iter().filter_map(|v| v.as_object()).collect(),
+                _ => vec![],
+            };
+
+            for obj in objects {
+                for property in properties {
+                    if let Some(value) = extract_value_from_object(obj, property) {
+                        return Some(value);
                     }
                 }
-                
-                // Fallback to regex for malformed JSON
-                for property in properties {
-                    let escaped_property = regex::escape(property);
-                    let pattern = format!(r#""{}"\s*:\s*"([^"]+)""#, escaped_property);
-                    if let Ok(re) = Regex::new(&pattern) {
-                        if let Some(captures) = re.captures(text) {
-                            if let Some(value) = captures.get(1) {
-                                return Some(value.as_str().to_string());
-                            }
-                        }
+            }
+            continue;
+        }
+        
+        // Fallback to regex for malformed JSON
+        for property in properties {
+            let escaped_property = regex::escape(property);
+            let pattern = format!(r#""{}"\s*:\s*"([^"]+)""#, escaped_property);
+            if let Ok(re) = Regex::new(&pattern) {
+                if let Some(captures) = re.captures(text) {
+                    if let Some(value) = captures.get(1) {
+                        return Some(value.as_str().to_string());
                     }
                 }
             }
@@ -115,26 +220,45 @@ pub fn extract_json_ld_property(document: &Html, properties: &[&str]) -> Option<
     None
 }
 
+/// Microdata item types of a product; related-product cards use them too, so lookups are
+/// scoped to the main one
+pub const PRODUCT_ITEM_TYPES: &[&str] = &["Product", "ProductGroup", "ProductModel", "IndividualProduct", "SomeProducts"];
+
+/// Extract a microdata property of the main Product item (including its nested `offers`,
+/// `aggregateRating`, ... items, but not related products). Pages without a Product item
+/// fall back to the first matching itemprop anywhere.
+pub fn extract_microdata_property(dom_index: &DomIndex, property: &str) -> Option<String> {
+    if let Some(product) = find_main_item(dom_index.document(), PRODUCT_ITEM_TYPES) {
+        return item_property(product, property, PRODUCT_ITEM_TYPES);
+    }
+
+    itemprops(dom_index.document(), property).next().and_then(property_value)
+}
+
+/// Every value of a microdata property of the main Product item, or of every matching
+/// itemprop when the page has no Product item
+pub fn extract_microdata_values(dom_index: &DomIndex, property: &str) -> Vec<String> {
+    if let Some(product) = find_main_item(dom_index.document(), PRODUCT_ITEM_TYPES) {
+        return item_property_values(product, property);
+    }
+
+    itemprops(dom_index.document(), property).filter_map(property_value).collect()
+}
+
+/// Elements whose `itemprop` attribute is exactly `property`, in document order
+fn itemprops<'a>(document: &'a Html, property: &'a str) -> impl Iterator<Item = scraper::ElementRef<'a>> + 'a {
+    document
+        .select(&ITEMPROP_SELECTOR)
+        .filter(move |element| element.value().attr("itemprop") == Some(property))
+}
+
 /// Extract a property value from schema.org microdata or JSON-LD
-pub fn extract_schema_property(document: &Html, property: &str) -> Option<String> {
+pub fn extract_schema_property(dom_index: &DomIndex, property: &str) -> Option<String> {
     // Try JSON-LD with the property name
-    if let Some(value) = extract_json_ld_property(document, &[property]) {
+    if let Some(value) = extract_json_ld_property(dom_index, &[property]) {
         return Some(value);
     }
-    
-    // Try microdata
-    if let Ok(selector) = Selector::parse(&format!("[itemprop='{}']", property)) {
-        if let Some(element) = document.select(&selector).next() {
-            if let Some(content) = element.value().attr("content") {
-                return Some(content.to_string());
-            }
-            let text = element.text().collect::<String>().trim().to_string();
-            if !text.is_empty() {
-                return Some(text);
-            }
-        }
-    }
-    
-    None
-}
 
+    // Try microdata of the main product
+    extract_microdata_property(dom_index, property)
+}
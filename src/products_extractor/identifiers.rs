@@ -0,0 +1,73 @@
+use crate::dom_index::DomIndex;
+use super::helpers::{extract_json_ld_value, extract_microdata_property, json_scalar_string};
+
+/// GTIN properties in the order `product_gtin` tries them
+const GTIN_PROPERTIES: &[&str] = &["gtin", "gtin13", "gtin12", "gtin14", "gtin8"];
+
+/// Extract a product identifier property (`gtin13`, `isbn`, ...) from JSON-LD, then microdata
+pub fn extract_product_identifier(dom_index: &DomIndex, property: &str) -> Option<String> {
+    if let Some(value) = extract_json_ld_value(dom_index, property).as_ref().and_then(json_scalar_string) {
+        return Some(value);
+    }
+
+    extract_microdata_property(dom_index, property)
+}
+
+/// The product's GTIN from whichever of `gtin`, `gtin13`, `gtin12`, `gtin14`, `gtin8` is declared
+pub fn extract_product_gtin(dom_index: &DomIndex) -> Option<String> {
+    GTIN_PROPERTIES
+        .iter()
+        .find_map(|property| extract_product_identifier(dom_index, property))
+}
+
+/// Whether a GTIN has 8, 12, 13 or 14 digits (spaces and hyphens ignored) and a correct
+/// GS1 check digit
+pub fn is_valid_gtin(value: &str) -> bool {
+    let digits: Vec<u32> = match value
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-'))
+        .map(|c| c.to_digit(10))
+        .collect::<Option<Vec<_>>>()
+    {
+        Some(digits) => digits,
+        None => return false,
+    };
+    if !matches!(digits.len(), 8 | 12 | 13 | 14) {
+        return false;
+    }
+
+    // Weights alternate 3, 1, 3, ... from the digit left of the check digit
+    let (check, body) = digits.split_last().unwrap();
+    let sum: u32 = body
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, d)| if i % 2 == 0 { d * 3 } else { *d })
+        .sum();
+    (10 - sum % 10) % 10 == *check
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gtin_check_digit_table() {
+        let cases: &[(&str, bool)] = &[
+            ("96385074", true),          // GTIN-8
+            ("036000291452", true),      // UPC-A
+            ("4006381333931", true),     // EAN-13
+            ("978-0-306-40615-7", true), // ISBN-13, hyphenated
+            ("10614141000415", true),    // GTIN-14
+            ("4006381333932", false),    // wrong check digit
+            ("036000291453", false),
+            ("400638133393", false),     // 12 digits, check digit off
+            ("123456789", false),        // no GTIN length
+            ("40063813339x1", false),
+            ("", false),
+        ];
+        for (value, valid) in cases {
+            assert_eq!(is_valid_gtin(value), *valid, "gtin {:?}", value);
+        }
+    }
+}
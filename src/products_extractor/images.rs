@@ -0,0 +1,51 @@
+use serde_json::Value;
+use crate::dom_index::DomIndex;
+use crate::link_extractor::resolve_url;
+use super::helpers::{extract_json_ld_value, extract_microdata_values};
+
+/// Every product image as an absolute URL, deduplicated, in declaration order: JSON-LD
+/// `image` (strings and `ImageObject.url`/`contentUrl`, arrays in order), then every
+/// `og:image` meta, then microdata `image` of the main product
+pub fn extract_product_images(dom_index: &DomIndex, base_url: &str) -> Vec<String> {
+    let mut candidates = Vec::new();
+
+    if let Some(value) = extract_json_ld_value(dom_index, "image") {
+        collect_json_ld_images(&value, &mut candidates);
+    }
+
+    if let Some(values) = dom_index.meta_by_property.get("og:image") {
+        candidates.extend(values.iter().cloned());
+    }
+
+    candidates.extend(extract_microdata_values(dom_index, "image"));
+
+    let mut images: Vec<String> = Vec::new();
+    for candidate in candidates {
+        let candidate = candidate.trim();
+        if candidate.is_empty() {
+            continue;
+        }
+        let url = resolve_url(base_url, candidate);
+        if !images.contains(&url) {
+            images.push(url);
+        }
+    }
+    images
+}
+
+pub fn collect_json_ld_images(value: &Value, images: &mut Vec<String>) {
+    match value {
+        Value::String(s) => images.push(s.clone()),
+        Value::Array(arr) => {
+            for item in arr {
+                collect_json_ld_images(item, images);
+            }
+        }
+        Value::Object(obj) => {
+            if let Some(url) = obj.get("url").or_else(|| obj.get("contentUrl")) {
+                collect_json_ld_images(url, images);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+use scraper::{ElementRef, Selector};
+use once_cell::sync::Lazy;
+use serde_json::{Map, Value};
+use crate::dom_index::DomIndex;
+use crate::link_extractor::resolve_url;
+use crate::microdata::{has_item_type, item_property};
+use super::helpers::{entity_name, json_scalar_string, PRODUCT_ITEM_TYPES};
+use crate::json_ld::has_json_ld_type;
+use super::images::collect_json_ld_images;
+
+/// Every product on a listing page (category, search results), in declaration order, up to
+/// `max` entries. JSON-LD is used when it declares any products: `ItemList.itemListElement`
+/// entries (a nested `item`, a bare URL, or the `ListItem`'s own `name`/`url`) and standalone
+/// Product objects across every script. Otherwise each top-level Product microdata card
+/// becomes an entry.
+///
+/// Entries use the single-product field names (`product_title`, `product_price`,
+/// `product_currency`, `product_image`, `product_url`, `product_rating`); URLs are absolute
+/// and a product URL seen twice is kept once.
+pub fn extract_products_list(dom_index: &DomIndex, base_url: &str, max: usize) -> Vec<HashMap<String, String>> {
+    let mut entries = json_ld_products(dom_index, base_url);
+    if entries.is_empty() {
+        entries = microdata_products(dom_index, base_url);
+    }
+
+    let mut seen_urls: Vec<String> = Vec::new();
+    entries.retain(|entry| match entry.get("product_url") {
+        Some(url) if seen_urls.contains(url) => false,
+        Some(url) => {
+            seen_urls.push(url.clone());
+            true
+        }
+        None => true,
+    });
+    entries.truncate(max);
+    entries
+}
+
+fn json_ld_products(dom_index: &DomIndex, base_url: &str) -> Vec<HashMap<String, String>> {
+    let mut entries = Vec::new();
+    for obj in dom_index.get_json_ld_entities() {
+        if has_json_ld_type(obj, &["ItemList", "OfferCatalog"]) {
+            for element in list_elements(obj) {
+                entries.extend(list_entry(element, base_url));
+            }
+        } else if has_json_ld_type(obj, PRODUCT_ITEM_TYPES) {
+            entries.extend(json_ld_entry(obj, base_url));
+        }
+    }
+    entries
+}
+
+/// `itemListElement` values in declaration order (a single element or an array)
+fn list_elements(list: &Map<String, Value>) -> Vec<&Value> {
+    match list.get("itemListElement") {
+        Some(Value::Array(arr)) => arr.iter().collect(),
+        Some(value) => vec![value],
+        None => Vec::new(),
+    }
+}
+
+/// One `itemListElement`: a `ListItem` wrapping its `item`, a product object, or a bare URL
+fn list_entry(element: &Value, base_url: &str) -> Option<HashMap<String, String>> {
+    match element {
+        Value::String(url) => url_only_entry(url, base_url),
+        Value::Object(obj) => match obj.get("item") {
+            Some(Value::Object(item)) => {
+                let mut entry = json_ld_entry(item, base_url)?;
+                // The ListItem often carries the URL while the nested Product doesn't
+                if !entry.contains_key("product_url") {
+                    if let Some(url) = obj.get("url").and_then(json_scalar_string) {
+                        entry.insert("product_url".to_string(), resolve_url(base_url, &url));
+                    }
+                }
+                Some(entry)
+            }
+            Some(Value::String(url)) => {
+                let mut entry = url_only_entry(url, base_url)?;
+                if let Some(name) = obj.get("name").and_then(entity_name) {
+                    entry.insert("product_title".to_string(), name);
+                }
+                Some(entry)
+            }
+            _ => json_ld_entry(obj, base_url),
+        },
+        _ => None,
+    }
+}
+
+fn url_only_entry(url: &str, base_url: &str) -> Option<HashMap<String, String>> {
+    let url = url.trim();
+    if url.is_empty() {
+        return None;
+    }
+    Some(HashMap::from([("product_url".to_string(), resolve_url(base_url, url))]))
+}
+
+/// Listing fields of one JSON-LD product (or `ListItem`); None when it has neither a name
+/// nor a URL
+fn json_ld_entry(obj: &Map<String, Value>, base_url: &str) -> Option<HashMap<String, String>> {
+    let mut entry = HashMap::new();
+
+    if let Some(name) = obj.get("name").and_then(entity_name) {
+        entry.insert("product_title".to_string(), name);
+    }
+    if let Some(url) = obj.get("url").or_else(|| obj.get("@id")).and_then(json_scalar_string) {
+        entry.insert("product_url".to_string(), resolve_url(base_url, &url));
+    }
+    if !entry.contains_key("product_title") && !entry.contains_key("product_url") {
+        return None;
+    }
+
+    let offer = match obj.get("offers") {
+        Some(Value::Array(arr)) => arr.iter().find_map(Value::as_object),
+        Some(Value::Object(offer)) => Some(offer),
+        _ => None,
+    };
+    if let Some(offer) = offer {
+        if let Some(price) = offer.get("price").or_else(|| offer.get("lowPrice")).and_then(json_scalar_string) {
+            entry.insert("product_price".to_string(), price);
+        }
+        if let Some(currency) = offer.get("priceCurrency").and_then(json_scalar_string) {
+            entry.insert("product_currency".to_string(), currency);
+        }
+    }
+
+    if let Some(image) = obj.get("image") {
+        let mut images = Vec::new();
+        collect_json_ld_images(image, &mut images);
+        if let Some(image) = images.iter().map(|i| i.trim()).find(|i| !i.is_empty()) {
+            entry.insert("product_image".to_string(), resolve_url(base_url, image));
+        }
+    }
+
+    if let Some(rating) = obj.get("aggregateRating")
+        .and_then(|rating| rating.get("ratingValue"))
+        .and_then(json_scalar_string)
+    {
+        entry.insert("product_rating".to_string(), rating);
+    }
+
+    Some(entry)
+}
+
+static TYPED_ITEM_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("[itemscope][itemtype]").unwrap());
+
+fn microdata_products(dom_index: &DomIndex, base_url: &str) -> Vec<HashMap<String, String>> {
+    dom_index.document()
+        .select(&TYPED_ITEM_SELECTOR)
+        .filter(|item| has_item_type(*item, PRODUCT_ITEM_TYPES))
+        .filter(|item| !nested_in_product(*item))
+        .filter_map(|item| microdata_entry(item, base_url))
+        .collect()
+}
+
+/// Whether a product item sits inside another product item (an accessory, a variant)
+fn nested_in_product(item: ElementRef) -> bool {
+    item.ancestors()
+        .filter_map(ElementRef::wrap)
+        .any(|ancestor| ancestor.value().attr("itemscope").is_some() && has_item_type(ancestor, PRODUCT_ITEM_TYPES))
+}
+
+fn microdata_entry(item: ElementRef, base_url: &str) -> Option<HashMap<String, String>> {
+    let property = |name: &str| item_property(item, name, PRODUCT_ITEM_TYPES);
+    let mut entry = HashMap::new();
+
+    if let Some(name) = property("name") {
+        entry.insert("product_title".to_string(), name);
+    }
+    if let Some(url) = property("url") {
+        entry.insert("product_url".to_string(), resolve_url(base_url, &url));
+    }
+    if !entry.contains_key("product_title") && !entry.contains_key("product_url") {
+        return None;
+    }
+    if let Some(price) = property("price").or_else(|| property("lowPrice")) {
+        entry.insert("product_price".to_string(), price);
+    }
+    if let Some(currency) = property("priceCurrency") {
+        entry.insert("product_currency".to_string(), currency);
+    }
+    if let Some(image) = property("image") {
+        entry.insert("product_image".to_string(), resolve_url(base_url, &image));
+    }
+    if let Some(rating) = property("ratingValue") {
+        entry.insert("product_rating".to_string(), rating);
+    }
+
+    Some(entry)
+}
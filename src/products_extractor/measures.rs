@@ -0,0 +1,280 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::ElementRef;
+use serde_json::{Map, Value};
+use crate::dom_index::DomIndex;
+use crate::microdata::{find_main_item, own_properties, property_value};
+use crate::types::{DimensionUnit, WeightUnit};
+use super::helpers::{extract_json_ld_value, json_number, json_scalar_string, PRODUCT_ITEM_TYPES};
+
+/// A number followed by an optional unit: "1.2 kg", "1,5kg", "12\"", "30 cm"
+static MEASURE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^\s*(\d+(?:[.,]\d+)?)\s*([A-Za-z]+\.?|["″']{1,2})?\s*$"#).unwrap()
+});
+
+/// "W x H x D unit" dimension strings ("10 x 20 x 5 cm", "10×20×5cm")
+static DIMENSIONS_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^\s*(\d+(?:[.,]\d+)?)\s*[x×*]\s*(\d+(?:[.,]\d+)?)\s*[x×*]\s*(\d+(?:[.,]\d+)?)\s*([A-Za-z]+\.?|["″])?\s*$"#).unwrap()
+});
+
+/// `additionalProperty` names accepted for each measure, lowercased
+const WEIGHT_NAMES: &[&str] = &["weight", "net weight", "gross weight", "item weight", "product weight", "shipping weight", "gewicht", "poids", "peso"];
+const WIDTH_NAMES: &[&str] = &["width", "breite", "largeur", "ancho"];
+const HEIGHT_NAMES: &[&str] = &["height", "höhe", "hauteur", "alto"];
+const DEPTH_NAMES: &[&str] = &["depth", "length", "tiefe", "profondeur", "profundidad"];
+const DIMENSIONS_NAMES: &[&str] = &["dimensions", "size", "product dimensions", "item dimensions", "abmessungen", "maße"];
+
+/// One physical measure: the value converted to the base unit (grams or millimetres) and
+/// the declared form it came from
+#[derive(Debug, Clone)]
+pub struct Measure {
+    pub base_value: f64,
+    pub raw: String,
+}
+
+/// Declared weight and dimensions of the product
+#[derive(Debug, Clone, Default)]
+pub struct Measures {
+    pub weight: Option<Measure>,
+    pub width: Option<Measure>,
+    pub height: Option<Measure>,
+    pub depth: Option<Measure>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Weight,
+    Length,
+}
+
+/// Grams per unit, for UN/CEFACT codes (KGM, GRM, LBR, ONZ, MGM) and common text units
+fn grams_per(unit: &str) -> Option<f64> {
+    match unit.trim().trim_end_matches('.').to_lowercase().as_str() {
+        "kgm" | "kg" | "kgs" | "kilo" | "kilos" | "kilogram" | "kilograms" => Some(1000.0),
+        "grm" | "g" | "gr" | "gram" | "grams" | "gramm" => Some(1.0),
+        "mgm" | "mg" | "milligram" | "milligrams" => Some(0.001),
+        "lbr" | "lb" | "lbs" | "pound" | "pounds" => Some(453.592_37),
+        "onz" | "oz" | "ounce" | "ounces" => Some(28.349_523_125),
+        _ => None,
+    }
+}
+
+/// Millimetres per unit, for UN/CEFACT codes (MMT, CMT, MTR, INH, FOT) and common text units
+fn millimetres_per(unit: &str) -> Option<f64> {
+    match unit.trim().trim_end_matches('.').to_lowercase().as_str() {
+        "mmt" | "mm" | "millimeter" | "millimeters" | "millimetre" | "millimetres" => Some(1.0),
+        "cmt" | "cm" | "centimeter" | "centimeters" | "centimetre" | "centimetres" => Some(10.0),
+        "mtr" | "m" | "meter" | "meters" | "metre" | "metres" => Some(1000.0),
+        "inh" | "in" | "inch" | "inches" | "\"" | "″" | "''" => Some(25.4),
+        "fot" | "ft" | "foot" | "feet" | "'" => Some(304.8),
+        _ => None,
+    }
+}
+
+fn base_factor(kind: Kind, unit: &str) -> Option<f64> {
+    match kind {
+        Kind::Weight => grams_per(unit),
+        Kind::Length => millimetres_per(unit),
+    }
+}
+
+fn parse_number(value: &str) -> Option<f64> {
+    value.replace(',', ".").parse().ok()
+}
+
+/// Parse a "1.2 kg" style string; a bare number can't be normalized and is rejected
+fn parse_measure_text(text: &str, kind: Kind) -> Option<Measure> {
+    let captures = MEASURE_RE.captures(text)?;
+    let value = parse_number(&captures[1])?;
+    let factor = base_factor(kind, captures.get(2)?.as_str())?;
+    Some(Measure { base_value: value * factor, raw: text.trim().to_string() })
+}
+
+/// Parse a schema.org value: a `QuantitativeValue` (`value` with `unitCode` or `unitText`),
+/// a `PropertyValue` holding either form, a "1.2 kg" string, or an array of those
+fn parse_measure_value(value: &Value, kind: Kind) -> Option<Measure> {
+    match value {
+        Value::String(text) => parse_measure_text(text, kind),
+        Value::Array(arr) => arr.iter().find_map(|item| parse_measure_value(item, kind)),
+        Value::Object(obj) => parse_quantitative(obj, kind),
+        _ => None,
+    }
+}
+
+fn parse_quantitative(obj: &Map<String, Value>, kind: Kind) -> Option<Measure> {
+    let value = obj.get("value")?;
+    let unit = obj.get("unitText")
+        .and_then(json_scalar_string)
+        .filter(|unit| base_factor(kind, unit).is_some())
+        .or_else(|| obj.get("unitCode").and_then(json_scalar_string));
+    match unit {
+        Some(unit) => {
+            let number = json_number(value)?;
+            let factor = base_factor(kind, &unit)?;
+            let raw = format!("{} {}", json_scalar_string(value)?, unit);
+            Some(Measure { base_value: number * factor, raw })
+        }
+        // A value without a unit may still carry one in its text ("1.2 kg")
+        None => value.as_str().and_then(|text| parse_measure_text(text, kind)),
+    }
+}
+
+/// Width, height and depth from a "W x H x D unit" string
+fn parse_dimensions_text(text: &str) -> Option<[Measure; 3]> {
+    let captures = DIMENSIONS_RE.captures(text)?;
+    let factor = millimetres_per(captures.get(4)?.as_str())?;
+    let measure = |i: usize| -> Option<Measure> {
+        Some(Measure { base_value: parse_number(&captures[i])? * factor, raw: text.trim().to_string() })
+    };
+    Some([measure(1)?, measure(2)?, measure(3)?])
+}
+
+/// Weight and dimensions of the main product: JSON-LD `weight`/`width`/`height`/`depth`,
+/// then `additionalProperty` entries with a matching name (or a "W x H x D" `dimensions`
+/// entry), then the microdata product's properties
+pub fn extract_measures(dom_index: &DomIndex) -> Measures {
+    let additional = extract_json_ld_value(dom_index, "additionalProperty");
+    let properties: Vec<&Map<String, Value>> = match &additional {
+        Some(Value::Array(arr)) => arr.iter().filter_map(Value::as_object).collect(),
+        Some(Value::Object(obj)) => vec![obj],
+        _ => Vec::new(),
+    };
+    let named = |names: &[&str], kind: Kind| -> Option<Measure> {
+        properties.iter()
+            .filter(|property| {
+                property.get("name").and_then(|n| n.as_str())
+                    .is_some_and(|name| names.contains(&name.trim().to_lowercase().as_str()))
+            })
+            .find_map(|property| parse_quantitative(property, kind))
+    };
+    let dimensions = properties.iter()
+        .filter(|property| {
+            property.get("name").and_then(|n| n.as_str())
+                .is_some_and(|name| DIMENSIONS_NAMES.contains(&name.trim().to_lowercase().as_str()))
+        })
+        .find_map(|property| property.get("value").and_then(|v| v.as_str()).and_then(parse_dimensions_text));
+    let [dim_width, dim_height, dim_depth] = match dimensions {
+        Some([width, height, depth]) => [Some(width), Some(height), Some(depth)],
+        None => [None, None, None],
+    };
+
+    let product = find_main_item(dom_index.document(), PRODUCT_ITEM_TYPES);
+    let lookup = |property: &str, kind: Kind, names: &[&str], from_dimensions: Option<Measure>| {
+        extract_json_ld_value(dom_index, property)
+            .and_then(|value| parse_measure_value(&value, kind))
+            .or_else(|| named(names, kind))
+            .or(from_dimensions)
+            .or_else(|| product.and_then(|item| microdata_measure(item, property, kind)))
+    };
+
+    Measures {
+        weight: lookup("weight", Kind::Weight, WEIGHT_NAMES, None),
+        width: lookup("width", Kind::Length, WIDTH_NAMES, dim_width),
+        height: lookup("height", Kind::Length, HEIGHT_NAMES, dim_height),
+        depth: lookup("depth", Kind::Length, DEPTH_NAMES, dim_depth),
+    }
+}
+
+/// A microdata measure: a nested `QuantitativeValue` item (`value` + `unitCode`/`unitText`)
+/// or a "1.2 kg" text
+fn microdata_measure(product: ElementRef, itemprop: &str, kind: Kind) -> Option<Measure> {
+    own_properties(product, itemprop).into_iter().find_map(|element| {
+        if element.value().attr("itemscope").is_some() {
+            let first = |name: &str| own_properties(element, name).into_iter().find_map(property_value);
+            let mut obj = Map::new();
+            obj.insert("value".to_string(), Value::String(first("value")?));
+            for unit in ["unitCode", "unitText"] {
+                if let Some(value) = first(unit) {
+                    obj.insert(unit.to_string(), Value::String(value));
+                }
+            }
+            parse_quantitative(&obj, kind)
+        } else {
+            property_value(element).and_then(|text| parse_measure_text(&text, kind))
+        }
+    })
+}
+
+/// Trim a converted amount to at most three decimals ("1200", "2.646")
+fn format_amount(value: f64) -> String {
+    let formatted = format!("{:.3}", value);
+    formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// A weight in the configured unit
+pub fn format_weight(measure: &Measure, unit: WeightUnit) -> String {
+    let grams = match unit {
+        WeightUnit::Grams => 1.0,
+        WeightUnit::Kilograms => 1000.0,
+        WeightUnit::Pounds => 453.592_37,
+        WeightUnit::Ounces => 28.349_523_125,
+    };
+    format_amount(measure.base_value / grams)
+}
+
+/// A length in the configured unit
+pub fn format_length(measure: &Measure, unit: DimensionUnit) -> String {
+    let millimetres = match unit {
+        DimensionUnit::Millimetres => 1.0,
+        DimensionUnit::Centimetres => 10.0,
+        DimensionUnit::Metres => 1000.0,
+        DimensionUnit::Inches => 25.4,
+    };
+    format_amount(measure.base_value / millimetres)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_code_table() {
+        let weights: &[(&str, f64)] = &[
+            ("KGM", 1000.0), ("GRM", 1.0), ("LBR", 453.592_37), ("ONZ", 28.349_523_125), ("MGM", 0.001),
+            ("kg", 1000.0), ("kgs", 1000.0), ("g", 1.0), ("gr.", 1.0), ("lbs", 453.592_37), ("oz", 28.349_523_125),
+        ];
+        for (unit, grams) in weights {
+            assert_eq!(grams_per(unit), Some(*grams), "unit {}", unit);
+            assert_eq!(millimetres_per(unit), None, "unit {}", unit);
+        }
+        let lengths: &[(&str, f64)] = &[
+            ("MMT", 1.0), ("CMT", 10.0), ("MTR", 1000.0), ("INH", 25.4), ("FOT", 304.8),
+            ("mm", 1.0), ("cm", 10.0), ("m", 1000.0), ("in.", 25.4), ("inches", 25.4), ("\"", 25.4), ("ft", 304.8),
+        ];
+        for (unit, millimetres) in lengths {
+            assert_eq!(millimetres_per(unit), Some(*millimetres), "unit {}", unit);
+        }
+        assert_eq!(grams_per("furlong"), None);
+    }
+
+    #[test]
+    fn measure_text_table() {
+        let cases: &[(&str, Kind, Option<f64>)] = &[
+            ("1.2 kg", Kind::Weight, Some(1200.0)),
+            ("1,5kg", Kind::Weight, Some(1500.0)),
+            ("250 g", Kind::Weight, Some(250.0)),
+            ("2 lb", Kind::Weight, Some(907.184_74)),
+            ("30 cm", Kind::Length, Some(300.0)),
+            ("12\"", Kind::Length, Some(304.8)),
+            // No unit, or a unit of the other kind
+            ("1.2", Kind::Weight, None),
+            ("30 cm", Kind::Weight, None),
+        ];
+        for (text, kind, expected) in cases {
+            let parsed = parse_measure_text(text, *kind).map(|m| m.base_value);
+            match (parsed, expected) {
+                (Some(value), Some(expected)) => assert!((value - expected).abs() < 1e-6, "{} -> {}", text, value),
+                (parsed, expected) => assert_eq!(parsed, *expected, "text {}", text),
+            }
+        }
+    }
+
+    #[test]
+    fn dimensions_text_splits_into_width_height_depth() {
+        let [width, height, depth] = parse_dimensions_text("10 x 20 x 5 cm").unwrap();
+        assert_eq!((width.base_value, height.base_value, depth.base_value), (100.0, 200.0, 50.0));
+        assert_eq!(width.raw, "10 x 20 x 5 cm");
+        assert!(parse_dimensions_text("10×20×5in").is_some());
+        assert!(parse_dimensions_text("10 x 20 cm").is_none());
+    }
+}
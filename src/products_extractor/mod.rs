@@ -2,9 +2,21 @@ mod basic;
 mod pricing;
 mod reviews;
 mod helpers;
+mod offers;
+mod shipping;
+mod energy;
+mod returns;
+mod identifiers;
+mod images;
+mod measures;
+mod listing;
 
 use std::collections::HashMap;
-use scraper::Html;
+use crate::dom_index::DomIndex;
+use crate::link_extractor::resolve_url;
+use crate::types::{ProductInfo, ProductOptions, Review};
+
+pub use listing::extract_products_list;
 
 /// Returns a list of all available product metadata field names
 pub fn get_all_product_fields() -> Vec<String> {
@@ -12,18 +24,55 @@ pub fn get_all_product_fields() -> Vec<String> {
         "product_title".to_string(),
         "product_description".to_string(),
         "product_brand".to_string(),
+        "product_brand_source".to_string(),
         "product_category".to_string(),
+        "product_category_source".to_string(),
+        "product_url".to_string(),
+        "product_id".to_string(),
         "product_sku".to_string(),
         "product_mpn".to_string(),
+        "product_gtin".to_string(),
+        "product_gtin_valid".to_string(),
+        "product_gtin8".to_string(),
+        "product_gtin12".to_string(),
+        "product_gtin13".to_string(),
+        "product_gtin14".to_string(),
+        "product_isbn".to_string(),
         "product_image".to_string(),
+        "product_images".to_string(),
         "product_price".to_string(),
         "product_currency".to_string(),
+        "product_currency_source".to_string(),
         "product_availability".to_string(),
+        "product_availability_raw".to_string(),
         "product_original_price".to_string(),
+        "product_original_price_source".to_string(),
+        "product_price_valid_until".to_string(),
+        "product_discount_percent".to_string(),
+        "product_on_sale".to_string(),
+        "product_offers".to_string(),
+        "product_seller".to_string(),
+        "product_seller_url".to_string(),
+        "product_price_range".to_string(),
+        "product_shipping_price".to_string(),
+        "product_shipping_currency".to_string(),
+        "product_delivery_days".to_string(),
+        "product_energy_class".to_string(),
+        "product_return_days".to_string(),
+        "product_return_fees".to_string(),
+        "product_weight".to_string(),
+        "product_weight_raw".to_string(),
+        "product_width".to_string(),
+        "product_width_raw".to_string(),
+        "product_height".to_string(),
+        "product_height_raw".to_string(),
+        "product_depth".to_string(),
+        "product_depth_raw".to_string(),
         "product_rating".to_string(),
         "product_review_count".to_string(),
         "product_best_rating".to_string(),
         "product_worst_rating".to_string(),
+        "product_reviews".to_string(),
     ]
 }
 
@@ -35,25 +84,271 @@ fn normalize_field_name(field: &str) -> String {
         "description" => "product_description".to_string(),
         "price" => "product_price".to_string(),
         "brand" => "product_brand".to_string(),
+        "brand_source" => "product_brand_source".to_string(),
         "category" => "product_category".to_string(),
+        "category_source" => "product_category_source".to_string(),
+        "url" => "product_url".to_string(),
+        "id" => "product_id".to_string(),
         "sku" => "product_sku".to_string(),
         "mpn" => "product_mpn".to_string(),
+        "gtin" => "product_gtin".to_string(),
+        "ean" => "product_gtin13".to_string(),
+        "upc" => "product_gtin12".to_string(),
+        "isbn" => "product_isbn".to_string(),
         "image" => "product_image".to_string(),
+        "images" => "product_images".to_string(),
         "currency" => "product_currency".to_string(),
+        "currency_source" => "product_currency_source".to_string(),
         "availability" => "product_availability".to_string(),
+        "availability_raw" => "product_availability_raw".to_string(),
         "original_price" => "product_original_price".to_string(),
+        "original_price_source" => "product_original_price_source".to_string(),
+        "price_valid_until" => "product_price_valid_until".to_string(),
+        "discount_percent" => "product_discount_percent".to_string(),
+        "on_sale" => "product_on_sale".to_string(),
+        "offers" => "product_offers".to_string(),
+        "seller" => "product_seller".to_string(),
+        "seller_url" => "product_seller_url".to_string(),
+        "price_range" => "product_price_range".to_string(),
+        "shipping_price" => "product_shipping_price".to_string(),
+        "shipping_currency" => "product_shipping_currency".to_string(),
+        "delivery_days" => "product_delivery_days".to_string(),
+        "energy_class" => "product_energy_class".to_string(),
+        "return_days" => "product_return_days".to_string(),
+        "return_fees" => "product_return_fees".to_string(),
+        "weight" => "product_weight".to_string(),
+        "weight_raw" => "product_weight_raw".to_string(),
+        "width" => "product_width".to_string(),
+        "width_raw" => "product_width_raw".to_string(),
+        "height" => "product_height".to_string(),
+        "height_raw" => "product_height_raw".to_string(),
+        "depth" => "product_depth".to_string(),
+        "depth_raw" => "product_depth_raw".to_string(),
         "rating" => "product_rating".to_string(),
         "review_count" => "product_review_count".to_string(),
         "best_rating" => "product_best_rating".to_string(),
         "worst_rating" => "product_worst_rating".to_string(),
+        "reviews" => "product_reviews".to_string(),
         // Full names pass through
         _ => field.to_string(),
     }
 }
 
-/// Extract product metadata from HTML document
-pub fn extract_products(document: &Html, product_fields: &[String]) -> HashMap<String, String> {
+/// Whether `field` is a product field name or alias (or "all")
+pub fn is_known_product_field(field: &str) -> bool {
+    let field = normalize_field_name(field);
+    field == "all" || get_all_product_fields().contains(&field)
+}
+
+fn sale_epsilon(options: &ProductOptions) -> f64 {
+    options.sale_epsilon.unwrap_or(pricing::DEFAULT_SALE_EPSILON)
+}
+
+/// Lookups read by several product fields, or by both the field map and `ProductInfo`; each
+/// runs at most once per page
+struct SharedLookups<'a> {
+    dom_index: &'a DomIndex<'a>,
+    base_url: &'a str,
+    options: &'a ProductOptions,
+    title: Option<Option<(String, &'static str)>>,
+    brand: Option<Option<(String, &'static str)>>,
+    sku: Option<Option<(String, &'static str)>>,
+    price: Option<Option<(String, &'static str)>>,
+    original_price: Option<Option<(String, &'static str)>>,
+    // Declared or inferred; the flag is true when inferred
+    currency: Option<Option<(String, bool)>>,
+    discount: Option<Option<pricing::Discount>>,
+    availability: Option<Option<(String, &'static str)>>,
+    rating: Option<Option<(String, &'static str)>>,
+    review_count: Option<Option<(String, &'static str)>>,
+    images: Option<Vec<String>>,
+    reviews: Option<Vec<Review>>,
+}
+
+impl<'a> SharedLookups<'a> {
+    fn new(dom_index: &'a DomIndex<'a>, base_url: &'a str, options: &'a ProductOptions) -> Self {
+        SharedLookups {
+            dom_index,
+            base_url,
+            options,
+            title: None,
+            brand: None,
+            sku: None,
+            price: None,
+            original_price: None,
+            currency: None,
+            discount: None,
+            availability: None,
+            rating: None,
+            review_count: None,
+            images: None,
+            reviews: None,
+        }
+    }
+
+    fn title(&mut self) -> Option<(String, &'static str)> {
+        let dom_index = self.dom_index;
+        self.title.get_or_insert_with(|| basic::extract_product_title_with_source(dom_index)).clone()
+    }
+
+    fn brand(&mut self) -> Option<(String, &'static str)> {
+        let dom_index = self.dom_index;
+        self.brand.get_or_insert_with(|| basic::extract_product_brand_with_source(dom_index)).clone()
+    }
+
+    fn sku(&mut self) -> Option<(String, &'static str)> {
+        let dom_index = self.dom_index;
+        self.sku.get_or_insert_with(|| basic::extract_product_sku_with_source(dom_index)).clone()
+    }
+
+    fn price(&mut self) -> Option<(String, &'static str)> {
+        let dom_index = self.dom_index;
+        self.price.get_or_insert_with(|| pricing::extract_product_price_with_source(dom_index)).clone()
+    }
+
+    fn original_price(&mut self) -> Option<(String, &'static str)> {
+        let dom_index = self.dom_index;
+        self.original_price
+            .get_or_insert_with(|| pricing::extract_product_original_price_with_source(dom_index))
+            .clone()
+    }
+
+    fn currency(&mut self) -> Option<(String, bool)> {
+        let dom_index = self.dom_index;
+        self.currency.get_or_insert_with(|| pricing::resolve_product_currency(dom_index)).clone()
+    }
+
+    /// Discount of the page's price against its original price
+    fn discount(&mut self) -> Option<pricing::Discount> {
+        if self.discount.is_none() {
+            let currency = self.currency().map(|(code, _)| code);
+            let discount = match (self.price(), self.original_price()) {
+                (Some((price, _)), Some((original, _))) => {
+                    pricing::compute_discount(&price, &original, currency.as_deref(), sale_epsilon(self.options))
+                },
+                _ => None,
+            };
+            self.discount = Some(discount);
+        }
+        self.discount.flatten()
+    }
+
+    fn availability(&mut self) -> Option<(String, &'static str)> {
+        let dom_index = self.dom_index;
+        self.availability
+            .get_or_insert_with(|| pricing::extract_product_availability_with_source(dom_index))
+            .clone()
+    }
+
+    fn rating(&mut self) -> Option<(String, &'static str)> {
+        let dom_index = self.dom_index;
+        self.rating.get_or_insert_with(|| reviews::extract_product_rating_with_source(dom_index)).clone()
+    }
+
+    fn review_count(&mut self) -> Option<(String, &'static str)> {
+        let dom_index = self.dom_index;
+        self.review_count
+            .get_or_insert_with(|| reviews::extract_product_review_count_with_source(dom_index))
+            .clone()
+    }
+
+    fn images(&mut self) -> &[String] {
+        let (dom_index, base_url) = (self.dom_index, self.base_url);
+        self.images.get_or_insert_with(|| images::extract_product_images(dom_index, base_url))
+    }
+
+    fn reviews(&mut self) -> &[Review] {
+        let (dom_index, max_reviews) = (self.dom_index, self.options.max_reviews);
+        self.reviews.get_or_insert_with(|| reviews::extract_product_reviews(dom_index, max_reviews))
+    }
+}
+
+/// Build typed product metadata from the lookups behind the requested `fields` (normalized
+/// names); the rest keep their defaults
+///
+/// Prices are parsed with `pricing::parse_price` using the declared currency, falling back to
+/// the currency inferred from the price's symbol or code, to tell decimal commas from
+/// thousands separators.
+fn product_info(lookups: &mut SharedLookups, fields: &[String]) -> ProductInfo {
+    let wants = |names: &[&str]| fields.iter().any(|f| names.contains(&f.as_str()));
+    let mut info = ProductInfo::default();
+
+    if wants(&["product_title"]) {
+        info.title = lookups.title().map(|(title, _)| title);
+    }
+    if wants(&["product_brand", "product_brand_source"]) {
+        info.brand = lookups.brand().map(|(brand, _)| brand);
+    }
+    if wants(&["product_sku"]) {
+        info.sku = lookups.sku().map(|(sku, _)| sku);
+    }
+    // Prices are parsed in the page currency, so the currency comes with any price
+    let wants_price = wants(&["product_price"]);
+    let wants_original = wants(&["product_original_price", "product_original_price_source"]);
+    if wants_price || wants_original || wants(&["product_currency", "product_currency_source"]) {
+        let currency = lookups.currency();
+        info.currency_inferred = currency.as_ref().is_some_and(|(_, inferred)| *inferred);
+        info.currency = currency.map(|(code, _)| code);
+    }
+    if wants_price {
+        let price = lookups.price().and_then(|(raw, _)| pricing::parse_price(&raw, info.currency.as_deref()));
+        info.price = price.as_ref().map(|p| p.amount);
+        info.price_is_range = price.as_ref().is_some_and(|p| p.is_range);
+    }
+    if wants_original {
+        info.original_price = lookups
+            .original_price()
+            .and_then(|(raw, _)| pricing::parse_price(&raw, info.currency.as_deref()))
+            .map(|p| p.amount);
+    }
+    if wants(&["product_discount_percent", "product_on_sale"]) {
+        let discount = lookups.discount();
+        info.discount_percent = discount.and_then(|d| d.percent);
+        info.on_sale = discount.map(|d| d.on_sale);
+    }
+    if wants(&["product_availability", "product_availability_raw"]) {
+        info.availability = lookups.availability().map(|(a, _)| pricing::normalize_availability(&a));
+    }
+    if wants(&["product_rating"]) {
+        info.rating = lookups.rating().and_then(|(r, _)| reviews::parse_rating(&r));
+    }
+    if wants(&["product_review_count"]) {
+        info.review_count = lookups.review_count().and_then(|(c, _)| reviews::parse_review_count(&c));
+    }
+    if wants(&["product_image", "product_images"]) {
+        info.images = lookups.images().to_vec();
+    }
+    if wants(&["product_reviews"]) {
+        info.reviews = lookups.reviews().to_vec();
+    }
+    info
+}
+
+/// Keep the value of a lookup that reports its source, noting the source in `source`
+fn tracked(found: Option<(String, &'static str)>, source: &mut Option<&'static str>) -> Option<String> {
+    found.map(|(value, from)| {
+        *source = Some(from);
+        value
+    })
+}
+
+/// Extract product metadata from HTML document, along with where each value came from
+/// (`"meta"`, `"jsonld"`, `"microdata"`, `"css"`, ...) keyed by the same field names. Only
+/// fields found through a tiered lookup (title, description, brand, category, sku, mpn, price,
+/// availability, original price, rating, review count) get a source.
+///
+/// The typed `ProductInfo` is built from the same lookups, so nothing is extracted twice; only
+/// its values behind the requested fields are filled in (every one for "all").
+///
+/// `base_url` resolves relative image URLs; `options.max_reviews` limits `product_reviews`.
+pub fn extract_products_with_sources(
+    dom_index: &DomIndex,
+    base_url: &str,
+    product_fields: &[String],
+    options: &ProductOptions,
+) -> (HashMap<String, String>, HashMap<String, String>, ProductInfo) {
     let mut products = HashMap::new();
+    let mut sources = HashMap::new();
 
     // Check if "all" is in the list
     let fields_to_extract = if product_fields.iter().any(|f| f == "all") {
@@ -62,31 +357,161 @@ pub fn extract_products(document: &Html, product_fields: &[String]) -> HashMap<S
         product_fields.iter().map(|f| normalize_field_name(f)).collect()
     };
 
+    let mut lookups = SharedLookups::new(dom_index, base_url, options);
+    // Category lookup (declared or breadcrumb-inferred) is shared by product_category and
+    // product_category_source
+    let mut category: Option<Option<(String, &'static str)>> = None;
+    // Seller lookup is shared by product_seller and product_seller_url
+    let mut seller: Option<Option<offers::Seller>> = None;
+    // Shipping lookup is shared by product_shipping_price, product_shipping_currency and
+    // product_delivery_days
+    let mut shipping: Option<Option<shipping::Shipping>> = None;
+    // Return policy lookup is shared by product_return_days and product_return_fees
+    let mut return_policy: Option<Option<returns::ReturnPolicy>> = None;
+    // Weight and dimensions are parsed in one pass shared by the measure fields
+    let mut measures: Option<measures::Measures> = None;
+
     for field in &fields_to_extract {
+        let mut source = None;
         let value = match field.as_str() {
-            "product_title" => basic::extract_product_title(document),
-            "product_description" => basic::extract_product_description(document),
-            "product_brand" => basic::extract_product_brand(document),
-            "product_category" => basic::extract_product_category(document),
-            "product_sku" => basic::extract_product_sku(document),
-            "product_mpn" => basic::extract_product_mpn(document),
-            "product_image" => basic::extract_product_image(document),
-            "product_price" => pricing::extract_product_price(document),
-            "product_currency" => pricing::extract_product_currency(document),
-            "product_availability" => pricing::extract_product_availability(document),
-            "product_original_price" => pricing::extract_product_original_price(document),
-            "product_rating" => reviews::extract_product_rating(document),
-            "product_review_count" => reviews::extract_product_review_count(document),
-            "product_best_rating" => reviews::extract_product_best_rating(document),
-            "product_worst_rating" => reviews::extract_product_worst_rating(document),
+            "product_title" => tracked(lookups.title(), &mut source),
+            "product_description" => tracked(basic::extract_product_description_with_source(dom_index), &mut source),
+            "product_brand" => tracked(lookups.brand(), &mut source),
+            "product_brand_source" => lookups.brand().map(|(_, source)| source.to_string()),
+            "product_category" => tracked(
+                category
+                    .get_or_insert_with(|| basic::extract_product_category_with_source(dom_index, base_url))
+                    .clone(),
+                &mut source,
+            ),
+            "product_category_source" => category
+                .get_or_insert_with(|| basic::extract_product_category_with_source(dom_index, base_url))
+                .as_ref()
+                .map(|(_, source)| source.to_string()),
+            "product_url" => basic::extract_product_url(dom_index, base_url),
+            "product_id" => basic::extract_product_id(dom_index, base_url),
+            "product_sku" => tracked(lookups.sku(), &mut source),
+            "product_mpn" => tracked(basic::extract_product_mpn_with_source(dom_index), &mut source),
+            "product_gtin" => identifiers::extract_product_gtin(dom_index),
+            "product_gtin_valid" => identifiers::extract_product_gtin(dom_index)
+                .map(|gtin| identifiers::is_valid_gtin(&gtin).to_string()),
+            "product_gtin8" => identifiers::extract_product_identifier(dom_index, "gtin8"),
+            "product_gtin12" => identifiers::extract_product_identifier(dom_index, "gtin12"),
+            "product_gtin13" => identifiers::extract_product_identifier(dom_index, "gtin13"),
+            "product_gtin14" => identifiers::extract_product_identifier(dom_index, "gtin14"),
+            "product_isbn" => identifiers::extract_product_identifier(dom_index, "isbn"),
+            "product_image" => lookups.images()
+                .first()
+                .cloned()
+                .or_else(|| basic::extract_product_image(dom_index)),
+            "product_images" => {
+                let images = lookups.images();
+                if images.is_empty() {
+                    None
+                } else {
+                    serde_json::to_string(images).ok()
+                }
+            },
+            "product_price" => tracked(lookups.price(), &mut source),
+            "product_currency" => lookups.currency().map(|(code, _)| code),
+            "product_currency_source" => lookups.currency()
+                .map(|(_, inferred)| if inferred { "inferred" } else { "declared" }.to_string()),
+            "product_availability" => tracked(lookups.availability(), &mut source)
+                .map(|a| pricing::normalize_availability(&a).as_str().to_string()),
+            "product_availability_raw" => tracked(lookups.availability(), &mut source),
+            "product_original_price" => tracked(lookups.original_price(), &mut source),
+            "product_original_price_source" => lookups.original_price().map(|(_, source)| source.to_string()),
+            "product_price_valid_until" => pricing::extract_product_price_valid_until(dom_index),
+            "product_discount_percent" | "product_on_sale" => {
+                let discount = lookups.discount();
+                match field.as_str() {
+                    "product_on_sale" => discount.map(|d| d.on_sale.to_string()),
+                    _ => discount.and_then(|d| d.percent).map(|percent| format!("{:.1}", percent)),
+                }
+            },
+            "product_offers" => {
+                let mut offers = offers::extract_offers(dom_index);
+                for offer in &mut offers {
+                    offer.seller_url = offer.seller_url.take().map(|url| resolve_url(base_url, &url));
+                }
+                if offers.is_empty() {
+                    None
+                } else {
+                    serde_json::to_string(&offers).ok()
+                }
+            },
+            "product_seller" => seller
+                .get_or_insert_with(|| offers::extract_product_seller(dom_index))
+                .as_ref()
+                .and_then(|seller| seller.name.clone()),
+            "product_seller_url" => seller
+                .get_or_insert_with(|| offers::extract_product_seller(dom_index))
+                .as_ref()
+                .and_then(|seller| seller.url.as_deref())
+                .map(|url| resolve_url(base_url, url)),
+            "product_price_range" => offers::extract_price_range(dom_index)
+                .and_then(|range| serde_json::to_string(&range).ok()),
+            "product_shipping_price" | "product_shipping_currency" | "product_delivery_days" => {
+                let shipping = shipping
+                    .get_or_insert_with(|| shipping::extract_shipping(dom_index))
+                    .as_ref();
+                match field.as_str() {
+                    "product_shipping_price" => shipping.and_then(|s| s.price.clone()),
+                    "product_shipping_currency" => shipping.and_then(|s| s.currency.clone()),
+                    _ => shipping.and_then(|s| s.delivery_days).map(|(min, max)| {
+                        if min == max { min.to_string() } else { format!("{}-{}", min, max) }
+                    }),
+                }
+            },
+            "product_energy_class" => energy::extract_energy_class(dom_index),
+            "product_return_days" | "product_return_fees" => {
+                let policy = return_policy
+                    .get_or_insert_with(|| returns::extract_return_policy(dom_index))
+                    .as_ref();
+                match field.as_str() {
+                    "product_return_days" => policy.and_then(|p| p.days.clone()),
+                    _ => policy.and_then(|p| p.fees).map(str::to_string),
+                }
+            },
+            "product_weight" | "product_weight_raw" | "product_width" | "product_width_raw"
+            | "product_height" | "product_height_raw" | "product_depth" | "product_depth_raw" => {
+                let measures = measures.get_or_insert_with(|| measures::extract_measures(dom_index));
+                let measure = match field.trim_end_matches("_raw") {
+                    "product_weight" => measures.weight.as_ref(),
+                    "product_width" => measures.width.as_ref(),
+                    "product_height" => measures.height.as_ref(),
+                    _ => measures.depth.as_ref(),
+                };
+                match field.as_str() {
+                    "product_weight" => measure.map(|m| measures::format_weight(m, options.weight_unit)),
+                    _ if field.ends_with("_raw") => measure.map(|m| m.raw.clone()),
+                    _ => measure.map(|m| measures::format_length(m, options.dimension_unit)),
+                }
+            },
+            "product_rating" => tracked(lookups.rating(), &mut source),
+            "product_review_count" => tracked(lookups.review_count(), &mut source),
+            "product_best_rating" => reviews::extract_product_best_rating(dom_index),
+            "product_worst_rating" => reviews::extract_product_worst_rating(dom_index),
+            "product_reviews" => {
+                let reviews = lookups.reviews();
+                if reviews.is_empty() {
+                    None
+                } else {
+                    serde_json::to_string(reviews).ok()
+                }
+            },
             _ => None,
         };
 
         if let Some(v) = value {
+            if let Some(source) = source {
+                sources.insert(field.clone(), source.to_string());
+            }
             products.insert(field.clone(), v);
         }
     }
 
-    products
+    let info = product_info(&mut lookups, &fields_to_extract);
+    (products, sources, info)
 }
 
@@ -0,0 +1,224 @@
+use scraper::Selector;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use crate::dom_index::DomIndex;
+use crate::microdata::{find_main_item, own_properties, property_value};
+use crate::types::Availability;
+use super::helpers::{entity_name, extract_json_ld_value, extract_meta_property, is_json_ld_type, json_scalar_string, PRODUCT_ITEM_TYPES};
+use super::pricing::{normalize_availability, parse_price};
+
+/// One JSON-LD `Offer`: a seller, size or condition variant of the product
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Offer {
+    pub price: Option<String>,
+    pub currency: Option<String>,
+    pub availability: Option<String>,
+    pub condition: Option<String>,
+    pub seller: Option<String>,
+    /// `seller.url` as declared (relative URLs are resolved by `extract_products`)
+    pub seller_url: Option<String>,
+    pub sku: Option<String>,
+    pub url: Option<String>,
+    /// `priceValidUntil`, as declared
+    pub price_valid_until: Option<String>,
+    /// Amount of a `priceSpecification` whose `priceType` marks a list or strikethrough price
+    pub list_price: Option<String>,
+}
+
+/// Price span declared by a JSON-LD `AggregateOffer`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PriceRange {
+    pub low_price: Option<String>,
+    pub high_price: Option<String>,
+    pub currency: Option<String>,
+    pub offer_count: Option<u64>,
+}
+
+/// Every individual offer of the product, in declaration order. Offers nested in an
+/// `AggregateOffer` are included; the aggregate itself is reported by `extract_price_range`.
+pub fn extract_offers(dom_index: &DomIndex) -> Vec<Offer> {
+    let mut offers = Vec::new();
+    if let Some(value) = extract_json_ld_value(dom_index, "offers") {
+        collect_offers(&value, &mut offers);
+    }
+    offers
+}
+
+fn collect_offers(value: &Value, offers: &mut Vec<Offer>) {
+    match value {
+        Value::Array(arr) => {
+            for item in arr {
+                collect_offers(item, offers);
+            }
+        }
+        Value::Object(obj) if is_json_ld_type(obj, "AggregateOffer") => {
+            if let Some(nested) = obj.get("offers") {
+                collect_offers(nested, offers);
+            }
+        }
+        Value::Object(obj) => offers.push(parse_offer(obj)),
+        _ => {}
+    }
+}
+
+/// `priceType` values (schema.org `PriceTypeEnumeration`, Google's `StrikethroughPrice`) of a
+/// price specification describing the regular price rather than the one charged
+const LIST_PRICE_TYPES: &[&str] = &["ListPrice", "StrikethroughPrice", "MSRP", "SRP"];
+
+fn is_list_price(specification: &Map<String, Value>) -> bool {
+    specification.get("priceType").and_then(Value::as_str).is_some_and(|price_type| {
+        let name = price_type.trim().trim_end_matches('/').rsplit('/').next().unwrap_or(price_type);
+        LIST_PRICE_TYPES.iter().any(|t| t.eq_ignore_ascii_case(name))
+    })
+}
+
+/// `priceSpecification` entries (a single object or an array)
+fn price_specifications(obj: &Map<String, Value>) -> Vec<&Map<String, Value>> {
+    match obj.get("priceSpecification") {
+        Some(Value::Array(arr)) => arr.iter().filter_map(Value::as_object).collect(),
+        Some(Value::Object(spec)) => vec![spec],
+        _ => Vec::new(),
+    }
+}
+
+fn parse_offer(obj: &Map<String, Value>) -> Offer {
+    let specifications = price_specifications(obj);
+    // The price charged comes from the first specification that isn't a list/strikethrough price
+    let price_specification = specifications.iter().copied().find(|spec| !is_list_price(spec));
+    let list_price = specifications.iter()
+        .copied()
+        .filter(|spec| is_list_price(spec))
+        .find_map(|spec| spec.get("price").and_then(json_scalar_string));
+    let from_offer_or_specification = |property: &str| {
+        obj.get(property)
+            .and_then(json_scalar_string)
+            .or_else(|| price_specification.and_then(|spec| spec.get(property)).and_then(json_scalar_string))
+    };
+
+    Offer {
+        price: from_offer_or_specification("price"),
+        currency: from_offer_or_specification("priceCurrency"),
+        availability: obj.get("availability").and_then(json_scalar_string),
+        condition: obj.get("itemCondition").and_then(json_scalar_string),
+        seller: obj.get("seller").and_then(entity_name).map(|name| collapse_whitespace(&name)),
+        seller_url: obj.get("seller").and_then(first_object).and_then(|seller| seller.get("url")).and_then(json_scalar_string),
+        sku: obj.get("sku").and_then(json_scalar_string),
+        url: obj.get("url").and_then(json_scalar_string),
+        price_valid_until: obj.get("priceValidUntil").and_then(json_scalar_string),
+        list_price,
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The object itself, or the first object of an array
+pub fn first_object(value: &Value) -> Option<&Map<String, Value>> {
+    match value {
+        Value::Object(obj) => Some(obj),
+        Value::Array(arr) => arr.iter().find_map(|v| v.as_object()),
+        _ => None,
+    }
+}
+
+/// The `AggregateOffer` price span (`lowPrice`, `highPrice`, `offerCount`), if the product has one
+pub fn extract_price_range(dom_index: &DomIndex) -> Option<PriceRange> {
+    let value = extract_json_ld_value(dom_index, "offers")?;
+    let aggregate = match &value {
+        Value::Object(obj) => Some(obj),
+        Value::Array(arr) => arr.iter().filter_map(|v| v.as_object()).find(|obj| is_json_ld_type(obj, "AggregateOffer")),
+        _ => None,
+    }
+    .filter(|obj| is_json_ld_type(obj, "AggregateOffer"))?;
+
+    Some(PriceRange {
+        low_price: aggregate.get("lowPrice").and_then(json_scalar_string),
+        high_price: aggregate.get("highPrice").and_then(json_scalar_string),
+        currency: aggregate.get("priceCurrency").and_then(json_scalar_string),
+        offer_count: aggregate.get("offerCount").and_then(|c| {
+            c.as_u64().or_else(|| c.as_str().and_then(|s| s.trim().parse().ok()))
+        }),
+    })
+}
+
+/// The best single offer: the lowest-priced one in stock, or the lowest-priced one overall
+/// when none is declared in stock
+pub fn best_offer(dom_index: &DomIndex) -> Option<Offer> {
+    let offers = extract_offers(dom_index);
+    let amount = |offer: &Offer| {
+        offer.price.as_deref().and_then(|p| parse_price(p, offer.currency.as_deref())).map(|p| p.amount)
+    };
+    let in_stock = |offer: &Offer| {
+        matches!(
+            offer.availability.as_deref().map(normalize_availability),
+            Some(Availability::InStock | Availability::LimitedAvailability)
+        )
+    };
+    let lowest = |candidates: Vec<&Offer>| {
+        candidates
+            .into_iter()
+            .filter_map(|offer| amount(offer).map(|a| (offer, a)))
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(offer, _)| offer.clone())
+    };
+
+    lowest(offers.iter().filter(|o| in_stock(o)).collect())
+        .or_else(|| lowest(offers.iter().collect()))
+}
+
+/// Who sells the product on a marketplace page
+#[derive(Debug, Clone, Default)]
+pub struct Seller {
+    pub name: Option<String>,
+    /// As declared; may be relative
+    pub url: Option<String>,
+}
+
+/// The seller of the offer the price came from (else of the first offer naming one), then
+/// microdata `seller` of the main product, then the Open Graph `product:retailer_title` /
+/// `product:retailer`
+pub fn extract_product_seller(dom_index: &DomIndex) -> Option<Seller> {
+    let from_offer = |offer: Offer| {
+        offer.seller.is_some().then_some(Seller { name: offer.seller, url: offer.seller_url })
+    };
+    if let Some(seller) = best_offer(dom_index).and_then(from_offer) {
+        return Some(seller);
+    }
+    if let Some(seller) = extract_offers(dom_index).into_iter().find_map(from_offer) {
+        return Some(seller);
+    }
+
+    if let Some(seller) = microdata_seller(dom_index) {
+        return Some(seller);
+    }
+
+    extract_meta_property(dom_index, "product:retailer_title")
+        .or_else(|| extract_meta_property(dom_index, "product:retailer"))
+        .map(|name| collapse_whitespace(&name))
+        .filter(|name| !name.is_empty())
+        .map(|name| Seller { name: Some(name), url: None })
+}
+
+static SELLER_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("[itemprop~='seller']").unwrap());
+
+/// First `[itemprop=seller]` of the main product (or of the page when it has no Product
+/// item): an Organization/Person item's own `name` and `url`, or the element's text
+fn microdata_seller(dom_index: &DomIndex) -> Option<Seller> {
+    let element = match find_main_item(dom_index.document(), PRODUCT_ITEM_TYPES) {
+        Some(product) => product.select(&SELLER_SELECTOR).next(),
+        None => dom_index.document().select(&SELLER_SELECTOR).next(),
+    }?;
+
+    let seller = if element.value().attr("itemscope").is_some() {
+        Seller {
+            name: own_properties(element, "name").into_iter().find_map(property_value),
+            url: own_properties(element, "url").into_iter().find_map(property_value),
+        }
+    } else {
+        Seller { name: property_value(element), url: None }
+    };
+    let name = seller.name.map(|name| collapse_whitespace(&name)).filter(|name| !name.is_empty());
+    (name.is_some() || seller.url.is_some()).then_some(Seller { name, ..seller })
+}
@@ -1,46 +1,75 @@
-use scraper::{Html, Selector};
-use super::helpers::{extract_meta_property, extract_json_ld_property, extract_schema_property};
+use scraper::{ElementRef, Selector};
+use crate::dom_index::DomIndex;
+use super::helpers::{parse_selectors, extract_meta_property, extract_json_ld_property, extract_schema_property, extract_page_locale};
+use super::offers::{best_offer, extract_offers};
+use crate::article_extractor::{parse_date, to_iso_string};
+use crate::types::Availability;
+use once_cell::sync::Lazy;
 use regex::Regex;
 
-pub fn extract_product_price(document: &Html) -> Option<String> {
+pub fn extract_product_price(dom_index: &DomIndex) -> Option<String> {
+    extract_product_price_with_source(dom_index).map(|(price, _)| price)
+}
+
+/// The price and where it came from: `"meta"` (`product:price:amount`, `product:price`,
+/// `og:price:amount`), `"jsonld"` (the best offer, then any price property), `"microdata"`,
+/// or `"css"` (`data-*` price attributes and price-class elements)
+pub fn extract_product_price_with_source(dom_index: &DomIndex) -> Option<(String, &'static str)> {
     // Try product:price:amount meta property
-    if let Some(price) = extract_meta_property(document, "product:price:amount") {
-        return Some(price);
+    if let Some(price) = extract_meta_property(dom_index, "product:price:amount") {
+        return Some((price, "meta"));
     }
 
     // Try product:price meta property
-    if let Some(price) = extract_meta_property(document, "product:price") {
-        return Some(price);
+    if let Some(price) = extract_meta_property(dom_index, "product:price") {
+        return Some((price, "meta"));
+    }
+
+    // Try the og:-prefixed form many Shopify/WooCommerce themes emit instead
+    if let Some(price) = extract_meta_property(dom_index, "og:price:amount") {
+        return Some((price, "meta"));
+    }
+
+    // Try the lowest in-stock price across all JSON-LD offers
+    if let Some(price) = best_offer(dom_index).and_then(|offer| offer.price) {
+        return Some((price, "jsonld"));
     }
 
     // Try JSON-LD Product schema
-    if let Some(price) = extract_json_ld_property(document, &["price", "offers.price", "offers.lowPrice"]) {
-        return Some(price);
+    if let Some(price) = extract_json_ld_property(dom_index, &["price", "offers.price", "offers.lowPrice"]) {
+        return Some((price, "jsonld"));
     }
 
     // Try schema.org Product
-    if let Some(price) = extract_schema_property(document, "price") {
-        return Some(price);
+    if let Some(price) = extract_schema_property(dom_index, "price") {
+        return Some((price, "microdata"));
     }
 
-    // Try to find price in common class names/ids
-    let price_selectors = [
-        ".price", ".product-price", ".price-current", ".current-price",
-        "[itemprop='price']", "[data-price]", "#price"
-    ];
+    // The remaining fallbacks read page markup rather than structured data
+    if dom_index.structured_only {
+        return None;
+    }
 
-    for selector_str in &price_selectors {
-        if let Ok(selector) = Selector::parse(selector_str) {
-            for element in document.select(&selector) {
-                if let Some(price_attr) = element.value().attr("content") {
-                    return Some(price_attr.to_string());
-                }
-                let text = element.text().collect::<String>().trim().to_string();
-                if !text.is_empty() {
-                    // Try to extract numeric price from text
-                    if let Some(price) = extract_price_from_text(&text) {
-                        return Some(price);
-                    }
+    // Try data-* price attributes set by JS-driven shops, which hold the authoritative
+    // amount where the visible text may include struck-through prices
+    if let Some(price) = extract_price_data_attribute(dom_index) {
+        return Some((price, "css"));
+    }
+
+    // Try to find price in common class names/ids, ignoring struck-through (original) prices
+    for selector in PRICE_SELECTORS.iter() {
+        for element in dom_index.document().select(selector) {
+            if is_struck_through(element) {
+                continue;
+            }
+            if let Some(price_attr) = element.value().attr("content") {
+                return Some((price_attr.to_string(), "css"));
+            }
+            let text = unstruck_text(element);
+            if !text.is_empty() {
+                // Try to extract numeric price from text
+                if let Some(price) = extract_price_from_text(&text) {
+                    return Some((price, "css"));
                 }
             }
         }
@@ -49,72 +78,234 @@ pub fn extract_product_price(document: &Html) -> Option<String> {
     None
 }
 
-pub fn extract_product_currency(document: &Html) -> Option<String> {
+/// Class names/ids of the element showing the current price
+static PRICE_SELECTORS: Lazy<Vec<Selector>> = Lazy::new(|| parse_selectors(&[
+    ".price", ".product-price", ".price-current", ".current-price",
+    "[itemprop='price']", "[data-price]", "#price"
+]));
+
+/// Class names of the element showing the original/old price
+static ORIGINAL_PRICE_SELECTORS: Lazy<Vec<Selector>> = Lazy::new(|| parse_selectors(&[
+    ".original-price", ".old-price", ".price-original", ".was-price",
+    "[data-original-price]"
+]));
+
+/// Descendants of a price container that may mark a struck-through price
+static STRUCK_CANDIDATE_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("del, s, strike, [class]").unwrap());
+
+/// Class fragments marking a struck-through (original) price
+const STRUCK_CLASS_HINTS: &[&str] = &["strikethrough", "strike-through", "line-through", "linethrough"];
+
+/// Whether an element is, or sits inside, a `<del>`/`<s>`/`<strike>` or `.strikethrough`
+fn is_struck_through(element: ElementRef) -> bool {
+    std::iter::once(element)
+        .chain(element.ancestors().filter_map(ElementRef::wrap))
+        .any(is_struck_element)
+}
+
+fn is_struck_element(element: ElementRef) -> bool {
+    let value = element.value();
+    let class = value.attr("class").unwrap_or_default().to_lowercase();
+    matches!(value.name(), "del" | "s" | "strike")
+        || STRUCK_CLASS_HINTS.iter().any(|hint| class.contains(hint))
+}
+
+/// Text of a price container without its struck-through descendants
+fn unstruck_text(container: ElementRef) -> String {
+    container
+        .descendants()
+        .filter_map(|node| node.value().as_text().map(|text| (node, text)))
+        .filter(|(node, _)| {
+            !node.ancestors()
+                .take_while(|ancestor| ancestor.id() != container.id())
+                .filter_map(ElementRef::wrap)
+                .any(is_struck_element)
+        })
+        .map(|(_, text)| &**text)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// The first struck-through price in (or marking) a price container, taken as the original
+/// price next to the current (non-struck) one
+fn extract_struck_price(dom_index: &DomIndex) -> Option<String> {
+    for selector in PRICE_SELECTORS.iter() {
+        for container in dom_index.document().select(selector) {
+            let struck = std::iter::once(container)
+                .chain(container.select(&STRUCK_CANDIDATE_SELECTOR))
+                .filter(|element| is_struck_element(*element))
+                .find_map(|element| extract_price_from_text(element.text().collect::<String>().trim()));
+            if struck.is_some() {
+                return struck;
+            }
+        }
+    }
+    None
+}
+
+/// `data-*` attributes holding the current price, in order of preference
+const PRICE_DATA_ATTRIBUTES: &[&str] = &[
+    "data-price-amount", "data-product-price", "data-price", "data-sale-price",
+    "data-current-price", "data-final-price", "data-price-value",
+];
+
+/// `[attribute]` selector for each of `PRICE_DATA_ATTRIBUTES`
+static PRICE_DATA_SELECTORS: Lazy<Vec<(&str, Selector)>> = Lazy::new(|| {
+    PRICE_DATA_ATTRIBUTES
+        .iter()
+        .map(|attribute| (*attribute, Selector::parse(&format!("[{}]", attribute)).unwrap()))
+        .collect()
+});
+
+/// First numeric `data-*` price attribute in the document, skipping old prices (Magento's
+/// `data-price-type="oldPrice"`) and struck-through values
+fn extract_price_data_attribute(dom_index: &DomIndex) -> Option<String> {
+    for (attribute, selector) in PRICE_DATA_SELECTORS.iter() {
+        for element in dom_index.document().select(selector) {
+            let is_old_price = element.value().attr("data-price-type")
+                .is_some_and(|t| t.to_lowercase().contains("old"));
+            if is_old_price || is_struck_through(element) {
+                continue;
+            }
+            if let Some(value) = element.value().attr(attribute).map(str::trim) {
+                if parse_price(value, None).is_some() {
+                    return Some(value.to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+pub fn extract_product_currency(dom_index: &DomIndex) -> Option<String> {
     // Try product:price:currency meta property
-    if let Some(currency) = extract_meta_property(document, "product:price:currency") {
+    if let Some(currency) = extract_meta_property(dom_index, "product:price:currency") {
+        return Some(currency);
+    }
+
+    // Try og:price:currency meta property
+    if let Some(currency) = extract_meta_property(dom_index, "og:price:currency") {
+        return Some(currency);
+    }
+
+    // Try the currency of the offer the price was taken from
+    if let Some(currency) = best_offer(dom_index).and_then(|offer| offer.currency) {
         return Some(currency);
     }
 
     // Try JSON-LD Product schema
-    if let Some(currency) = extract_json_ld_property(document, &["priceCurrency", "offers.priceCurrency"]) {
+    if let Some(currency) = extract_json_ld_property(dom_index, &["priceCurrency", "offers.priceCurrency"]) {
         return Some(currency);
     }
 
     // Try schema.org Product
-    if let Some(currency) = extract_schema_property(document, "priceCurrency") {
+    if let Some(currency) = extract_schema_property(dom_index, "priceCurrency") {
         return Some(currency);
     }
 
     None
 }
 
-pub fn extract_product_availability(document: &Html) -> Option<String> {
+/// The declared product currency, or one inferred from the price's currency symbol with
+/// `infer_currency`. The flag is `true` when the currency was inferred.
+pub fn resolve_product_currency(dom_index: &DomIndex) -> Option<(String, bool)> {
+    if let Some(currency) = extract_product_currency(dom_index) {
+        return Some((currency, false));
+    }
+    if dom_index.structured_only {
+        return None;
+    }
+    let price = extract_product_price(dom_index)?;
+    let locale = extract_page_locale(dom_index);
+    infer_currency(&price, locale.as_deref()).map(|currency| (currency, true))
+}
+
+/// The declared availability and where it came from: `"meta"` (`product:availability`,
+/// `og:availability`), `"jsonld"` (the best offer, then any availability property) or
+/// `"microdata"`
+pub fn extract_product_availability_with_source(dom_index: &DomIndex) -> Option<(String, &'static str)> {
     // Try product:availability meta property
-    if let Some(availability) = extract_meta_property(document, "product:availability") {
-        return Some(availability);
+    if let Some(availability) = extract_meta_property(dom_index, "product:availability") {
+        return Some((availability, "meta"));
+    }
+
+    // Try og:availability meta property
+    if let Some(availability) = extract_meta_property(dom_index, "og:availability") {
+        return Some((availability, "meta"));
+    }
+
+    // Try the availability of the offer the price was taken from
+    if let Some(availability) = best_offer(dom_index).and_then(|offer| offer.availability) {
+        return Some((availability, "jsonld"));
     }
 
     // Try JSON-LD Product schema
-    if let Some(availability) = extract_json_ld_property(document, &["availability", "offers.availability"]) {
-        return Some(availability);
+    if let Some(availability) = extract_json_ld_property(dom_index, &["availability", "offers.availability"]) {
+        return Some((availability, "jsonld"));
     }
 
     // Try schema.org Product
-    if let Some(availability) = extract_schema_property(document, "availability") {
-        return Some(availability);
+    if let Some(availability) = extract_schema_property(dom_index, "availability") {
+        return Some((availability, "microdata"));
     }
 
     None
 }
 
-pub fn extract_product_original_price(document: &Html) -> Option<String> {
+/// The original price and where it came from: `"meta"` (`product:original_price` or
+/// `og:price:standard_amount`), `"jsonld_pricespec"` (a list/strikethrough
+/// `priceSpecification`), `"jsonld"` (`offers.highPrice`/`originalPrice`) or `"css"`
+/// (struck-through or `.old-price`-style text)
+pub fn extract_product_original_price_with_source(dom_index: &DomIndex) -> Option<(String, &'static str)> {
     // Try product:original_price meta property
-    if let Some(price) = extract_meta_property(document, "product:original_price") {
-        return Some(price);
+    if let Some(price) = extract_meta_property(dom_index, "product:original_price") {
+        return Some((price, "meta"));
+    }
+
+    // Try og:price:standard_amount meta property
+    if let Some(price) = extract_meta_property(dom_index, "og:price:standard_amount") {
+        return Some((price, "meta"));
+    }
+
+    // Try the list/strikethrough price specification of the offer the price came from,
+    // then of any offer
+    let list_price = best_offer(dom_index)
+        .and_then(|offer| offer.list_price)
+        .or_else(|| extract_offers(dom_index).into_iter().find_map(|offer| offer.list_price));
+    if let Some(price) = list_price {
+        return Some((price, "jsonld_pricespec"));
     }
 
     // Try JSON-LD Product schema
-    if let Some(price) = extract_json_ld_property(document, &["offers.highPrice", "originalPrice"]) {
+    if let Some(price) = extract_json_ld_property(dom_index, &["offers.highPrice", "originalPrice"]) {
+        return Some((price, "jsonld"));
+    }
+
+    if dom_index.structured_only {
+        return None;
+    }
+    extract_css_original_price(dom_index).map(|price| (price, "css"))
+}
+
+/// Original price shown in the page: struck-through next to the current price, else in an
+/// `.old-price`-style element
+fn extract_css_original_price(dom_index: &DomIndex) -> Option<String> {
+    // Try a struck-through price shown next to the current price
+    if let Some(price) = extract_struck_price(dom_index) {
         return Some(price);
     }
 
     // Try common class names for original/old price
-    let price_selectors = [
-        ".original-price", ".old-price", ".price-original", ".was-price",
-        "[data-original-price]"
-    ];
-
-    for selector_str in &price_selectors {
-        if let Ok(selector) = Selector::parse(selector_str) {
-            for element in document.select(&selector) {
-                if let Some(price_attr) = element.value().attr("content") {
-                    return Some(price_attr.to_string());
-                }
-                let text = element.text().collect::<String>().trim().to_string();
-                if !text.is_empty() {
-                    if let Some(price) = extract_price_from_text(&text) {
-                        return Some(price);
-                    }
+    for selector in ORIGINAL_PRICE_SELECTORS.iter() {
+        for element in dom_index.document().select(selector) {
+            if let Some(price_attr) = element.value().attr("content") {
+                return Some(price_attr.to_string());
+            }
+            let text = element.text().collect::<String>().trim().to_string();
+            if !text.is_empty() {
+                if let Some(price) = extract_price_from_text(&text) {
+                    return Some(price);
                 }
             }
         }
@@ -123,23 +314,473 @@ pub fn extract_product_original_price(document: &Html) -> Option<String> {
     None
 }
 
-/// Extract price from text using regex (e.g., "$19.99", "€25,50", "£10.00")
+/// `priceValidUntil` of the offer the price came from (else of the first offer declaring
+/// one, else microdata), normalized to ISO 8601. Date-only values stay dates; values that
+/// can't be parsed are kept as written.
+pub fn extract_product_price_valid_until(dom_index: &DomIndex) -> Option<String> {
+    let value = best_offer(dom_index)
+        .and_then(|offer| offer.price_valid_until)
+        .or_else(|| extract_offers(dom_index).into_iter().find_map(|offer| offer.price_valid_until))
+        .or_else(|| extract_schema_property(dom_index, "priceValidUntil"))?;
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok() {
+        return Some(value.to_string());
+    }
+    Some(parse_date(value).map(|date| to_iso_string(&date)).unwrap_or_else(|| value.to_string()))
+}
+
+/// How far the original price must exceed the current one before the product counts as on sale
+pub const DEFAULT_SALE_EPSILON: f64 = 0.01;
+
+/// Discount of the current price against the original ("was") price
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Discount {
+    /// `(original - current) / original * 100`, rounded to one decimal; None unless on sale
+    pub percent: Option<f64>,
+    pub on_sale: bool,
+}
+
+/// Compare a current and an original price as written on the page
+///
+/// Both are parsed with `parse_price` using `currency` (else the symbol or code written next
+/// to either price). None when either doesn't parse or they are written in different
+/// currencies. An original price that doesn't exceed the current one by more than `epsilon`
+/// (including swapped values) gives `on_sale: false` and no percentage.
+pub fn compute_discount(price: &str, original_price: &str, currency: Option<&str>, epsilon: f64) -> Option<Discount> {
+    let written_price = parse_price(price, None)?;
+    let written_original = parse_price(original_price, None)?;
+    if let (Some(a), Some(b)) = (&written_price.currency, &written_original.currency) {
+        if a != b {
+            return None;
+        }
+    }
+
+    let currency = currency
+        .map(str::to_string)
+        .or(written_price.currency)
+        .or(written_original.currency);
+    let current = parse_price(price, currency.as_deref())?.amount;
+    let original = parse_price(original_price, currency.as_deref())?.amount;
+
+    if original > 0.0 && original - current > epsilon {
+        let percent = ((original - current) / original * 1000.0).round() / 10.0;
+        Some(Discount { percent: Some(percent), on_sale: true })
+    } else {
+        Some(Discount { percent: None, on_sale: false })
+    }
+}
+
+/// Extract the first price written with a currency symbol or code from free text
+/// ("$19.99", "€25,50", "1.299,00 €", "19.99 USD"), or a range of two prices ("$10 – $25").
 fn extract_price_from_text(text: &str) -> Option<String> {
-    // Match prices like $19.99, €25.50, £10.00, 19.99 USD
-    let patterns = [
-        r#"[£$€¥]\s*[\d,]+\.?\d*"#,  // Currency symbol before number
-        r#"[\d,]+\.?\d*\s*[£$€¥]"#,  // Currency symbol after number
-        r#"[\d,]+\.?\d*\s*(USD|EUR|GBP|JPY|CAD|AUD)"#,  // Currency code
-    ];
-
-    for pattern in &patterns {
-        if let Ok(re) = Regex::new(pattern) {
-            if let Some(captures) = re.find(text) {
-                return Some(captures.as_str().trim().to_string());
-            }
+    parse_price(text, None)
+        .filter(|price| price.currency.is_some())
+        .map(|price| price.raw)
+}
+
+/// A price parsed from free text
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedPrice {
+    /// Numeric amount, with thousands separators and decimal commas resolved
+    pub amount: f64,
+    /// ISO 4217 code: the hint, else the symbol or code next to the number, else any in the text
+    pub currency: Option<String>,
+    /// The price as written, including its symbol or code ("1.299,00 €") and, for ranges,
+    /// both ends ("$10 – $25")
+    pub raw: String,
+    /// Whether the text is a range ("$10 – $25"); `amount` is then the low end
+    pub is_range: bool,
+}
+
+/// Currencies whose prices are conventionally written with a decimal comma ("1.299,00 €")
+const DECIMAL_COMMA_CURRENCIES: &[&str] = &[
+    "EUR", "ARS", "BRL", "CLP", "COP", "CZK", "DKK", "HUF", "IDR",
+    "NOK", "PLN", "RON", "RUB", "SEK", "TRY", "UAH", "VND",
+];
+
+/// Currencies always written with a decimal point, whatever side the symbol is on
+const DECIMAL_POINT_CURRENCIES: &[&str] = &["GBP", "JPY", "CNY", "INR", "KRW", "MXN"];
+
+/// Currency symbols (longest first, so "R$" wins over "$") and the code they imply
+const CURRENCY_SYMBOLS: &[(&str, &str)] = &[
+    ("US$", "USD"), ("CA$", "CAD"), ("AU$", "AUD"), ("HK$", "HKD"),
+    ("R$", "BRL"), ("C$", "CAD"), ("A$", "AUD"), ("zł", "PLN"), ("€", "EUR"), ("£", "GBP"),
+    ("¥", "JPY"), ("₹", "INR"), ("₽", "RUB"), ("₺", "TRY"), ("₩", "KRW"), ("$", "USD"),
+];
+
+/// ISO 4217 codes recognized next to a number ("19.99 USD", "EUR 25,50")
+const CURRENCY_CODES: &[&str] = &[
+    "USD", "EUR", "GBP", "JPY", "CAD", "AUD", "CHF", "CNY", "INR", "BRL",
+    "PLN", "SEK", "NOK", "DKK", "CZK", "HUF", "RUB", "TRY", "MXN", "HKD",
+];
+
+/// Words and dashes separating the two ends of a price range
+const RANGE_SEPARATORS: &[&str] = &["-", "–", "—", "~", "to", "bis", "à", "a"];
+
+static CURRENCY_CODE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(r"\b(?:{})\b", CURRENCY_CODES.join("|"))).unwrap()
+});
+
+/// A number with optional separators: space/apostrophe-grouped thousands ("1 299,00",
+/// "1'299.00") or any run of digits, dots and commas
+const PRICE_NUMBER: &str = r"\d{1,3}(?:[ \u{00A0}\u{202F}']\d{3})+(?:[.,]\d+)?|\d(?:[\d.,]*\d)?";
+
+/// A number with the currency symbol or code written right before or after it
+static PRICE_RE: Lazy<Regex> = Lazy::new(|| {
+    let symbols: Vec<String> = CURRENCY_SYMBOLS.iter().map(|(symbol, _)| regex::escape(symbol)).collect();
+    let currency = format!(r"{}|\b(?:{})\b", symbols.join("|"), CURRENCY_CODES.join("|"));
+    Regex::new(&format!(
+        r"(?:(?P<prefix>{currency})\s*)?(?P<number>{PRICE_NUMBER})(?:\s*(?P<suffix>{currency}))?"
+    ))
+    .unwrap()
+});
+
+/// ISO code of a currency symbol or code as matched next to a number
+fn currency_code(marker: &str) -> Option<String> {
+    if CURRENCY_CODES.contains(&marker) {
+        return Some(marker.to_string());
+    }
+    CURRENCY_SYMBOLS
+        .iter()
+        .find(|(symbol, _)| *symbol == marker)
+        .map(|(_, code)| code.to_string())
+}
+
+/// Countries whose own dollar is written as a bare "$"
+const DOLLAR_REGIONS: &[(&str, &str)] = &[
+    ("CA", "CAD"), ("AU", "AUD"), ("NZ", "NZD"), ("HK", "HKD"), ("SG", "SGD"),
+    ("MX", "MXN"), ("AR", "ARS"), ("CL", "CLP"), ("CO", "COP"),
+];
+
+/// Infer the ISO 4217 code of a price written with a currency symbol or code, for pages that
+/// don't declare one. The ambiguous symbols are resolved with the page locale (`og:locale` or
+/// `<html lang>`, e.g. "en_CA", "zh-CN"): "$" is USD unless the locale's region has its own
+/// dollar (CAD, AUD, NZD, ...), and "¥" is JPY unless the locale is Chinese (CNY).
+pub fn infer_currency(price_text: &str, locale: Option<&str>) -> Option<String> {
+    let code = detect_currency(price_text)?;
+    let (language, region) = locale.map(split_locale).unwrap_or_default();
+
+    let bare_dollar = code == "USD" && !price_text.contains("US$") && !CURRENCY_CODE_RE.is_match(price_text);
+    if bare_dollar {
+        if let Some((_, dollar)) = DOLLAR_REGIONS.iter().find(|(r, _)| Some(*r) == region.as_deref()) {
+            return Some(dollar.to_string());
         }
     }
+    if code == "JPY" && price_text.contains('¥') && language.as_deref() == Some("zh") {
+        return Some("CNY".to_string());
+    }
+    Some(code)
+}
 
-    None
+/// Split a locale such as "en_CA", "fr-CA" or "zh-Hans-CN" into its lowercased language and
+/// uppercased region
+fn split_locale(locale: &str) -> (Option<String>, Option<String>) {
+    let mut parts = locale.split(['_', '-']).filter(|p| !p.is_empty());
+    let language = parts.next().map(|l| l.to_lowercase());
+    let region = parts
+        .rfind(|p| p.len() == 2 && p.chars().all(|c| c.is_ascii_alphabetic()))
+        .map(|r| r.to_uppercase());
+    (language, region)
+}
+
+/// Detect the currency a price string is written in, from an ISO code or a currency symbol
+pub fn detect_currency(text: &str) -> Option<String> {
+    if let Some(code) = CURRENCY_CODE_RE.find(text) {
+        return Some(code.as_str().to_string());
+    }
+    CURRENCY_SYMBOLS
+        .iter()
+        .find(|(symbol, _)| text.contains(symbol))
+        .map(|(_, code)| code.to_string())
 }
 
+/// Parse a price written in free text ("$1,299.00", "1299", "1.299,00 €", "EUR 25,50",
+/// "$10 – $25")
+///
+/// When both `.` and `,` appear, the last one is the decimal separator. A single separator
+/// followed by exactly three digits is ambiguous ("1,299" vs "1.299"): it is read as the
+/// decimal separator only if it matches the currency's convention. The currency is
+/// `currency_hint`, else the symbol or code next to the number, else any found in the text.
+/// A symbol (not an ISO code) written after the number ("10,50 $") implies the decimal-comma
+/// convention unless the currency always uses a decimal point.
+///
+/// For ranges, the low end is returned with `is_range` set.
+pub fn parse_price(text: &str, currency_hint: Option<&str>) -> Option<ParsedPrice> {
+    let mut matches = PRICE_RE.captures_iter(text);
+    let first = matches.next()?;
+    let whole = first.get(0)?;
+    let number = first.name("number")?.as_str();
+
+    let range_end = matches.next().and_then(|second| {
+        let second = second.get(0)?;
+        let between = text[whole.end()..second.start()].trim();
+        RANGE_SEPARATORS
+            .iter()
+            .any(|separator| between.eq_ignore_ascii_case(separator))
+            .then_some(second.end())
+    });
+
+    let prefix = first.name("prefix").map(|m| m.as_str());
+    let suffix = first.name("suffix").map(|m| m.as_str());
+    let currency = currency_hint
+        .map(|c| c.trim().to_uppercase())
+        .or_else(|| prefix.or(suffix).and_then(currency_code))
+        .or_else(|| detect_currency(text));
+
+    let uses = |list: &[&str]| currency.as_deref().is_some_and(|c| list.contains(&c));
+    // Only a trailing symbol hints at the decimal-comma convention; "1,299 USD" is a dollar
+    // amount written the US way
+    let symbol_suffix = suffix.is_some_and(|s| !CURRENCY_CODES.contains(&s));
+    let decimal_comma = uses(DECIMAL_COMMA_CURRENCIES)
+        || (prefix.is_none() && symbol_suffix && !uses(DECIMAL_POINT_CURRENCIES));
+
+    Some(ParsedPrice {
+        amount: parse_amount(number, decimal_comma)?,
+        currency,
+        raw: text[whole.start()..range_end.unwrap_or(whole.end())].trim().to_string(),
+        is_range: range_end.is_some(),
+    })
+}
+
+/// Parse the numeric part of a price, given whether the locale writes a decimal comma
+fn parse_amount(number: &str, decimal_comma: bool) -> Option<f64> {
+    let digits: String = number
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '\u{00A0}' | '\u{202F}' | '\''))
+        .collect();
+    let decimal_separator = match (digits.rfind('.'), digits.rfind(',')) {
+        (Some(dot), Some(comma)) => Some(if dot > comma { '.' } else { ',' }),
+        (Some(_), None) => lone_decimal_separator(&digits, '.', decimal_comma),
+        (None, Some(_)) => lone_decimal_separator(&digits, ',', decimal_comma),
+        (None, None) => None,
+    };
+
+    let normalized: String = digits
+        .chars()
+        .filter_map(|c| match c {
+            '0'..='9' => Some(c),
+            c if Some(c) == decimal_separator => Some('.'),
+            _ => None,
+        })
+        .collect();
+    normalized.parse().ok()
+}
+
+/// Whether the only separator kind in `digits` is the decimal separator: repeated
+/// separators group thousands, and a single one before exactly three digits follows the
+/// locale convention
+fn lone_decimal_separator(digits: &str, separator: char, decimal_comma: bool) -> Option<char> {
+    if digits.matches(separator).count() > 1 {
+        return None;
+    }
+    let fraction_len = digits.len() - digits.rfind(separator)? - 1;
+    if fraction_len != 3 {
+        return Some(separator);
+    }
+    let locale_decimal = if decimal_comma { ',' } else { '.' };
+    (separator == locale_decimal).then_some(separator)
+}
+
+/// Normalize an availability value to the `Availability` enum: schema.org names with or
+/// without the `http(s)://schema.org/` prefix (`InStock`, `schema:OutOfStock`), Open Graph
+/// values (`instock`, `oos`, `available for order`) and common free-text phrases in English,
+/// German, French, Spanish, Italian and Dutch ("in stock", "sold out", "auf Lager",
+/// "épuisé", ...). Anything else is `Availability::Unknown`.
+pub fn normalize_availability(value: &str) -> Availability {
+    let value = value.trim();
+    let name = SCHEMA_PREFIXES
+        .iter()
+        .find_map(|prefix| {
+            value.get(..prefix.len())
+                .filter(|head| head.eq_ignore_ascii_case(prefix))
+                .map(|_| &value[prefix.len()..])
+        })
+        .unwrap_or(value);
+    let key: String = name
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .collect::<String>()
+        .to_lowercase();
+    if key.is_empty() {
+        return Availability::Unknown;
+    }
+
+    // Negated and more specific phrases first: "not in stock" contains "instock",
+    // "available for preorder" contains "available"
+    let contains_any = |phrases: &[&str]| phrases.iter().any(|phrase| key.contains(phrase));
+    let has_word = |words: &[&str]| {
+        name.split(|c: char| !c.is_alphabetic())
+            .any(|word| words.iter().any(|w| word.eq_ignore_ascii_case(w)))
+    };
+    if key == "oos" || contains_any(OUT_OF_STOCK_PHRASES) {
+        Availability::OutOfStock
+    } else if contains_any(DISCONTINUED_PHRASES) {
+        Availability::Discontinued
+    } else if contains_any(PRE_ORDER_PHRASES) {
+        Availability::PreOrder
+    } else if contains_any(BACK_ORDER_PHRASES) {
+        Availability::BackOrder
+    } else if contains_any(LIMITED_PHRASES) || has_word(LIMITED_WORDS) {
+        Availability::LimitedAvailability
+    } else if contains_any(IN_STOCK_PHRASES) {
+        Availability::InStock
+    } else {
+        Availability::Unknown
+    }
+}
+
+/// Prefixes of schema.org availability URLs and CURIEs, stripped before matching
+const SCHEMA_PREFIXES: &[&str] = &[
+    "https://schema.org/", "http://schema.org/", "https://www.schema.org/", "http://www.schema.org/",
+    "schema:",
+];
+
+// Phrases are matched against the value lowercased with everything but letters removed
+
+const OUT_OF_STOCK_PHRASES: &[&str] = &[
+    "outofstock", "soldout", "notinstock", "notavailable", "unavailable", "currentlyunavailable",
+    "ausverkauft", "nichtverfügbar", "nichtauflager", "nichtlieferbar",
+    "rupturedestock", "épuisé", "epuise", "indisponible", "enrupture",
+    "agotado", "sinexistencias", "nodisponible",
+    "esaurito", "nondisponibile",
+    "uitverkocht", "nietopvoorraad", "nietbeschikbaar",
+];
+
+const DISCONTINUED_PHRASES: &[&str] = &[
+    "discontinued", "nolongeravailable", "auslaufartikel", "nichtmehrverfügbar",
+    "plusdisponible", "descatalogado", "fuoriproduzione",
+];
+
+const PRE_ORDER_PHRASES: &[&str] = &[
+    "preorder", "presale", "vorbestell", "précommande", "precommande", "preventa", "reserva",
+    "preordine", "voorbestel",
+];
+
+const BACK_ORDER_PHRASES: &[&str] = &[
+    "backorder", "availablefororder", "nachbestellt", "enréapprovisionnement", "bajopedido",
+];
+
+const LIMITED_PHRASES: &[&str] = &[
+    "limitedavailability", "limitedstock", "fewleft", "lowstock", "lastitems",
+    "nurnoch", "wenigevorrätig", "stockslimités", "pocasunidades", "ultimipezzi",
+];
+
+/// Limited-stock words only matched whole ("only 3 left", not "leftover")
+const LIMITED_WORDS: &[&str] = &["left"];
+
+const IN_STOCK_PHRASES: &[&str] = &[
+    "instock", "onlineonly", "instoreonly", "available", "inlager", "auflager", "lieferbar",
+    "verfügbar", "enstock", "disponible", "enexistencia", "disponibile", "opvoorraad",
+    "beschikbaar",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (text, currency hint, amount, currency, is_range)
+    type PriceCase = (&'static str, Option<&'static str>, f64, Option<&'static str>, bool);
+
+    #[test]
+    fn parse_price_table() {
+        let cases: &[PriceCase] = &[
+            // Thousands and decimal separators
+            ("$1,299.00", None, 1299.0, Some("USD"), false),
+            ("$1,299", None, 1299.0, Some("USD"), false),
+            ("$19.99", None, 19.99, Some("USD"), false),
+            ("1299", None, 1299.0, None, false),
+            ("1.299,00 €", None, 1299.0, Some("EUR"), false),
+            ("1.299 €", None, 1299.0, Some("EUR"), false),
+            ("€25,50", None, 25.5, Some("EUR"), false),
+            ("1 299,00 zł", None, 1299.0, Some("PLN"), false),
+            ("1\u{00A0}299,00 €", None, 1299.0, Some("EUR"), false),
+            ("CHF 1'299.50", None, 1299.5, Some("CHF"), false),
+            ("1,234,567.89 USD", None, 1_234_567.89, Some("USD"), false),
+            ("1.234.567,89 EUR", None, 1_234_567.89, Some("EUR"), false),
+            // Symbol vs ISO code, before and after the number
+            ("19.99 USD", None, 19.99, Some("USD"), false),
+            ("USD 19.99", None, 19.99, Some("USD"), false),
+            ("EUR 25,50", None, 25.5, Some("EUR"), false),
+            ("1,299 USD", None, 1299.0, Some("USD"), false),
+            ("10,50 $", None, 10.5, Some("USD"), false),
+            ("£1,299", None, 1299.0, Some("GBP"), false),
+            ("1,299 £", None, 1299.0, Some("GBP"), false),
+            ("R$ 1.299,90", None, 1299.9, Some("BRL"), false),
+            ("US$ 5", None, 5.0, Some("USD"), false),
+            ("¥1,200", None, 1200.0, Some("JPY"), false),
+            // The hint wins over the symbol and picks the convention
+            ("1.299", Some("eur"), 1299.0, Some("EUR"), false),
+            ("1.299", Some("USD"), 1.299, Some("USD"), false),
+            ("$12", Some("CAD"), 12.0, Some("CAD"), false),
+            // Ranges report the low end
+            ("$10 – $25", None, 10.0, Some("USD"), true),
+            ("10 - 25 €", None, 10.0, Some("EUR"), true),
+            ("ab 10 bis 25 EUR", None, 10.0, Some("EUR"), true),
+            ("$10 or $25", None, 10.0, Some("USD"), false),
+        ];
+        for &(text, hint, amount, currency, is_range) in cases {
+            let parsed = parse_price(text, hint).unwrap_or_else(|| panic!("no price in {:?}", text));
+            assert!((parsed.amount - amount).abs() < 1e-9, "{:?}: amount {} != {}", text, parsed.amount, amount);
+            assert_eq!(parsed.currency.as_deref(), currency, "{:?}: currency", text);
+            assert_eq!(parsed.is_range, is_range, "{:?}: is_range", text);
+        }
+    }
+
+    #[test]
+    fn parse_price_raw_keeps_marker_and_range() {
+        assert_eq!(parse_price("Now only 1.299,00 € incl. VAT", None).unwrap().raw, "1.299,00 €");
+        assert_eq!(parse_price("From $10 – $25 per month", None).unwrap().raw, "$10 – $25");
+        assert_eq!(parse_price("no digits here", None), None);
+    }
+
+    #[test]
+    fn infer_currency_table() {
+        // (price text, page locale, inferred currency)
+        let cases: &[(&str, Option<&str>, Option<&str>)] = &[
+            ("$19.99", None, Some("USD")),
+            ("$19.99", Some("en_US"), Some("USD")),
+            ("$19.99", Some("en_CA"), Some("CAD")),
+            ("$19.99", Some("fr-CA"), Some("CAD")),
+            ("$19.99", Some("en-AU"), Some("AUD")),
+            ("$19.99", Some("es_MX"), Some("MXN")),
+            ("US$19.99", Some("en_CA"), Some("USD")),
+            ("19.99 USD", Some("en_AU"), Some("USD")),
+            ("C$19.99", None, Some("CAD")),
+            ("¥1200", None, Some("JPY")),
+            ("¥1200", Some("ja_JP"), Some("JPY")),
+            ("¥1200", Some("zh-Hans-CN"), Some("CNY")),
+            ("€25", Some("en_CA"), Some("EUR")),
+            ("R$ 10", None, Some("BRL")),
+            ("25 zł", None, Some("PLN")),
+            ("1299", Some("en_CA"), None),
+        ];
+        for &(text, locale, expected) in cases {
+            assert_eq!(infer_currency(text, locale).as_deref(), expected, "{:?} with {:?}", text, locale);
+        }
+    }
+
+    #[test]
+    fn normalize_availability_table() {
+        let cases: &[(&str, Availability)] = &[
+            ("https://schema.org/InStock", Availability::InStock),
+            ("http://schema.org/OutOfStock", Availability::OutOfStock),
+            ("schema:PreOrder", Availability::PreOrder),
+            ("instock", Availability::InStock),
+            ("oos", Availability::OutOfStock),
+            ("In stock: ships in 2 days", Availability::InStock),
+            ("Sold out: back soon", Availability::OutOfStock),
+            ("Only 3 left", Availability::LimitedAvailability),
+            ("Leftover stock available", Availability::InStock),
+            ("not in stock", Availability::OutOfStock),
+            ("auf Lager", Availability::InStock),
+            ("", Availability::Unknown),
+            ("call us", Availability::Unknown),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(&normalize_availability(value), expected, "{:?}", value);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use serde_json::{Map, Value};
+use crate::dom_index::DomIndex;
+use super::helpers::{extract_json_ld_value, json_number};
+use crate::json_ld::has_json_ld_type;
+use super::shipping::offer_objects;
+
+/// Seller types whose `hasMerchantReturnPolicy` stands in for the product's own
+const SELLER_TYPES: &[&str] = &["Organization", "OnlineStore", "OnlineBusiness", "Store", "LocalBusiness"];
+
+/// Return window and fees from schema.org `MerchantReturnPolicy`
+#[derive(Debug, Clone, Default)]
+pub struct ReturnPolicy {
+    /// Days allowed for returns, `"0"` when returns aren't permitted, `"unlimited"` for an
+    /// unlimited window
+    pub days: Option<String>,
+    /// `"free"` or `"paid"`
+    pub fees: Option<&'static str>,
+}
+
+/// The return policy of the first offer declaring `hasMerchantReturnPolicy`, else the
+/// product's, else the seller organization's. With several entries (one per country), the
+/// first stating a window or fees is used.
+pub fn extract_return_policy(dom_index: &DomIndex) -> Option<ReturnPolicy> {
+    let from_offers = extract_json_ld_value(dom_index, "offers").and_then(|offers| {
+        offer_objects(&offers).into_iter().find_map(|offer| offer.get("hasMerchantReturnPolicy").and_then(parse_policies))
+    });
+    from_offers
+        .or_else(|| extract_json_ld_value(dom_index, "hasMerchantReturnPolicy").and_then(|value| parse_policies(&value)))
+        .or_else(|| {
+            dom_index.get_json_ld_entities()
+                .filter(|obj| has_json_ld_type(obj, SELLER_TYPES))
+                .find_map(|obj| obj.get("hasMerchantReturnPolicy").and_then(parse_policies))
+        })
+}
+
+fn parse_policies(value: &Value) -> Option<ReturnPolicy> {
+    match value {
+        Value::Array(arr) => arr.iter().find_map(parse_policies),
+        Value::Object(obj) => {
+            let policy = ReturnPolicy { days: return_days(obj), fees: return_fees(obj) };
+            (policy.days.is_some() || policy.fees.is_some()).then_some(policy)
+        }
+        _ => None,
+    }
+}
+
+/// Name of a schema.org enumeration member, with or without its URL or `schema:` prefix
+fn enum_name(value: &Value) -> Option<&str> {
+    let text = match value {
+        Value::Object(obj) => obj.get("@id")?.as_str()?,
+        _ => value.as_str()?,
+    };
+    text.trim().rsplit(['/', ':']).next()
+}
+
+/// `merchantReturnDays` as whole days; the window from `returnPolicyCategory` when it's
+/// not a finite one
+fn return_days(policy: &Map<String, Value>) -> Option<String> {
+    match policy.get("returnPolicyCategory").and_then(enum_name) {
+        Some("MerchantReturnNotPermitted") => return Some("0".to_string()),
+        Some("MerchantReturnUnlimitedWindow") => return Some("unlimited".to_string()),
+        _ => {}
+    }
+    policy.get("merchantReturnDays")
+        .and_then(json_number)
+        .filter(|days| *days >= 0.0)
+        .map(|days| (days.ceil() as u32).to_string())
+}
+
+/// `returnFees` (`FreeReturn` is free; `ReturnFeesCustomerResponsibility`,
+/// `ReturnShippingFees`, `RestockingFees` and `OriginalShippingFees` are paid), else
+/// whether `returnShippingFeesAmount` is zero
+fn return_fees(policy: &Map<String, Value>) -> Option<&'static str> {
+    match policy.get("returnFees").and_then(enum_name) {
+        Some("FreeReturn") => return Some("free"),
+        Some("ReturnFeesCustomerResponsibility" | "ReturnShippingFees" | "RestockingFees" | "OriginalShippingFees") => {
+            return Some("paid")
+        }
+        _ => {}
+    }
+    let amount = policy.get("returnShippingFeesAmount")?;
+    let value = match amount {
+        Value::Object(obj) => obj.get("value").and_then(json_number),
+        _ => json_number(amount),
+    }?;
+    Some(if value > 0.0 { "paid" } else { "free" })
+}
@@ -1,33 +1,48 @@
-use scraper::{Html, Selector};
-use super::helpers::{extract_json_ld_property, extract_schema_property};
-
-pub fn extract_product_rating(document: &Html) -> Option<String> {
+use scraper::{ElementRef, Selector};
+use crate::dom_index::DomIndex;
+use serde_json::{Map, Value};
+use super::helpers::{parse_selectors, entity_name, extract_json_ld_property, extract_json_ld_value, extract_schema_property, json_scalar_string};
+use crate::types::Review;
+use crate::microdata::{own_properties, property_value};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static RATING_SELECTORS: Lazy<Vec<Selector>> = Lazy::new(|| parse_selectors(&[
+    "[itemprop='ratingValue']", ".rating", ".product-rating",
+    "[data-rating]", ".star-rating"
+]));
+
+static REVIEW_COUNT_SELECTORS: Lazy<Vec<Selector>> = Lazy::new(|| parse_selectors(&[
+    "[itemprop='reviewCount']", ".review-count", ".reviews-count",
+    "[data-review-count]"
+]));
+
+static REVIEW_ITEM_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("[itemscope][itemtype*='Review']").unwrap());
+
+/// The aggregate rating and where it came from: `"jsonld"`, `"microdata"` or `"css"`
+pub fn extract_product_rating_with_source(dom_index: &DomIndex) -> Option<(String, &'static str)> {
     // Try JSON-LD Product schema
-    if let Some(rating) = extract_json_ld_property(document, &["aggregateRating.ratingValue", "ratingValue"]) {
-        return Some(rating);
+    if let Some(rating) = extract_json_ld_property(dom_index, &["aggregateRating.ratingValue", "ratingValue"]) {
+        return Some((rating, "jsonld"));
     }
 
     // Try schema.org Product
-    if let Some(rating) = extract_schema_property(document, "ratingValue") {
-        return Some(rating);
+    if let Some(rating) = extract_schema_property(dom_index, "ratingValue") {
+        return Some((rating, "microdata"));
     }
 
     // Try common class names for rating
-    let rating_selectors = [
-        "[itemprop='ratingValue']", ".rating", ".product-rating",
-        "[data-rating]", ".star-rating"
-    ];
-
-    for selector_str in &rating_selectors {
-        if let Ok(selector) = Selector::parse(selector_str) {
-            for element in document.select(&selector) {
-                if let Some(rating_attr) = element.value().attr("content") {
-                    return Some(rating_attr.to_string());
-                }
-                let text = element.text().collect::<String>().trim().to_string();
-                if !text.is_empty() {
-                    return Some(text);
-                }
+    if dom_index.structured_only {
+        return None;
+    }
+    for selector in RATING_SELECTORS.iter() {
+        for element in dom_index.document().select(selector) {
+            if let Some(rating_attr) = element.value().attr("content") {
+                return Some((rating_attr.to_string(), "css"));
+            }
+            let text = element.text().collect::<String>().trim().to_string();
+            if !text.is_empty() {
+                return Some((text, "css"));
             }
         }
     }
@@ -35,33 +50,30 @@ pub fn extract_product_rating(document: &Html) -> Option<String> {
     None
 }
 
-pub fn extract_product_review_count(document: &Html) -> Option<String> {
+/// The review count and where it came from: `"jsonld"`, `"microdata"` or `"css"`
+pub fn extract_product_review_count_with_source(dom_index: &DomIndex) -> Option<(String, &'static str)> {
     // Try JSON-LD Product schema
-    if let Some(count) = extract_json_ld_property(document, &["aggregateRating.reviewCount", "reviewCount"]) {
-        return Some(count);
+    if let Some(count) = extract_json_ld_property(dom_index, &["aggregateRating.reviewCount", "reviewCount"]) {
+        return Some((count, "jsonld"));
     }
 
     // Try schema.org Product
-    if let Some(count) = extract_schema_property(document, "reviewCount") {
-        return Some(count);
+    if let Some(count) = extract_schema_property(dom_index, "reviewCount") {
+        return Some((count, "microdata"));
     }
 
     // Try common class names for review count
-    let count_selectors = [
-        "[itemprop='reviewCount']", ".review-count", ".reviews-count",
-        "[data-review-count]"
-    ];
-
-    for selector_str in &count_selectors {
-        if let Ok(selector) = Selector::parse(selector_str) {
-            for element in document.select(&selector) {
-                if let Some(count_attr) = element.value().attr("content") {
-                    return Some(count_attr.to_string());
-                }
-                let text = element.text().collect::<String>().trim().to_string();
-                if !text.is_empty() {
-                    return Some(text);
-                }
+    if dom_index.structured_only {
+        return None;
+    }
+    for selector in REVIEW_COUNT_SELECTORS.iter() {
+        for element in dom_index.document().select(selector) {
+            if let Some(count_attr) = element.value().attr("content") {
+                return Some((count_attr.to_string(), "css"));
+            }
+            let text = element.text().collect::<String>().trim().to_string();
+            if !text.is_empty() {
+                return Some((text, "css"));
             }
         }
     }
@@ -69,31 +81,123 @@ pub fn extract_product_review_count(document: &Html) -> Option<String> {
     None
 }
 
-pub fn extract_product_best_rating(document: &Html) -> Option<String> {
+pub fn extract_product_best_rating(dom_index: &DomIndex) -> Option<String> {
     // Try JSON-LD Product schema
-    if let Some(rating) = extract_json_ld_property(document, &["aggregateRating.bestRating", "bestRating"]) {
+    if let Some(rating) = extract_json_ld_property(dom_index, &["aggregateRating.bestRating", "bestRating"]) {
         return Some(rating);
     }
 
     // Try schema.org Product
-    if let Some(rating) = extract_schema_property(document, "bestRating") {
+    if let Some(rating) = extract_schema_property(dom_index, "bestRating") {
         return Some(rating);
     }
 
     None
 }
 
-pub fn extract_product_worst_rating(document: &Html) -> Option<String> {
+pub fn extract_product_worst_rating(dom_index: &DomIndex) -> Option<String> {
     // Try JSON-LD Product schema
-    if let Some(rating) = extract_json_ld_property(document, &["aggregateRating.worstRating", "worstRating"]) {
+    if let Some(rating) = extract_json_ld_property(dom_index, &["aggregateRating.worstRating", "worstRating"]) {
         return Some(rating);
     }
 
     // Try schema.org Product
-    if let Some(rating) = extract_schema_property(document, "worstRating") {
+    if let Some(rating) = extract_schema_property(dom_index, "worstRating") {
         return Some(rating);
     }
 
     None
 }
 
+/// Parse a rating such as "4.5", "4,5" or "4.5 out of 5" (the first number wins)
+pub fn parse_rating(value: &str) -> Option<f32> {
+    RATING_RE.find(value)?.as_str().replace(',', ".").parse().ok()
+}
+
+/// Parse a review count such as "1,234" or "1.234 reviews", ignoring grouping separators
+pub fn parse_review_count(value: &str) -> Option<u32> {
+    let number = REVIEW_COUNT_RE.find(value)?.as_str();
+    number.chars().filter(|c| c.is_ascii_digit()).collect::<String>().parse().ok()
+}
+
+/// Individual reviews of the product, in declaration order, keeping at most `max_reviews`.
+///
+/// JSON-LD `review` (or the older `reviews`) is read first, as a single object or an array.
+/// Without it, every microdata `Review` scope is read using only its own itemprops.
+pub fn extract_product_reviews(dom_index: &DomIndex, max_reviews: Option<usize>) -> Vec<Review> {
+    let mut reviews = Vec::new();
+    if let Some(value) = extract_json_ld_value(dom_index, "review")
+        .or_else(|| extract_json_ld_value(dom_index, "reviews"))
+    {
+        collect_json_ld_reviews(&value, &mut reviews);
+    }
+
+    if reviews.is_empty() {
+        reviews.extend(dom_index.document().select(&REVIEW_ITEM_SELECTOR).map(parse_microdata_review));
+    }
+
+    reviews.retain(|review| review.rating.is_some() || review.body.is_some() || review.title.is_some());
+    if let Some(max) = max_reviews {
+        reviews.truncate(max);
+    }
+    reviews
+}
+
+fn collect_json_ld_reviews(value: &Value, reviews: &mut Vec<Review>) {
+    match value {
+        Value::Array(arr) => {
+            for item in arr {
+                collect_json_ld_reviews(item, reviews);
+            }
+        }
+        Value::Object(obj) => reviews.push(parse_json_ld_review(obj)),
+        _ => {}
+    }
+}
+
+fn parse_json_ld_review(obj: &Map<String, Value>) -> Review {
+    let string = |property: &str| obj.get(property).and_then(json_scalar_string);
+    let rating = match obj.get("reviewRating") {
+        Some(Value::Object(rating)) => rating.get("ratingValue").and_then(json_scalar_string),
+        Some(value) => json_scalar_string(value),
+        None => None,
+    };
+
+    Review {
+        author: obj.get("author").and_then(entity_name),
+        rating: rating.and_then(|r| parse_rating(&r)),
+        date: string("datePublished"),
+        title: string("name").or_else(|| string("headline")),
+        body: string("reviewBody").or_else(|| string("description")),
+    }
+}
+
+fn parse_microdata_review(scope: ElementRef) -> Review {
+    let find = |name: &str| own_properties(scope, name).into_iter().next();
+    let value = |name: &str| find(name).and_then(property_value);
+
+    // Nested Rating/Person scopes carry the actual value in their own itemprops
+    let from_nested = |name: &str, nested_prop: &str| {
+        find(name).and_then(|element| {
+            if element.value().attr("itemscope").is_some() {
+                own_properties(element, nested_prop).into_iter().find_map(property_value)
+            } else {
+                property_value(element)
+            }
+        })
+    };
+
+    Review {
+        author: from_nested("author", "name"),
+        rating: from_nested("reviewRating", "ratingValue")
+            .or_else(|| value("ratingValue"))
+            .and_then(|r| parse_rating(&r)),
+        date: value("datePublished"),
+        title: value("name").or_else(|| value("headline")),
+        body: value("reviewBody").or_else(|| value("description")),
+    }
+}
+
+static RATING_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d+(?:[.,]\d+)?").unwrap());
+
+static REVIEW_COUNT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d(?:[\d.,' \u{00A0}]*\d)?").unwrap());
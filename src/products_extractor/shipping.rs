@@ -0,0 +1,68 @@
+use serde_json::{Map, Value};
+use crate::dom_index::DomIndex;
+use super::helpers::{extract_json_ld_value, json_scalar_string, quantitative_days};
+use super::offers::first_object;
+
+/// Shipping cost and delivery estimate from schema.org `OfferShippingDetails`
+#[derive(Debug, Clone, Default)]
+pub struct Shipping {
+    pub price: Option<String>,
+    pub currency: Option<String>,
+    /// Handling plus transit time in whole days, (min, max)
+    pub delivery_days: Option<(u32, u32)>,
+}
+
+/// Shipping details of the first offer declaring `shippingDetails`. With several entries
+/// (one per destination or service), the first with a `shippingRate` is used, falling back
+/// to the first entry.
+pub fn extract_shipping(dom_index: &DomIndex) -> Option<Shipping> {
+    let offers = extract_json_ld_value(dom_index, "offers")?;
+    let details = offer_objects(&offers)
+        .into_iter()
+        .find_map(|offer| offer.get("shippingDetails"))?;
+
+    let entries: Vec<&Map<String, Value>> = match details {
+        Value::Array(arr) => arr.iter().filter_map(Value::as_object).collect(),
+        Value::Object(obj) => vec![obj],
+        _ => Vec::new(),
+    };
+    let entry = entries.iter()
+        .find(|entry| entry.contains_key("shippingRate"))
+        .or(entries.first())?;
+
+    let rate = entry.get("shippingRate").and_then(first_object);
+    let shipping = Shipping {
+        price: rate.and_then(|rate| rate.get("value")).and_then(json_scalar_string),
+        currency: rate.and_then(|rate| rate.get("currency")).and_then(json_scalar_string),
+        delivery_days: entry.get("deliveryTime").and_then(delivery_days),
+    };
+    (shipping.price.is_some() || shipping.delivery_days.is_some()).then_some(shipping)
+}
+
+/// Offers in declaration order, including those nested in an `AggregateOffer`
+pub fn offer_objects(value: &Value) -> Vec<&Map<String, Value>> {
+    match value {
+        Value::Array(arr) => arr.iter().flat_map(offer_objects).collect(),
+        Value::Object(obj) => {
+            let mut objects = vec![obj];
+            if let Some(nested) = obj.get("offers") {
+                objects.extend(offer_objects(nested));
+            }
+            objects
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// `handlingTime` + `transitTime` of a `ShippingDeliveryTime`, rounded out to whole days
+fn delivery_days(value: &Value) -> Option<(u32, u32)> {
+    let delivery = first_object(value)?;
+    let handling = delivery.get("handlingTime").and_then(quantitative_days);
+    let transit = delivery.get("transitTime").and_then(quantitative_days);
+    let (min, max) = match (handling, transit) {
+        (Some(h), Some(t)) => (h.0 + t.0, h.1 + t.1),
+        (Some(only), None) | (None, Some(only)) => only,
+        (None, None) => return None,
+    };
+    Some((min.floor().max(0.0) as u32, max.ceil().max(min.floor()).max(0.0) as u32))
+}
@@ -0,0 +1,1037 @@
+#![allow(non_local_definitions)]
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::types::{PyDict, PyList, PyType};
+use std::collections::HashMap;
+use crate::article_extractor::JSON_ARTICLE_FIELDS;
+use crate::{ArticleInfo, DateWithConfidence, DimensionUnit, Event, ExtractionResult, Faq, GroupedLinks, InlineLinkMode, LanguageFilterMode, LinkInfo, ListBlock, MetaRefresh, OEmbed, ProductInfo, Recipe, RobotsChecker, SocialsInfo, TextSanitize, UnicodeNormalizationForm, WebExtractor, WeightUnit};
+
+/// Helper function to convert a LinkInfo to a Python dictionary
+fn link_info_to_dict(py: Python, link: &LinkInfo) -> PyObject {
+    let link_dict = PyDict::new(py);
+    link_dict.set_item("url", &link.url).unwrap();
+    link_dict.set_item("text", &link.text).unwrap();
+    if let Some(ref context) = link.context {
+        link_dict.set_item("context", context).unwrap();
+    }
+    link_dict.into()
+}
+
+/// Helper function to convert a list of LinkInfo to a Python list
+fn link_list_to_pylist(py: Python, links: &[LinkInfo]) -> PyObject {
+    let list = PyList::empty(py);
+    for link in links {
+        list.append(link_info_to_dict(py, link)).unwrap();
+    }
+    list.into()
+}
+
+/// Helper function to convert the rel-keyed `<link>` elements to a dict of rel -> list
+fn alternate_links_to_dict(py: Python, links: &HashMap<String, Vec<LinkInfo>>) -> PyObject {
+    let dict = PyDict::new(py);
+    for (rel, rel_links) in links {
+        dict.set_item(rel, link_list_to_pylist(py, rel_links)).unwrap();
+    }
+    dict.into()
+}
+
+/// Helper function to convert GroupedLinks to a Python dictionary
+fn grouped_links_to_dict(py: Python, gl: &GroupedLinks) -> PyObject {
+    let dict = PyDict::new(py);
+    
+    dict.set_item("internal", link_list_to_pylist(py, &gl.internal)).unwrap();
+    dict.set_item("external", link_list_to_pylist(py, &gl.external)).unwrap();
+    
+    // By domain
+    let by_domain_dict = PyDict::new(py);
+    for (domain, links) in &gl.by_domain {
+        by_domain_dict.set_item(domain, link_list_to_pylist(py, links)).unwrap();
+    }
+    dict.set_item("by_domain", by_domain_dict).unwrap();
+    
+    // Summary
+    let summary_dict = PyDict::new(py);
+    summary_dict.set_item("total", gl.summary.total).unwrap();
+    summary_dict.set_item("internal_count", gl.summary.internal_count).unwrap();
+    summary_dict.set_item("external_count", gl.summary.external_count).unwrap();
+    summary_dict.set_item("unique_domains", gl.summary.unique_domains).unwrap();
+    summary_dict.set_item("truncated", gl.summary.truncated).unwrap();
+    summary_dict.set_item("dropped", gl.summary.dropped).unwrap();
+    dict.set_item("summary", summary_dict).unwrap();
+    
+    dict.into()
+}
+
+/// Helper function to convert a HashMap to a Python dictionary
+fn hashmap_to_dict(py: Python, map: &HashMap<String, String>) -> PyObject {
+    let dict = PyDict::new(py);
+    for (k, v) in map {
+        dict.set_item(k, v).unwrap();
+    }
+    dict.into()
+}
+
+/// Helper function to convert a serde_json value into the equivalent Python object
+fn json_value_to_py(py: Python, value: &serde_json::Value) -> PyObject {
+    match value {
+        serde_json::Value::Null => py.None(),
+        serde_json::Value::Bool(b) => b.to_object(py),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => i.to_object(py),
+            None => n.as_f64().unwrap_or_default().to_object(py),
+        },
+        serde_json::Value::String(s) => s.to_object(py),
+        serde_json::Value::Array(arr) => {
+            let list = PyList::empty(py);
+            for item in arr {
+                list.append(json_value_to_py(py, item)).unwrap();
+            }
+            list.into()
+        }
+        serde_json::Value::Object(obj) => {
+            let dict = PyDict::new(py);
+            for (k, v) in obj {
+                dict.set_item(k, json_value_to_py(py, v)).unwrap();
+            }
+            dict.into()
+        }
+    }
+}
+
+/// Helper function to convert the article HashMap to a Python dictionary, decoding the
+/// JSON-encoded list fields (`publication_date`, `all_tags`, ...) into real lists
+fn article_to_dict(py: Python, article: &HashMap<String, String>) -> PyObject {
+    let dict = PyDict::new(py);
+    for (k, v) in article {
+        let decoded = JSON_ARTICLE_FIELDS
+            .contains(&k.as_str())
+            .then(|| serde_json::from_str::<serde_json::Value>(v).ok())
+            .flatten();
+        match decoded {
+            Some(value) => dict.set_item(k, json_value_to_py(py, &value)).unwrap(),
+            None => dict.set_item(k, v).unwrap(),
+        }
+    }
+    dict.into()
+}
+
+fn dates_with_confidence_to_list(py: Python, dates: &[DateWithConfidence]) -> PyObject {
+    let list = PyList::empty(py);
+    for date in dates {
+        let date_dict = PyDict::new(py);
+        date_dict.set_item("date", &date.date).unwrap();
+        date_dict.set_item("confidence", date.confidence).unwrap();
+        list.append(date_dict).unwrap();
+    }
+    list.into()
+}
+
+/// Helper function to convert ArticleInfo to a Python dictionary
+fn article_info_to_dict(py: Python, info: &ArticleInfo) -> PyObject {
+    let dict = PyDict::new(py);
+    dict.set_item("article_type", &info.article_type).unwrap();
+    dict.set_item("is_article", info.is_article).unwrap();
+    dict.set_item("title", &info.title).unwrap();
+    dict.set_item("description", &info.description).unwrap();
+    dict.set_item("authors", &info.authors).unwrap();
+    dict.set_item("published", info.published.map(|d| d.to_rfc3339())).unwrap();
+    dict.set_item("modified", info.modified.map(|d| d.to_rfc3339())).unwrap();
+    dict.set_item("section", &info.section).unwrap();
+    dict.set_item("tags", &info.tags).unwrap();
+    dict.set_item("word_count", info.word_count).unwrap();
+    dict.set_item("reading_time", info.reading_time).unwrap();
+
+    dict.set_item("dates_with_confidence", dates_with_confidence_to_list(py, &info.dates_with_confidence)).unwrap();
+    dict.set_item("modified_dates_with_confidence", dates_with_confidence_to_list(py, &info.modified_dates_with_confidence)).unwrap();
+
+    dict.into()
+}
+
+// Helper to convert SocialsInfo into {"open_graph": {...}, "twitter": {...}} (integer image dimensions)
+fn socials_info_to_dict(py: Python, info: &SocialsInfo) -> PyObject {
+    let og = &info.open_graph;
+    let og_dict = PyDict::new(py);
+    og_dict.set_item("url", &og.url).unwrap();
+    og_dict.set_item("type", &og.og_type).unwrap();
+    og_dict.set_item("title", &og.title).unwrap();
+    og_dict.set_item("description", &og.description).unwrap();
+    let images = PyList::empty(py);
+    for image in &og.images {
+        let image_dict = PyDict::new(py);
+        image_dict.set_item("url", &image.url).unwrap();
+        image_dict.set_item("width", image.width).unwrap();
+        image_dict.set_item("height", image.height).unwrap();
+        image_dict.set_item("alt", &image.alt).unwrap();
+        images.append(image_dict).unwrap();
+    }
+    og_dict.set_item("images", images).unwrap();
+    og_dict.set_item("site_name", &og.site_name).unwrap();
+    og_dict.set_item("locale", &og.locale).unwrap();
+    og_dict.set_item("locale_alternates", &og.locale_alternates).unwrap();
+
+    let twitter = &info.twitter;
+    let twitter_dict = PyDict::new(py);
+    twitter_dict.set_item("card", &twitter.card).unwrap();
+    twitter_dict.set_item("site", &twitter.site).unwrap();
+    twitter_dict.set_item("creator", &twitter.creator).unwrap();
+    twitter_dict.set_item("title", &twitter.title).unwrap();
+    twitter_dict.set_item("description", &twitter.description).unwrap();
+    twitter_dict.set_item("image", &twitter.image).unwrap();
+    twitter_dict.set_item("image_alt", &twitter.image_alt).unwrap();
+
+    let dict = PyDict::new(py);
+    dict.set_item("open_graph", og_dict).unwrap();
+    dict.set_item("twitter", twitter_dict).unwrap();
+    dict.into()
+}
+
+// Helper to convert listing products into a list of dicts
+fn products_to_list(py: Python, products: &[HashMap<String, String>]) -> PyObject {
+    let list = PyList::empty(py);
+    for product in products {
+        list.append(hashmap_to_dict(py, product)).unwrap();
+    }
+    list.into()
+}
+
+// Helper to convert ProductInfo into a dict (numeric prices, availability as its schema.org name)
+fn product_info_to_dict(py: Python, info: &ProductInfo) -> PyObject {
+    let dict = PyDict::new(py);
+    dict.set_item("title", &info.title).unwrap();
+    dict.set_item("brand", &info.brand).unwrap();
+    dict.set_item("sku", &info.sku).unwrap();
+    dict.set_item("price", info.price).unwrap();
+    dict.set_item("price_is_range", info.price_is_range).unwrap();
+    dict.set_item("currency", &info.currency).unwrap();
+    dict.set_item("currency_inferred", info.currency_inferred).unwrap();
+    dict.set_item("original_price", info.original_price).unwrap();
+    dict.set_item("discount_percent", info.discount_percent).unwrap();
+    dict.set_item("on_sale", info.on_sale).unwrap();
+    dict.set_item("availability", info.availability.map(|a| a.as_str())).unwrap();
+    dict.set_item("rating", info.rating).unwrap();
+    dict.set_item("review_count", info.review_count).unwrap();
+    dict.set_item("images", &info.images).unwrap();
+    let reviews = PyList::empty(py);
+    for review in &info.reviews {
+        let review_dict = PyDict::new(py);
+        review_dict.set_item("author", &review.author).unwrap();
+        review_dict.set_item("rating", review.rating).unwrap();
+        review_dict.set_item("date", &review.date).unwrap();
+        review_dict.set_item("title", &review.title).unwrap();
+        review_dict.set_item("body", &review.body).unwrap();
+        reviews.append(review_dict).unwrap();
+    }
+    dict.set_item("reviews", reviews).unwrap();
+    dict.into()
+}
+
+// Helper to convert nested ListBlocks into lists of {"ordered", "items": [{"text", "children"}]} dicts
+fn list_blocks_to_list(py: Python, lists: &[ListBlock]) -> PyObject {
+    let py_lists = PyList::empty(py);
+    for list in lists {
+        let list_dict = PyDict::new(py);
+        list_dict.set_item("ordered", list.ordered).unwrap();
+        let items = PyList::empty(py);
+        for item in &list.items {
+            let item_dict = PyDict::new(py);
+            item_dict.set_item("text", &item.text).unwrap();
+            item_dict.set_item("children", list_blocks_to_list(py, &item.children)).unwrap();
+            items.append(item_dict).unwrap();
+        }
+        list_dict.set_item("items", items).unwrap();
+        py_lists.append(list_dict).unwrap();
+    }
+    py_lists.into()
+}
+
+// Helper to convert a Recipe into a dict (times in minutes, lists as Python lists)
+fn recipe_to_dict(py: Python, recipe: &Recipe) -> PyObject {
+    let dict = PyDict::new(py);
+    dict.set_item("name", &recipe.name).unwrap();
+    dict.set_item("ingredients", &recipe.ingredients).unwrap();
+    dict.set_item("instructions", &recipe.instructions).unwrap();
+    dict.set_item("prep_time", recipe.prep_time).unwrap();
+    dict.set_item("cook_time", recipe.cook_time).unwrap();
+    dict.set_item("total_time", recipe.total_time).unwrap();
+    dict.set_item("yield", &recipe.recipe_yield).unwrap();
+    dict.set_item("calories", &recipe.calories).unwrap();
+    dict.into()
+}
+
+// Helper to convert an Event into a dict with nested location / offer dicts
+fn event_to_dict(py: Python, event: &Event) -> PyObject {
+    let dict = PyDict::new(py);
+    dict.set_item("event_type", &event.event_type).unwrap();
+    dict.set_item("name", &event.name).unwrap();
+    dict.set_item("start_date", &event.start_date).unwrap();
+    dict.set_item("end_date", &event.end_date).unwrap();
+    let location = event.location.as_ref().map(|location| {
+        let location_dict = PyDict::new(py);
+        location_dict.set_item("name", &location.name).unwrap();
+        location_dict.set_item("address", &location.address).unwrap();
+        location_dict.set_item("url", &location.url).unwrap();
+        location_dict
+    });
+    dict.set_item("location", location).unwrap();
+    let offers = PyList::empty(py);
+    for offer in &event.offers {
+        let offer_dict = PyDict::new(py);
+        offer_dict.set_item("price", &offer.price).unwrap();
+        offer_dict.set_item("currency", &offer.currency).unwrap();
+        offer_dict.set_item("url", &offer.url).unwrap();
+        offers.append(offer_dict).unwrap();
+    }
+    dict.set_item("offers", offers).unwrap();
+    dict.set_item("performers", &event.performers).unwrap();
+    dict.into()
+}
+
+// Helper to convert FAQs into a list of {"question", "answer"} dicts
+fn faqs_to_list(py: Python, faqs: &[Faq]) -> PyObject {
+    let list = PyList::empty(py);
+    for faq in faqs {
+        let faq_dict = PyDict::new(py);
+        faq_dict.set_item("question", &faq.question).unwrap();
+        faq_dict.set_item("answer", &faq.answer).unwrap();
+        list.append(faq_dict).unwrap();
+    }
+    list.into()
+}
+
+fn meta_refresh_to_dict(py: Python, refresh: &MetaRefresh) -> PyObject {
+    let dict = PyDict::new(py);
+    dict.set_item("url", &refresh.url).unwrap();
+    dict.set_item("delay", refresh.delay).unwrap();
+    dict.into()
+}
+
+fn oembed_to_dict(py: Python, oembed: &OEmbed) -> PyObject {
+    let dict = PyDict::new(py);
+    dict.set_item("title", &oembed.title).unwrap();
+    dict.set_item("author_name", &oembed.author_name).unwrap();
+    dict.set_item("author_url", &oembed.author_url).unwrap();
+    dict.set_item("provider_name", &oembed.provider_name).unwrap();
+    dict.set_item("thumbnail_url", &oembed.thumbnail_url).unwrap();
+    dict.set_item("html", &oembed.html).unwrap();
+    dict.into()
+}
+
+// Python bindings
+#[pymodule]
+fn _ferriscope_native(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_class::<PyWebExtractor>()?;
+    m.add_class::<PyExtractionResult>()?;
+    m.add_class::<PyLinkInfo>()?;
+    Ok(())
+}
+
+#[pyclass]
+pub struct PyWebExtractor {
+    extractor: WebExtractor,
+}
+
+#[pymethods]
+impl PyWebExtractor {
+    #[new]
+    #[pyo3(signature = (url, html = None))]
+    fn new(url: String, html: Option<String>) -> Self {
+        if let Some(html_content) = html {
+            PyWebExtractor {
+                extractor: WebExtractor::new_with_html(url, html_content),
+            }
+        } else {
+            PyWebExtractor {
+                extractor: WebExtractor::new(url),
+            }
+        }
+    }
+
+    /// Create an extractor for an HTML string; pair with `extract()` to skip the network entirely
+    #[classmethod]
+    fn from_html(_cls: &PyType, url: String, html: String) -> Self {
+        PyWebExtractor {
+            extractor: WebExtractor::new_with_html(url, html),
+        }
+    }
+
+    /// Create an extractor for gzip-compressed HTML bytes
+    #[classmethod]
+    fn from_gzip_html(_cls: &PyType, url: String, data: Vec<u8>) -> PyResult<Self> {
+        Ok(PyWebExtractor {
+            extractor: WebExtractor::new_with_gzip_html(url, data)?,
+        })
+    }
+
+    fn extract_text(&mut self, language_detection: bool) {
+        self.extractor.extract_text(language_detection);
+    }
+
+    fn set_inline_link_mode(&mut self, mode: String) -> PyResult<()> {
+        let mode = mode.parse::<InlineLinkMode>()?;
+        self.extractor.set_inline_link_mode(mode);
+        Ok(())
+    }
+
+    fn set_min_content_length(&mut self, chars: usize) {
+        self.extractor.set_min_content_length(chars);
+    }
+
+    fn set_compute_simhash(&mut self, enabled: bool) {
+        self.extractor.set_compute_simhash(enabled);
+    }
+
+    fn set_unicode_normalization(&mut self, form: String) -> PyResult<()> {
+        let form = form.parse::<UnicodeNormalizationForm>()?;
+        self.extractor.set_unicode_normalization(form);
+        Ok(())
+    }
+
+    fn set_allowed_languages(&mut self, langs: Vec<String>) {
+        self.extractor.set_allowed_languages(langs);
+    }
+
+    fn set_language_filter_mode(&mut self, mode: String) -> PyResult<()> {
+        let mode = mode.parse::<LanguageFilterMode>()?;
+        self.extractor.set_language_filter_mode(mode);
+        Ok(())
+    }
+
+    #[pyo3(signature = (strip_emoji = false, strip_zero_width = false, normalize_unicode = false))]
+    fn set_text_sanitize(&mut self, strip_emoji: bool, strip_zero_width: bool, normalize_unicode: bool) {
+        self.extractor.set_text_sanitize(TextSanitize {
+            strip_emoji,
+            strip_zero_width,
+            normalize_unicode,
+        });
+    }
+
+    #[pyo3(signature = (fields = None))]
+    fn extract_links(&mut self, fields: Option<Vec<String>>) {
+        let fields = fields.unwrap_or_else(|| vec!["all".to_string()]);
+        self.extractor.extract_links(fields);
+    }
+
+    fn set_allowed_schemes(&mut self, schemes: Vec<String>) {
+        self.extractor.set_allowed_schemes(schemes);
+    }
+
+    fn set_max_links(&mut self, total: usize) {
+        self.extractor.set_max_links(total);
+    }
+
+    fn set_max_links_per_domain(&mut self, n: usize) {
+        self.extractor.set_max_links_per_domain(n);
+    }
+
+    #[pyo3(signature = (fields = None))]
+    fn extract_socials(&mut self, fields: Option<Vec<String>>) {
+        let fields = fields.unwrap_or_else(|| vec!["all".to_string()]);
+        self.extractor.extract_socials(fields);
+    }
+
+    #[pyo3(signature = (fields = None))]
+    fn extract_video(&mut self, fields: Option<Vec<String>>) {
+        let fields = fields.unwrap_or_else(|| vec!["all".to_string()]);
+        self.extractor.extract_video(fields);
+    }
+
+    #[pyo3(signature = (fields = None))]
+    fn extract_product(&mut self, fields: Option<Vec<String>>) {
+        let fields = fields.unwrap_or_else(|| vec!["all".to_string()]);
+        self.extractor.extract_product(fields);
+    }
+
+    fn set_max_product_reviews(&mut self, n: usize) {
+        self.extractor.set_max_product_reviews(n);
+    }
+
+    fn set_sale_epsilon(&mut self, epsilon: f64) {
+        self.extractor.set_sale_epsilon(epsilon);
+    }
+
+    fn set_weight_unit(&mut self, unit: String) -> PyResult<()> {
+        let unit = unit.parse::<WeightUnit>()?;
+        self.extractor.set_weight_unit(unit);
+        Ok(())
+    }
+
+    fn set_dimension_unit(&mut self, unit: String) -> PyResult<()> {
+        let unit = unit.parse::<DimensionUnit>()?;
+        self.extractor.set_dimension_unit(unit);
+        Ok(())
+    }
+
+    #[pyo3(signature = (max = 100))]
+    fn extract_products_list(&mut self, max: usize) {
+        self.extractor.extract_products_list(max);
+    }
+
+    #[pyo3(signature = (fields = None))]
+    fn extract_article(&mut self, fields: Option<Vec<String>>) {
+        let fields = fields.unwrap_or_else(|| vec!["all".to_string()]);
+        self.extractor.extract_article(fields);
+    }
+
+    fn extract_lists(&mut self) {
+        self.extractor.extract_lists();
+    }
+
+    fn extract_recipe(&mut self) {
+        self.extractor.extract_recipe();
+    }
+
+    fn extract_event(&mut self) {
+        self.extractor.extract_event();
+    }
+
+    fn extract_faqs(&mut self) {
+        self.extractor.extract_faqs();
+    }
+
+    fn extract_contacts(&mut self) {
+        self.extractor.extract_contacts();
+    }
+
+    fn extract_emails(&mut self) {
+        self.extractor.extract_emails();
+    }
+
+    fn extract_preview_image(&mut self) {
+        self.extractor.extract_preview_image();
+    }
+
+    fn extract_alternate_links(&mut self) {
+        self.extractor.extract_alternate_links();
+    }
+
+    fn set_phone_region(&mut self, region: String) -> PyResult<()> {
+        self.extractor.set_phone_region(&region)?;
+        Ok(())
+    }
+
+    fn extract_all(&mut self) {
+        self.extractor.extract_all();
+    }
+
+    fn set_strict_fields(&mut self, strict: bool) {
+        self.extractor.set_strict_fields(strict);
+    }
+
+    fn set_collect_provenance(&mut self, enabled: bool) {
+        self.extractor.set_collect_provenance(enabled);
+    }
+
+    fn set_structured_only(&mut self, enabled: bool) {
+        self.extractor.set_structured_only(enabled);
+    }
+
+    fn set_follow_meta_refresh(&mut self, enabled: bool) {
+        self.extractor.set_follow_meta_refresh(enabled);
+    }
+
+    fn set_fetch_oembed(&mut self, enabled: bool) {
+        self.extractor.set_fetch_oembed(enabled);
+    }
+
+    fn set_parallel_passes(&mut self, enabled: bool) {
+        self.extractor.set_parallel_passes(enabled);
+    }
+
+    /// `callback(completed, total)` is called with the GIL held, from the batch's worker threads
+    fn set_progress_callback(&mut self, callback: PyObject) {
+        self.extractor.set_progress_callback(move |completed, total| {
+            Python::with_gil(|py| {
+                if let Err(err) = callback.call1(py, (completed, total)) {
+                    err.print(py);
+                }
+            });
+        });
+    }
+
+    fn set_max_json_ld_bytes(&mut self, bytes: usize) {
+        self.extractor.set_max_json_ld_bytes(bytes);
+    }
+
+    fn validate(&self) -> Vec<String> {
+        match self.extractor.validate() {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+
+    fn set_date_confidence_threshold(&mut self, threshold: f64) {
+        self.extractor.set_date_confidence_threshold(threshold);
+    }
+
+    fn add_date_cues(&mut self, cues: Vec<String>) {
+        self.extractor.add_date_cues(cues);
+    }
+
+    fn set_timeout(&mut self, timeout_secs: u64) {
+        self.extractor.set_timeout(timeout_secs);
+    }
+
+    fn set_user_agent(&mut self, user_agent: String) {
+        self.extractor.set_user_agent(user_agent);
+    }
+
+    fn set_random_user_agent(&mut self, enabled: bool) {
+        self.extractor.set_random_user_agent(enabled);
+    }
+
+    fn add_header(&mut self, name: String, value: String) {
+        self.extractor.add_header(name, value);
+    }
+
+    fn set_headers(&mut self, headers: HashMap<String, String>) {
+        self.extractor.set_headers(headers);
+    }
+
+    fn set_accept_language(&mut self, langs: String) {
+        self.extractor.set_accept_language(langs);
+    }
+
+    fn set_danger_accept_invalid_certs(&mut self, enabled: bool) {
+        self.extractor.set_danger_accept_invalid_certs(enabled);
+    }
+
+    fn set_http2_prior_knowledge(&mut self, enabled: bool) {
+        self.extractor.set_http2_prior_knowledge(enabled);
+    }
+
+    fn set_pool_idle_timeout(&mut self, idle_secs: u64) {
+        self.extractor.set_pool_idle_timeout(idle_secs);
+    }
+
+    fn add_root_certificate(&mut self, pem: Vec<u8>) -> PyResult<()> {
+        self.extractor.add_root_certificate(pem)
+            .map_err(PyErr::from)
+    }
+
+    fn enable_robots_check(&mut self) {
+        self.extractor.enable_robots_check();
+    }
+
+    fn enable_robots_check_with_redis(&mut self, redis_url: String) -> PyResult<()> {
+        self.extractor.enable_robots_check_with_redis(&redis_url)
+            .map_err(PyErr::from)
+    }
+
+    fn set_robots_redis_ttl(&mut self, ttl_secs: u64) -> PyResult<()> {
+        self.extractor.set_robots_redis_ttl(ttl_secs)
+            .map_err(PyErr::from)
+    }
+
+    fn set_robots_txt(&mut self, content: String) -> PyResult<()> {
+        let rt = tokio::runtime::Runtime::new()
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
+        rt.block_on(self.extractor.set_robots_txt(&content))
+            .map_err(PyErr::from)
+    }
+
+    fn check_robots_allowed(&self) -> PyResult<bool> {
+        let rt = tokio::runtime::Runtime::new()
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
+        rt.block_on(self.extractor.check_robots_allowed())
+            .map_err(PyErr::from)
+    }
+
+    fn remove_robots_from_redis(&self) -> PyResult<()> {
+        let rt = tokio::runtime::Runtime::new()
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
+        rt.block_on(self.extractor.remove_robots_from_redis())
+            .map_err(PyErr::from)
+    }
+
+    fn clear_robots_cache(&self) {
+        let rt = tokio::runtime::Runtime::new()
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))
+            .ok();
+        if let Some(rt) = rt {
+            rt.block_on(self.extractor.clear_robots_cache());
+        }
+    }
+
+    #[staticmethod]
+    fn parse_robots_directives(py: Python, content: &str) -> PyObject {
+        let directives = RobotsChecker::parse_directives(content);
+        let dict = PyDict::new(py);
+        dict.set_item("sitemaps", &directives.sitemaps).unwrap();
+        dict.set_item("host", &directives.host).unwrap();
+        dict.set_item("crawl_delays", &directives.crawl_delays).unwrap();
+        dict.into()
+    }
+
+    fn robots_stats(&self, py: Python) -> Option<PyObject> {
+        self.extractor.robots_stats().map(|stats| {
+            let dict = PyDict::new(py);
+            dict.set_item("memory_hits", stats.memory_hits).unwrap();
+            dict.set_item("memory_misses", stats.memory_misses).unwrap();
+            dict.set_item("redis_hits", stats.redis_hits).unwrap();
+            dict.set_item("redis_misses", stats.redis_misses).unwrap();
+            dict.set_item("fetches", stats.fetches).unwrap();
+            dict.into()
+        })
+    }
+
+    fn extract(&self) -> PyResult<PyExtractionResult> {
+        match self.extractor.extract() {
+            Ok(result) => Ok(PyExtractionResult { result }),
+            Err(e) => Err(PyErr::from(e)),
+        }
+    }
+
+    fn run(&mut self) -> PyResult<PyExtractionResult> {
+        match self.extractor.run() {
+            Ok(result) => Ok(PyExtractionResult { result }),
+            Err(e) => Err(PyErr::from(e)),
+        }
+    }
+
+    /// Extract every URL, `None` for the ones that failed. The GIL is released while the
+    /// batch runs so the progress callback can take it from the worker threads.
+    #[pyo3(signature = (urls, max_concurrent = 10))]
+    fn run_batch(&mut self, py: Python, urls: Vec<String>, max_concurrent: usize) -> PyResult<Vec<Option<PyExtractionResult>>> {
+        let extractor = &mut self.extractor;
+        let results = py.allow_threads(|| {
+            let rt = tokio::runtime::Runtime::new()
+                .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
+            rt.block_on(extractor.run_batch(urls, max_concurrent)).map_err(PyErr::from)
+        })?;
+        Ok(results.into_iter().map(|result| result.ok().map(|result| PyExtractionResult { result })).collect())
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyExtractionResult {
+    result: ExtractionResult,
+}
+
+#[pymethods]
+impl PyExtractionResult {
+    #[getter]
+    fn url(&self) -> String {
+        self.result.url.clone()
+    }
+
+    #[getter]
+    fn text(&self) -> Option<String> {
+        self.result.text.clone()
+    }
+
+    #[getter]
+    fn links(&self, py: Python) -> Option<PyObject> {
+        self.result.links.as_ref().map(|gl| grouped_links_to_dict(py, gl))
+    }
+
+    #[getter]
+    fn title(&self) -> Option<String> {
+        self.result.title.clone()
+    }
+
+    #[getter]
+    fn description(&self) -> Option<String> {
+        self.result.description.clone()
+    }
+
+    #[getter]
+    fn preview_image(&self) -> Option<String> {
+        self.result.preview_image.clone()
+    }
+
+    #[getter]
+    fn amp_url(&self) -> Option<String> {
+        self.result.amp_url.clone()
+    }
+
+    #[getter]
+    fn alternate_links(&self, py: Python) -> Option<PyObject> {
+        self.result.alternate_links.as_ref().map(|links| alternate_links_to_dict(py, links))
+    }
+
+    #[getter]
+    fn meta_refresh(&self, py: Python) -> Option<PyObject> {
+        self.result.meta_refresh.as_ref().map(|refresh| meta_refresh_to_dict(py, refresh))
+    }
+
+    #[getter]
+    fn oembed_url(&self) -> Option<String> {
+        self.result.oembed_url.clone()
+    }
+
+    #[getter]
+    fn oembed(&self, py: Python) -> Option<PyObject> {
+        self.result.oembed.as_ref().map(|oembed| oembed_to_dict(py, oembed))
+    }
+
+    #[getter]
+    fn language(&self) -> Option<String> {
+        self.result.language.clone()
+    }
+
+    #[getter]
+    fn language_confidence(&self) -> Option<f64> {
+        self.result.language_confidence
+    }
+
+    #[getter]
+    fn language_allowed(&self) -> Option<bool> {
+        self.result.language_allowed
+    }
+
+    #[getter]
+    fn robots_warning(&self) -> Option<String> {
+        self.result.robots_warning.clone()
+    }
+
+    #[getter]
+    fn warnings(&self) -> Vec<String> {
+        self.result.warnings.clone()
+    }
+
+    #[getter]
+    fn content_hash(&self) -> Option<String> {
+        self.result.content_hash.clone()
+    }
+
+    #[getter]
+    fn simhash(&self) -> Option<u64> {
+        self.result.simhash
+    }
+
+    // Deprecated: Use links property instead
+    #[getter]
+    fn grouped_links(&self, py: Python) -> Option<PyObject> {
+        self.links(py)
+    }
+
+    #[getter]
+    fn socials(&self, py: Python) -> Option<PyObject> {
+        self.result.socials.as_ref().map(|socials| hashmap_to_dict(py, socials))
+    }
+
+    #[getter]
+    fn videos(&self, py: Python) -> Option<PyObject> {
+        self.result.videos.as_ref().map(|videos| hashmap_to_dict(py, videos))
+    }
+
+    #[getter]
+    fn product(&self, py: Python) -> Option<PyObject> {
+        self.result.product.as_ref().map(|product| hashmap_to_dict(py, product))
+    }
+
+    #[getter]
+    fn article(&self, py: Python) -> Option<PyObject> {
+        self.result.article.as_ref().map(|article| article_to_dict(py, article))
+    }
+
+    #[getter]
+    fn socials_info(&self, py: Python) -> Option<PyObject> {
+        self.result.socials_info.as_ref().map(|info| socials_info_to_dict(py, info))
+    }
+
+    #[getter]
+    fn product_info(&self, py: Python) -> Option<PyObject> {
+        self.result.product_info.as_ref().map(|info| product_info_to_dict(py, info))
+    }
+
+    #[getter]
+    fn product_sources(&self, py: Python) -> Option<PyObject> {
+        self.result.product_sources.as_ref().map(|sources| hashmap_to_dict(py, sources))
+    }
+
+    #[getter]
+    fn products(&self, py: Python) -> Option<PyObject> {
+        self.result.products.as_ref().map(|products| products_to_list(py, products))
+    }
+
+    #[getter]
+    fn article_info(&self, py: Python) -> Option<PyObject> {
+        self.result.article_info.as_ref().map(|info| article_info_to_dict(py, info))
+    }
+
+    #[getter]
+    fn article_sources(&self, py: Python) -> Option<PyObject> {
+        self.result.article_sources.as_ref().map(|sources| hashmap_to_dict(py, sources))
+    }
+
+    #[getter]
+    fn lists(&self, py: Python) -> Option<PyObject> {
+        self.result.lists.as_ref().map(|lists| list_blocks_to_list(py, lists))
+    }
+
+    #[getter]
+    fn recipe(&self, py: Python) -> Option<PyObject> {
+        self.result.recipe.as_ref().map(|recipe| recipe_to_dict(py, recipe))
+    }
+
+    #[getter]
+    fn event(&self, py: Python) -> Option<PyObject> {
+        self.result.event.as_ref().map(|event| event_to_dict(py, event))
+    }
+
+    #[getter]
+    fn faqs(&self, py: Python) -> Option<PyObject> {
+        self.result.faqs.as_ref().map(|faqs| faqs_to_list(py, faqs))
+    }
+
+    #[getter]
+    fn phones(&self) -> Option<Vec<String>> {
+        self.result.phones.clone()
+    }
+
+    #[getter]
+    fn emails(&self) -> Option<Vec<String>> {
+        self.result.emails.clone()
+    }
+
+    #[getter]
+    fn content(&self, py: Python) -> Option<PyObject> {
+        self.result.content.as_ref().map(|c| {
+            let dict = PyDict::new(py);
+            if let Some(ref text) = c.text {
+                dict.set_item("text", text.clone()).unwrap();
+            }
+            dict.set_item("text_length", c.text_length).unwrap();
+            dict.into()
+        })
+    }
+
+    fn get_result(&self, py: Python) -> PyObject {
+        // Return the grouped dictionary structure by category
+        self.to_dict(py)
+    }
+
+    fn to_dict(&self, py: Python) -> PyObject {
+        let dict = PyDict::new(py);
+        
+        dict.set_item("url", self.result.url.clone()).unwrap();
+        
+        // Group text-related data into "text" category
+        if self.result.text.is_some() || self.result.language.is_some() || self.result.content.is_some() || self.result.simhash.is_some() {
+            let text_dict = PyDict::new(py);
+            if let Some(ref text) = self.result.text {
+                text_dict.set_item("content", text.clone()).unwrap();
+            }
+            if let Some(ref lang) = self.result.language {
+                text_dict.set_item("language", lang.clone()).unwrap();
+            }
+            if let Some(confidence) = self.result.language_confidence {
+                text_dict.set_item("language_confidence", confidence).unwrap();
+            }
+            if let Some(allowed) = self.result.language_allowed {
+                text_dict.set_item("language_allowed", allowed).unwrap();
+            }
+            if let Some(ref hash) = self.result.content_hash {
+                text_dict.set_item("content_hash", hash.clone()).unwrap();
+            }
+            if let Some(hash) = self.result.simhash {
+                text_dict.set_item("simhash", hash).unwrap();
+            }
+            if let Some(ref c) = self.result.content {
+                text_dict.set_item("text_length", c.text_length).unwrap();
+            }
+            dict.set_item("text", text_dict).unwrap();
+        }
+        
+        if let Some(ref title) = self.result.title {
+            dict.set_item("title", title.clone()).unwrap();
+        }
+
+        if let Some(ref description) = self.result.description {
+            dict.set_item("description", description.clone()).unwrap();
+        }
+
+        if let Some(ref preview_image) = self.result.preview_image {
+            dict.set_item("preview_image", preview_image.clone()).unwrap();
+        }
+
+        // Add links (grouped)
+        if let Some(ref gl) = self.result.links {
+            dict.set_item("links", grouped_links_to_dict(py, gl)).unwrap();
+        }
+        
+        if let Some(ref amp_url) = self.result.amp_url {
+            dict.set_item("amp_url", amp_url.clone()).unwrap();
+        }
+
+        if let Some(ref links) = self.result.alternate_links {
+            dict.set_item("alternate_links", alternate_links_to_dict(py, links)).unwrap();
+        }
+
+        if let Some(ref refresh) = self.result.meta_refresh {
+            dict.set_item("meta_refresh", meta_refresh_to_dict(py, refresh)).unwrap();
+        }
+
+        if let Some(ref oembed_url) = self.result.oembed_url {
+            dict.set_item("oembed_url", oembed_url).unwrap();
+        }
+
+        if let Some(ref oembed) = self.result.oembed {
+            dict.set_item("oembed", oembed_to_dict(py, oembed)).unwrap();
+        }
+        
+        // Add socials
+        if let Some(ref socials) = self.result.socials {
+            dict.set_item("socials", hashmap_to_dict(py, socials)).unwrap();
+        }
+        
+        // Add videos
+        if let Some(ref videos) = self.result.videos {
+            dict.set_item("videos", hashmap_to_dict(py, videos)).unwrap();
+        }
+        
+        // Add product
+        if let Some(ref product) = self.result.product {
+            dict.set_item("product", hashmap_to_dict(py, product)).unwrap();
+        }
+
+        if let Some(ref products) = self.result.products {
+            dict.set_item("products", products_to_list(py, products)).unwrap();
+        }
+
+        // Add article
+        if let Some(ref article) = self.result.article {
+            dict.set_item("article", article_to_dict(py, article)).unwrap();
+        }
+
+        if let Some(ref lists) = self.result.lists {
+            dict.set_item("lists", list_blocks_to_list(py, lists)).unwrap();
+        }
+
+        if let Some(ref recipe) = self.result.recipe {
+            dict.set_item("recipe", recipe_to_dict(py, recipe)).unwrap();
+        }
+
+        if let Some(ref event) = self.result.event {
+            dict.set_item("event", event_to_dict(py, event)).unwrap();
+        }
+
+        if let Some(ref faqs) = self.result.faqs {
+            dict.set_item("faqs", faqs_to_list(py, faqs)).unwrap();
+        }
+
+        if let Some(ref phones) = self.result.phones {
+            dict.set_item("phones", phones.clone()).unwrap();
+        }
+
+        if let Some(ref emails) = self.result.emails {
+            dict.set_item("emails", emails.clone()).unwrap();
+        }
+        
+        dict.into()
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyLinkInfo {
+    #[pyo3(get)]
+    url: String,
+    #[pyo3(get)]
+    text: String,
+    #[pyo3(get)]
+    context: Option<String>,
+}
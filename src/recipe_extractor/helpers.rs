@@ -0,0 +1,36 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// ISO 8601 duration (`P1DT2H30M`, `PT45M`, `PT1H30M15S`); week and month forms are not
+/// used for recipe times
+static ISO_DURATION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^P(?:(\d+(?:\.\d+)?)D)?(?:T(?:(\d+(?:\.\d+)?)H)?(?:(\d+(?:\.\d+)?)M)?(?:(\d+(?:\.\d+)?)S)?)?$").unwrap()
+});
+
+/// Parse an ISO 8601 duration into whole minutes (seconds are rounded)
+pub fn parse_duration_minutes(value: &str) -> Option<u32> {
+    let captures = ISO_DURATION_RE.captures(value.trim())?;
+    let part = |i: usize| {
+        captures.get(i)
+            .and_then(|m| m.as_str().parse::<f64>().ok())
+            .unwrap_or(0.0)
+    };
+
+    // "P" / "PT" alone match the pattern but carry no duration
+    if (1..=4).all(|i| captures.get(i).is_none()) {
+        return None;
+    }
+
+    let minutes = part(1) * 24.0 * 60.0 + part(2) * 60.0 + part(3) + part(4) / 60.0;
+    Some(minutes.round() as u32)
+}
+
+/// Collapse whitespace runs and trim, returning None for empty values
+pub fn clean_text(text: &str) -> Option<String> {
+    let cleaned = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if cleaned.is_empty() {
+        None
+    } else {
+        Some(cleaned)
+    }
+}
@@ -0,0 +1,94 @@
+use crate::dom_index::DomIndex;
+use crate::json_ld::has_json_ld_type;
+use crate::types::Recipe;
+use super::helpers::{clean_text, parse_duration_minutes};
+
+/// Find the first JSON-LD object typed `Recipe`, looking through arrays and `@graph` containers
+fn find_recipe_object(value: &serde_json::Value) -> Option<&serde_json::Map<String, serde_json::Value>> {
+    match value {
+        serde_json::Value::Object(obj) => {
+            if has_json_ld_type(obj, &["Recipe"]) {
+                return Some(obj);
+            }
+            obj.get("@graph").and_then(find_recipe_object)
+        }
+        serde_json::Value::Array(arr) => arr.iter().find_map(find_recipe_object),
+        _ => None,
+    }
+}
+
+/// Text values of a string, number or array value (recipeIngredient, recipeYield, ...)
+fn text_values(value: &serde_json::Value) -> Vec<String> {
+    match value {
+        serde_json::Value::String(s) => clean_text(s).into_iter().collect(),
+        serde_json::Value::Number(n) => vec![n.to_string()],
+        serde_json::Value::Array(arr) => arr.iter().flat_map(text_values).collect(),
+        _ => vec![],
+    }
+}
+
+/// Flatten `recipeInstructions`: a single string, strings, `HowToStep` objects with `text`
+/// (or `name`), and `HowToSection` objects whose `itemListElement` holds further steps
+fn collect_instructions(value: &serde_json::Value, steps: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(s) => {
+            // A single block of text usually separates steps with newlines
+            steps.extend(s.lines().filter_map(clean_text));
+        }
+        serde_json::Value::Array(arr) => {
+            for item in arr {
+                collect_instructions(item, steps);
+            }
+        }
+        serde_json::Value::Object(obj) => {
+            if let Some(elements) = obj.get("itemListElement") {
+                collect_instructions(elements, steps);
+            } else if let Some(text) = obj.get("text").or_else(|| obj.get("name")) {
+                collect_instructions(text, steps);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn string_property(obj: &serde_json::Map<String, serde_json::Value>, property: &str) -> Option<String> {
+    obj.get(property)
+        .map(text_values)
+        .and_then(|values| values.into_iter().next())
+}
+
+fn duration_property(obj: &serde_json::Map<String, serde_json::Value>, property: &str) -> Option<u32> {
+    string_property(obj, property).and_then(|d| parse_duration_minutes(&d))
+}
+
+/// Build a recipe from the first JSON-LD `Recipe` object on the page
+pub fn extract_json_ld_recipe(dom_index: &DomIndex) -> Option<Recipe> {
+    let obj = dom_index.get_parsed_json_ld()
+        .find_map(find_recipe_object)?;
+
+    // `ingredients` is the pre-2015 name of `recipeIngredient`
+    let ingredients = obj.get("recipeIngredient")
+        .or_else(|| obj.get("ingredients"))
+        .map(text_values)
+        .unwrap_or_default();
+
+    let mut instructions = Vec::new();
+    if let Some(value) = obj.get("recipeInstructions") {
+        collect_instructions(value, &mut instructions);
+    }
+
+    let calories = obj.get("nutrition")
+        .and_then(|n| n.as_object())
+        .and_then(|n| string_property(n, "calories"));
+
+    Some(Recipe {
+        name: string_property(obj, "name"),
+        ingredients,
+        instructions,
+        prep_time: duration_property(obj, "prepTime"),
+        cook_time: duration_property(obj, "cookTime"),
+        total_time: duration_property(obj, "totalTime"),
+        recipe_yield: string_property(obj, "recipeYield"),
+        calories,
+    })
+}
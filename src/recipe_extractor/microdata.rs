@@ -0,0 +1,92 @@
+use scraper::{ElementRef, Selector};
+use crate::dom_index::DomIndex;
+use crate::microdata::{find_main_item, item_property, item_property_values, own_properties, property_value};
+use crate::types::Recipe;
+use super::helpers::{clean_text, parse_duration_minutes};
+
+/// Every value of `itemprop` that belongs to the recipe itself; properties of nested items
+/// (the author's `name`, a `NutritionInformation`'s fields) aren't mixed in
+fn property_values(recipe: ElementRef, itemprop: &str) -> Vec<String> {
+    item_property_values(recipe, itemprop).iter().filter_map(|value| clean_text(value)).collect()
+}
+
+fn first_property_value(recipe: ElementRef, itemprop: &str) -> Option<String> {
+    property_values(recipe, itemprop).into_iter().next()
+}
+
+/// Instruction steps: one per `recipeInstructions` element, or one per `<li>` when a single
+/// element wraps the whole list
+fn instruction_values(recipe: ElementRef) -> Vec<String> {
+    let Ok(li_selector) = Selector::parse("li") else {
+        return Vec::new();
+    };
+
+    let mut steps = Vec::new();
+    for element in own_properties(recipe, "recipeInstructions") {
+        let items: Vec<String> = element.select(&li_selector)
+            .filter_map(|li| clean_text(&li.text().collect::<Vec<_>>().join(" ")))
+            .collect();
+        if items.is_empty() {
+            steps.extend(property_value(element));
+        } else {
+            steps.extend(items);
+        }
+    }
+    steps
+}
+
+/// Build a recipe from the page's main `itemtype="https://schema.org/Recipe"` scope
+pub fn extract_microdata_recipe(dom_index: &DomIndex) -> Option<Recipe> {
+    let recipe = find_main_item(dom_index.document(), &["Recipe"])?;
+
+    let mut ingredients = property_values(recipe, "recipeIngredient");
+    if ingredients.is_empty() {
+        ingredients = property_values(recipe, "ingredients");
+    }
+
+    let duration = |itemprop: &str| {
+        first_property_value(recipe, itemprop).and_then(|d| parse_duration_minutes(&d))
+    };
+
+    Some(Recipe {
+        name: first_property_value(recipe, "name"),
+        ingredients,
+        instructions: instruction_values(recipe),
+        prep_time: duration("prepTime"),
+        cook_time: duration("cookTime"),
+        total_time: duration("totalTime"),
+        recipe_yield: first_property_value(recipe, "recipeYield"),
+        // Declared on the nested `nutrition` item (NutritionInformation)
+        calories: item_property(recipe, "calories", &["Recipe"]).and_then(|c| clean_text(&c)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scraper::Html;
+    use crate::types::Activities;
+
+    #[test]
+    fn nested_item_properties_stay_out_of_recipe_fields() {
+        let html = r#"<div itemscope itemtype="https://schema.org/Recipe">
+            <h1 itemprop="name">Pancakes</h1>
+            <div itemprop="author" itemscope itemtype="https://schema.org/Person"><span itemprop="name">Ann</span></div>
+            <span itemprop="recipeIngredient">2 eggs</span>
+            <span itemprop="recipeIngredient">1 cup flour</span>
+            <div itemprop="nutrition" itemscope itemtype="https://schema.org/NutritionInformation">
+                <span itemprop="calories">250 calories</span>
+                <span itemprop="name">Per serving</span>
+            </div>
+            <ol itemprop="recipeInstructions"><li>Mix</li><li>Fry</li></ol>
+        </div>"#;
+        let document = Html::parse_document(html);
+        let dom_index = DomIndex::build_for(&document, &Activities::default());
+        let recipe = extract_microdata_recipe(&dom_index).unwrap();
+
+        assert_eq!(recipe.name.as_deref(), Some("Pancakes"));
+        assert_eq!(recipe.ingredients, vec!["2 eggs", "1 cup flour"]);
+        assert_eq!(recipe.instructions, vec!["Mix", "Fry"]);
+        assert_eq!(recipe.calories.as_deref(), Some("250 calories"));
+    }
+}
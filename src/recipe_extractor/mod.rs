@@ -0,0 +1,14 @@
+mod helpers;
+mod json_ld;
+mod microdata;
+
+use crate::dom_index::DomIndex;
+use crate::types::Recipe;
+
+/// Extract a schema.org `Recipe` from JSON-LD, falling back to microdata.
+///
+/// Returns None when the page declares no recipe.
+pub fn extract_recipe(dom_index: &DomIndex) -> Option<Recipe> {
+    json_ld::extract_json_ld_recipe(dom_index)
+        .or_else(|| microdata::extract_microdata_recipe(dom_index))
+}
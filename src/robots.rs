@@ -1,13 +1,43 @@
 use crate::error::ExtractionError;
 use url::Url;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::RwLock;
-use redis;
 
 /// In-memory cache for robots.txt content
 pub type RobotsCache = Arc<RwLock<HashMap<String, Arc<robots::Robots>>>>;
 
+/// Maximum robots.txt size read from the network; anything beyond is ignored, as crawlers
+/// such as Googlebot do
+pub const MAX_ROBOTS_TXT_BYTES: usize = 512 * 1024;
+
+/// Cache hit/miss counters of a `RobotsChecker`, since it was created
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RobotsStats {
+    /// Lookups answered by the in-memory cache
+    pub memory_hits: u64,
+    /// Lookups the in-memory cache (when enabled) couldn't answer
+    pub memory_misses: u64,
+    /// Lookups answered by Redis
+    pub redis_hits: u64,
+    /// Lookups Redis (when enabled) couldn't answer
+    pub redis_misses: u64,
+    /// robots.txt downloads
+    pub fetches: u64,
+}
+
+/// Non-rule directives of a robots.txt
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RobotsDirectives {
+    /// Every `Sitemap:` URL, in file order
+    pub sitemaps: Vec<String>,
+    /// The first `Host:` directive (preferred mirror, used by Yandex)
+    pub host: Option<String>,
+    /// `Crawl-delay:` in seconds per user agent (lowercased, `*` for the default group)
+    pub crawl_delays: HashMap<String, f64>,
+}
+
 /// Robots.txt checker with caching support
 pub struct RobotsChecker {
     /// In-memory cache (domain -> robots.txt)
@@ -16,6 +46,15 @@ pub struct RobotsChecker {
     redis_client: Option<redis::Client>,
     /// Redis TTL in seconds (default: 1800 = 30 minutes)
     redis_ttl: u64,
+    /// HTTP client used to fetch robots.txt; a bare client with a 10s timeout when unset
+    client: Option<reqwest::Client>,
+    /// Domains whose fetched robots.txt couldn't be parsed and are treated as allow-all
+    malformed_domains: Arc<RwLock<HashSet<String>>>,
+    memory_hits: AtomicU64,
+    memory_misses: AtomicU64,
+    redis_hits: AtomicU64,
+    redis_misses: AtomicU64,
+    fetches: AtomicU64,
 }
 
 impl RobotsChecker {
@@ -24,7 +63,81 @@ impl RobotsChecker {
             memory_cache: None,
             redis_client: None,
             redis_ttl: 1800, // 30 minutes default
+            client: None,
+            malformed_domains: Arc::new(RwLock::new(HashSet::new())),
+            memory_hits: AtomicU64::new(0),
+            memory_misses: AtomicU64::new(0),
+            redis_hits: AtomicU64::new(0),
+            redis_misses: AtomicU64::new(0),
+            fetches: AtomicU64::new(0),
+        }
+    }
+
+    /// Parse the `Sitemap`, `Host` and `Crawl-delay` directives of a robots.txt, without
+    /// fetching, caching or evaluating any allow/disallow rules
+    pub fn parse_directives(content: &str) -> RobotsDirectives {
+        let mut directives = RobotsDirectives::default();
+        // User agents of the current group; a rule line ends the run of `User-agent` lines
+        let mut agents: Vec<String> = Vec::new();
+        let mut in_agent_lines = false;
+
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+
+            match key.trim().to_lowercase().as_str() {
+                "user-agent" => {
+                    if !in_agent_lines {
+                        agents.clear();
+                        in_agent_lines = true;
+                    }
+                    agents.push(value.to_lowercase());
+                }
+                "sitemap" if !value.is_empty() => directives.sitemaps.push(value.to_string()),
+                "host" if !value.is_empty() => {
+                    directives.host.get_or_insert_with(|| value.to_string());
+                }
+                "crawl-delay" => {
+                    in_agent_lines = false;
+                    if let Ok(delay) = value.parse::<f64>() {
+                        for agent in &agents {
+                            directives.crawl_delays.insert(agent.clone(), delay);
+                        }
+                    }
+                }
+                _ => in_agent_lines = false,
+            }
         }
+
+        directives
+    }
+
+    /// Cache hit/miss counts, e.g. to tune the Redis TTL
+    pub fn stats(&self) -> RobotsStats {
+        RobotsStats {
+            memory_hits: self.memory_hits.load(Ordering::Relaxed),
+            memory_misses: self.memory_misses.load(Ordering::Relaxed),
+            redis_hits: self.redis_hits.load(Ordering::Relaxed),
+            redis_misses: self.redis_misses.load(Ordering::Relaxed),
+            fetches: self.fetches.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Create a checker that fetches robots.txt with `client`, so the fetch uses the same
+    /// timeout, user agent, proxy and headers as page fetches
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self {
+            client: Some(client),
+            ..Self::new()
+        }
+    }
+
+    /// Replace the HTTP client used to fetch robots.txt
+    pub fn set_client(&mut self, client: reqwest::Client) {
+        self.client = Some(client);
     }
 
     /// Enable in-memory caching
@@ -45,14 +158,15 @@ impl RobotsChecker {
         self.redis_ttl = ttl_secs;
     }
 
-    /// Get robots.txt URL for a given page URL
+    /// Get robots.txt URL for a given page URL, on the page's port
     fn get_robots_url(page_url: &str) -> Result<String, ExtractionError> {
         let url = Url::parse(page_url)
             .map_err(|e| ExtractionError::InvalidUrl(format!("Invalid URL: {}", e)))?;
         
-        let robots_url = format!("{}://{}/robots.txt", 
+        let robots_url = format!("{}://{}{}/robots.txt", 
             url.scheme(), 
-            url.host_str().ok_or_else(|| ExtractionError::InvalidUrl("No host in URL".to_string()))?
+            url.host_str().ok_or_else(|| ExtractionError::InvalidUrl("No host in URL".to_string()))?,
+            url.port().map(|port| format!(":{}", port)).unwrap_or_default()
         );
         Ok(robots_url)
     }
@@ -68,27 +182,68 @@ impl RobotsChecker {
 
     /// Fetch robots.txt from URL
     async fn fetch_robots_txt(&self, robots_url: &str) -> Result<String, ExtractionError> {
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
-            .build()
-            .map_err(|e| ExtractionError::HttpError(format!("Failed to create HTTP client: {}", e)))?;
+        let client = match self.client {
+            Some(ref client) => client.clone(),
+            None => reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()
+                .map_err(|e| ExtractionError::HttpError(format!("Failed to create HTTP client: {}", e)))?,
+        };
         
-        let response = client
+        let mut response = client
             .get(robots_url)
             .send()
             .await
             .map_err(|e| ExtractionError::HttpError(format!("Failed to fetch robots.txt: {}", e)))?;
 
         if response.status().is_success() {
-            response.text()
+            // Read at most MAX_ROBOTS_TXT_BYTES so a huge file can't stall the crawl
+            let mut body = Vec::new();
+            while let Some(chunk) = response.chunk()
                 .await
-                .map_err(|e| ExtractionError::HttpError(format!("Failed to read robots.txt: {}", e)))
+                .map_err(|e| ExtractionError::HttpError(format!("Failed to read robots.txt: {}", e)))?
+            {
+                let remaining = MAX_ROBOTS_TXT_BYTES - body.len();
+                body.extend_from_slice(&chunk[..chunk.len().min(remaining)]);
+                if body.len() >= MAX_ROBOTS_TXT_BYTES {
+                    break;
+                }
+            }
+            Ok(String::from_utf8_lossy(&body).into_owned())
         } else {
             // If robots.txt doesn't exist (404), return empty content (allows all)
             Ok(String::new())
         }
     }
 
+    /// Parse fetched robots.txt content. Content that can't be parsed, including a binary body
+    /// (an image or compressed error page served as robots.txt), is treated as allow-all (empty
+    /// rules) and the domain is recorded as malformed instead of failing the extraction.
+    async fn parse_fetched(&self, domain: &str, content: &str) -> Result<Arc<robots::Robots>, ExtractionError> {
+        let parsed = if content.contains('\0') {
+            Err(())
+        } else {
+            robots::Robots::new("*", content.as_bytes()).map_err(|_| ())
+        };
+        match parsed {
+            Ok(robots) => Ok(Arc::new(robots)),
+            Err(()) => {
+                self.malformed_domains.write().await.insert(domain.to_string());
+                robots::Robots::new("*", b"")
+                    .map(Arc::new)
+                    .map_err(|e| ExtractionError::ParseError(format!("Failed to parse robots.txt: {}", e)))
+            }
+        }
+    }
+
+    /// Whether the robots.txt fetched for `page_url`'s domain was malformed and treated as allow-all
+    pub async fn is_malformed(&self, page_url: &str) -> bool {
+        match Self::extract_domain(page_url) {
+            Ok(domain) => self.malformed_domains.read().await.contains(&domain),
+            Err(_) => false,
+        }
+    }
+
     /// Get robots.txt from Redis cache
     async fn get_from_redis(&self, domain: &str) -> Result<Option<String>, ExtractionError> {
         if let Some(ref client) = self.redis_client {
@@ -103,7 +258,7 @@ impl RobotsChecker {
             
             match result {
                 Ok(content) => Ok(Some(content)),
-                Err(redis::RedisError::from((redis::ErrorKind::TypeError, _))) => Ok(None),
+                Err(e) if e.kind() == redis::ErrorKind::TypeError => Ok(None),
                 Err(e) => Err(ExtractionError::Other(format!("Redis error: {}", e))),
             }
         } else {
@@ -122,7 +277,7 @@ impl RobotsChecker {
                 .arg(&key)
                 .arg(self.redis_ttl)
                 .arg(content)
-                .query_async(&mut conn)
+                .query_async::<_, ()>(&mut conn)
                 .await
                 .map_err(|e| ExtractionError::Other(format!("Failed to set Redis cache: {}", e)))?;
         }
@@ -138,7 +293,7 @@ impl RobotsChecker {
             let key = format!("robots:{}", domain);
             redis::cmd("DEL")
                 .arg(&key)
-                .query_async(&mut conn)
+                .query_async::<_, ()>(&mut conn)
                 .await
                 .map_err(|e| ExtractionError::Other(format!("Failed to delete from Redis: {}", e)))?;
         }
@@ -153,14 +308,16 @@ impl RobotsChecker {
         if let Some(ref cache) = self.memory_cache {
             let cache_read = cache.read().await;
             if let Some(robots) = cache_read.get(&domain) {
+                self.memory_hits.fetch_add(1, Ordering::Relaxed);
                 return Ok(Arc::clone(robots));
             }
+            self.memory_misses.fetch_add(1, Ordering::Relaxed);
         }
 
         // Try Redis cache
         if let Some(content) = self.get_from_redis(&domain).await? {
-            let robots = Arc::new(robots::Robots::new("*", content.as_bytes())
-                .map_err(|e| ExtractionError::ParseError(format!("Failed to parse robots.txt: {}", e)))?);
+            self.redis_hits.fetch_add(1, Ordering::Relaxed);
+            let robots = self.parse_fetched(&domain, &content).await?;
             
             // Store in memory cache if enabled
             if let Some(ref cache) = self.memory_cache {
@@ -171,12 +328,16 @@ impl RobotsChecker {
             return Ok(robots);
         }
 
+        if self.redis_client.is_some() {
+            self.redis_misses.fetch_add(1, Ordering::Relaxed);
+        }
+
         // Fetch from URL
         let robots_url = Self::get_robots_url(page_url)?;
+        self.fetches.fetch_add(1, Ordering::Relaxed);
         let content = self.fetch_robots_txt(&robots_url).await?;
         
-        let robots = Arc::new(robots::Robots::new("*", content.as_bytes())
-            .map_err(|e| ExtractionError::ParseError(format!("Failed to parse robots.txt: {}", e)))?);
+        let robots = self.parse_fetched(&domain, &content).await?;
 
         // Store in memory cache if enabled
         if let Some(ref cache) = self.memory_cache {
@@ -229,6 +390,7 @@ impl RobotsChecker {
             let mut cache_write = cache.write().await;
             cache_write.clear();
         }
+        self.malformed_domains.write().await.clear();
     }
 }
 
@@ -238,3 +400,73 @@ impl Default for RobotsChecker {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    /// Serve `body` once as `/robots.txt` on a local port, returning the port
+    fn serve_once(body: Vec<u8>) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            let head = format!("HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: {}\r\n\r\n", body.len());
+            // The checker stops reading at the cap, so a broken pipe here is expected
+            let _ = stream.write_all(head.as_bytes()).and_then(|_| stream.write_all(&body));
+        });
+        port
+    }
+
+    #[tokio::test]
+    async fn junk_robots_txt_is_capped_and_allows_all() {
+        // Real rules followed by binary junk: the rules must not survive the failed parse
+        let mut junk = b"User-agent: *\nDisallow: /private\n".to_vec();
+        junk.extend(
+            b"Disallow: /private }{ \0\x07 Allow:: Crawl-delay: soon\n"
+                .iter()
+                .copied()
+                .cycle()
+                .take(1024 * 1024),
+        );
+        let robots_url = format!("http://127.0.0.1:{}/robots.txt", serve_once(junk));
+        let checker = RobotsChecker::new();
+
+        let content = checker.fetch_robots_txt(&robots_url).await.unwrap();
+        assert_eq!(content.len(), MAX_ROBOTS_TXT_BYTES);
+
+        let robots = checker.parse_fetched("127.0.0.1", &content).await.unwrap();
+        assert!(checker.is_malformed("http://127.0.0.1/").await);
+        assert!(robots.allowed("/private/page", "ferriscope"));
+        assert!(robots.allowed("/", "*"));
+    }
+
+    #[tokio::test]
+    async fn well_formed_robots_txt_is_not_malformed() {
+        let checker = RobotsChecker::new();
+        checker.parse_fetched("example.com", "User-agent: *\nDisallow: /private\n").await.unwrap();
+        assert!(!checker.is_malformed("https://example.com/").await);
+    }
+
+    #[test]
+    fn robots_url_keeps_the_port() {
+        assert_eq!(RobotsChecker::get_robots_url("http://127.0.0.1:8080/a/b?c=d").unwrap(), "http://127.0.0.1:8080/robots.txt");
+        assert_eq!(RobotsChecker::get_robots_url("https://example.com/a").unwrap(), "https://example.com/robots.txt");
+    }
+
+    #[tokio::test]
+    async fn unparseable_robots_txt_sets_the_result_warning() {
+        // A PNG served as robots.txt
+        let body = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\0\x01\0\0\0\x01".to_vec();
+        let url = format!("http://127.0.0.1:{}/page", serve_once(body));
+        let mut extractor = crate::WebExtractor::new_with_html(url, "<html><body><p>Text</p></body></html>".to_string());
+        extractor.enable_robots_check();
+
+        let result = extractor.run_async().await.unwrap();
+        assert_eq!(result.robots_warning.as_deref(), Some("robots.txt could not be parsed; treated as allow-all"));
+    }
+}
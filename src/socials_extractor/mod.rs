@@ -1,5 +1,22 @@
+mod profiles;
+
 use std::collections::HashMap;
+use serde::Serialize;
 use crate::dom_index::DomIndex;
+use crate::link_extractor::resolve_url;
+use crate::types::{OgImage, OpenGraph, SocialsInfo, TwitterCard};
+
+/// One `og:image` with the structured properties declared right after it, as declared
+#[derive(Debug, Default, Serialize)]
+struct OgImageMeta {
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    width: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    height: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    alt: Option<String>,
+}
 
 /// Returns a list of all available social metadata field names
 pub fn get_all_social_fields() -> Vec<String> {
@@ -20,28 +37,104 @@ pub fn get_all_social_fields() -> Vec<String> {
         "og_image_alt".to_string(),
         "og_site_name".to_string(),
         "og_locale".to_string(),
+        "og_images".to_string(),
+        "og_locale_alternates".to_string(),
+        "og_video".to_string(),
+        "og_video_secure_url".to_string(),
+        "og_video_type".to_string(),
+        "og_video_width".to_string(),
+        "og_video_height".to_string(),
+        "twitter_player".to_string(),
+        "twitter_player_width".to_string(),
+        "twitter_player_height".to_string(),
+        "twitter_player_stream".to_string(),
+        "og_determiner".to_string(),
+        "og_audio".to_string(),
+        "fb_app_id".to_string(),
+        "fb_pages".to_string(),
+        "fb_publisher".to_string(),
+        "social_profiles".to_string(),
+        "viewport".to_string(),
+        "theme_color".to_string(),
+        "application_name".to_string(),
     ]
 }
 
+/// Field aliases: short names, and groups expanding to several fields. "title" and
+/// "description" return both the Open Graph and the Twitter Card value; "video" is the
+/// `og:video` cluster, "player" the `twitter:player` cluster.
+fn expand_field_alias(field: &str) -> Option<&'static [&'static str]> {
+    match field {
+        "title" => Some(&["og_title", "twitter_title"]),
+        "description" => Some(&["og_description", "twitter_description"]),
+        "image" => Some(&["og_image"]),
+        "images" => Some(&["og_images"]),
+        "url" => Some(&["og_url"]),
+        "type" => Some(&["og_type"]),
+        "site_name" => Some(&["og_site_name"]),
+        "locale" => Some(&["og_locale"]),
+        "card" => Some(&["twitter_card"]),
+        "site" => Some(&["twitter_site"]),
+        "creator" => Some(&["twitter_creator"]),
+        "app_id" => Some(&["fb_app_id"]),
+        "pages" => Some(&["fb_pages"]),
+        "publisher" => Some(&["fb_publisher"]),
+        "profiles" => Some(&["social_profiles"]),
+        "video" => Some(&["og_video", "og_video_secure_url", "og_video_type", "og_video_width", "og_video_height"]),
+        "player" => Some(&["twitter_player", "twitter_player_width", "twitter_player_height", "twitter_player_stream"]),
+        _ => None,
+    }
+}
+
+/// Whether `field` is a social field name, a group alias, or "all"
+pub fn is_known_social_field(field: &str) -> bool {
+    field == "all" || expand_field_alias(field).is_some() || get_all_social_fields().iter().any(|f| f == field)
+}
+
+/// `fb:*`/`og:*` value from `<meta property>`, or from `<meta name>` as some pages declare it
+fn property_meta<'a>(dom_index: &'a DomIndex, property: &str) -> Option<&'a String> {
+    dom_index.get_meta_by_property(property).or_else(|| dom_index.get_meta_by_name(property))
+}
+
+/// `twitter:*` value from `<meta name>`, or from `<meta property>` as many pages declare it
+fn twitter_meta<'a>(dom_index: &'a DomIndex, name: &str) -> Option<&'a String> {
+    dom_index.get_meta_by_name(name).or_else(|| dom_index.get_meta_by_property(name))
+}
+
+/// A URL-valued meta, resolved against `base_url`
+fn resolved(value: Option<&String>, base_url: &str) -> Option<String> {
+    value
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(|v| resolve_url(base_url, v))
+}
+
 /// Extract social metadata using pre-built DOM index (avoids re-traversing DOM)
-pub fn extract_socials_with_index(dom_index: &DomIndex, social_fields: &[String]) -> HashMap<String, String> {
+///
+/// `base_url` resolves relative video and player URLs.
+pub fn extract_socials_with_index(dom_index: &DomIndex, base_url: &str, social_fields: &[String]) -> HashMap<String, String> {
     let mut socials = HashMap::new();
 
     // Check if "all" is in the list
     let fields_to_extract = if social_fields.iter().any(|f| f == "all") {
         get_all_social_fields()
     } else {
-        social_fields.to_vec()
+        social_fields.iter()
+            .flat_map(|field| match expand_field_alias(field) {
+                Some(group) => group.iter().map(|f| f.to_string()).collect(),
+                None => vec![field.clone()],
+            })
+            .collect()
     };
 
     for field in &fields_to_extract {
         let value = match field.as_str() {
-            "twitter_card" => dom_index.get_meta_by_name("twitter:card").cloned(),
-            "twitter_site" => dom_index.get_meta_by_name("twitter:site").cloned(),
-            "twitter_creator" => dom_index.get_meta_by_name("twitter:creator").cloned(),
-            "twitter_title" => dom_index.get_meta_by_name("twitter:title").cloned(),
-            "twitter_description" => dom_index.get_meta_by_name("twitter:description").cloned(),
-            "twitter_image" => dom_index.get_meta_by_name("twitter:image").cloned(),
+            "twitter_card" => twitter_meta(dom_index, "twitter:card").cloned(),
+            "twitter_site" => twitter_meta(dom_index, "twitter:site").cloned(),
+            "twitter_creator" => twitter_meta(dom_index, "twitter:creator").cloned(),
+            "twitter_title" => twitter_meta(dom_index, "twitter:title").cloned(),
+            "twitter_description" => twitter_meta(dom_index, "twitter:description").cloned(),
+            "twitter_image" => twitter_meta(dom_index, "twitter:image").cloned(),
             "og_url" => dom_index.get_meta_by_property("og:url").cloned(),
             "og_type" => dom_index.get_meta_by_property("og:type").cloned(),
             "og_title" => dom_index.get_meta_by_property("og:title").cloned(),
@@ -52,6 +145,62 @@ pub fn extract_socials_with_index(dom_index: &DomIndex, social_fields: &[String]
             "og_image_alt" => dom_index.get_meta_by_property("og:image:alt").cloned(),
             "og_site_name" => dom_index.get_meta_by_property("og:site_name").cloned(),
             "og_locale" => dom_index.get_meta_by_property("og:locale").cloned(),
+            "og_images" => {
+                let images = og_images(dom_index);
+                if images.is_empty() {
+                    None
+                } else {
+                    serde_json::to_string(&images).ok()
+                }
+            },
+            "og_locale_alternates" => {
+                let locales = dom_index.get_all_meta_by_property("og:locale:alternate");
+                if locales.is_empty() {
+                    None
+                } else {
+                    serde_json::to_string(locales).ok()
+                }
+            },
+            "og_video" => resolved(
+                dom_index.get_meta_by_property("og:video").or_else(|| dom_index.get_meta_by_property("og:video:url")),
+                base_url,
+            ),
+            "og_video_secure_url" => resolved(dom_index.get_meta_by_property("og:video:secure_url"), base_url),
+            "og_video_type" => dom_index.get_meta_by_property("og:video:type").cloned(),
+            "og_video_width" => dom_index.get_meta_by_property("og:video:width").cloned(),
+            "og_video_height" => dom_index.get_meta_by_property("og:video:height").cloned(),
+            "twitter_player" => resolved(twitter_meta(dom_index, "twitter:player"), base_url),
+            "twitter_player_width" => twitter_meta(dom_index, "twitter:player:width").cloned(),
+            "twitter_player_height" => twitter_meta(dom_index, "twitter:player:height").cloned(),
+            "twitter_player_stream" => resolved(twitter_meta(dom_index, "twitter:player:stream"), base_url),
+            "og_determiner" => dom_index.get_meta_by_property("og:determiner").cloned(),
+            "og_audio" => resolved(
+                dom_index.get_meta_by_property("og:audio").or_else(|| dom_index.get_meta_by_property("og:audio:url")),
+                base_url,
+            ),
+            // Facebook attribution
+            "fb_app_id" => property_meta(dom_index, "fb:app_id").cloned(),
+            "fb_pages" => {
+                let pages = fb_pages(dom_index);
+                if pages.is_empty() {
+                    None
+                } else {
+                    serde_json::to_string(&pages).ok()
+                }
+            },
+            "fb_publisher" => resolved(dom_index.get_meta_by_property("article:publisher"), base_url),
+            "social_profiles" => {
+                let profiles = profiles::extract_social_profiles(dom_index, base_url);
+                if profiles.is_empty() {
+                    None
+                } else {
+                    serde_json::to_string(&profiles).ok()
+                }
+            },
+            // Head signals used for rich link previews
+            "viewport" => dom_index.get_meta_by_name("viewport").cloned(),
+            "theme_color" => dom_index.get_meta_by_name("theme-color").cloned(),
+            "application_name" => dom_index.get_meta_by_name("application-name").cloned(),
             _ => None,
         };
 
@@ -63,3 +212,137 @@ pub fn extract_socials_with_index(dom_index: &DomIndex, social_fields: &[String]
     socials
 }
 
+/// Typed Open Graph and Twitter Card metadata, read from the same metas as the flat map.
+/// Values are as declared; image dimensions are parsed to integers.
+pub fn extract_socials_typed(dom_index: &DomIndex) -> SocialsInfo {
+    let og = |property: &str| dom_index.get_meta_by_property(property).cloned();
+    let twitter = |name: &str| twitter_meta(dom_index, name).cloned();
+    let dimension = |value: Option<String>| value.and_then(|v| v.trim().parse::<u32>().ok());
+
+    let open_graph = OpenGraph {
+        url: og("og:url"),
+        og_type: og("og:type"),
+        title: og("og:title"),
+        description: og("og:description"),
+        images: og_images(dom_index).into_iter()
+            .map(|image| OgImage {
+                url: image.url,
+                width: dimension(image.width),
+                height: dimension(image.height),
+                alt: image.alt,
+            })
+            .collect(),
+        site_name: og("og:site_name"),
+        locale: og("og:locale"),
+        locale_alternates: dom_index.get_all_meta_by_property("og:locale:alternate").to_vec(),
+    };
+    let twitter = TwitterCard {
+        card: twitter("twitter:card"),
+        site: twitter("twitter:site"),
+        creator: twitter("twitter:creator"),
+        title: twitter("twitter:title"),
+        description: twitter("twitter:description"),
+        image: twitter("twitter:image"),
+        image_alt: twitter("twitter:image:alt"),
+    };
+    SocialsInfo { open_graph, twitter }
+}
+
+/// Every `og:image` in document order. Following the Open Graph structured-property rules,
+/// `og:image:width`, `:height` and `:alt` belong to the closest `og:image` before them;
+/// `og:image:url` repeating the current image's URL adds nothing.
+fn og_images(dom_index: &DomIndex) -> Vec<OgImageMeta> {
+    let mut images: Vec<OgImageMeta> = Vec::new();
+    for (property, content) in &dom_index.meta_properties {
+        let content = content.trim();
+        match property.as_str() {
+            "og:image" => images.push(OgImageMeta { url: content.to_string(), ..Default::default() }),
+            // Repeats the URL of the `og:image` it follows; any other URL is a new image
+            "og:image:url" if images.last().is_none_or(|image| image.url != content) => {
+                images.push(OgImageMeta { url: content.to_string(), ..Default::default() });
+            }
+            "og:image:width" | "og:image:height" | "og:image:alt" => {
+                if let Some(image) = images.last_mut() {
+                    let slot = match property.as_str() {
+                        "og:image:width" => &mut image.width,
+                        "og:image:height" => &mut image.height,
+                        _ => &mut image.alt,
+                    };
+                    // A repeated property starts nothing new; the first one wins
+                    slot.get_or_insert_with(|| content.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    images.retain(|image| !image.url.is_empty());
+    images
+}
+
+/// Every Facebook page ID from `fb:pages` metas, which may also list several IDs
+/// comma-separated in one tag, in document order without duplicates
+fn fb_pages(dom_index: &DomIndex) -> Vec<String> {
+    let mut pages: Vec<String> = Vec::new();
+    let metas = dom_index.get_all_meta_by_property("fb:pages").iter()
+        .chain(dom_index.meta_by_name.get("fb:pages").into_iter().flatten());
+    for page in metas.flat_map(|content| content.split(',')).map(str::trim) {
+        if !page.is_empty() && !pages.iter().any(|p| p == page) {
+            pages.push(page.to_string());
+        }
+    }
+    pages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Activities;
+    use scraper::Html;
+
+    const PAGE: &str = r#"<head>
+        <meta property="og:title" content="OG title"><meta name="twitter:title" content="Card title">
+        <meta property="og:description" content="OG description"><meta property="og:image" content="/cover.jpg">
+        <meta name="twitter:card" content="summary_large_image"><meta property="twitter:site" content="@acme">
+        </head>"#;
+
+    fn socials(fields: &[&str]) -> HashMap<String, String> {
+        let document = Html::parse_document(PAGE);
+        let fields: Vec<String> = fields.iter().map(|f| f.to_string()).collect();
+        let activities = Activities { extract_socials: fields.clone(), ..Default::default() };
+        let index = DomIndex::build_for(&document, &activities);
+        extract_socials_with_index(&index, "https://acme.example/post", &fields)
+    }
+
+    #[test]
+    fn aliases_expand_to_their_fields() {
+        let found = socials(&["title", "image", "card", "site"]);
+        assert_eq!(found.get("og_title").map(String::as_str), Some("OG title"));
+        assert_eq!(found.get("twitter_title").map(String::as_str), Some("Card title"));
+        assert_eq!(found.get("og_image").map(String::as_str), Some("/cover.jpg"));
+        assert_eq!(found.get("twitter_card").map(String::as_str), Some("summary_large_image"));
+        assert_eq!(found.get("twitter_site").map(String::as_str), Some("@acme"));
+        assert!(!found.contains_key("title"));
+
+        // Alias groups only return what the page declares
+        assert_eq!(socials(&["description"]).keys().collect::<Vec<_>>(), vec!["og_description"]);
+    }
+
+    #[test]
+    fn unknown_fields_are_skipped_and_reported_unknown() {
+        assert!(socials(&["titel", "og_imag"]).is_empty());
+        assert!(!is_known_social_field("titel"));
+        assert!(is_known_social_field("title"));
+        assert!(is_known_social_field("og_title"));
+        assert!(is_known_social_field("all"));
+    }
+
+    #[test]
+    fn all_returns_every_declared_field() {
+        let found = socials(&["all"]);
+        for field in ["og_title", "twitter_title", "og_description", "og_image", "twitter_card", "twitter_site"] {
+            assert!(found.contains_key(field), "missing {}", field);
+        }
+        assert!(found.keys().all(|f| get_all_social_fields().contains(f)));
+        assert_eq!(socials(&["all", "titel"]), found);
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::BTreeMap;
+use once_cell::sync::Lazy;
+use scraper::Selector;
+use serde_json::Value;
+use url::Url;
+use crate::dom_index::DomIndex;
+use crate::json_ld::has_json_ld_type;
+use crate::link_extractor::resolve_url;
+
+static REL_ME_SELECTOR: Lazy<Selector> = Lazy::new(|| Selector::parse("a[rel~=me][href]").unwrap());
+
+/// JSON-LD types whose `sameAs` lists the site's (or author's) own profiles
+const PROFILE_OWNER_TYPES: &[&str] = &[
+    "Organization", "Corporation", "NewsMediaOrganization", "LocalBusiness", "OnlineStore",
+    "Store", "Person", "WebSite", "Brand",
+];
+
+/// Query parameters added by share buttons and analytics, dropped from profile URLs
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "igshid", "igsh", "si", "ref", "ref_src", "ref_url", "s", "t", "hl", "lang",
+    "feature", "trk", "originalSubdomain",
+];
+
+/// First path segments that are platform features (sharing, search, login), never an account
+const RESERVED_SEGMENTS: &[&str] = &[
+    "share", "sharer", "sharer.php", "share.php", "sharearticle", "sharing", "intent", "dialog",
+    "plugins", "home", "search", "hashtag", "explore", "login", "signup", "i", "tr", "watch",
+    "embed", "shorts", "playlist", "results", "feed", "p", "reel", "reels", "tv", "stories",
+    "accounts", "about", "legal", "policies", "privacy", "terms", "help", "settings", "features",
+    "sponsors", "marketplace", "events", "hashtags", "tag", "tags",
+];
+
+/// The platform an http(s) URL's host belongs to, with the host its profiles are listed under
+fn platform(host: &str) -> Option<(&'static str, &'static str)> {
+    match host {
+        "twitter.com" | "x.com" => Some(("twitter", "x.com")),
+        "facebook.com" | "fb.com" => Some(("facebook", "facebook.com")),
+        "instagram.com" => Some(("instagram", "instagram.com")),
+        "linkedin.com" => Some(("linkedin", "linkedin.com")),
+        "youtube.com" => Some(("youtube", "youtube.com")),
+        "tiktok.com" => Some(("tiktok", "tiktok.com")),
+        "github.com" => Some(("github", "github.com")),
+        _ => None,
+    }
+}
+
+/// Whether `segments` (the non-empty path segments) name an account on `platform` rather
+/// than a post, a share dialog or a platform feature
+fn is_profile_path(platform: &str, segments: &[&str], url: &Url) -> bool {
+    let Some(first) = segments.first() else {
+        return false;
+    };
+    if RESERVED_SEGMENTS.contains(&first.to_lowercase().as_str()) {
+        return false;
+    }
+    match platform {
+        // /company/acme, /in/jane, /school/..., /showcase/...
+        "linkedin" => segments.len() == 2 && matches!(*first, "company" | "in" | "school" | "showcase"),
+        // /@handle, /channel/UC..., /c/name, /user/name
+        "youtube" => match segments {
+            [handle] => handle.starts_with('@'),
+            [kind, _] => matches!(*kind, "channel" | "c" | "user"),
+            _ => false,
+        },
+        "tiktok" => segments.len() == 1 && first.starts_with('@'),
+        // profile.php?id=123, /acme, /pages/Acme/123, /groups/acme
+        "facebook" => match segments {
+            ["profile.php"] => url.query_pairs().any(|(key, _)| key == "id"),
+            [_] => true,
+            [kind, ..] => matches!(*kind, "pages" | "groups"),
+            _ => false,
+        },
+        // A single account segment; /acme/status/123 is a post
+        _ => segments.len() == 1,
+    }
+}
+
+/// Canonical form of a profile URL: https, the platform's main host (`twitter.com` becomes
+/// `x.com`, `fb.com` becomes `facebook.com`, `www.`/`m.` dropped), tracking parameters and
+/// fragment dropped, no trailing slash. `None` for URLs that aren't a profile on a known
+/// platform.
+fn normalize_profile(raw: &str, base_url: &str) -> Option<(&'static str, String)> {
+    let mut url = Url::parse(&resolve_url(base_url, raw.trim())).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let host = url.host_str()?.to_lowercase();
+    let host = ["www.", "m.", "mobile.", "web."].iter()
+        .find_map(|prefix| host.strip_prefix(prefix))
+        .unwrap_or(&host);
+    let (platform, host) = platform(host)?;
+    let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+    if !is_profile_path(platform, &segments, &url) {
+        return None;
+    }
+    Some((platform, canonical_url(&mut url, host)))
+}
+
+fn canonical_url(url: &mut Url, host: &str) -> String {
+    let kept: Vec<(String, String)> = url.query_pairs()
+        .filter(|(key, _)| !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_ref()))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    url.set_query(None);
+    if !kept.is_empty() {
+        url.query_pairs_mut().extend_pairs(kept);
+    }
+    url.set_fragment(None);
+    let _ = url.set_scheme("https");
+    let _ = url.set_host(Some(host));
+    let path = url.path().trim_end_matches('/').to_string();
+    url.set_path(&path);
+    url.to_string()
+}
+
+/// A `rel="me"` link to a Mastodon-style account (`https://instance.social/@name`) on a host
+/// that isn't one of the known platforms
+fn mastodon_profile(raw: &str, base_url: &str) -> Option<String> {
+    let mut url = Url::parse(&resolve_url(base_url, raw.trim())).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let host = url.host_str()?.to_lowercase();
+    let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+    match segments.as_slice() {
+        [handle] if handle.starts_with('@') && handle.len() > 1 => Some(canonical_url(&mut url, &host)),
+        _ => None,
+    }
+}
+
+/// `sameAs` URLs of the page's Organization/Person/WebSite entities
+fn same_as_urls(dom_index: &DomIndex) -> Vec<String> {
+    let mut urls = Vec::new();
+    for obj in dom_index.get_json_ld_entities() {
+        if !has_json_ld_type(obj, PROFILE_OWNER_TYPES) {
+            continue;
+        }
+        match obj.get("sameAs") {
+            Some(Value::String(url)) => urls.push(url.clone()),
+            Some(Value::Array(values)) => urls.extend(values.iter().filter_map(Value::as_str).map(str::to_string)),
+            _ => {}
+        }
+    }
+    urls
+}
+
+/// The site's social profiles by platform (`twitter`, `facebook`, `instagram`, `linkedin`,
+/// `youtube`, `tiktok`, `github`, `mastodon`): JSON-LD `sameAs` first, then page links, then
+/// `rel="me"` links. Share buttons, posts and platform pages are left out, and URLs are
+/// normalized so the same profile is listed once.
+pub fn extract_social_profiles(dom_index: &DomIndex, base_url: &str) -> BTreeMap<&'static str, Vec<String>> {
+    let mut profiles: BTreeMap<&'static str, Vec<String>> = BTreeMap::new();
+    let mut add = |platform: &'static str, url: String| {
+        let urls = profiles.entry(platform).or_default();
+        if !urls.iter().any(|u| u.eq_ignore_ascii_case(&url)) {
+            urls.push(url);
+        }
+    };
+
+    let same_as = same_as_urls(dom_index);
+    let links = dom_index.get_link_data().iter().map(|(href, _)| href.clone());
+    for raw in same_as.into_iter().chain(links) {
+        if let Some((platform, url)) = normalize_profile(&raw, base_url) {
+            add(platform, url);
+        }
+    }
+
+    let rel_me = dom_index.document().select(&REL_ME_SELECTOR)
+        .filter_map(|a| a.value().attr("href"))
+        .chain(dom_index.get_link_tags("me").iter().map(|link| link.href.as_str()));
+    for raw in rel_me {
+        if let Some((platform, url)) = normalize_profile(raw, base_url) {
+            add(platform, url);
+        } else if let Some(url) = mastodon_profile(raw, base_url) {
+            add("mastodon", url);
+        }
+    }
+    profiles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom_index::DomIndex;
+    use crate::types::Activities;
+    use scraper::Html;
+
+    const BASE: &str = "https://www.acme.example/blog/post";
+
+    #[test]
+    fn normalize_profile_table() {
+        let cases: &[(&str, Option<(&str, &str)>)] = &[
+            // Profiles, normalized
+            ("https://twitter.com/acme", Some(("twitter", "https://x.com/acme"))),
+            ("http://www.x.com/acme/?ref_src=twsrc", Some(("twitter", "https://x.com/acme"))),
+            ("https://m.facebook.com/acme#about", Some(("facebook", "https://facebook.com/acme"))),
+            ("https://www.facebook.com/profile.php?id=123", Some(("facebook", "https://facebook.com/profile.php?id=123"))),
+            ("https://www.instagram.com/acme/?igshid=abc", Some(("instagram", "https://instagram.com/acme"))),
+            ("https://www.linkedin.com/company/acme/", Some(("linkedin", "https://linkedin.com/company/acme"))),
+            ("https://www.youtube.com/@acme", Some(("youtube", "https://youtube.com/@acme"))),
+            ("https://www.youtube.com/channel/UC123", Some(("youtube", "https://youtube.com/channel/UC123"))),
+            ("https://www.tiktok.com/@acme?lang=en", Some(("tiktok", "https://tiktok.com/@acme"))),
+            ("https://github.com/acme", Some(("github", "https://github.com/acme"))),
+            // Share dialogs
+            ("https://www.facebook.com/sharer.php?u=https://acme.example", None),
+            ("https://www.facebook.com/sharer/sharer.php?u=https://acme.example", None),
+            ("https://twitter.com/intent/tweet?text=hi&url=https://acme.example", None),
+            ("https://x.com/share?url=https://acme.example", None),
+            ("https://twitter.com/home?status=Reading%20this", None),
+            ("https://www.linkedin.com/shareArticle?mini=true&url=https://acme.example", None),
+            ("https://www.linkedin.com/sharing/share-offsite/?url=https://acme.example", None),
+            // Posts and videos
+            ("https://twitter.com/acme/status/1234567890", None),
+            ("https://www.instagram.com/p/Cabc123/", None),
+            ("https://www.instagram.com/reel/Cabc123/", None),
+            ("https://www.youtube.com/watch?v=abc123", None),
+            ("https://www.tiktok.com/@acme/video/123", None),
+            ("https://www.linkedin.com/posts/acme_launch-123", None),
+            ("https://www.facebook.com/acme/posts/123", None),
+            // Other hosts and schemes
+            ("https://acme.example/twitter", None),
+            ("mailto:hello@acme.example", None),
+        ];
+        for (raw, expected) in cases {
+            let actual = normalize_profile(raw, BASE);
+            let expected = expected.map(|(platform, url)| (platform, url.to_string()));
+            assert_eq!(actual, expected, "raw {:?}", raw);
+        }
+    }
+
+    #[test]
+    fn icon_only_links_and_same_as_are_collected_once() {
+        let html = r#"<html><head>
+            <script type="application/ld+json">
+            {"@context": "https://schema.org", "@type": ["Organization", "Brand"],
+             "sameAs": ["https://twitter.com/acme", "https://github.com/acme"]}
+            </script>
+            <script type="application/ld+json">
+            {"@type": "Article", "sameAs": "https://twitter.com/writer"}
+            </script>
+            </head><body>
+            <a href="https://twitter.com/intent/tweet?url=https://acme.example">Share</a>
+            <footer>
+              <a href="https://x.com/acme/"><svg aria-hidden="true"></svg></a>
+              <a href="https://www.instagram.com/acme"><i class="icon-instagram"></i></a>
+              <a rel="me" href="https://mastodon.social/@acme"><img src="/m.svg" alt=""></a>
+            </footer>
+            </body></html>"#;
+        let document = Html::parse_document(html);
+        let activities = Activities { extract_socials: vec!["social_profiles".to_string()], ..Default::default() };
+        let dom_index = DomIndex::build_for(&document, &activities);
+
+        let profiles = extract_social_profiles(&dom_index, BASE);
+        let expected: BTreeMap<&str, Vec<String>> = [
+            ("github", vec!["https://github.com/acme".to_string()]),
+            ("instagram", vec!["https://instagram.com/acme".to_string()]),
+            ("mastodon", vec!["https://mastodon.social/@acme".to_string()]),
+            ("twitter", vec!["https://x.com/acme".to_string()]),
+        ].into_iter().collect();
+        assert_eq!(profiles, expected);
+    }
+}
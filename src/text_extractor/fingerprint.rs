@@ -0,0 +1,62 @@
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Hex SHA-256 of the text after lowercasing and collapsing whitespace, so pages whose main
+/// text only differs in case or spacing hash the same
+pub fn content_hash(text: &str) -> String {
+    let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    Sha256::digest(normalized.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Number of consecutive words per shingle
+const SHINGLE_SIZE: usize = 3;
+
+/// 64-bit simhash of the text for near-duplicate detection: pages whose hashes differ in only
+/// a few bits (small Hamming distance) carry nearly the same text.
+///
+/// The text is lowercased and split into Unicode words (punctuation dropped), then into
+/// overlapping 3-word shingles; texts shorter than a shingle use their single words. Each
+/// shingle is hashed with 64-bit FNV-1a and weighted by how often it occurs.
+pub fn simhash(text: &str) -> u64 {
+    let lowered = text.to_lowercase();
+    let words: Vec<&str> = lowered.unicode_words().collect();
+    let mut shingles: HashMap<String, i64> = HashMap::new();
+    if words.len() < SHINGLE_SIZE {
+        for word in &words {
+            *shingles.entry(word.to_string()).or_insert(0) += 1;
+        }
+    } else {
+        for window in words.windows(SHINGLE_SIZE) {
+            *shingles.entry(window.join(" ")).or_insert(0) += 1;
+        }
+    }
+
+    let mut weights = [0i64; 64];
+    for (shingle, count) in &shingles {
+        let hash = fnv1a_64(shingle.as_bytes());
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            if hash & (1 << bit) != 0 {
+                *weight += count;
+            } else {
+                *weight -= count;
+            }
+        }
+    }
+
+    weights
+        .iter()
+        .enumerate()
+        .filter(|(_, weight)| **weight > 0)
+        .fold(0u64, |hash, (bit, _)| hash | (1 << bit))
+}
+
+/// FNV-1a: stable across runs and platforms, unlike `DefaultHasher`
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
@@ -1,3 +1,13 @@
+use url::Url;
+use crate::types::InlineLinkMode;
+
+/// Settings the text walker needs while descending the tree
+pub struct WalkContext {
+    pub inline_link_mode: InlineLinkMode,
+    /// Page URL used to resolve relative anchor hrefs
+    pub base_url: Option<Url>,
+}
+
 /// Check if an element is a boilerplate element (nav, header, footer, etc.)
 pub fn is_boilerplate_element(element: &scraper::element_ref::ElementRef) -> bool {
     let tag_name = element.value().name();
@@ -40,8 +50,69 @@ pub fn is_boilerplate_element(element: &scraper::element_ref::ElementRef) -> boo
     false
 }
 
-/// Recursively extract text from non-boilerplate elements
-pub fn extract_text_from_clean_elements(element: scraper::element_ref::ElementRef) -> String {
+/// Resolve an anchor href to an absolute http(s) URL, skipping fragments and pseudo-schemes
+fn resolve_anchor_href(elem_ref: &scraper::element_ref::ElementRef, ctx: &WalkContext) -> Option<String> {
+    let href = elem_ref.value().attr("href")?.trim();
+    if href.is_empty() || href.starts_with('#') {
+        return None;
+    }
+    let resolved = match ctx.base_url {
+        Some(ref base) => base.join(href).ok()?,
+        None => Url::parse(href).ok()?,
+    };
+    if matches!(resolved.scheme(), "http" | "https") {
+        Some(resolved.to_string())
+    } else {
+        None
+    }
+}
+
+/// A piece of walker output: prose whose whitespace gets collapsed, or a code block kept as is
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextSegment {
+    Normal(String),
+    Verbatim(String),
+}
+
+/// Join walker output, collapsing whitespace in prose and keeping code blocks verbatim on
+/// their own lines
+pub fn collapse_whitespace(segments: &[TextSegment]) -> String {
+    let mut output = String::new();
+
+    for segment in segments {
+        match segment {
+            TextSegment::Normal(text) => {
+                for word in text.split_whitespace() {
+                    if !output.is_empty() && !output.ends_with('\n') {
+                        output.push(' ');
+                    }
+                    output.push_str(word);
+                }
+            }
+            TextSegment::Verbatim(code) => {
+                if !output.is_empty() && !output.ends_with('\n') {
+                    output.push('\n');
+                }
+                output.push_str(code);
+                output.push('\n');
+            }
+        }
+    }
+
+    output.trim_end().to_string()
+}
+
+/// Raw text of a `<pre>`/`<code>` element with surrounding blank lines removed but
+/// indentation and internal newlines kept
+fn verbatim_text(elem_ref: &scraper::element_ref::ElementRef) -> String {
+    let raw: String = elem_ref.text().collect();
+    raw.trim_start_matches(['\n', '\r'])
+        .trim_end()
+        .to_string()
+}
+
+/// Recursively extract text from non-boilerplate elements, as non-blank segments in document order
+pub fn extract_text_from_clean_elements(element: scraper::element_ref::ElementRef, ctx: &WalkContext) -> Vec<TextSegment> {
     let mut text_parts = Vec::new();
     
     // Recursively extract text from non-boilerplate elements
@@ -53,21 +124,51 @@ pub fn extract_text_from_clean_elements(element: scraper::element_ref::ElementRe
             if is_boilerplate_element(&elem_ref) {
                 continue;
             }
+
+            // Inline links are handled according to the configured mode
+            if elem_ref.value().name() == "a" {
+                match ctx.inline_link_mode {
+                    InlineLinkMode::Drop => continue,
+                    InlineLinkMode::TextOnly => {}
+                    InlineLinkMode::WithUrl => {
+                        let anchor_text = extract_text_from_clean_elements(elem_ref, ctx);
+                        if anchor_text.is_empty() {
+                            continue;
+                        }
+                        text_parts.extend(anchor_text);
+                        if let Some(url) = resolve_anchor_href(&elem_ref, ctx) {
+                            text_parts.push(TextSegment::Normal(format!("({})", url)));
+                        }
+                        continue;
+                    }
+                }
+            }
             
-            // Recursively extract from children
-            let child_text = extract_text_from_clean_elements(elem_ref);
-            if !child_text.trim().is_empty() {
-                text_parts.push(child_text);
+            // Code keeps its whitespace: <pre> blocks and multi-line <code> are fenced by
+            // newlines, while short inline <code> stays part of the sentence
+            if matches!(elem_ref.value().name(), "pre" | "code") {
+                let code = verbatim_text(&elem_ref);
+                if code.trim().is_empty() {
+                    continue;
+                }
+                if elem_ref.value().name() == "pre" || code.contains('\n') {
+                    text_parts.push(TextSegment::Verbatim(code));
+                } else {
+                    text_parts.push(TextSegment::Normal(code));
+                }
+                continue;
             }
+            
+            // Recursively extract from children
+            text_parts.extend(extract_text_from_clean_elements(elem_ref, ctx));
         } else if child.value().is_text() {
             // Direct text node - include it
             let text = child.value().as_text().unwrap().text.trim();
             if !text.is_empty() {
-                text_parts.push(text.to_string());
+                text_parts.push(TextSegment::Normal(text.to_string()));
             }
         }
     }
     
-    text_parts.join(" ")
+    text_parts
 }
-
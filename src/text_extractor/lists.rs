@@ -0,0 +1,75 @@
+use scraper::{ElementRef, Html, Selector};
+use crate::types::{ListBlock, ListItem};
+use super::helpers::is_boilerplate_element;
+
+/// Extract `<ul>`/`<ol>` lists from the page body as nested data, skipping lists that sit
+/// inside navigation, footers, sidebars and other boilerplate
+pub fn extract_lists(document: &Html) -> Vec<ListBlock> {
+    let root = Selector::parse("body")
+        .ok()
+        .and_then(|selector| document.select(&selector).next())
+        .unwrap_or_else(|| document.root_element());
+
+    let mut lists = Vec::new();
+    collect_lists(root, &mut lists);
+    lists
+}
+
+/// Collect the outermost lists below `element`; nested lists are picked up by their parent item
+fn collect_lists(element: ElementRef, lists: &mut Vec<ListBlock>) {
+    for child in element.children().filter_map(ElementRef::wrap) {
+        if is_boilerplate_element(&child) {
+            continue;
+        }
+        match child.value().name() {
+            "ul" | "ol" => {
+                if let Some(list) = parse_list(child) {
+                    lists.push(list);
+                }
+            }
+            _ => collect_lists(child, lists),
+        }
+    }
+}
+
+fn parse_list(list: ElementRef) -> Option<ListBlock> {
+    let items: Vec<ListItem> = list.children()
+        .filter_map(ElementRef::wrap)
+        .filter(|child| child.value().name() == "li")
+        .map(|li| {
+            let mut text = String::new();
+            collect_item_text(li, &mut text);
+            let mut children = Vec::new();
+            collect_lists(li, &mut children);
+            ListItem {
+                text: text.split_whitespace().collect::<Vec<_>>().join(" "),
+                children,
+            }
+        })
+        .filter(|item| !item.text.is_empty() || !item.children.is_empty())
+        .collect();
+
+    if items.is_empty() {
+        return None;
+    }
+
+    Some(ListBlock {
+        ordered: list.value().name() == "ol",
+        items,
+    })
+}
+
+/// Text of a list item without the text of its nested lists
+fn collect_item_text(element: ElementRef, text: &mut String) {
+    for child in element.children() {
+        if let Some(child_element) = ElementRef::wrap(child) {
+            if matches!(child_element.value().name(), "ul" | "ol" | "script" | "style") {
+                continue;
+            }
+            collect_item_text(child_element, text);
+        } else if let Some(node_text) = child.value().as_text() {
+            text.push_str(node_text);
+            text.push(' ');
+        }
+    }
+}
@@ -1,30 +1,76 @@
 mod helpers;
+mod lists;
+mod sanitize;
+mod fingerprint;
 
-use scraper::{Html, Selector};
+use scraper::{ElementRef, Html, Selector};
+use url::Url;
+use crate::types::{InlineLinkMode, TextExtraction};
+
+pub use helpers::is_boilerplate_element;
+pub use fingerprint::{content_hash, simhash};
+pub use lists::extract_lists;
+pub use sanitize::sanitize_text;
+
+/// Selectors for main content containers, in order of preference
+const MAIN_CONTENT_SELECTORS: &[&str] = &[
+    "article",
+    "main",
+    "[role='main']",
+    ".main-content",
+    ".content",
+    "#main-content",
+    "#content",
+];
+
+/// Find the first main content container in the document, if any
+pub fn find_main_content_element(document: &Html) -> Option<ElementRef<'_>> {
+    MAIN_CONTENT_SELECTORS
+        .iter()
+        .filter_map(|s| Selector::parse(s).ok())
+        .find_map(|selector| document.select(&selector).next())
+}
+
+/// Whitespace-collapsed text of a single element, skipping boilerplate descendants
+/// (anchor text is kept, without URLs)
+pub fn extract_element_text(element: ElementRef) -> String {
+    let ctx = helpers::WalkContext {
+        inline_link_mode: InlineLinkMode::TextOnly,
+        base_url: None,
+    };
+    helpers::collapse_whitespace(&helpers::extract_text_from_clean_elements(element, &ctx))
+}
 
 /// Extract text content from HTML document, filtering out boilerplate elements
-pub fn extract_text_content(document: &Html) -> String {
+///
+/// `base_url` is used to resolve anchor hrefs when inline links are rendered with their URL.
+/// The whitespace-collapsed text is normalized to `config.unicode_normalization` (NFC by default).
+pub fn extract_text_content(document: &Html, base_url: &str, config: &TextExtraction) -> String {
+    let text = extract_collapsed_text(document, base_url, config);
+    sanitize::normalize_unicode(text, config.unicode_normalization)
+}
+
+fn extract_collapsed_text(document: &Html, base_url: &str, config: &TextExtraction) -> String {
+    let ctx = helpers::WalkContext {
+        inline_link_mode: config.inline_link_mode,
+        base_url: Url::parse(base_url).ok(),
+    };
+
     // First, try to find main content containers (these are usually the main article content)
-    let main_content_selectors = [
-        Selector::parse("article").ok(),
-        Selector::parse("main").ok(),
-        Selector::parse("[role='main']").ok(),
-        Selector::parse(".main-content").ok(),
-        Selector::parse(".content").ok(),
-        Selector::parse("#main-content").ok(),
-        Selector::parse("#content").ok(),
-    ];
+    let main_content_selectors: Vec<Option<Selector>> = MAIN_CONTENT_SELECTORS
+        .iter()
+        .map(|s| Selector::parse(s).ok())
+        .collect();
     
     // Try main content selectors first
-    for selector_opt in main_content_selectors.iter() {
-        if let Some(selector) = selector_opt {
-            if let Some(element) = document.select(selector).next() {
-                // Still filter boilerplate from main content (e.g., ads within articles)
-                let text = helpers::extract_text_from_clean_elements(element);
-                if !text.trim().is_empty() && text.len() > 50 {
-                    // Only use if we got substantial content
-                    return text.split_whitespace().collect::<Vec<_>>().join(" ");
-                }
+    for selector in main_content_selectors.iter().flatten() {
+        if let Some(element) = document.select(selector).next() {
+            // Still filter boilerplate from main content (e.g., ads within articles)
+            let text = helpers::collapse_whitespace(&helpers::extract_text_from_clean_elements(element, &ctx));
+            // Measured in characters so CJK and other multi-byte scripts aren't over-counted
+            if !text.is_empty() && text.chars().count() > config.min_content_length {
+                // Only use if we got substantial content
+                return text;
             }
         }
     }
@@ -36,11 +82,78 @@ pub fn extract_text_content(document: &Html) -> String {
     
     if let Some(body) = document.select(&body_selector).next() {
         // Extract text while excluding boilerplate elements
-        let text = helpers::extract_text_from_clean_elements(body);
+        let text = helpers::extract_text_from_clean_elements(body, &ctx);
         
-        // Clean up whitespace
-        text.split_whitespace().collect::<Vec<_>>().join(" ")
+        // Clean up whitespace (code blocks keep theirs)
+        helpers::collapse_whitespace(&text)
     } else {
         document.root_element().text().collect::<Vec<_>>().join(" ")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::UnicodeNormalizationForm;
+
+    fn body_text(html: &str, inline_link_mode: InlineLinkMode) -> String {
+        let config = TextExtraction { inline_link_mode, ..Default::default() };
+        extract_text_content(&Html::parse_document(html), "https://docs.example.com/guide/", &config)
+    }
+
+    #[test]
+    fn code_blocks_keep_indentation_on_their_own_lines() {
+        let html = "<body><p>Install   the\n  crate:</p><pre>\nfn main() {\n    if ready {\n        run();\n    }\n}\n</pre>\
+                    <p>Then call <code>run()</code> from   main.</p></body>";
+        assert_eq!(
+            body_text(html, InlineLinkMode::TextOnly),
+            "Install the crate:\nfn main() {\n    if ready {\n        run();\n    }\n}\nThen call run() from main.",
+        );
+    }
+
+    #[test]
+    fn multi_line_code_is_fenced_and_links_keep_their_url() {
+        let html = "<body><p>See <a href=\"/api\">the  API</a> and</p><code>let a = 1;\n  let b = 2;</code></body>";
+        assert_eq!(
+            body_text(html, InlineLinkMode::WithUrl),
+            "See the API (https://docs.example.com/api) and\nlet a = 1;\n  let b = 2;",
+        );
+    }
+
+    #[test]
+    fn inline_links_are_dropped_or_kept_as_text() {
+        let html = "<body><p>Read <a href=\"/api\">the  API</a> before upgrading.</p></body>";
+        assert_eq!(body_text(html, InlineLinkMode::Drop), "Read before upgrading.");
+        assert_eq!(body_text(html, InlineLinkMode::TextOnly), "Read the API before upgrading.");
+        assert_eq!(body_text(html, InlineLinkMode::WithUrl), "Read the API (https://docs.example.com/api) before upgrading.");
+    }
+
+    #[test]
+    fn boilerplate_links_are_left_out_with_their_urls() {
+        let html = "<body><nav><a href=\"/pricing\">Pricing</a></nav>\
+                    <main><p>The guide walks through configuring <a href=\"/api\">the API</a> for a first project.</p>\
+                    <aside><a href=\"/newsletter\">Subscribe</a></aside></main>\
+                    <footer><a href=\"/privacy\">Privacy</a></footer></body>";
+        assert_eq!(
+            body_text(html, InlineLinkMode::WithUrl),
+            "The guide walks through configuring the API (https://docs.example.com/api) for a first project.",
+        );
+    }
+
+    #[test]
+    fn text_is_normalized_after_whitespace_collapse() {
+        // "Café" with a decomposed é (e + combining acute accent) and an "ﬁ" ligature
+        let html = "<body><p>Cafe\u{301}   ﬁne</p></body>";
+        let text = |unicode_normalization| {
+            let config = TextExtraction { unicode_normalization, ..Default::default() };
+            extract_text_content(&Html::parse_document(html), "https://docs.example.com/", &config)
+        };
+
+        let nfc = text(UnicodeNormalizationForm::Nfc);
+        assert_eq!(nfc, "Caf\u{e9} ﬁne");
+        assert_eq!(nfc.chars().filter(|c| *c == '\u{e9}').count(), 1);
+        assert_eq!(text(UnicodeNormalizationForm::Nfkc), "Caf\u{e9} fine");
+        assert_eq!(text(UnicodeNormalizationForm::None), "Cafe\u{301} ﬁne");
+        assert_eq!(TextExtraction::default().unicode_normalization, UnicodeNormalizationForm::Nfc);
+    }
+}
@@ -0,0 +1,123 @@
+use unicode_normalization::UnicodeNormalization;
+use crate::types::{TextSanitize, UnicodeNormalizationForm};
+
+/// Normalize text to the given Unicode form
+pub fn normalize_unicode(text: String, form: UnicodeNormalizationForm) -> String {
+    match form {
+        UnicodeNormalizationForm::None => text,
+        UnicodeNormalizationForm::Nfc => text.nfc().collect(),
+        UnicodeNormalizationForm::Nfkc => text.nfkc().collect(),
+    }
+}
+
+/// Apply the enabled sanitization steps to extracted text
+pub fn sanitize_text(text: &str, options: &TextSanitize) -> String {
+    if !options.strip_emoji && !options.strip_zero_width && !options.normalize_unicode {
+        return text.to_string();
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    let emoji = if options.strip_emoji { emoji_mask(&chars) } else { vec![false; chars.len()] };
+
+    let mut stripped = String::with_capacity(text.len());
+    let mut just_removed = false;
+    for (i, &c) in chars.iter().enumerate() {
+        if emoji[i] || (options.strip_zero_width && is_invisible(c)) {
+            just_removed = true;
+            continue;
+        }
+        // Removing a character between two spaces would leave a double space behind
+        if c == ' ' && just_removed && stripped.ends_with(' ') {
+            continue;
+        }
+        just_removed = false;
+        stripped.push(c);
+    }
+    let stripped = stripped.trim().to_string();
+
+    if options.normalize_unicode {
+        normalize_unicode(stripped, UnicodeNormalizationForm::Nfc)
+    } else {
+        stripped
+    }
+}
+
+/// Which of `chars` belong to an emoji: emoji code points, symbols the emoji variation
+/// selector turns into emoji (❤️, 🏳️), and the joiners and variation selectors next to those,
+/// so ZWJ sequences (👨‍👩‍👧, 🏳️‍🌈) go without leaving U+200D or U+FE0F behind
+fn emoji_mask(chars: &[char]) -> Vec<bool> {
+    let mut mask: Vec<bool> = chars.iter().enumerate()
+        .map(|(i, &c)| is_emoji(c) || chars.get(i + 1) == Some(&EMOJI_VARIATION_SELECTOR))
+        .collect();
+    for i in 0..chars.len() {
+        if matches!(chars[i], ZERO_WIDTH_JOINER | TEXT_VARIATION_SELECTOR | EMOJI_VARIATION_SELECTOR) {
+            mask[i] |= (i > 0 && mask[i - 1]) || mask.get(i + 1).copied().unwrap_or(false);
+        }
+    }
+    mask
+}
+
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+const TEXT_VARIATION_SELECTOR: char = '\u{FE0E}';
+const EMOJI_VARIATION_SELECTOR: char = '\u{FE0F}';
+
+/// Code points with default emoji presentation (Unicode `Emoji_Presentation`), regional
+/// indicator flags, skin tone modifiers, the emoji variation selector, the keycap combiner and
+/// subdivision flag tags. Symbols that render as text by default (⌘, ✓, ★, ©) are kept.
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32,
+        0x231A..=0x231B | 0x23E9..=0x23EC | 0x23F0 | 0x23F3 | 0x25FD..=0x25FE
+        | 0x2614..=0x2615 | 0x2648..=0x2653 | 0x267F | 0x2693 | 0x26A1 | 0x26AA..=0x26AB
+        | 0x26BD..=0x26BE | 0x26C4..=0x26C5 | 0x26CE | 0x26D4 | 0x26EA | 0x26F2..=0x26F3
+        | 0x26F5 | 0x26FA | 0x26FD | 0x2705 | 0x270A..=0x270B | 0x2728 | 0x274C | 0x274E
+        | 0x2753..=0x2755 | 0x2757 | 0x2795..=0x2797 | 0x27B0 | 0x27BF | 0x2B1B..=0x2B1C
+        | 0x2B50 | 0x2B55
+        | 0x1F004 | 0x1F0CF | 0x1F18E | 0x1F191..=0x1F19A
+        | 0x1F1E6..=0x1F1FF // Regional indicators (flags)
+        | 0x1F201 | 0x1F21A | 0x1F22F | 0x1F232..=0x1F236 | 0x1F238..=0x1F23A | 0x1F250..=0x1F251
+        | 0x1F300..=0x1F320 | 0x1F32D..=0x1F335 | 0x1F337..=0x1F37C | 0x1F37E..=0x1F393
+        | 0x1F3A0..=0x1F3CA | 0x1F3CF..=0x1F3D3 | 0x1F3E0..=0x1F3F0 | 0x1F3F4
+        | 0x1F3F8..=0x1F43E // Includes the skin tone modifiers 1F3FB..1F3FF
+        | 0x1F440 | 0x1F442..=0x1F4FC | 0x1F4FF..=0x1F53D | 0x1F54B..=0x1F54E
+        | 0x1F550..=0x1F567 | 0x1F57A | 0x1F595..=0x1F596 | 0x1F5A4 | 0x1F5FB..=0x1F64F
+        | 0x1F680..=0x1F6C5 | 0x1F6CC | 0x1F6D0..=0x1F6D2 | 0x1F6D5..=0x1F6D7 | 0x1F6DC..=0x1F6DF
+        | 0x1F6EB..=0x1F6EC | 0x1F6F4..=0x1F6FC | 0x1F7E0..=0x1F7EB | 0x1F7F0
+        | 0x1F90C..=0x1F93A | 0x1F93C..=0x1F945 | 0x1F947..=0x1F9FF | 0x1FA70..=0x1FA7C
+        | 0x1FA80..=0x1FA89 | 0x1FA8F..=0x1FAC6 | 0x1FACE..=0x1FADC | 0x1FADF..=0x1FAE9
+        | 0x1FAF0..=0x1FAF8
+        | 0xFE0F            // Emoji variation selector
+        | 0x20E3            // Combining enclosing keycap
+        | 0xE0020..=0xE007F // Tag characters used in subdivision flags
+    )
+}
+
+/// Zero-width characters, bidi marks, the BOM and control characters other than whitespace
+fn is_invisible(c: char) -> bool {
+    matches!(c as u32,
+        0x200B..=0x200F     // Zero-width space/joiners and directional marks
+        | 0x202A..=0x202E   // Bidi embeddings and overrides
+        | 0x2060..=0x2064   // Word joiner and invisible operators
+        | 0x2066..=0x2069   // Bidi isolates
+        | 0xFEFF            // Byte order mark / zero-width no-break space
+        | 0x00AD            // Soft hyphen
+    ) || (c.is_control() && !c.is_whitespace())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strip_emoji(text: &str) -> String {
+        sanitize_text(text, &TextSanitize { strip_emoji: true, ..Default::default() })
+    }
+
+    #[test]
+    fn zwj_sequences_leave_no_joiners_or_selectors() {
+        assert_eq!(strip_emoji("Family \u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467} day"), "Family day");
+        assert_eq!(strip_emoji("Pride \u{1F3F3}\u{FE0F}\u{200D}\u{1F308} march"), "Pride march");
+        assert_eq!(strip_emoji("I \u{2764}\u{FE0F} Rust"), "I Rust");
+        // Symbols without the emoji selector and joiners in other scripts are text
+        assert_eq!(strip_emoji("\u{2318} \u{2713} \u{2605}"), "\u{2318} \u{2713} \u{2605}");
+        assert_eq!(strip_emoji("\u{0915}\u{094D}\u{200D}\u{0937}"), "\u{0915}\u{094D}\u{200D}\u{0937}");
+    }
+}
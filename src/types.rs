@@ -1,41 +1,360 @@
+use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
+use crate::error::ExtractionError;
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, Default)]
+/// How hyperlinks inside extracted text are rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InlineLinkMode {
+    /// Drop anchor elements (and their text) from the output
+    Drop,
+    /// Keep anchor text only (default)
+    #[default]
+    TextOnly,
+    /// Keep anchor text followed by its resolved URL: `foo (https://...)`
+    WithUrl,
+}
+
+impl std::str::FromStr for InlineLinkMode {
+    type Err = ExtractionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "drop" => Ok(InlineLinkMode::Drop),
+            "text_only" => Ok(InlineLinkMode::TextOnly),
+            "with_url" => Ok(InlineLinkMode::WithUrl),
+            _ => Err(ExtractionError::Other(format!("unknown inline link mode: {}", s))),
+        }
+    }
+}
+
+/// What happens to a page whose detected language isn't in the allowlist
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LanguageFilterMode {
+    /// Extract as usual and set `ExtractionResult.language_allowed` to false (default)
+    #[default]
+    Flag,
+    /// Flag the page and skip the extractors that haven't run yet
+    Skip,
+    /// Fail with `ExtractionError::LanguageNotAllowed`
+    Reject,
+}
+
+impl std::str::FromStr for LanguageFilterMode {
+    type Err = ExtractionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "flag" => Ok(LanguageFilterMode::Flag),
+            "skip" => Ok(LanguageFilterMode::Skip),
+            "reject" => Ok(LanguageFilterMode::Reject),
+            _ => Err(ExtractionError::Other(format!("unknown language filter mode: {}", s))),
+        }
+    }
+}
+
+/// Unicode normalization form applied to extracted text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnicodeNormalizationForm {
+    /// Leave text as found in the page
+    None,
+    /// Canonical composition (default): merges decomposed characters such as "e" + U+0301 into "é"
+    #[default]
+    Nfc,
+    /// Compatibility composition: additionally folds ligatures, full-width forms, etc.
+    Nfkc,
+}
+
+impl std::str::FromStr for UnicodeNormalizationForm {
+    type Err = ExtractionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(UnicodeNormalizationForm::None),
+            "nfc" => Ok(UnicodeNormalizationForm::Nfc),
+            "nfkc" => Ok(UnicodeNormalizationForm::Nfkc),
+            _ => Err(ExtractionError::Other(format!("unknown unicode normalization form: {}", s))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct TextExtraction {
     pub enabled: bool,
     pub language_detection: bool,
+    pub inline_link_mode: InlineLinkMode,
+    /// A main-content container is used instead of falling back to the whole body only when
+    /// it has more than this many characters
+    pub min_content_length: usize,
+    pub unicode_normalization: UnicodeNormalizationForm,
+    pub sanitize: TextSanitize,
+    /// Compute a simhash of the extracted text for near-duplicate detection
+    pub compute_simhash: bool,
+    /// ISO 639-3 codes of the accepted languages; empty accepts every page
+    pub allowed_languages: Vec<String>,
+    pub language_filter: LanguageFilterMode,
+}
+
+/// Post-processing applied to extracted text (before language detection)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextSanitize {
+    /// Remove emoji-presentation characters and flag sequences (text symbols like ✓ stay)
+    pub strip_emoji: bool,
+    /// Remove zero-width characters, bidi marks and non-whitespace control characters
+    pub strip_zero_width: bool,
+    /// Normalize to Unicode NFC
+    pub normalize_unicode: bool,
+}
+
+impl Default for TextExtraction {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            language_detection: false,
+            inline_link_mode: InlineLinkMode::default(),
+            min_content_length: 50,
+            unicode_normalization: UnicodeNormalizationForm::default(),
+            sanitize: TextSanitize::default(),
+            compute_simhash: false,
+            allowed_languages: Vec::new(),
+            language_filter: LanguageFilterMode::default(),
+        }
+    }
+}
+
+/// Settings for the article extractor
+#[derive(Debug, Clone)]
+pub struct ArticleOptions {
+    /// Minimum confidence a date needs to be reported as the best single date
+    pub date_confidence_threshold: f64,
+    /// Extra words that mark a nearby body date as the publication date, on top of the
+    /// built-in en/de/fr/es cues ("published", "veröffentlicht", "publié", "publicado", ...)
+    pub date_cues: Vec<String>,
+}
+
+impl Default for ArticleOptions {
+    fn default() -> Self {
+        Self {
+            date_confidence_threshold: 0.2,
+            date_cues: Vec::new(),
+        }
+    }
+}
+
+/// Settings for the link extractor
+#[derive(Debug, Clone)]
+pub struct LinkOptions {
+    /// URL schemes kept during link extraction; links with any other scheme
+    /// (`javascript:`, `data:`, ...) are dropped
+    pub allowed_schemes: Vec<String>,
+    /// Maximum number of links kept in total (first-seen links win)
+    pub max_links: Option<usize>,
+    /// Maximum number of links kept per domain (first-seen links win)
+    pub max_links_per_domain: Option<usize>,
+}
+
+impl Default for LinkOptions {
+    fn default() -> Self {
+        Self {
+            allowed_schemes: vec!["http".to_string(), "https".to_string()],
+            max_links: None,
+            max_links_per_domain: None,
+        }
+    }
+}
+
+/// Unit `product_weight` is reported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WeightUnit {
+    #[default]
+    Grams,
+    Kilograms,
+    Pounds,
+    Ounces,
+}
+
+impl std::str::FromStr for WeightUnit {
+    type Err = ExtractionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "g" | "grams" => Ok(WeightUnit::Grams),
+            "kg" | "kilograms" => Ok(WeightUnit::Kilograms),
+            "lb" | "pounds" => Ok(WeightUnit::Pounds),
+            "oz" | "ounces" => Ok(WeightUnit::Ounces),
+            _ => Err(ExtractionError::Other(format!("unknown weight unit: {}", s))),
+        }
+    }
+}
+
+/// Unit `product_width`, `product_height` and `product_depth` are reported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DimensionUnit {
+    #[default]
+    Millimetres,
+    Centimetres,
+    Metres,
+    Inches,
+}
+
+impl std::str::FromStr for DimensionUnit {
+    type Err = ExtractionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mm" | "millimetres" => Ok(DimensionUnit::Millimetres),
+            "cm" | "centimetres" => Ok(DimensionUnit::Centimetres),
+            "m" | "metres" => Ok(DimensionUnit::Metres),
+            "in" | "inches" => Ok(DimensionUnit::Inches),
+            _ => Err(ExtractionError::Other(format!("unknown dimension unit: {}", s))),
+        }
+    }
+}
+
+/// Settings for the product extractor
+#[derive(Debug, Clone, Default)]
+pub struct ProductOptions {
+    /// Maximum number of individual reviews kept (first declared reviews win)
+    pub max_reviews: Option<usize>,
+    /// Margin by which the original price must exceed the current one to count as a sale
+    /// (`pricing::DEFAULT_SALE_EPSILON` when unset)
+    pub sale_epsilon: Option<f64>,
+    /// Unit for `product_weight` (grams by default)
+    pub weight_unit: WeightUnit,
+    /// Unit for `product_width`/`product_height`/`product_depth` (millimetres by default)
+    pub dimension_unit: DimensionUnit,
+}
+
+/// Settings for the contacts extractor
+#[derive(Debug, Clone, Default)]
+pub struct ContactOptions {
+    /// ISO 3166 region (`"US"`, `"DE"`, ...) for phone numbers written without a `+` prefix
+    pub phone_region: Option<String>,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct Activities {
     pub extract_text: TextExtraction,
     pub extract_links: Vec<String>,
+    pub link_options: LinkOptions,
     pub extract_socials: Vec<String>,
     pub extract_video: Vec<String>,
     pub extract_product: Vec<String>,
     pub extract_article: Vec<String>,
+    pub extract_lists: bool,
+    /// Reject unknown field names instead of silently skipping them
+    pub strict_fields: bool,
+    /// Record where each article and product value came from (`article_sources`, `product_sources`)
+    pub collect_provenance: bool,
+    /// Trust only JSON-LD, meta tags and microdata: skip CSS-selector and text-scan fallbacks
+    pub structured_only: bool,
+    /// Cap on the total JSON-LD bytes indexed per page (`DEFAULT_MAX_JSON_LD_BYTES` when unset)
+    pub max_json_ld_bytes: Option<usize>,
+    pub extract_recipe: bool,
+    pub extract_event: bool,
+    pub extract_faqs: bool,
+    /// Enumerate every product on a listing page, up to this many
+    pub extract_products_list: Option<usize>,
+    pub extract_contacts: bool,
+    pub extract_emails: bool,
+    /// Pick the best image for link previews (`preview_image`)
+    pub extract_preview_image: bool,
+    /// Group the page's `<link>` elements by rel (`alternate_links`)
+    pub extract_alternate_links: bool,
+    pub contact_options: ContactOptions,
+    pub article_options: ArticleOptions,
+    pub product_options: ProductOptions,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Missing keys deserialize to their defaults, so `from_json` reads results saved before a
+/// field was added
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ExtractionResult {
     pub url: String,
     pub text: Option<String>,
     pub language: Option<String>,
     pub language_confidence: Option<f64>,
+    /// Whether the detected language is in the allowlist (`set_allowed_languages`); unset
+    /// without an allowlist or when no language was detected
+    pub language_allowed: Option<bool>,
+    /// Hex SHA-256 of the normalized extracted text, for deduplication across a crawl
+    pub content_hash: Option<String>,
+    /// 64-bit simhash of the extracted text; compare by Hamming distance
+    pub simhash: Option<u64>,
+    /// Document title (`<title>`, then `og:title`, `twitter:title`, the first `<h1>`)
+    pub title: Option<String>,
+    /// Document description (`<meta name="description">`, then `og:description`, `twitter:description`)
+    pub description: Option<String>,
+    /// Best single image for link previews (`og:image`, then `twitter:image`, JSON-LD
+    /// `image`, the largest `<img>` by declared size), resolved absolute; set by
+    /// `extract_preview_image`
+    pub preview_image: Option<String>,
     // Grouped data (extracted directly, no separate grouping step needed)
     pub links: Option<GroupedLinks>,
+    pub amp_url: Option<String>,
+    /// Every `<link>` element keyed by rel token, hrefs resolved absolute; set by
+    /// `extract_alternate_links`
+    pub alternate_links: Option<HashMap<String, Vec<LinkInfo>>>,
+    /// Client-side redirect declared by `<meta http-equiv="refresh">`
+    pub meta_refresh: Option<MetaRefresh>,
+    /// oEmbed endpoint from `<link rel="alternate" type="application/json+oembed">` (or the
+    /// `text/xml+oembed` variant), resolved absolute
+    pub oembed_url: Option<String>,
+    /// The JSON oEmbed document, when fetching it is enabled and it could be read
+    pub oembed: Option<OEmbed>,
     pub socials: Option<std::collections::HashMap<String, String>>,
+    pub socials_info: Option<SocialsInfo>,
     pub videos: Option<std::collections::HashMap<String, String>>,
     pub product: Option<std::collections::HashMap<String, String>>,
+    pub product_info: Option<ProductInfo>,
+    /// Source of each `product` value (`"meta"`, `"jsonld"`, `"microdata"`, `"css"`, ...), when
+    /// provenance collection is on
+    pub product_sources: Option<std::collections::HashMap<String, String>>,
+    /// Every product on a listing page, each keyed like `product`
+    pub products: Option<Vec<std::collections::HashMap<String, String>>>,
     pub article: Option<std::collections::HashMap<String, String>>,
+    pub article_info: Option<ArticleInfo>,
+    /// Source of each `article` value (`"meta"`, `"jsonld"`, `"title_tag"`, ...), when
+    /// provenance collection is on
+    pub article_sources: Option<std::collections::HashMap<String, String>>,
+    pub lists: Option<Vec<ListBlock>>,
+    pub recipe: Option<Recipe>,
+    pub event: Option<Event>,
+    pub faqs: Option<Vec<Faq>>,
+    /// Phone numbers in E.164 where possible
+    pub phones: Option<Vec<String>>,
+    pub emails: Option<Vec<String>>,
     pub content: Option<ContentInfo>,
+    /// Set when the site's robots.txt couldn't be parsed and was treated as allow-all
+    pub robots_warning: Option<String>,
+    /// Configuration problems that didn't stop the run, such as requested field names no
+    /// extractor knows (`unknown socials field: titel`); strict mode fails on those instead
+    pub warnings: Vec<String>,
+}
+
+impl ExtractionResult {
+    /// Serialize the whole result to JSON (absent sections as `null`), for callers that don't
+    /// go through the Python bindings
+    pub fn to_json(&self) -> Result<String, ExtractionError> {
+        serde_json::to_string(self)
+            .map_err(|e| ExtractionError::ParseError(format!("Failed to serialize result: {}", e)))
+    }
+
+    /// Parse a result previously serialized with `to_json`
+    pub fn from_json(json: &str) -> Result<Self, ExtractionError> {
+        serde_json::from_str(json)
+            .map_err(|e| ExtractionError::ParseError(format!("Failed to parse result JSON: {}", e)))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LinkInfo {
     pub url: String,
     pub text: String,
+    /// Text around the anchor in its block element, with `extract_links(["with_context"])`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +363,209 @@ pub struct DateWithConfidence {
     pub confidence: f64,
 }
 
+/// A `<ul>`/`<ol>` list with its items
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListBlock {
+    pub ordered: bool,
+    pub items: Vec<ListItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListItem {
+    /// Item text, excluding the text of nested lists
+    pub text: String,
+    /// Lists nested inside this item
+    pub children: Vec<ListBlock>,
+}
+
+/// A schema.org `Recipe`, read from JSON-LD or microdata
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Recipe {
+    pub name: Option<String>,
+    pub ingredients: Vec<String>,
+    pub instructions: Vec<String>,
+    /// Times in minutes, parsed from ISO 8601 durations (`PT1H30M`)
+    pub prep_time: Option<u32>,
+    pub cook_time: Option<u32>,
+    pub total_time: Option<u32>,
+    /// Servings or amount made, as written ("4 servings")
+    #[serde(rename = "yield")]
+    pub recipe_yield: Option<String>,
+    /// Calories per serving, as written ("240 calories")
+    pub calories: Option<String>,
+}
+
+/// A schema.org `Event` (or subtype such as `MusicEvent`), read from JSON-LD or microdata
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Event {
+    /// Declared type: `Event`, `MusicEvent`, `SportsEvent`, ...
+    pub event_type: Option<String>,
+    pub name: Option<String>,
+    /// ISO 8601; date-only values stay dates
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub location: Option<EventLocation>,
+    pub offers: Vec<EventOffer>,
+    pub performers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventLocation {
+    pub name: Option<String>,
+    /// Postal address parts joined with ", "
+    pub address: Option<String>,
+    /// Set for online events (`VirtualLocation`)
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventOffer {
+    pub price: Option<String>,
+    pub currency: Option<String>,
+    pub url: Option<String>,
+}
+
+/// A question/answer pair from JSON-LD `FAQPage` markup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Faq {
+    pub question: String,
+    /// Answer text with HTML markup stripped
+    pub answer: String,
+}
+
+/// Stock status of a product, using the schema.org `ItemAvailability` names
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Availability {
+    InStock,
+    OutOfStock,
+    PreOrder,
+    BackOrder,
+    Discontinued,
+    LimitedAvailability,
+    /// A value was declared but isn't one of the known statuses
+    Unknown,
+}
+
+impl Availability {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Availability::InStock => "InStock",
+            Availability::OutOfStock => "OutOfStock",
+            Availability::PreOrder => "PreOrder",
+            Availability::BackOrder => "BackOrder",
+            Availability::Discontinued => "Discontinued",
+            Availability::LimitedAvailability => "LimitedAvailability",
+            Availability::Unknown => "Unknown",
+        }
+    }
+}
+
+/// Typed product metadata, built from the same lookups as the product HashMap. Only the
+/// values behind the requested product fields are filled in (every one for "all"); the
+/// currency comes with any price.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProductInfo {
+    pub title: Option<String>,
+    pub brand: Option<String>,
+    pub sku: Option<String>,
+    /// Numeric price, with thousands separators and decimal commas resolved
+    pub price: Option<f64>,
+    /// Whether the price was written as a range ("$10 – $25"); `price` is then the low end
+    pub price_is_range: bool,
+    /// ISO 4217 code, declared or inferred from the price's currency symbol
+    pub currency: Option<String>,
+    /// Whether `currency` was inferred from the price's symbol rather than declared
+    pub currency_inferred: bool,
+    pub original_price: Option<f64>,
+    /// Discount against `original_price` in percent (one decimal), when on sale
+    pub discount_percent: Option<f64>,
+    /// Whether `original_price` exceeds `price`; None unless both parsed in the same currency
+    pub on_sale: Option<bool>,
+    pub availability: Option<Availability>,
+    pub rating: Option<f32>,
+    pub review_count: Option<u32>,
+    pub images: Vec<String>,
+    pub reviews: Vec<Review>,
+}
+
+/// One customer review of a product, from JSON-LD `review` or a microdata `Review` scope
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Review {
+    pub author: Option<String>,
+    /// `reviewRating.ratingValue`
+    pub rating: Option<f32>,
+    /// `datePublished`, as declared
+    pub date: Option<String>,
+    pub title: Option<String>,
+    pub body: Option<String>,
+}
+
+/// Typed article metadata, built from the same sources as the article HashMap. Only the
+/// values behind the requested article fields are filled in (every one for "all").
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArticleInfo {
+    /// JSON-LD `@type` of the main entity (Article subtypes preferred), else `og:type`
+    pub article_type: Option<String>,
+    pub is_article: bool,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub authors: Vec<String>,
+    pub published: Option<DateTime<FixedOffset>>,
+    pub modified: Option<DateTime<FixedOffset>>,
+    pub section: Option<String>,
+    pub tags: Vec<String>,
+    pub dates_with_confidence: Vec<DateWithConfidence>,
+    pub modified_dates_with_confidence: Vec<DateWithConfidence>,
+    /// Words in the page text `extract_text` returns (not `article_body`); only populated
+    /// when `word_count`/`reading_time` are requested
+    pub word_count: Option<u32>,
+    /// Estimated reading time in minutes
+    pub reading_time: Option<u32>,
+}
+
+/// Typed social metadata, built from the same metas as the socials HashMap
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SocialsInfo {
+    pub open_graph: OpenGraph,
+    pub twitter: TwitterCard,
+}
+
+/// Open Graph properties (`og:*`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OpenGraph {
+    pub url: Option<String>,
+    #[serde(rename = "type")]
+    pub og_type: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub images: Vec<OgImage>,
+    pub site_name: Option<String>,
+    pub locale: Option<String>,
+    pub locale_alternates: Vec<String>,
+}
+
+/// One `og:image` with its structured properties; dimensions that aren't whole numbers are
+/// left out
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OgImage {
+    pub url: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub alt: Option<String>,
+}
+
+/// Twitter Card properties (`twitter:*`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TwitterCard {
+    pub card: Option<String>,
+    pub site: Option<String>,
+    pub creator: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub image_alt: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GroupedLinks {
     pub internal: Vec<LinkInfo>,
@@ -52,12 +574,37 @@ pub struct GroupedLinks {
     pub summary: LinkSummary,
 }
 
+/// A `<meta http-equiv="refresh">` redirect
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetaRefresh {
+    /// Target URL, resolved to absolute
+    pub url: String,
+    /// Seconds before the browser follows it
+    pub delay: u32,
+}
+
+/// Fields of a fetched oEmbed document
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OEmbed {
+    pub title: Option<String>,
+    pub author_name: Option<String>,
+    pub author_url: Option<String>,
+    pub provider_name: Option<String>,
+    pub thumbnail_url: Option<String>,
+    /// Embed markup (`rich` and `video` types)
+    pub html: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LinkSummary {
     pub total: usize,
     pub internal_count: usize,
     pub external_count: usize,
     pub unique_domains: usize,
+    /// Whether `max_links` / `max_links_per_domain` dropped any links
+    pub truncated: bool,
+    /// Number of links dropped by the limits
+    pub dropped: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +22,11 @@ pub fn get_all_video_fields() -> Vec<String> {
     ]
 }
 
+/// Whether `field` is a video/book field name (or "all")
+pub fn is_known_video_field(field: &str) -> bool {
+    field == "all" || get_all_video_fields().iter().any(|f| f == field)
+}
+
 /// Extract video/book metadata from HTML document
 pub fn extract_video(document: &Html, video_fields: &[String]) -> HashMap<String, String> {
     let mut videos = HashMap::new();
@@ -0,0 +1,327 @@
+//! Article extraction end to end, through `WebExtractor::new_with_html` and `extract`
+
+use std::collections::HashMap;
+use _ferriscope_native::WebExtractor;
+
+/// Extract the article `fields` (names or aliases) from `html` served at `url`
+fn article_at(url: &str, html: &str, fields: &[&str]) -> HashMap<String, String> {
+    let mut extractor = WebExtractor::new_with_html(url.to_string(), html.to_string());
+    extractor.extract_article(fields.iter().map(|f| f.to_string()).collect());
+    extractor.extract().unwrap().article.unwrap_or_default()
+}
+
+fn field<'a>(article: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    article.get(name).map(String::as_str)
+}
+
+mod authors {
+    use super::*;
+
+    /// Three authors, declared across metas, a byline link and JSON-LD with different casing
+    const PAGE: &str = r#"<html><head><title>Joint report</title>
+<meta property="article:author" content="Jane Doe">
+<meta name="author" content="john smith">
+<script type="application/ld+json">{"@context":"https://schema.org","@type":"NewsArticle",
+ "headline":"Joint report",
+ "author":[{"@type":"Person","name":"John Smith"},"Ana  Lima",{"@list":[{"name":"JANE DOE"}]}]}</script>
+</head><body><article><h1>Joint report</h1>
+<p>By <a rel="author" href="/staff/ana-lima">Ana Lima</a> and colleagues.</p>
+</article></body></html>"#;
+
+    #[test]
+    fn authors_from_every_source_are_deduplicated_case_insensitively() {
+        let article = article_at("https://news.example.com/joint-report", PAGE, &["author", "authors"]);
+        let authors: Vec<String> = serde_json::from_str(&article["authors"]).unwrap();
+        assert_eq!(authors, vec!["Jane Doe", "john smith", "Ana Lima"]);
+        assert_eq!(article["author"], "Jane Doe");
+    }
+
+    #[test]
+    fn single_author_page_keeps_one_entry() {
+        let html = r#"<head><meta name="author" content="Jane Doe"></head><body><p>Text</p></body>"#;
+        let article = article_at("https://news.example.com/solo", html, &["authors"]);
+        assert_eq!(article["authors"], r#"["Jane Doe"]"#);
+    }
+
+    #[test]
+    fn byline_href_is_resolved_and_same_as_profiles_are_listed() {
+        let html = include_str!("fixtures/author_profile_article.html");
+        let article = article_at("https://harbour.example.net/posts/tide-tables", html, &["author_url", "author_profiles"]);
+        // The relative rel="author" href wins over the JSON-LD author url
+        assert_eq!(field(&article, "author_url"), Some("https://harbour.example.net/authors/maren-holt"));
+        let profiles: Vec<String> = serde_json::from_str(&article["author_profiles"]).unwrap();
+        assert_eq!(profiles, vec!["https://mastodon.example.social/@maren", "https://www.linkedin.com/in/maren-holt"]);
+    }
+
+    #[test]
+    fn json_ld_author_url_is_used_without_a_byline_link() {
+        let html = include_str!("fixtures/author_profile_article.html").replace(r#" rel="author""#, "");
+        let article = article_at("https://harbour.example.net/posts/tide-tables", &html, &["author_url"]);
+        assert_eq!(field(&article, "author_url"), Some("https://harbour.example.net/about/maren"));
+    }
+}
+
+mod scholar {
+    use super::*;
+    use _ferriscope_native::DateWithConfidence;
+
+    fn paper(fields: &[&str]) -> HashMap<String, String> {
+        article_at("https://journals.example.org/jch/2023/0001", include_str!("fixtures/scholar_paper.html"), fields)
+    }
+
+    #[test]
+    fn citation_authors_are_all_collected() {
+        let article = paper(&["author", "authors"]);
+        let authors: Vec<String> = serde_json::from_str(&article["authors"]).unwrap();
+        assert_eq!(authors, vec!["Lovelace, Ada", "Babbage, Charles", "Menabrea, Luigi Federico"]);
+        assert_eq!(article["author"], "Lovelace, Ada");
+    }
+
+    #[test]
+    fn citation_title_and_dublin_core_description() {
+        let article = paper(&["title", "description"]);
+        // citation_title wins over the <title> tag and its journal suffix
+        assert_eq!(field(&article, "title"), Some("Sketch of the Analytical Engine, with Notes by the Translator"));
+        assert_eq!(
+            field(&article, "description"),
+            Some("A translation of Menabrea's memoir on the Analytical Engine, with the translator's notes on its programming.")
+        );
+    }
+
+    #[test]
+    fn citation_publication_date_is_a_meta_date() {
+        let article = paper(&["publication_date", "best_publication_date"]);
+        let dates: Vec<DateWithConfidence> = serde_json::from_str(&article["publication_date"]).unwrap();
+        assert_eq!(dates.len(), 1);
+        assert_eq!(dates[0].date, "2023/03/14");
+        // Meta-only confidence
+        assert_eq!(dates[0].confidence, 0.5);
+        assert_eq!(field(&article, "best_publication_date"), Some("2023-03-14T00:00:00Z"));
+    }
+}
+
+mod body {
+    use super::*;
+
+    const URL: &str = "https://news.example.com/river";
+
+    fn article_body(html: &str) -> Option<String> {
+        article_at(URL, html, &["article_body"]).remove("article_body")
+    }
+
+    #[test]
+    fn article_body_drops_the_boilerplate_generic_text_keeps() {
+        let html = include_str!("fixtures/article_with_boilerplate.html");
+        let mut extractor = WebExtractor::new_with_html(URL.to_string(), html.to_string());
+        extractor.extract_text(false);
+        let text = extractor.extract().unwrap().text.unwrap();
+        assert!(text.contains("She lives upstream"));
+
+        let body = article_body(html).unwrap();
+        assert!(body.starts_with("For three centuries the river ran west"));
+        assert!(body.ends_with("more than the town spends in a decade."));
+        for boilerplate in ["She lives upstream", "Sign up today", "floods of 1953"] {
+            assert!(!body.contains(boilerplate), "body kept {:?}", boilerplate);
+        }
+        assert!(body.len() < text.len());
+    }
+
+    #[test]
+    fn json_ld_and_microdata_bodies_are_preferred() {
+        let html = r#"<script type="application/ld+json">{"@type":"NewsArticle","articleBody":"The  publisher's\n own body."}</script>
+            <article><div itemprop="articleBody"><p>Microdata body.</p></div><p>Other text.</p></article>"#;
+        assert_eq!(article_body(html).as_deref(), Some("The publisher's own body."));
+
+        let html = r#"<article><div class="bio"><p>A much longer author biography than the story itself, really.</p></div>
+            <div itemprop="articleBody"><p>Microdata body.</p></div></article>"#;
+        assert_eq!(article_body(html).as_deref(), Some("Microdata body."));
+    }
+
+    #[test]
+    fn article_body_is_not_part_of_all() {
+        let article = article_at(URL, include_str!("fixtures/article_with_boilerplate.html"), &["all"]);
+        assert!(!article.is_empty());
+        assert!(!article.contains_key("article_body"));
+    }
+}
+
+mod comments {
+    use super::*;
+
+    fn comment_fields(html: &str) -> HashMap<String, String> {
+        article_at("https://blog.example.com/post", html, &["comment_count", "has_comments_section"])
+    }
+
+    fn comment_count(html: &str) -> Option<String> {
+        comment_fields(html).remove("comment_count")
+    }
+
+    #[test]
+    fn json_ld_interaction_statistic_arrays() {
+        let html = r#"<script type="application/ld+json">{"@context":"https://schema.org","@type":"BlogPosting",
+            "interactionStatistic":[
+              {"@type":"InteractionCounter","interactionType":"https://schema.org/LikeAction","userInteractionCount":900},
+              {"@type":"InteractionCounter","interactionType":{"@type":"CommentAction"},"userInteractionCount":"1,204"}
+            ]}</script>"#;
+        assert_eq!(comment_count(html).as_deref(), Some("1204"));
+
+        let html = r#"<script type="application/ld+json">{"@type":"Article","interactionStatistic":
+            {"interactionType":"http://schema.org/CommentAction","userInteractionCount":7}}</script>"#;
+        assert_eq!(comment_count(html).as_deref(), Some("7"));
+
+        let html = r#"<script type="application/ld+json">{"@type":"Article","commentCount":"42"}</script>
+            <span class="comments-count">99</span>"#;
+        assert_eq!(comment_count(html).as_deref(), Some("42"));
+    }
+
+    #[test]
+    fn microdata_and_dom_fallbacks() {
+        let cases: &[(&str, Option<&str>)] = &[
+            (r#"<div itemscope itemtype="https://schema.org/Article"><meta itemprop="commentCount" content="12"></div>"#, Some("12")),
+            (r#"<span class="comments-count">Comments (31)</span>"#, Some("31")),
+            (r##"<a href="#comments">42 Comments</a>"##, Some("42")),
+            (r#"<h3>1 comment</h3>"#, Some("1")),
+            // Prose mentioning comments isn't a counter
+            (r#"<p>We read 300 comments before writing this piece about the new policy.</p>"#, None),
+            (r#"<span>Comments</span>"#, None),
+        ];
+        for (html, expected) in cases {
+            assert_eq!(comment_count(html).as_deref(), *expected, "html {:?}", html);
+        }
+    }
+
+    #[test]
+    fn comment_section_detection() {
+        let has_section = |html: &str| comment_fields(html).remove("has_comments_section");
+        assert_eq!(has_section(r#"<div id="comments"></div>"#).as_deref(), Some("true"));
+        assert_eq!(has_section(r#"<ol class="comment-list"></ol>"#).as_deref(), Some("true"));
+        assert_eq!(has_section(r#"<section aria-label="Reader Comments"></section>"#).as_deref(), Some("true"));
+        assert_eq!(has_section(r#"<div class="related"></div>"#).as_deref(), Some("false"));
+    }
+}
+
+mod location {
+    use super::*;
+
+    fn location(html: &str) -> HashMap<String, String> {
+        article_at("https://news.example.com/world/story", html, &["location", "location_source"])
+    }
+
+    fn found(article: &HashMap<String, String>) -> (Option<&str>, Option<&str>) {
+        (field(article, "location"), field(article, "location_source"))
+    }
+
+    #[test]
+    fn reuters_style_dateline_is_found_heuristically() {
+        let article = location(include_str!("fixtures/reuters_dateline.html"));
+        assert_eq!(found(&article), (Some("LONDON"), Some("heuristic")));
+    }
+
+    #[test]
+    fn json_ld_content_location_wins_over_dateline_meta_and_text() {
+        let article = location(include_str!("fixtures/content_location_article.html"));
+        assert_eq!(found(&article), (Some("Tyrol, Austria"), Some("content_location")));
+    }
+
+    #[test]
+    fn dateline_variants_and_non_datelines() {
+        let cases: &[(&str, Option<&str>)] = &[
+            ("<article><p>NEW YORK — Stocks rose.</p></article>", Some("NEW YORK")),
+            ("<article><p>WASHINGTON, D.C. -- Lawmakers met.</p></article>", Some("WASHINGTON, D.C.")),
+            ("<article><p>PARIS (AP) — Museums reopened.</p></article>", Some("PARIS")),
+            ("<article><p>A — is the first letter.</p></article>", None),
+            ("<article><p>London — Lowercase places are not datelines.</p></article>", None),
+            ("<article><p>The story starts here. LONDON — later on.</p></article>", None),
+        ];
+        for (html, expected) in cases {
+            assert_eq!(field(&location(html), "location"), *expected, "html {:?}", html);
+        }
+    }
+}
+
+mod paywall {
+    use super::*;
+
+    fn paywall_fields(html: &str) -> HashMap<String, String> {
+        article_at("https://www.example.com/2024/02/11/science/ocean.html", html, &["paywall", "paywall_selector"])
+    }
+
+    #[test]
+    fn nyt_style_json_ld_marks_the_article_paywalled() {
+        let article = paywall_fields(include_str!("fixtures/paywalled_article.html"));
+        assert_eq!(field(&article, "paywall"), Some("true"));
+        assert_eq!(field(&article, "paywall_selector"), Some(".meteredContent, #gated-graphics"));
+    }
+
+    #[test]
+    fn free_article_is_not_paywalled() {
+        // JSON-LD wins over the content tier meta
+        let article = paywall_fields(include_str!("fixtures/free_article.html"));
+        assert_eq!(field(&article, "paywall"), Some("false"));
+        assert_eq!(field(&article, "paywall_selector"), None);
+    }
+
+    #[test]
+    fn content_tier_meta_alone_is_used() {
+        let article = paywall_fields(r#"<head><meta property="article:content_tier" content="metered"></head>"#);
+        assert_eq!(field(&article, "paywall"), Some("true"));
+
+        let article = paywall_fields("<head><title>No markers</title></head>");
+        assert_eq!(field(&article, "paywall"), None);
+    }
+}
+
+mod article_info {
+    use super::*;
+    use _ferriscope_native::ArticleInfo;
+
+    const PAGE: &str = r#"<html><head><title>Harbour report</title>
+<link rel="canonical" href="https://news.example.com/harbour">
+<meta name="author" content="Jane Doe">
+<meta property="article:published_time" content="2024-05-03T10:00:00Z">
+<meta property="article:tag" content="ports">
+</head><body><article><h1>Harbour report</h1><p>Published 3 May 2024. The harbour reopened.</p></article></body></html>"#;
+
+    fn article_info(fields: &[&str]) -> ArticleInfo {
+        let mut extractor = WebExtractor::new_with_html("https://news.example.com/harbour".to_string(), PAGE.to_string());
+        extractor.extract_article(fields.iter().map(|f| f.to_string()).collect());
+        extractor.extract().unwrap().article_info.unwrap()
+    }
+
+    #[test]
+    fn unrequested_parts_are_not_looked_up() {
+        let info = article_info(&["canonical_url"]);
+        assert!(info.authors.is_empty());
+        assert!(info.dates_with_confidence.is_empty());
+        assert!(info.published.is_none());
+        assert!(info.title.is_none());
+        assert!(info.tags.is_empty());
+    }
+
+    #[test]
+    fn date_fields_fill_the_dates_and_all_fills_everything() {
+        let info = article_info(&["best_publication_date"]);
+        assert!(!info.dates_with_confidence.is_empty());
+        assert!(info.published.is_some());
+        assert!(info.title.is_none());
+
+        let info = article_info(&["all"]);
+        assert_eq!(info.authors, vec!["Jane Doe"]);
+        assert_eq!(info.title.as_deref(), Some("Harbour report"));
+        assert!(info.published.is_some());
+        assert!(!info.tags.is_empty());
+    }
+}
+
+mod typed_json_ld {
+    use super::*;
+
+    #[test]
+    fn article_fields_come_from_the_article_object() {
+        // An untyped lookup would take the Organization's name and description
+        let html = include_str!("fixtures/mixed_json_ld.html");
+        let article = article_at("https://ledger.example.com/reviews/kettles", html, &["title", "description"]);
+        assert_eq!(article["title"], "The best kettles of 2024");
+        assert_eq!(article["description"], "We boiled 40 kettles to find the fastest.");
+    }
+}
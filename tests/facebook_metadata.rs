@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+use _ferriscope_native::WebExtractor;
+
+const PAGE_URL: &str = "https://radio.example.org/podcasts/212-the-sound-of-cities";
+
+fn socials(html: &str, fields: &[&str]) -> HashMap<String, String> {
+    let mut extractor = WebExtractor::new_with_html(PAGE_URL.to_string(), html.to_string());
+    extractor.extract_socials(fields.iter().map(|f| f.to_string()).collect());
+    extractor.extract().unwrap().socials.unwrap_or_default()
+}
+
+#[test]
+fn facebook_instrumented_page_fills_every_new_key() {
+    let html = include_str!("fixtures/facebook_instrumented.html");
+    let socials = socials(html, &["fb_app_id", "fb_pages", "fb_publisher", "og_determiner", "og_audio"]);
+    let expected: HashMap<String, String> = [
+        ("fb_app_id", "966242223397117"),
+        ("fb_pages", r#"["112233445566778","998877665544332"]"#),
+        ("fb_publisher", "https://www.facebook.com/exampleradio"),
+        ("og_determiner", "the"),
+        ("og_audio", "https://radio.example.org/media/podcasts/212-the-sound-of-cities.mp3"),
+    ]
+    .into_iter()
+    .map(|(key, value)| (key.to_string(), value.to_string()))
+    .collect();
+    assert_eq!(socials, expected);
+}
+
+#[test]
+fn short_aliases_and_all_include_the_facebook_keys() {
+    let html = include_str!("fixtures/facebook_instrumented.html");
+    let aliased = socials(html, &["app_id", "pages", "publisher"]);
+    let mut keys: Vec<&str> = aliased.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    assert_eq!(keys, ["fb_app_id", "fb_pages", "fb_publisher"]);
+
+    let all = socials(html, &["all"]);
+    for (key, value) in &aliased {
+        assert_eq!(all.get(key), Some(value), "key {}", key);
+    }
+    for key in ["og_determiner", "og_audio"] {
+        assert!(all.contains_key(key), "all is missing {}", key);
+    }
+}
+
+#[test]
+fn name_attribute_metas_and_audio_url_are_accepted() {
+    let html = r#"<head><meta name="fb:app_id" content="123"><meta name="fb:pages" content="456">
+        <meta property="og:audio:url" content="https://cdn.example.org/ep.mp3"></head>"#;
+    let socials = socials(html, &["fb_app_id", "fb_pages", "og_audio"]);
+    assert_eq!(socials.get("fb_app_id").map(String::as_str), Some("123"));
+    assert_eq!(socials.get("fb_pages").map(String::as_str), Some(r#"["456"]"#));
+    assert_eq!(socials.get("og_audio").map(String::as_str), Some("https://cdn.example.org/ep.mp3"));
+}
@@ -0,0 +1,33 @@
+use _ferriscope_native::WebExtractor;
+
+const PAGE: &str = r#"<html><body>
+<a href="javascript:void(0)">Open menu</a>
+<a href="JavaScript:window.print()">Print</a>
+<a href="data:text/plain,hello">Data</a>
+<a href="mailto:press@example.com">Press</a>
+<a href="ftp://files.example.com/report.pdf">Report</a>
+<a href="/about">About</a>
+<a href="https://partner.example.org/">Partner</a>
+</body></html>"#;
+
+fn link_urls(allowed_schemes: Option<&[&str]>) -> Vec<String> {
+    let mut extractor = WebExtractor::new_with_html("https://www.example.com/".to_string(), PAGE.to_string());
+    extractor.extract_links(vec!["all".to_string()]);
+    if let Some(schemes) = allowed_schemes {
+        extractor.set_allowed_schemes(schemes.iter().map(|s| s.to_string()).collect());
+    }
+    let links = extractor.extract().unwrap().links.unwrap();
+    links.internal.iter().chain(&links.external).map(|link| link.url.clone()).collect()
+}
+
+#[test]
+fn javascript_and_other_pseudo_links_are_dropped_by_default() {
+    assert_eq!(link_urls(None), vec!["https://www.example.com/about", "https://partner.example.org/"]);
+}
+
+#[test]
+fn allowlist_replaces_the_default_schemes() {
+    let urls = link_urls(Some(&["https", "ftp"]));
+    assert!(urls.contains(&"ftp://files.example.com/report.pdf".to_string()));
+    assert!(urls.iter().all(|url| !url.starts_with("javascript:") && !url.starts_with("data:")));
+}
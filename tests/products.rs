@@ -0,0 +1,692 @@
+//! Product extraction end to end, through `WebExtractor::new_with_html` and `extract`
+
+use std::collections::HashMap;
+use _ferriscope_native::{DimensionUnit, ExtractionResult, WebExtractor, WeightUnit};
+
+const SHOP_URL: &str = "https://shop.example.com/item";
+
+/// Extract the product `fields` (names or aliases) from `html` served at `url`, after
+/// `configure` has set any other options
+fn extract_with(url: &str, html: &str, fields: &[&str], configure: impl FnOnce(&mut WebExtractor)) -> ExtractionResult {
+    let mut extractor = WebExtractor::new_with_html(url.to_string(), html.to_string());
+    extractor.extract_product(fields.iter().map(|f| f.to_string()).collect());
+    configure(&mut extractor);
+    extractor.extract().unwrap()
+}
+
+fn product_at(url: &str, html: &str, fields: &[&str]) -> HashMap<String, String> {
+    extract_with(url, html, fields, |_| {}).product.unwrap_or_default()
+}
+
+fn product(html: &str, fields: &[&str]) -> HashMap<String, String> {
+    product_at(SHOP_URL, html, fields)
+}
+
+fn field<'a>(product: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    product.get(name).map(String::as_str)
+}
+
+/// A value together with its `<name>_source` field
+fn with_source<'a>(product: &'a HashMap<String, String>, name: &str) -> Option<(&'a str, &'a str)> {
+    field(product, name).zip(field(product, &format!("{}_source", name)))
+}
+
+mod currency {
+    use super::*;
+
+    fn currency(html: &str) -> HashMap<String, String> {
+        product(html, &["price", "currency", "currency_source"])
+    }
+
+    #[test]
+    fn currency_is_inferred_from_the_price_symbol() {
+        let product = currency(r#"<h1>Speaker</h1><div class="price">£349.99</div>"#);
+        assert_eq!(field(&product, "product_price"), Some("£349.99"));
+        assert_eq!(with_source(&product, "product_currency"), Some(("GBP", "inferred")));
+    }
+
+    #[test]
+    fn ambiguous_symbols_follow_the_page_locale() {
+        let cases: &[(&str, &str)] = &[
+            (r#"<html><head><meta property="og:locale" content="en_CA"></head><body><div class="price">$19.99</div></body></html>"#, "CAD"),
+            (r#"<html lang="en-AU"><body><div class="price">$19.99</div></body></html>"#, "AUD"),
+            (r#"<html lang="en-US"><body><div class="price">$19.99</div></body></html>"#, "USD"),
+            (r#"<html lang="zh-CN"><body><div class="price">¥1200</div></body></html>"#, "CNY"),
+            (r#"<html lang="ja"><body><div class="price">¥1200</div></body></html>"#, "JPY"),
+        ];
+        for (html, expected) in cases {
+            assert_eq!(with_source(&currency(html), "product_currency"), Some((*expected, "inferred")), "html {:?}", html);
+        }
+    }
+
+    #[test]
+    fn declared_currency_is_not_marked_inferred() {
+        let html = r#"<head><meta property="product:price:currency" content="EUR"></head><body><div class="price">$19.99</div></body>"#;
+        assert_eq!(with_source(&currency(html), "product_currency"), Some(("EUR", "declared")));
+    }
+}
+
+mod data_attributes {
+    use super::*;
+
+    /// The extracted price and the source recorded for it by provenance collection
+    fn price(html: &str) -> Option<(String, String)> {
+        let result = extract_with(SHOP_URL, html, &["price"], |extractor| extractor.set_collect_provenance(true));
+        let price = result.product.unwrap_or_default().remove("product_price")?;
+        let source = result.product_sources.unwrap_or_default().remove("product_price")?;
+        Some((price, source))
+    }
+
+    fn found(price: &Option<(String, String)>) -> Option<(&str, &str)> {
+        price.as_ref().map(|(price, source)| (price.as_str(), source.as_str()))
+    }
+
+    #[test]
+    fn data_price_amount_is_read_from_any_element() {
+        let price = price(r#"<h1>Kettle</h1><div class="buy-box" data-price-amount="19.99"><span>Add to cart</span></div>"#);
+        assert_eq!(found(&price), Some(("19.99", "css")));
+    }
+
+    #[test]
+    fn data_attribute_beats_scraped_price_text() {
+        let html = r#"<div class="price"><span>Was $30.00</span> <span>Now $24.00</span></div>
+            <span data-price-amount="19.99"></span>"#;
+        assert_eq!(found(&price(html)), Some(("19.99", "css")));
+    }
+
+    #[test]
+    fn old_price_and_non_numeric_attributes_are_skipped() {
+        let html = r#"<span data-price-type="oldPrice" data-price-amount="30.00"></span>
+            <span data-price-type="finalPrice" data-price-amount="19.99"></span>
+            <span data-product-price="call us"></span>"#;
+        assert_eq!(found(&price(html)), Some(("19.99", "css")));
+    }
+
+    #[test]
+    fn structured_data_still_wins_over_data_attributes() {
+        let html = r#"<head><meta property="product:price:amount" content="17.50"></head>
+            <body><span data-price-amount="19.99"></span></body>"#;
+        assert_eq!(found(&price(html)), Some(("17.50", "meta")));
+    }
+}
+
+mod struck_through {
+    use super::*;
+
+    fn pricing(html: &str) -> HashMap<String, String> {
+        product_at("https://shop.example.com/tote", html, &["price", "original_price", "on_sale"])
+    }
+
+    fn prices(product: &HashMap<String, String>) -> (Option<&str>, Option<&str>) {
+        (field(product, "product_price"), field(product, "product_original_price"))
+    }
+
+    #[test]
+    fn struck_price_is_the_original_and_the_other_is_current() {
+        let product = pricing(include_str!("fixtures/struck_through_price.html"));
+        assert_eq!(prices(&product), (Some("$20"), Some("$30")));
+        assert_eq!(field(&product, "product_on_sale"), Some("true"));
+    }
+
+    #[test]
+    fn s_strike_and_strikethrough_class_mark_the_original() {
+        let cases = [
+            r#"<div class="price"><s>$30</s> <span>$20</span></div>"#,
+            r#"<div class="price"><strike>$30</strike> <span>$20</span></div>"#,
+            r#"<div class="price"><span class="strikethrough">$30</span> <span>$20</span></div>"#,
+            r#"<div class="price"><span>$20</span> <del>$30</del></div>"#,
+        ];
+        for html in cases {
+            assert_eq!(prices(&pricing(html)), (Some("$20"), Some("$30")), "html {:?}", html);
+        }
+    }
+
+    #[test]
+    fn single_price_has_no_original() {
+        assert_eq!(prices(&pricing(r#"<div class="price">$20</div>"#)), (Some("$20"), None));
+    }
+}
+
+mod price_specification {
+    use super::*;
+
+    fn pricing(html: &str) -> HashMap<String, String> {
+        product_at(
+            "https://shop.example.com/espresso-grinder",
+            html,
+            &["price", "original_price", "original_price_source", "price_valid_until"],
+        )
+    }
+
+    #[test]
+    fn strikethrough_price_specification_is_the_original_price() {
+        let product = pricing(include_str!("fixtures/merchant_price_specification.html"));
+        assert_eq!(field(&product, "product_price"), Some("249.0"));
+        // The declared strikethrough price wins over the .old-price text
+        assert_eq!(with_source(&product, "product_original_price"), Some(("299.0", "jsonld_pricespec")));
+        assert_eq!(field(&product, "product_price_valid_until"), Some("2026-12-31"));
+    }
+
+    #[test]
+    fn single_list_price_specification_object() {
+        let html = r#"<script type="application/ld+json">{"@type": "Product", "name": "Mug", "offers": {"@type": "Offer",
+            "price": "12.00", "priceCurrency": "EUR",
+            "priceSpecification": {"@type": "UnitPriceSpecification", "priceType": "ListPrice", "price": "15.00", "priceCurrency": "EUR"}}}</script>"#;
+        let product = pricing(html);
+        assert_eq!(field(&product, "product_price"), Some("12.00"));
+        assert_eq!(with_source(&product, "product_original_price"), Some(("15.00", "jsonld_pricespec")));
+    }
+
+    #[test]
+    fn specification_without_list_price_type_falls_back_to_css() {
+        let html = r#"<script type="application/ld+json">{"@type": "Product", "offers": {"@type": "Offer",
+            "priceSpecification": {"@type": "UnitPriceSpecification", "price": "12.00", "priceCurrency": "EUR"}}}</script>
+            <div class="old-price">€15,00</div>"#;
+        let product = pricing(html);
+        assert_eq!(field(&product, "product_price"), Some("12.00"));
+        assert_eq!(with_source(&product, "product_original_price"), Some(("€15,00", "css")));
+    }
+
+    #[test]
+    fn price_valid_until_is_normalized_to_iso() {
+        let html = r#"<script type="application/ld+json">{"@type": "Product", "offers": {"@type": "Offer",
+            "price": "12.00", "priceValidUntil": "2026-12-31T23:59:59Z"}}</script>"#;
+        let valid_until = pricing(html).remove("product_price_valid_until").unwrap();
+        assert!(valid_until.starts_with("2026-12-31T23:59:59"), "{}", valid_until);
+    }
+}
+
+mod og_tags {
+    use super::*;
+
+    fn pricing(html: &str) -> HashMap<String, String> {
+        product_at("https://example-goods.myshopify.com/products/waxed-canvas-backpack", html, &[
+            "price", "currency", "currency_source", "availability", "availability_raw",
+            "original_price", "original_price_source", "on_sale",
+        ])
+    }
+
+    #[test]
+    fn shopify_og_only_tags_populate_pricing() {
+        let product = pricing(include_str!("fixtures/shopify_og_product.html"));
+        assert_eq!(field(&product, "product_price"), Some("89.00"));
+        assert_eq!(with_source(&product, "product_currency"), Some(("CAD", "declared")));
+        assert_eq!(field(&product, "product_availability"), Some("InStock"));
+        assert_eq!(field(&product, "product_availability_raw"), Some("instock"));
+        assert_eq!(with_source(&product, "product_original_price"), Some(("120.00", "meta")));
+        assert_eq!(field(&product, "product_on_sale"), Some("true"));
+    }
+
+    #[test]
+    fn product_namespace_wins_over_og_namespace() {
+        let html = r#"<head>
+            <meta property="og:price:amount" content="89.00">
+            <meta property="og:price:currency" content="CAD">
+            <meta property="og:availability" content="oos">
+            <meta property="product:price:amount" content="79.00">
+            <meta property="product:price:currency" content="USD">
+            <meta property="product:availability" content="in stock">
+        </head>"#;
+        let product = pricing(html);
+        assert_eq!(field(&product, "product_price"), Some("79.00"));
+        assert_eq!(field(&product, "product_currency"), Some("USD"));
+        assert_eq!(field(&product, "product_availability_raw"), Some("in stock"));
+    }
+
+    #[test]
+    fn og_tags_win_over_json_ld() {
+        let html = r#"<head>
+            <meta property="og:price:amount" content="89.00">
+            <meta property="og:price:currency" content="CAD">
+            <script type="application/ld+json">{"@type": "Product", "offers": {"@type": "Offer",
+                "price": "95.00", "priceCurrency": "USD", "availability": "https://schema.org/OutOfStock"}}</script>
+        </head>"#;
+        let product = pricing(html);
+        assert_eq!(field(&product, "product_price"), Some("89.00"));
+        assert_eq!(field(&product, "product_currency"), Some("CAD"));
+        // No og:availability, so the JSON-LD offer supplies it
+        assert_eq!(field(&product, "product_availability"), Some("OutOfStock"));
+    }
+}
+
+mod offers {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn offers(html: &str) -> HashMap<String, String> {
+        product_at("https://market.example.com/tr3", html, &["offers", "price_range", "price", "currency"])
+    }
+
+    #[test]
+    fn multi_seller_offers_are_all_reported() {
+        let product = offers(include_str!("fixtures/multi_seller_offers.html"));
+        let offers: Vec<Value> = serde_json::from_str(&product["product_offers"]).unwrap();
+        assert_eq!(offers.len(), 3);
+        assert_eq!(offers[0]["price"], "119.00");
+        assert_eq!(offers[0]["seller"], "Peak Outfitters");
+        assert_eq!(offers[0]["seller_url"], "https://market.example.com/sellers/peak");
+        assert_eq!(offers[0]["sku"], "TR3-42-PEAK");
+        assert_eq!(offers[0]["url"], "https://market.example.com/tr3?seller=peak");
+        assert_eq!(offers[0]["availability"], "https://schema.org/OutOfStock");
+        assert_eq!(offers[1]["price"], "129.5");
+        assert_eq!(offers[1]["seller"], "Run Depot");
+        assert_eq!(offers[2]["condition"], "https://schema.org/UsedCondition");
+        assert_eq!(offers[2]["seller"], "Second Stride");
+
+        // The scalar price is the lowest in-stock offer, not the first one
+        assert_eq!(product["product_price"], "129.5");
+        assert_eq!(product["product_currency"], "USD");
+        assert!(!product.contains_key("product_price_range"));
+    }
+
+    #[test]
+    fn aggregate_offer_becomes_a_price_range() {
+        let product = offers(include_str!("fixtures/aggregate_offer.html"));
+        let range: Value = serde_json::from_str(&product["product_price_range"]).unwrap();
+        assert_eq!(range, json!({"low_price": "89", "high_price": "240", "currency": "EUR", "offer_count": 14}));
+        assert_eq!(product["product_price"], "89");
+    }
+}
+
+mod shipping {
+    use super::*;
+
+    fn shipping(html: &str) -> HashMap<String, String> {
+        product_at("https://shop.example.com/shoe", html, &["shipping_price", "shipping_currency", "delivery_days"])
+    }
+
+    /// JSON-LD Product page whose single offer carries the given `shippingDetails`
+    fn with_details(details: &str) -> String {
+        format!(
+            r#"<script type="application/ld+json">{{"@type": "Product", "name": "Lamp",
+                "offers": {{"@type": "Offer", "price": "40", "shippingDetails": {}}}}}</script>"#,
+            details
+        )
+    }
+
+    #[test]
+    fn first_destination_supplies_rate_and_delivery_range() {
+        let product = shipping(include_str!("fixtures/shipping_destinations.html"));
+        assert_eq!(field(&product, "product_shipping_price"), Some("4.95"));
+        assert_eq!(field(&product, "product_shipping_currency"), Some("USD"));
+        // handling 0-1 days + transit 2-5 days
+        assert_eq!(field(&product, "product_delivery_days"), Some("2-6"));
+    }
+
+    #[test]
+    fn entry_without_rate_is_skipped_for_one_with_rate() {
+        let html = with_details(r#"[
+            {"@type": "OfferShippingDetails", "shippingDestination": {"addressCountry": "DE"}},
+            {"@type": "OfferShippingDetails", "shippingDestination": [{"addressCountry": "FR"}, {"addressCountry": "BE"}],
+             "shippingRate": {"value": "0", "currency": "EUR"},
+             "deliveryTime": {"transitTime": {"value": 3, "unitCode": "d"}}}
+        ]"#);
+        let product = shipping(&html);
+        assert_eq!(field(&product, "product_shipping_price"), Some("0"));
+        assert_eq!(field(&product, "product_shipping_currency"), Some("EUR"));
+        assert_eq!(field(&product, "product_delivery_days"), Some("3"));
+    }
+
+    #[test]
+    fn delivery_units_are_converted_to_days() {
+        let html = with_details(r#"{"@type": "OfferShippingDetails",
+            "deliveryTime": {"handlingTime": {"minValue": 12, "maxValue": 36, "unitCode": "HUR"},
+                             "transitTime": {"minValue": 1, "maxValue": 2, "unitCode": "WEE"}}}"#);
+        let product = shipping(&html);
+        assert_eq!(field(&product, "product_shipping_price"), None);
+        // 0.5-1.5 days + 7-14 days, rounded outwards
+        assert_eq!(field(&product, "product_delivery_days"), Some("7-16"));
+    }
+
+    #[test]
+    fn offers_without_shipping_details_yield_nothing() {
+        let html = r#"<script type="application/ld+json">{"@type": "Product", "offers": {"price": "40"}}</script>"#;
+        assert!(shipping(html).is_empty());
+    }
+}
+
+mod identifiers {
+    use super::*;
+
+    #[test]
+    fn json_ld_identifiers_and_aliases() {
+        let html = r#"<script type="application/ld+json">{"@context":"https://schema.org","@type":"Product",
+            "name":"Kettle","gtin13":"4006381333931","gtin12":"036000291452","isbn":"9780306406157"}</script>"#;
+        let product = product(html, &["gtin", "product_gtin_valid", "ean", "upc", "isbn"]);
+        assert_eq!(product["product_gtin"], "4006381333931");
+        assert_eq!(product["product_gtin_valid"], "true");
+        assert_eq!(product["product_gtin13"], "4006381333931");
+        assert_eq!(product["product_gtin12"], "036000291452");
+        assert_eq!(product["product_isbn"], "9780306406157");
+    }
+
+    #[test]
+    fn invalid_microdata_gtin_is_kept_and_flagged() {
+        let html = r#"<div itemscope itemtype="https://schema.org/Product"><span itemprop="name">Kettle</span>
+            <meta itemprop="gtin13" content="4006381333932"></div>"#;
+        let product = product(html, &["gtin", "product_gtin_valid", "ean"]);
+        assert_eq!(product["product_gtin"], "4006381333932");
+        assert_eq!(product["product_gtin13"], "4006381333932");
+        assert_eq!(product["product_gtin_valid"], "false");
+    }
+}
+
+mod brand {
+    use super::*;
+
+    fn brand(html: &str) -> HashMap<String, String> {
+        product_at("https://shop.example.com/drill", html, &["brand", "brand_source"])
+    }
+
+    #[test]
+    fn brand_object_yields_its_name_not_its_id() {
+        let product = brand(include_str!("fixtures/brand_object.html"));
+        assert_eq!(with_source(&product, "product_brand"), Some(("Acme", "jsonld")));
+    }
+
+    #[test]
+    fn brand_array_yields_the_first_name() {
+        let product = brand(include_str!("fixtures/brand_array.html"));
+        assert_eq!(with_source(&product, "product_brand"), Some(("Acme", "jsonld")));
+    }
+
+    #[test]
+    fn meta_brand_is_trimmed() {
+        let product = brand(include_str!("fixtures/brand_meta_whitespace.html"));
+        assert_eq!(with_source(&product, "product_brand"), Some(("Acme Tools", "meta")));
+    }
+
+    #[test]
+    fn junk_brands_fall_back_to_the_manufacturer() {
+        let product = brand(include_str!("fixtures/brand_junk_with_manufacturer.html"));
+        assert_eq!(with_source(&product, "product_brand"), Some(("Acme Manufacturing Ltd.", "manufacturer")));
+    }
+
+    #[test]
+    fn junk_values_are_rejected() {
+        for junk in ["[object Object]", "undefined", "null", "   "] {
+            let html = format!(r#"<script type="application/ld+json">{{"@type": "Product", "name": "Drill", "brand": "{}"}}</script>"#, junk);
+            assert!(brand(&html).is_empty(), "junk {:?}", junk);
+        }
+    }
+
+    #[test]
+    fn microdata_brand_item_yields_its_name() {
+        let html = r#"<div itemscope itemtype="https://schema.org/Product">
+            <h1 itemprop="name">Cordless Drill</h1>
+            <div itemprop="brand" itemscope itemtype="https://schema.org/Brand"><span itemprop="name">Acme</span></div>
+        </div>"#;
+        assert_eq!(with_source(&brand(html), "product_brand"), Some(("Acme", "microdata")));
+    }
+}
+
+mod category {
+    use super::*;
+
+    const PRODUCT_URL: &str = "https://shop.example.com/furniture/bedroom/oak-bedside-table";
+
+    fn category(html: &str) -> HashMap<String, String> {
+        product_at(PRODUCT_URL, html, &["category", "category_source"])
+    }
+
+    #[test]
+    fn breadcrumb_trail_is_the_category_path() {
+        let product = category(include_str!("fixtures/breadcrumb_only_product.html"));
+        assert_eq!(with_source(&product, "product_category"), Some(("Furniture > Bedroom Furniture", "breadcrumb")));
+    }
+
+    #[test]
+    fn final_crumb_is_kept_when_it_is_not_the_product() {
+        let html = r#"<script type="application/ld+json">{"@type": "Product", "name": "Oak Bedside Table"}</script>
+            <script type="application/ld+json">{"@type": "BreadcrumbList", "itemListElement": [
+                {"@type": "ListItem", "position": 1, "name": "Furniture", "item": "https://shop.example.com/furniture"},
+                {"@type": "ListItem", "position": 2, "name": "Bedside Tables", "item": "https://shop.example.com/furniture/bedside"}
+            ]}</script>"#;
+        assert_eq!(with_source(&category(html), "product_category"), Some(("Furniture > Bedside Tables", "breadcrumb")));
+    }
+
+    #[test]
+    fn declared_category_wins_over_breadcrumbs() {
+        let html = include_str!("fixtures/breadcrumb_only_product.html")
+            .replace(r#""name": "Oak Bedside Table","#, r#""name": "Oak Bedside Table", "category": "Home & Garden > Bedroom","#);
+        assert_eq!(with_source(&category(&html), "product_category"), Some(("Home & Garden > Bedroom", "jsonld")));
+    }
+
+    #[test]
+    fn object_and_array_categories_are_accepted() {
+        let cases = [
+            (r#"{"@type": "CategoryCode", "name": "Bedside Tables"}"#, "Bedside Tables"),
+            (r#"["Bedside Tables", "Furniture"]"#, "Bedside Tables"),
+        ];
+        for (value, expected) in cases {
+            let html = format!(r#"<script type="application/ld+json">{{"@type": "Product", "name": "Table", "category": {}}}</script>"#, value);
+            assert_eq!(with_source(&category(&html), "product_category"), Some((expected, "jsonld")));
+        }
+    }
+
+    #[test]
+    fn meta_category_reports_meta() {
+        let html = r#"<meta property="product:category" content=" Furniture ">"#;
+        assert_eq!(with_source(&category(html), "product_category"), Some(("Furniture", "meta")));
+    }
+}
+
+mod measures {
+    use super::*;
+
+    const FIELDS: &[&str] = &["weight", "weight_raw", "width", "width_raw", "height", "depth"];
+
+    const QUANTITATIVE: &str = r#"<script type="application/ld+json">{"@type": "Product", "name": "Kettle",
+        "weight": {"@type": "QuantitativeValue", "value": 1.2, "unitCode": "KGM"},
+        "width": {"@type": "QuantitativeValue", "value": 22, "unitCode": "CMT"},
+        "height": {"@type": "QuantitativeValue", "value": 9.5, "unitCode": "INH"},
+        "depth": "150 mm"}</script>"#;
+
+    #[test]
+    fn quantitative_values_normalize_to_grams_and_millimetres() {
+        let product = product(QUANTITATIVE, FIELDS);
+        assert_eq!(field(&product, "product_weight"), Some("1200"));
+        assert_eq!(field(&product, "product_weight_raw"), Some("1.2 KGM"));
+        assert_eq!(field(&product, "product_width"), Some("220"));
+        assert_eq!(field(&product, "product_width_raw"), Some("22 CMT"));
+        assert_eq!(field(&product, "product_height"), Some("241.3"));
+        assert_eq!(field(&product, "product_depth"), Some("150"));
+    }
+
+    #[test]
+    fn target_units_are_configurable() {
+        let product = extract_with(SHOP_URL, QUANTITATIVE, FIELDS, |extractor| {
+            extractor.set_weight_unit(WeightUnit::Pounds);
+            extractor.set_dimension_unit(DimensionUnit::Centimetres);
+        })
+        .product
+        .unwrap_or_default();
+        assert_eq!(field(&product, "product_weight"), Some("2.646"));
+        assert_eq!(field(&product, "product_width"), Some("22"));
+        assert_eq!(field(&product, "product_depth"), Some("15"));
+        // The raw value keeps the declared form
+        assert_eq!(field(&product, "product_weight_raw"), Some("1.2 KGM"));
+    }
+
+    #[test]
+    fn text_weight_and_additional_property_dimensions() {
+        let html = r#"<script type="application/ld+json">{"@type": "Product", "name": "Kettle", "weight": "2 lbs",
+            "additionalProperty": [
+                {"@type": "PropertyValue", "name": "Colour", "value": "Red"},
+                {"@type": "PropertyValue", "name": "Dimensions", "value": "10 x 20 x 5 cm"}
+            ]}</script>"#;
+        let product = product(html, FIELDS);
+        assert_eq!(field(&product, "product_weight"), Some("907.185"));
+        assert_eq!(field(&product, "product_weight_raw"), Some("2 lbs"));
+        assert_eq!(field(&product, "product_width"), Some("100"));
+        assert_eq!(field(&product, "product_height"), Some("200"));
+        assert_eq!(field(&product, "product_depth"), Some("50"));
+    }
+
+    #[test]
+    fn additional_property_weight_with_unit_code() {
+        let html = r#"<script type="application/ld+json">{"@type": "Product", "name": "Kettle", "additionalProperty":
+            {"@type": "PropertyValue", "name": "Net weight", "value": 850, "unitCode": "GRM"}}</script>"#;
+        let product = product(html, FIELDS);
+        assert_eq!(field(&product, "product_weight"), Some("850"));
+        assert_eq!(field(&product, "product_weight_raw"), Some("850 GRM"));
+    }
+
+    #[test]
+    fn bare_numbers_without_units_are_ignored() {
+        let html = r#"<script type="application/ld+json">{"@type": "Product", "name": "Kettle", "weight": "1.2"}</script>"#;
+        assert!(product(html, FIELDS).is_empty());
+    }
+}
+
+mod eu_labels {
+    use super::*;
+
+    fn labels(html: &str) -> HashMap<String, String> {
+        product_at("https://www.beispiel-elektro.de/p/kg3600-w", html, &["energy_class", "return_days", "return_fees"])
+    }
+
+    /// JSON-LD Product page whose single offer carries the given `hasMerchantReturnPolicy`
+    fn with_policy(policy: &str) -> String {
+        format!(
+            r#"<script type="application/ld+json">{{"@type": "Product", "name": "Kettle",
+                "offers": {{"@type": "Offer", "price": "40", "hasMerchantReturnPolicy": {}}}}}</script>"#,
+            policy
+        )
+    }
+
+    #[test]
+    fn eu_retailer_energy_class_and_first_country_policy() {
+        let product = labels(include_str!("fixtures/eu_retailer_appliance.html"));
+        assert_eq!(field(&product, "product_energy_class"), Some("C"));
+        assert_eq!(field(&product, "product_return_days"), Some("30"));
+        assert_eq!(field(&product, "product_return_fees"), Some("free"));
+    }
+
+    #[test]
+    fn energy_class_on_the_offer_as_an_enumeration_object() {
+        let html = r#"<script type="application/ld+json">{"@type": "Product", "name": "Dryer", "offers": {"@type": "Offer",
+            "hasEnergyConsumptionDetails": {"@type": "EnergyConsumptionDetails",
+                "hasEnergyEfficiencyCategory": {"@id": "schema:EUEnergyEfficiencyCategoryA2Plus"}}}}</script>"#;
+        assert_eq!(field(&labels(html), "product_energy_class"), Some("A++"));
+    }
+
+    #[test]
+    fn return_policy_categories_and_fee_amounts() {
+        let cases = [
+            (r#"{"returnPolicyCategory": "https://schema.org/MerchantReturnNotPermitted"}"#, Some("0"), None),
+            (r#"{"returnPolicyCategory": "MerchantReturnUnlimitedWindow", "returnFees": "FreeReturn"}"#, Some("unlimited"), Some("free")),
+            (r#"{"merchantReturnDays": 14, "returnFees": "https://schema.org/ReturnFeesCustomerResponsibility"}"#, Some("14"), Some("paid")),
+            (r#"{"merchantReturnDays": "28", "returnShippingFeesAmount": {"value": 0, "currency": "EUR"}}"#, Some("28"), Some("free")),
+            (r#"[{"applicableCountry": "FR"}, {"merchantReturnDays": 60, "returnShippingFeesAmount": 4.5}]"#, Some("60"), Some("paid")),
+        ];
+        for (policy, days, fees) in cases {
+            let product = labels(&with_policy(policy));
+            assert_eq!(
+                (field(&product, "product_return_days"), field(&product, "product_return_fees")),
+                (days, fees),
+                "policy {}",
+                policy
+            );
+        }
+    }
+
+    #[test]
+    fn seller_organization_policy_is_the_fallback() {
+        let html = r#"<script type="application/ld+json">[
+            {"@type": "Product", "name": "Kettle", "offers": {"@type": "Offer", "price": "40"}},
+            {"@type": "OnlineStore", "name": "Beispiel Elektro GmbH", "hasMerchantReturnPolicy":
+                {"@type": "MerchantReturnPolicy", "merchantReturnDays": 100, "returnFees": "FreeReturn"}}
+        ]</script>"#;
+        let product = labels(html);
+        assert_eq!(field(&product, "product_return_days"), Some("100"));
+        assert_eq!(field(&product, "product_return_fees"), Some("free"));
+    }
+}
+
+mod main_product {
+    use super::*;
+
+    const RELATED_CARDS_URL: &str = "https://shop.example.com/trail-runner-2";
+    const RELATED_CARDS: &str = include_str!("fixtures/product_with_related_cards.html");
+
+    #[test]
+    fn main_product_wins_over_related_cards() {
+        let product = product_at(RELATED_CARDS_URL, RELATED_CARDS, &["all"]);
+        assert_eq!(field(&product, "product_title"), Some("Trail Runner 2"));
+        assert_eq!(field(&product, "product_description"), Some("A lightweight trail running shoe."));
+        assert_eq!(field(&product, "product_sku"), Some("TR2-42"));
+        assert_eq!(field(&product, "product_brand"), Some("Stride"));
+        assert_eq!(field(&product, "product_price"), Some("139.95"));
+        assert_eq!(field(&product, "product_currency"), Some("EUR"));
+        assert_eq!(field(&product, "product_availability"), Some("InStock"));
+        assert_eq!(field(&product, "product_rating"), Some("4.6"));
+        assert_eq!(field(&product, "product_review_count"), Some("212"));
+    }
+
+    #[test]
+    fn typed_product_info_uses_the_main_product() {
+        let info = extract_with(RELATED_CARDS_URL, RELATED_CARDS, &["all"], |_| {}).product_info.unwrap();
+        assert_eq!(info.title.as_deref(), Some("Trail Runner 2"));
+        assert_eq!(info.price, Some(139.95));
+        assert_eq!(info.currency.as_deref(), Some("EUR"));
+    }
+
+    #[test]
+    fn typed_product_info_only_fills_the_requested_values() {
+        let result = extract_with(RELATED_CARDS_URL, RELATED_CARDS, &["title"], |_| {});
+        let info = result.product_info.unwrap();
+        assert_eq!(info.title.as_deref(), Some("Trail Runner 2"));
+        assert_eq!(info.price, None);
+        assert_eq!(info.currency, None);
+        assert_eq!(info.rating, None);
+        assert!(info.images.is_empty());
+
+        // Any price brings its currency along
+        let info = extract_with(RELATED_CARDS_URL, RELATED_CARDS, &["price"], |_| {}).product_info.unwrap();
+        assert_eq!(info.title, None);
+        assert_eq!(info.price, Some(139.95));
+        assert_eq!(info.currency.as_deref(), Some("EUR"));
+    }
+
+    #[test]
+    fn product_fields_come_from_the_product_object() {
+        let html = include_str!("fixtures/mixed_json_ld.html");
+        let product = product_at("https://ledger.example.com/reviews/kettles", html, &["title", "description", "price"]);
+        assert_eq!(product["product_title"], "SteamPro 2000 Kettle");
+        assert_eq!(product["product_description"], "A 1.7 litre stainless steel kettle.");
+        assert_eq!(product["product_price"], "49.99");
+    }
+}
+
+mod structured_only {
+    use super::*;
+
+    const PAGE: &str = r#"<html lang="de-DE"><head><title>Widget | Shop</title></head><body>
+    <h1>Widget</h1>
+    <span class="old-price">24,99 €</span>
+    <span class="price">19,99 €</span>
+    <div class="rating">4.5</div>
+    <span class="review-count">12 reviews</span>
+    </body></html>"#;
+
+    fn widget(structured_only: bool) -> HashMap<String, String> {
+        extract_with("https://shop.example.de/widget", PAGE, &["all"], |extractor| extractor.set_structured_only(structured_only))
+            .product
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn css_only_product_is_found_by_default() {
+        let product = widget(false);
+        assert_eq!(field(&product, "product_title"), Some("Widget"));
+        assert_eq!(field(&product, "product_price"), Some("19,99 €"));
+        assert_eq!(field(&product, "product_currency"), Some("EUR"));
+    }
+
+    #[test]
+    fn css_only_product_is_empty_when_structured_only() {
+        let product = widget(true);
+        assert!(product.is_empty(), "unexpected fields: {:?}", product);
+    }
+}
@@ -0,0 +1,87 @@
+//! The crate used as a plain Rust library: these run under `--no-default-features`, where
+//! nothing links Python.
+
+use std::error::Error;
+use std::time::Duration;
+use _ferriscope_native::{ExtractionError, ExtractionResult, WebExtractorBuilder};
+
+const PAGE: &str = r#"<html lang="en"><head><title>Field Notes</title>
+    <script type="application/ld+json">{"@type": "Product", "name": "Field Notebook",
+        "offers": {"@type": "Offer", "price": "12.50", "priceCurrency": "EUR"}}</script>
+    </head><body><main><h1>Field Notebook</h1>
+    <p>A pocket notebook with ninety-six pages of dot grid paper, bound in recycled card.</p>
+    <a href="/about">About us</a></main></body></html>"#;
+
+fn extract(builder: WebExtractorBuilder) -> Result<ExtractionResult, Box<dyn Error>> {
+    Ok(builder.build()?.extract()?)
+}
+
+#[test]
+fn builder_extracts_without_python() -> Result<(), Box<dyn Error>> {
+    let result = extract(
+        WebExtractorBuilder::new()
+            .url("https://shop.example.com/notebook")
+            .html(PAGE)
+            .extract_text(false)
+            .extract_links(["internal"])
+            .extract_product(["title", "price", "currency"]),
+    )?;
+    assert!(result.text.as_deref().is_some_and(|text| text.contains("dot grid paper")));
+    let product = result.product.unwrap_or_default();
+    assert_eq!(product.get("product_title").map(String::as_str), Some("Field Notebook"));
+    assert_eq!(product.get("product_price").map(String::as_str), Some("12.50"));
+    assert_eq!(product.get("product_currency").map(String::as_str), Some("EUR"));
+    Ok(())
+}
+
+#[test]
+fn built_extractor_carries_the_configured_settings() -> Result<(), Box<dyn Error>> {
+    let extractor = WebExtractorBuilder::new()
+        .url("https://shop.example.com/notebook")
+        .timeout(10)
+        .user_agent("MyBot/1.0")
+        .header("X-Crawl-Team", "catalog")
+        .accept_language("de-DE,de;q=0.9")
+        .extract_text(true)
+        .min_content_length(120)
+        .extract_links(["internal"])
+        .extract_article(["title", "authors"])
+        .build()?;
+
+    let client = extractor.client_config();
+    assert_eq!(client.timeout, Some(Duration::from_secs(10)));
+    assert_eq!(client.user_agent.as_deref(), Some("MyBot/1.0"));
+    assert_eq!(client.headers.get("X-Crawl-Team").map(String::as_str), Some("catalog"));
+    assert_eq!(client.accept_language.as_deref(), Some("de-DE,de;q=0.9"));
+
+    let activities = extractor.activities();
+    assert!(activities.extract_text.enabled && activities.extract_text.language_detection);
+    assert_eq!(activities.extract_text.min_content_length, 120);
+    assert_eq!(activities.extract_links, vec!["internal"]);
+    assert_eq!(activities.extract_article, vec!["title", "authors"]);
+    assert!(activities.extract_product.is_empty());
+    Ok(())
+}
+
+#[test]
+fn results_round_trip_through_json() -> Result<(), Box<dyn Error>> {
+    let result = extract(WebExtractorBuilder::new().url("https://shop.example.com/notebook").html(PAGE).extract_text(false))?;
+    let restored = ExtractionResult::from_json(&result.to_json()?)?;
+    assert_eq!(restored.url, result.url);
+    assert_eq!(restored.text, result.text);
+
+    // A result saved before most fields existed still loads
+    let old = ExtractionResult::from_json(r#"{"url": "https://shop.example.com/notebook", "text": "Dot grid"}"#)?;
+    assert_eq!(old.text.as_deref(), Some("Dot grid"));
+    assert!(old.product.is_none() && old.warnings.is_empty());
+    Ok(())
+}
+
+#[test]
+fn errors_are_std_errors() {
+    let missing_url = WebExtractorBuilder::new().html(PAGE).build().err().expect("a URL is required");
+    assert!(matches!(missing_url, ExtractionError::InvalidUrl(_)));
+
+    let boxed: Box<dyn Error> = Box::new(missing_url);
+    assert_eq!(boxed.to_string(), "Invalid URL: No URL set on WebExtractorBuilder");
+}